@@ -60,8 +60,11 @@ pub trait Ics20Reader: ChannelReader + PortReader {
     }
 }
 
+/// Returns the address of the account escrowing the tokens sent over the given channel,
+/// as derived by the transfer module of the Cosmos SDK, to be bech32-encoded with the
+/// account prefix of the chain.
 // https://github.com/cosmos/cosmos-sdk/blob/master/docs/architecture/adr-028-public-key-addresses.md
-fn cosmos_adr028_escrow_address(port_id: &PortId, channel_id: ChannelId) -> Vec<u8> {
+pub fn cosmos_adr028_escrow_address(port_id: &PortId, channel_id: ChannelId) -> Vec<u8> {
     let contents = format!("{}/{}", port_id, channel_id);
 
     let mut hasher = Sha256::new();
@@ -116,6 +116,17 @@ impl AnyClientState {
         }
     }
 
+    /// The period during which the client trusts the latest consensus state it
+    /// stores, if it has one, after which the client expires.
+    pub fn trusting_period(&self) -> Option<Duration> {
+        match self {
+            AnyClientState::Tendermint(state) => Some(state.trusting_period),
+
+            #[cfg(any(test, feature = "mocks"))]
+            AnyClientState::Mock(_) => None,
+        }
+    }
+
     pub fn max_clock_drift(&self) -> Duration {
         match self {
             AnyClientState::Tendermint(state) => state.max_clock_drift,
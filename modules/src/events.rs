@@ -79,6 +79,8 @@ pub enum WithBlockDataType {
     UpdateClient,
     SendPacket,
     WriteAck,
+    AckPacket,
+    TimeoutPacket,
 }
 
 impl WithBlockDataType {
@@ -88,6 +90,8 @@ impl WithBlockDataType {
             WithBlockDataType::UpdateClient => "update_client",
             WithBlockDataType::SendPacket => "send_packet",
             WithBlockDataType::WriteAck => "write_acknowledgement",
+            WithBlockDataType::AckPacket => "acknowledge_packet",
+            WithBlockDataType::TimeoutPacket => "timeout_packet",
         }
     }
 }
@@ -13,7 +13,7 @@ use abscissa_core::{
 use ibc_relayer::config::Config;
 
 use crate::{
-    components::{JsonTracing, PrettyTracing},
+    components::{JsonTracing, LogFormat, PrettyTracing},
     config::validate_config,
     entry::EntryPoint,
 };
@@ -171,14 +171,24 @@ impl Application for CliApp {
         // Update the `json_output` flag used by `conclude::Output`
         self.json_output = command.json;
 
-        if command.json {
-            // Enable JSON by using the crate-level `Tracing`
-            let tracing = JsonTracing::new(config.global)?;
-            Ok(vec![Box::new(terminal), Box::new(tracing)])
+        // Unless given explicitly, the log format follows the output mode
+        let log_format = command.log_format.unwrap_or(if command.json {
+            LogFormat::Json
         } else {
-            // Use abscissa's tracing, which pretty-prints to the terminal obeying log levels
-            let tracing = PrettyTracing::new(config.global)?;
-            Ok(vec![Box::new(terminal), Box::new(tracing)])
+            LogFormat::Text
+        });
+
+        match log_format {
+            LogFormat::Json => {
+                // Enable JSON by using the crate-level `Tracing`
+                let tracing = JsonTracing::new(config.global)?;
+                Ok(vec![Box::new(terminal), Box::new(tracing)])
+            }
+            LogFormat::Text => {
+                // Use abscissa's tracing, which pretty-prints to the terminal obeying log levels
+                let tracing = PrettyTracing::new(config.global)?;
+                Ok(vec![Box::new(terminal), Box::new(tracing)])
+            }
         }
     }
 
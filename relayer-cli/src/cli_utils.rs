@@ -1,5 +1,10 @@
 use alloc::sync::Arc;
+use core::time::Duration;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
 
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use tokio::runtime::Runtime as TokioRuntime;
 
 use ibc::core::ics02_client::client_state::ClientState;
@@ -64,6 +69,18 @@ pub fn spawn_chain_runtime_generic<Handle: ChainHandle>(
     spawn::spawn_chain_runtime(config, chain_id, rt).map_err(Error::spawn)
 }
 
+/// Spawns the runtimes of all the chains in the configuration, concurrently,
+/// after the ones of the chains listed in its `spawn_priority`.
+/// Returns the outcome of the spawn of each chain, in spawn order.
+pub fn spawn_chain_runtimes(config: &Config) -> Vec<(ChainId, Result<impl ChainHandle, Error>)> {
+    let rt = Arc::new(TokioRuntime::new().unwrap());
+
+    spawn::spawn_chain_runtimes::<BaseChainHandle>(config, rt)
+        .into_iter()
+        .map(|(chain_id, result)| (chain_id, result.map_err(Error::spawn)))
+        .collect()
+}
+
 /// Spawns a chain runtime for specified chain identifier, queries the counterparty chain associated
 /// with specified port and channel id, and spawns a chain runtime for the counterparty chain.
 /// Returns a tuple with a pair of associated chain handles and the ChannelEnd
@@ -89,3 +106,85 @@ pub fn spawn_chain_counterparty<Chain: ChainHandle>(
         channel_connection_client,
     ))
 }
+
+/// The reply of the REST server, as serialized by `ibc-relayer-rest`.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "status", content = "result")]
+#[serde(rename_all = "lowercase")]
+enum JsonResult<R> {
+    Success(R),
+    Error(serde_json::Value),
+}
+
+/// Sends the given `body` as JSON in a POST request to the given `path` of the REST
+/// server of a running relayer, as configured in the `[rest]` section of the config,
+/// and returns the result of the reply. Waits for the reply for up to `timeout`.
+pub fn rest_post<T: DeserializeOwned>(
+    config: &Config,
+    path: &str,
+    body: &impl Serialize,
+    timeout: Duration,
+) -> Result<T, Error> {
+    if !config.rest.enabled {
+        return Err(Error::cli_arg(
+            "this command requires the REST server to be enabled \
+             in the [rest] section of the configuration"
+                .to_string(),
+        ));
+    }
+
+    let address = format!("{}:{}", config.rest.host, config.rest.port);
+
+    let body = serde_json::to_string(body).map_err(|e| {
+        Error::rest_request(address.clone(), format!("invalid request body: {}", e))
+    })?;
+
+    let response = post_json(&address, path, &body, timeout)
+        .map_err(|e| Error::rest_request(address.clone(), e.to_string()))?;
+
+    match serde_json::from_str(&response) {
+        Ok(JsonResult::Success(result)) => Ok(result),
+        Ok(JsonResult::Error(e)) => Err(Error::rest_request(address, e.to_string())),
+        Err(e) => Err(Error::rest_request(
+            address,
+            format!("unexpected reply '{}': {}", response, e),
+        )),
+    }
+}
+
+/// Sends the JSON `body` in a POST request to the given `path` of the HTTP server
+/// listening at `address`, and returns the body of the response.
+fn post_json(address: &str, path: &str, body: &str, timeout: Duration) -> io::Result<String> {
+    let mut stream = TcpStream::connect(address)?;
+    stream.set_read_timeout(Some(timeout))?;
+
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\n\
+         Host: {}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        path,
+        address,
+        body.len(),
+        body
+    )?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "malformed HTTP response"))?;
+
+    let status = head.lines().next().unwrap_or_default();
+    if !status.contains(" 200 ") {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!("unexpected HTTP status '{}'", status),
+        ));
+    }
+
+    Ok(body.to_string())
+}
@@ -9,22 +9,29 @@ mod clear;
 mod completions;
 mod config;
 mod create;
+mod diagnose;
 mod health;
 mod keys;
 mod listen;
 mod misbehaviour;
+mod probe;
 mod query;
+mod replay;
 mod start;
+mod supervisor;
+mod telemetry;
 mod tx;
 mod update;
 mod upgrade;
+mod util;
 mod version;
 
 use self::{
     clear::ClearCmds, completions::CompletionsCmd, config::ConfigCmd, create::CreateCmds,
-    health::HealthCheckCmd, keys::KeysCmd, listen::ListenCmd, misbehaviour::MisbehaviourCmd,
-    query::QueryCmd, start::StartCmd, tx::TxCmd, update::UpdateCmds, upgrade::UpgradeCmds,
-    version::VersionCmd,
+    diagnose::DiagnoseCmds, health::HealthCheckCmd, keys::KeysCmd, listen::ListenCmd,
+    misbehaviour::MisbehaviourCmd, probe::ProbeCmds, query::QueryCmd, replay::ReplayCmds,
+    start::StartCmd, supervisor::SupervisorCmds, telemetry::TelemetryCmds, tx::TxCmd,
+    update::UpdateCmds, upgrade::UpgradeCmds, util::UtilCmds, version::VersionCmd,
 };
 
 use core::time::Duration;
@@ -74,6 +81,10 @@ pub enum CliCmd {
     /// Relays packets and open handshake messages between all chains in the config.
     Start(StartCmd),
 
+    /// Control the relayer started with `start`
+    #[clap(subcommand)]
+    Supervisor(SupervisorCmds),
+
     /// Query objects from the chain
     #[clap(subcommand)]
     Query(QueryCmd),
@@ -82,18 +93,38 @@ pub enum CliCmd {
     #[clap(subcommand)]
     Tx(TxCmd),
 
+    /// Diagnose the state of a path between two chains, without relaying
+    #[clap(subcommand)]
+    Diagnose(DiagnoseCmds),
+
+    /// Probe a path between two chains by relaying a packet end-to-end
+    #[clap(subcommand)]
+    Probe(ProbeCmds),
+
     /// Listen to and display IBC events emitted by a chain
     Listen(ListenCmd),
 
+    /// Replay recorded events to the relayer
+    #[clap(subcommand)]
+    Replay(ReplayCmds),
+
     /// Listen to client update IBC events and handles misbehaviour
     Misbehaviour(MisbehaviourCmd),
 
+    /// Client-side utilities, which do not connect to any chain
+    #[clap(subcommand)]
+    Util(UtilCmds),
+
     /// The `version` subcommand, retained for backward compatibility.
     Version(VersionCmd),
 
-    /// Performs a health check of all chains in the the config
+    /// Checks the connectivity to, and the relayer key on, all chains in the config
     HealthCheck(HealthCheckCmd),
 
+    /// Inspect the telemetry exported by the relayer
+    #[clap(subcommand, hide = true)]
+    Telemetry(TelemetryCmds),
+
     /// Generate auto-complete scripts for different shells.
     #[clap(display_order = 1000)]
     Completions(CompletionsCmd),
@@ -153,6 +184,7 @@ impl Configurable<Config> for CliCmd {
 
         match self {
             CliCmd::Tx(cmd) => cmd.override_config(config),
+            CliCmd::Clear(cmd) => cmd.override_config(config),
             // CliCmd::Help(cmd) => cmd.override_config(config),
             // CliCmd::Keys(cmd) => cmd.override_config(config),
             // CliCmd::Create(cmd) => cmd.override_config(config),
@@ -1,15 +1,26 @@
+use std::io::{self, BufRead, Write};
+
 use abscissa_core::clap::Parser;
-use abscissa_core::{Command, Runnable};
+use abscissa_core::{config::Override, Command, FrameworkErrorKind, Runnable};
+use serde::Serialize;
 
-use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::core::ics02_client::client_state::ClientState;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
 use ibc::events::IbcEvent;
-use ibc_relayer::chain::handle::BaseChainHandle;
+use ibc::Height;
+use ibc_relayer::chain::counterparty::{
+    channel_on_destination, pending_packet_summary, ChannelConnectionClient,
+};
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::chain::requests::{IncludeProof, QueryClientStateRequest};
+use ibc_relayer::config::Config;
+use ibc_relayer::foreign_client::{ConsensusStatesSummary, ForeignClient};
 use ibc_relayer::link::error::LinkError;
-use ibc_relayer::link::{Link, LinkParameters};
+use ibc_relayer::link::{HeightWindow, Link, LinkParameters};
 
 use crate::application::app_config;
-use crate::cli_utils::spawn_chain_counterparty;
-use crate::conclude::Output;
+use crate::cli_utils::{spawn_chain_counterparty, spawn_chain_runtime, ChainHandlePair};
+use crate::conclude::{json, Output};
 use crate::error::Error;
 
 /// `clear` subcommands
@@ -18,7 +29,15 @@ pub enum ClearCmds {
     /// Clear outstanding packets (i.e., packet-recv and packet-ack)
     /// on a given channel in both directions. The channel is identified
     /// by the chain, port, and channel IDs at one of its ends.
+    ///
+    /// With `--timeouts-only`, only the timeouts of the packets which timed out,
+    /// or whose channel was closed on the destination chain, are relayed.
     Packets(ClearPacketsCmd),
+
+    /// Report the consensus states stored for a client, and the ones outside of its
+    /// trusting period, optionally updating the client so that the chains which prune
+    /// expired consensus states on client updates prune them.
+    ConsensusStates(ClearConsensusStatesCmd),
 }
 
 #[derive(Debug, Parser)]
@@ -31,22 +50,157 @@ pub struct ClearPacketsCmd {
 
     #[clap(required = true, help = "identifier of the channel")]
     channel_id: ChannelId,
+
+    #[clap(
+        long = "yes",
+        help = "clear all the pending packets without asking for confirmation, required in JSON or non-interactive mode"
+    )]
+    yes: bool,
+
+    #[clap(
+        long = "from-height",
+        help = "only clear the packets sent, and acknowledgements written, on the chain given by <CHAIN_ID> at or after this height"
+    )]
+    from_height: Option<u64>,
+
+    #[clap(
+        long = "to-height",
+        help = "only clear the packets sent, and acknowledgements written, on the chain given by <CHAIN_ID> at or before this height"
+    )]
+    to_height: Option<u64>,
+
+    #[clap(
+        long = "timeouts-only",
+        help = "only relay the timeouts of the pending packets, eg. to refund the packets of a channel being closed, \
+                leaving the other packets and the acknowledgements pending"
+    )]
+    timeouts_only: bool,
+
+    #[clap(
+        long = "gas-price",
+        value_name = "PRICE",
+        help = "override the gas price of both chains for this run, in the denomination configured for each chain"
+    )]
+    gas_price: Option<f64>,
+
+    #[clap(
+        long = "gas-adjustment",
+        value_name = "ADJUSTMENT",
+        help = "override the gas adjustment of both chains for this run"
+    )]
+    gas_adjustment: Option<f64>,
+}
+
+impl Override<Config> for ClearCmds {
+    fn override_config(&self, config: Config) -> Result<Config, abscissa_core::FrameworkError> {
+        match self {
+            Self::Packets(cmd) => cmd.override_config(config),
+            Self::ConsensusStates(_) => Ok(config),
+        }
+    }
+}
+
+/// Overrides the gas price and adjustment of the chains for this invocation only,
+/// so that a backlog can be cleared with a higher fee than the one configured.
+///
+/// The override applies to all the chains in the configuration, as the counterparty
+/// chain of the channel is only known once the chain runtimes are spawned.
+impl Override<Config> for ClearPacketsCmd {
+    fn override_config(&self, mut config: Config) -> Result<Config, abscissa_core::FrameworkError> {
+        if let Some(gas_price) = self.gas_price {
+            if !gas_price.is_finite() || gas_price < 0.0 {
+                return Err(FrameworkErrorKind::ConfigError
+                    .context(format!("invalid gas price '{}'", gas_price))
+                    .into());
+            }
+
+            for chain_config in config.chains.iter_mut() {
+                chain_config.gas_price.price = gas_price;
+            }
+        }
+
+        if let Some(gas_adjustment) = self.gas_adjustment {
+            if !gas_adjustment.is_finite() || gas_adjustment < 0.0 {
+                return Err(FrameworkErrorKind::ConfigError
+                    .context(format!("invalid gas adjustment '{}'", gas_adjustment))
+                    .into());
+            }
+
+            for chain_config in config.chains.iter_mut() {
+                chain_config.gas_adjustment = Some(gas_adjustment);
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+impl ClearPacketsCmd {
+    /// The window of heights on the chain given by `<CHAIN_ID>` to clear packets within,
+    /// or `None` if the whole channel should be cleared.
+    fn height_window(&self) -> Result<Option<HeightWindow>, Error> {
+        if let (Some(from), Some(to)) = (self.from_height, self.to_height) {
+            if from > to {
+                return Err(Error::cli_arg(format!(
+                    "--from-height ({}) must not be greater than --to-height ({})",
+                    from, to
+                )));
+            }
+        }
+
+        if self.from_height.is_none() && self.to_height.is_none() {
+            return Ok(None);
+        }
+
+        let revision_number = self.chain_id.version();
+        let height = |h| Height::new(revision_number, h);
+
+        Ok(Some(HeightWindow::new(
+            self.from_height.map(height),
+            self.to_height.map(height),
+        )))
+    }
 }
 
 impl Runnable for ClearPacketsCmd {
     fn run(&self) {
         let config = app_config();
 
-        let chains = match spawn_chain_counterparty::<BaseChainHandle>(
+        let window = match self.height_window() {
+            Ok(window) => window,
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        };
+
+        if !self.yes && (json() || !atty::is(atty::Stream::Stdin)) {
+            Output::error(
+                "clearing all the pending packets of a channel requires \
+                 the --yes flag in JSON or non-interactive mode"
+                    .to_string(),
+            )
+            .exit()
+        }
+
+        let (chains, chan_conn_cli) = match spawn_chain_counterparty::<BaseChainHandle>(
             &config,
             &self.chain_id,
             &self.port_id,
             &self.channel_id,
         ) {
-            Ok((chains, _)) => chains,
+            Ok(result) => result,
             Err(e) => Output::error(format!("{}", e)).exit(),
         };
 
+        if !self.yes {
+            let pending_count = match count_pending_sequences(&chains, &chan_conn_cli) {
+                Ok(count) => count,
+                Err(e) => Output::error(format!("{}", e)).exit(),
+            };
+
+            if !confirm_clearing(pending_count, &self.port_id, &self.channel_id) {
+                Output::error("aborted clearing of the pending packets".to_string()).exit()
+            }
+        }
+
         let mut ev_list = vec![];
 
         // Construct links in both directions.
@@ -54,15 +208,36 @@ impl Runnable for ClearPacketsCmd {
             src_port_id: self.port_id.clone(),
             src_channel_id: self.channel_id,
         };
-        let fwd_link = match Link::new_from_opts(chains.src.clone(), chains.dst, opts, false) {
+        let mut fwd_link = match Link::new_from_opts(chains.src.clone(), chains.dst, opts, false) {
             Ok(link) => link,
             Err(e) => Output::error(format!("{}", e)).exit(),
         };
-        let rev_link = match fwd_link.reverse(false) {
+        let mut rev_link = match fwd_link.reverse(false) {
             Ok(link) => link,
             Err(e) => Output::error(format!("{}", e)).exit(),
         };
 
+        if self.timeouts_only {
+            fwd_link.a_to_b.set_relay_recv_packets(false);
+            rev_link.a_to_b.set_relay_recv_packets(false);
+        }
+
+        if let Some(window) = window {
+            // The height window is on the chain given by `<CHAIN_ID>`, so only the packets
+            // sent and the acknowledgements written on that chain are cleared, ie. the ones
+            // relayed by the forward link.
+            run_and_collect_events(&mut ev_list, || {
+                fwd_link.relay_recv_packet_and_timeout_messages_within(window)
+            });
+            if !self.timeouts_only {
+                run_and_collect_events(&mut ev_list, || {
+                    fwd_link.relay_ack_packet_messages_within(window)
+                });
+            }
+
+            Output::success(ev_list).exit()
+        }
+
         // Schedule RecvPacket messages for pending packets in both directions.
         // This may produce pending acks which will be processed in the next phase.
         run_and_collect_events(&mut ev_list, || {
@@ -72,6 +247,10 @@ impl Runnable for ClearPacketsCmd {
             rev_link.relay_recv_packet_and_timeout_messages()
         });
 
+        if self.timeouts_only {
+            Output::success(ev_list).exit()
+        }
+
         // Schedule AckPacket messages in both directions.
         run_and_collect_events(&mut ev_list, || fwd_link.relay_ack_packet_messages());
         run_and_collect_events(&mut ev_list, || rev_link.relay_ack_packet_messages());
@@ -80,6 +259,140 @@ impl Runnable for ClearPacketsCmd {
     }
 }
 
+/// IBC has no message to remove the consensus states of a client. Instead, the host
+/// chains implementing the pruning of ibc-go prune the expired consensus states of
+/// a client when it is updated, so that with `--update`, this command submits an
+/// update of the client and reports how many expired consensus states were pruned.
+#[derive(Debug, Parser)]
+pub struct ClearConsensusStatesCmd {
+    #[clap(required = true, help = "identifier of the chain hosting the client")]
+    chain_id: ChainId,
+
+    #[clap(required = true, help = "identifier of the client")]
+    client_id: ClientId,
+
+    #[clap(
+        long = "update",
+        help = "update the client, so that the chain prunes its expired consensus states if it supports it"
+    )]
+    update: bool,
+}
+
+/// The consensus states of a client, before and after the update of the client.
+#[derive(Debug, Serialize)]
+struct ClearConsensusStatesOutput {
+    client_id: ClientId,
+    /// The number of expired consensus states which the chain pruned.
+    pruned: usize,
+    remaining: ConsensusStatesSummary,
+}
+
+impl ClearConsensusStatesCmd {
+    fn execute(&self) -> Result<ClearConsensusStatesOutput, Error> {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)?;
+
+        let (client_state, _) = chain
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: self.client_id.clone(),
+                    height: Height::zero(),
+                },
+                IncludeProof::No,
+            )
+            .map_err(Error::relayer)?;
+
+        let src_chain = spawn_chain_runtime(&config, &client_state.chain_id())?;
+
+        let client = ForeignClient::find(src_chain, chain, &self.client_id)
+            .map_err(Error::foreign_client)?;
+
+        let before = client
+            .consensus_states_summary()
+            .map_err(Error::foreign_client)?;
+
+        if !self.update {
+            return Ok(ClearConsensusStatesOutput {
+                client_id: self.client_id.clone(),
+                pruned: 0,
+                remaining: before,
+            });
+        }
+
+        client
+            .build_latest_update_client_and_send()
+            .map_err(Error::foreign_client)?;
+
+        let after = client
+            .consensus_states_summary()
+            .map_err(Error::foreign_client)?;
+
+        Ok(ClearConsensusStatesOutput {
+            client_id: self.client_id.clone(),
+            // More consensus states may have expired since they were first counted.
+            pruned: before.expired.saturating_sub(after.expired),
+            remaining: after,
+        })
+    }
+}
+
+impl Runnable for ClearConsensusStatesCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(output) => Output::success(output).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+/// Counts the pending packet sequences on both ends of the channel, ie. the packets
+/// and acknowledgements that would be relayed by clearing this channel.
+fn count_pending_sequences(
+    chains: &ChainHandlePair,
+    chan_conn_cli: &ChannelConnectionClient,
+) -> Result<usize, Error> {
+    let src_pending = pending_packet_summary(&chains.src, &chains.dst, &chan_conn_cli.channel)
+        .map_err(Error::supervisor)?;
+
+    let counterparty_channel = channel_on_destination(
+        &chan_conn_cli.channel,
+        &chan_conn_cli.connection,
+        &chains.dst,
+    )
+    .map_err(Error::supervisor)?
+    .ok_or_else(|| Error::missing_counterparty_channel_id(chan_conn_cli.channel.clone()))?;
+
+    let dst_pending = pending_packet_summary(&chains.dst, &chains.src, &counterparty_channel)
+        .map_err(Error::supervisor)?;
+
+    Ok(src_pending.unreceived_packets.len()
+        + src_pending.unreceived_acks.len()
+        + dst_pending.unreceived_packets.len()
+        + dst_pending.unreceived_acks.len())
+}
+
+/// Reports the number of pending sequences found on the channel,
+/// and asks the user to confirm that all of them should be cleared.
+fn confirm_clearing(pending_count: usize, port_id: &PortId, channel_id: &ChannelId) -> bool {
+    print!(
+        "Found {} pending sequence(s) on channel {}/{} and its counterparty. \
+         Clear them all? [y/N] ",
+        pending_count, port_id, channel_id
+    );
+
+    if io::stdout().flush().is_err() {
+        return false;
+    }
+
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
 fn run_and_collect_events<F>(ev_list: &mut Vec<IbcEvent>, f: F)
 where
     F: FnOnce() -> Result<Vec<IbcEvent>, LinkError>,
@@ -0,0 +1,118 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc_relayer::config::filter::PacketFilter;
+use ibc_relayer::config::Config;
+use ibc_relayer::link::Resubmit;
+
+use crate::conclude::Output;
+use crate::prelude::*;
+
+/// The packet workers which the supervisor would spawn for the loaded configuration.
+#[derive(Debug, Serialize)]
+struct Topology {
+    packets_enabled: bool,
+    workers: Vec<PacketWorkers>,
+}
+
+/// The packet workers relaying packets sent from `chain` to `counterparty`, one for
+/// each channel between the two chains which is allowed by the packet filter of `chain`.
+#[derive(Debug, Serialize)]
+struct PacketWorkers {
+    chain: ChainId,
+    counterparty: ChainId,
+    channels: PacketFilter,
+    clear_interval: u64,
+    /// Whether the workers clear the pending packets when they start.
+    /// Workers on ordered channels always do.
+    clear_on_start: bool,
+    /// Whether the workers resubmit the transactions which
+    /// could not be confirmed in time.
+    resubmit: bool,
+}
+
+/// Lists the packet workers which the supervisor would spawn for the loaded configuration,
+/// without connecting to the chains.
+///
+/// As the channels between two chains are only known by querying the chains, the workers
+/// are listed for every pair of configured chains, along with the packet filter restricting
+/// the channels they would be spawned for.
+#[derive(Command, Debug, Parser)]
+pub struct TopologyCmd {}
+
+impl Runnable for TopologyCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        Output::success(topology(&config)).exit()
+    }
+}
+
+fn topology(config: &Config) -> Topology {
+    let packets = &config.mode.packets;
+
+    let workers = if packets.enabled {
+        config
+            .chains
+            .iter()
+            .flat_map(|chain| {
+                config
+                    .chains
+                    .iter()
+                    .filter(move |counterparty| counterparty.id != chain.id)
+                    .filter(move |counterparty| {
+                        config.relays_direction(&chain.id, &counterparty.id)
+                    })
+                    .map(move |counterparty| PacketWorkers {
+                        chain: chain.id.clone(),
+                        counterparty: counterparty.id.clone(),
+                        channels: chain.packet_filter.clone(),
+                        clear_interval: packets.clear_interval,
+                        clear_on_start: packets.clear_on_start,
+                        resubmit: Resubmit::from_clear_interval(packets.clear_interval)
+                            == Resubmit::Yes,
+                    })
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    Topology {
+        packets_enabled: packets.enabled,
+        workers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_relayer::config::{load, ChainPairDirection, RelayDirection};
+
+    use super::topology;
+
+    #[test]
+    fn workers_are_only_listed_in_the_relayed_directions() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../relayer/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let mut config = load(path).expect("could not parse config");
+        let (a, b) = (config.chains[0].id.clone(), config.chains[1].id.clone());
+
+        assert_eq!(topology(&config).workers.len(), 2);
+
+        config.relay_directions = vec![ChainPairDirection {
+            chain_a: a.clone(),
+            chain_b: b.clone(),
+            relay_direction: RelayDirection::AToB,
+        }];
+
+        let topology = topology(&config);
+        assert_eq!(topology.workers.len(), 1);
+        assert_eq!(topology.workers[0].chain, a);
+        assert_eq!(topology.workers[0].counterparty, b);
+    }
+}
@@ -0,0 +1,172 @@
+use core::time::Duration;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc::core::ics02_client::client_state::AnyClientState;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
+use ibc::downcast;
+use ibc_relayer::account::Balance;
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::foreign_client::{ForeignClient, HasExpiredOrFrozenError};
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// `diagnose` subcommands
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum DiagnoseCmds {
+    /// Report the relayer balances and the state of the clients
+    /// on both ends of a channel, without relaying anything.
+    Path(DiagnosePathCmd),
+}
+
+#[derive(Clone, Command, Debug, Parser)]
+pub struct DiagnosePathCmd {
+    #[clap(
+        required = true,
+        help = "identifier of the chain at one end of the channel"
+    )]
+    chain_a_id: ChainId,
+
+    #[clap(
+        required = true,
+        help = "identifier of the chain at the other end of the channel"
+    )]
+    chain_b_id: ChainId,
+
+    #[clap(
+        required = true,
+        help = "identifier of the channel on the chain given by <CHAIN_A_ID>"
+    )]
+    channel_id: ChannelId,
+
+    #[clap(
+        long = "port",
+        default_value = "transfer",
+        help = "identifier of the port on the chain given by <CHAIN_A_ID>"
+    )]
+    port_id: PortId,
+}
+
+/// A health report of both ends of a channel.
+#[derive(Debug, Serialize)]
+struct PathReport {
+    chain_a: EndReport,
+    chain_b: EndReport,
+}
+
+/// A health report of one end of a channel.
+#[derive(Debug, Serialize)]
+struct EndReport {
+    chain_id: ChainId,
+    /// The balance of the relayer wallet on this chain.
+    balance: Result<Balance, String>,
+    /// The client hosted on this chain, which tracks the counterparty chain.
+    client: ClientReport,
+}
+
+#[derive(Debug, Serialize)]
+struct ClientReport {
+    client_id: ClientId,
+    expired_or_frozen: bool,
+    /// How much time is left before the client expires, if it is not expired or frozen.
+    trusting_period_remaining: Option<String>,
+    /// The error encountered while validating the client state, if any.
+    error: Option<String>,
+}
+
+impl DiagnosePathCmd {
+    fn execute(&self) -> Result<PathReport, Error> {
+        let config = app_config();
+
+        let (chains, chan_conn_cli) = spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.chain_a_id,
+            &self.port_id,
+            &self.channel_id,
+        )?;
+
+        if chains.dst.id() != self.chain_b_id {
+            return Err(Error::cli_arg(format!(
+                "channel {}/{} on chain {} does not lead to chain {} but to chain {}",
+                self.port_id,
+                self.channel_id,
+                self.chain_a_id,
+                self.chain_b_id,
+                chains.dst.id()
+            )));
+        }
+
+        let connection_end = &chan_conn_cli.connection.connection_end;
+
+        let client_a = ForeignClient::restore(
+            connection_end.client_id().clone(),
+            chains.src.clone(),
+            chains.dst.clone(),
+        );
+
+        let client_b = ForeignClient::restore(
+            connection_end.counterparty().client_id().clone(),
+            chains.dst.clone(),
+            chains.src.clone(),
+        );
+
+        Ok(PathReport {
+            chain_a: end_report(&chains.src, &client_a),
+            chain_b: end_report(&chains.dst, &client_b),
+        })
+    }
+}
+
+impl Runnable for DiagnosePathCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(report) => Output::success(report).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+fn end_report<Chain: ChainHandle>(
+    chain: &Chain,
+    client: &ForeignClient<Chain, Chain>,
+) -> EndReport {
+    let balance = chain.query_balance(None).map_err(|e| format!("{}", e));
+
+    EndReport {
+        chain_id: chain.id(),
+        balance,
+        client: client_report(client),
+    }
+}
+
+fn client_report<Chain: ChainHandle>(client: &ForeignClient<Chain, Chain>) -> ClientReport {
+    match client.validated_client_state() {
+        Ok((client_state, elapsed)) => ClientReport {
+            client_id: client.id().clone(),
+            expired_or_frozen: false,
+            trusting_period_remaining: trusting_period_remaining(&client_state, elapsed),
+            error: None,
+        },
+        Err(e) => ClientReport {
+            client_id: client.id().clone(),
+            expired_or_frozen: e.is_expired_or_frozen_error(),
+            trusting_period_remaining: None,
+            error: Some(format!("{}", e)),
+        },
+    }
+}
+
+fn trusting_period_remaining(
+    client_state: &AnyClientState,
+    elapsed: Option<Duration>,
+) -> Option<String> {
+    let client_state = downcast!(client_state => AnyClientState::Tendermint)?;
+    let remaining = client_state.trusting_period.saturating_sub(elapsed?);
+
+    Some(humantime::format_duration(remaining).to_string())
+}
@@ -1,41 +1,208 @@
+use std::thread;
+
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
+use serde::Serialize;
 
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc::Height;
+use ibc_relayer::account::Balance;
 use ibc_relayer::chain::endpoint::HealthCheck::*;
 use ibc_relayer::chain::handle::ChainHandle;
 
-use crate::cli_utils::spawn_chain_runtime;
-use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::cli_utils::spawn_chain_runtimes;
+use crate::conclude::{json, Output};
+use crate::error::Error;
 use crate::prelude::*;
 
+/// Checks that every chain in the config can be relayed on, ie. that its
+/// runtime can be spawned, that its node answers a status query and passes
+/// the health check, and that the configured key exists and holds a non-zero
+/// balance of the gas price denom.
+///
+/// The runtimes are spawned following the `spawn_priority` of the config, the chains
+/// are then checked in parallel, and the command fails if any of them fails.
 #[derive(Clone, Command, Debug, Parser)]
 pub struct HealthCheckCmd {}
 
+/// The outcome of the checks of a chain.
+#[derive(Debug, Serialize)]
+struct ChainHealth {
+    chain_id: ChainId,
+    passed: bool,
+    /// The latest height of the chain, if its status could be queried.
+    latest_height: Option<Height>,
+    /// The account of the configured key, if it exists.
+    account: Option<String>,
+    balance: Option<Balance>,
+    /// The reason why the chain failed the checks, if it did.
+    error: Option<String>,
+}
+
+impl ChainHealth {
+    fn new(chain_id: ChainId) -> Self {
+        Self {
+            chain_id,
+            passed: false,
+            latest_height: None,
+            account: None,
+            balance: None,
+            error: None,
+        }
+    }
+}
+
 impl Runnable for HealthCheckCmd {
     fn run(&self) {
         let config = (*app_config()).clone();
 
-        for ch in &config.chains {
-            info!("[{}] performing health check...", ch.id);
-
-            let chain =
-                spawn_chain_runtime(&config, &ch.id).unwrap_or_else(exit_with_unrecoverable_error);
-
-            match chain.health_check() {
-                Ok(Healthy) => info!(chain = %ch.id, "chain is healthy"),
-                Ok(Unhealthy(_)) => {
-                    // No need to print the error here as it's already printed in `Chain::health_check`
-                    // TODO(romac): Move the printing code here and in the supervisor/registry
-                    warn!("[{}] chain is unhealthy", ch.id)
-                }
-                Err(e) => error!(
-                    "[{}] failed to perform health check, reason: {}",
-                    ch.id,
-                    e.detail()
+        let handles: Vec<_> = spawn_chain_runtimes(&config)
+            .into_iter()
+            .map(|(chain_id, spawned)| {
+                thread::spawn(move || {
+                    info!("[{}] performing health check...", chain_id);
+
+                    let mut health = ChainHealth::new(chain_id);
+                    match spawned.and_then(|chain| check_chain(&chain, &mut health)) {
+                        Ok(()) => health.passed = true,
+                        Err(e) => health.error = Some(format!("{}", e)),
+                    }
+
+                    health
+                })
+            })
+            .collect();
+
+        let reports: Vec<ChainHealth> = handles
+            .into_iter()
+            .map(|handle| handle.join().expect("health check thread panicked"))
+            .collect();
+
+        let output = if reports.iter().all(|report| report.passed) {
+            Output::with_success()
+        } else {
+            Output::with_error()
+        };
+
+        if json() {
+            output.with_result(reports).exit()
+        } else {
+            output.with_msg(format_table(&reports)).exit()
+        }
+    }
+}
+
+/// Performs the checks of the chain, recording what was found in the given report,
+/// and stopping at the first failed check.
+fn check_chain(chain: &impl ChainHandle, health: &mut ChainHealth) -> Result<(), Error> {
+    let status = chain.query_application_status().map_err(Error::relayer)?;
+    health.latest_height = Some(status.height);
+
+    match chain.health_check().map_err(Error::relayer)? {
+        Healthy => {}
+        Unhealthy(e) => {
+            return Err(Error::cli_arg(format!("chain is unhealthy: {}", e)));
+        }
+    }
+
+    let key = chain.get_key().map_err(Error::relayer)?;
+    health.account = Some(key.account);
+
+    let balance = chain.query_balance(None).map_err(Error::relayer)?;
+    let empty = is_zero(&balance.amount);
+    health.balance = Some(balance);
+
+    if empty {
+        return Err(Error::cli_arg(
+            "the configured key has no balance to pay fees with".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn is_zero(amount: &str) -> bool {
+    amount.chars().all(|c| c == '0')
+}
+
+/// Formats the given reports as a table, with one row per chain, and columns
+/// aligned to their widest cell.
+fn format_table(reports: &[ChainHealth]) -> String {
+    let header = ["CHAIN", "RESULT", "HEIGHT", "ACCOUNT", "BALANCE", "ERROR"].map(String::from);
+
+    let rows: Vec<[String; 6]> = reports
+        .iter()
+        .map(|report| {
+            [
+                report.chain_id.to_string(),
+                if report.passed { "pass" } else { "fail" }.to_string(),
+                cell(&report.latest_height),
+                cell(&report.account),
+                cell(
+                    &report
+                        .balance
+                        .as_ref()
+                        .map(|balance| format!("{}{}", balance.amount, balance.denom)),
                 ),
-            }
+                cell(&report.error),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.clone().map(|cell| cell.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
         }
+    }
+
+    core::iter::once(&header)
+        .chain(&rows)
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect();
+
+            cells.join("  ").trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn cell(value: &Option<impl ToString>) -> String {
+    value
+        .as_ref()
+        .map_or_else(|| "-".to_string(), ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc::core::ics24_host::identifier::ChainId;
+
+    use super::{format_table, is_zero, ChainHealth};
+
+    #[test]
+    fn failed_chains_are_reported_with_their_error() {
+        assert!(is_zero("0"));
+        assert!(is_zero("000"));
+        assert!(!is_zero("100"));
+
+        let passed = ChainHealth {
+            passed: true,
+            ..ChainHealth::new(ChainId::new("ibc".to_string(), 0))
+        };
+        let failed = ChainHealth {
+            error: Some("no key".to_string()),
+            ..ChainHealth::new(ChainId::new("other".to_string(), 1))
+        };
+
+        let table = format_table(&[passed, failed]);
+        let lines: Vec<&str> = table.lines().collect();
 
-        Output::success_msg("performed health check for all chains in the config").exit()
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("ibc-0") && lines[1].contains("pass"));
+        assert!(lines[2].starts_with("other-1") && lines[2].ends_with("no key"));
     }
 }
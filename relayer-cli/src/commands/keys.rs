@@ -6,6 +6,7 @@ mod add;
 mod balance;
 mod delete;
 mod list;
+mod sequence;
 
 /// `keys` subcommand
 #[derive(Command, Debug, Parser, Runnable)]
@@ -21,4 +22,8 @@ pub enum KeysCmd {
 
     /// Query balance for a key from a configured chain. If no key is given, the key is retrieved from the configuration file.
     Balance(balance::KeyBalanceCmd),
+
+    /// Show the on-chain and cached account sequence of a key in the running relayer,
+    /// and optionally reset the cached sequence.
+    Sequence(sequence::KeySequenceCmd),
 }
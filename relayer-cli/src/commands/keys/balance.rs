@@ -1,12 +1,19 @@
+use core::{fmt, str::FromStr};
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
+use serde::Serialize;
 
 use ibc::core::ics24_host::identifier::ChainId;
-use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::account::{Balance, DenomTrace};
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::config::{Config, DisplayDenom};
 
 use crate::application::app_config;
-use crate::cli_utils::spawn_chain_runtime;
-use crate::conclude::{exit_with_unrecoverable_error, json, Output};
+use crate::cli_utils::{spawn_chain_runtime, spawn_chain_runtime_generic};
+use crate::conclude::{exit_with_unrecoverable_error, json, write_line, Output};
 
 /// The data structure that represents the arguments when invoking the `keys balance` CLI command.
 ///
@@ -17,10 +24,28 @@ use crate::conclude::{exit_with_unrecoverable_error, json, Output};
 /// If no key name is given, it will be taken from the configuration file.
 /// If successful the balance and denominator of the account, associated with the key name
 /// on the given chain, will be displayed.
+///
+/// If the `display_denom_map` of the chain configuration maps the denomination
+/// of the balance, the balance is also displayed in the mapped denomination.
+///
+/// With the `--resolve-traces` flag, the trace of an IBC voucher denomination,
+/// ie. of the form `ibc/<HASH>`, is queried and displayed along with the balance.
+///
+/// With the `--batch` flag, the command instead reads `<CHAIN_ID> [KEY_NAME]` pairs
+/// from the standard input, one per line, and outputs the balance of each key in turn.
+///
+/// With the `--all-chains` flag, the command instead outputs the balance of the key
+/// configured for each chain of the configuration file.
+///
+/// With `--format prometheus`, the balances are output as the lines of a Prometheus
+/// text exposition, eg. for the textfile collector of the node exporter.
 #[derive(Clone, Command, Debug, Parser)]
 pub struct KeyBalanceCmd {
-    #[clap(required = true, help = "identifier of the chain")]
-    chain_id: ChainId,
+    #[clap(
+        required_unless_present_any = &["batch", "all-chains"],
+        help = "identifier of the chain"
+    )]
+    chain_id: Option<ChainId>,
 
     #[clap(
         long,
@@ -28,33 +53,196 @@ pub struct KeyBalanceCmd {
         help = "(optional) name of the key (defaults to the `key_name` defined in the config)"
     )]
     key_name: Option<String>,
+
+    #[clap(
+        long,
+        conflicts_with_all = &["chain-id", "key-name"],
+        help = "read `<CHAIN_ID> [KEY_NAME]` pairs from the standard input, one per line, \
+                and output the balance of each key"
+    )]
+    batch: bool,
+
+    #[clap(
+        long,
+        conflicts_with_all = &["chain-id", "key-name", "batch"],
+        help = "output the balance of the key configured for each chain of the configuration"
+    )]
+    all_chains: bool,
+
+    #[clap(
+        long,
+        value_name = "FORMAT",
+        default_value = "text",
+        possible_values = ["text", "prometheus"],
+        conflicts_with = "batch",
+        help = "format of the balances, `prometheus` outputting the lines of a Prometheus text exposition"
+    )]
+    format: BalanceFormat,
+
+    #[clap(
+        long,
+        help = "resolve the denomination trace of IBC vouchers, ie. of `ibc/<HASH>` denominations"
+    )]
+    resolve_traces: bool,
+}
+
+/// The format of the balances, set with `--format`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum BalanceFormat {
+    /// The output of the other commands, ie. text or JSON with the `--json` flag
+    Text,
+    /// The lines of a Prometheus text exposition, regardless of the `--json` flag
+    Prometheus,
+}
+
+impl fmt::Display for BalanceFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Prometheus => write!(f, "prometheus"),
+        }
+    }
+}
+
+impl FromStr for BalanceFormat {
+    type Err = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "prometheus" => Ok(Self::Prometheus),
+            invalid => Err(format!("unrecognized balance format: {}", invalid).into()),
+        }
+    }
+}
+
+/// A balance, along with its display amount and denomination,
+/// if the chain configuration maps its denomination.
+#[derive(Debug, Serialize)]
+struct DisplayBalance {
+    #[serde(flatten)]
+    balance: Balance,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_denom: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    denom_trace: Option<DenomTrace>,
+}
+
+impl DisplayBalance {
+    fn new(balance: Balance, display_denom: Option<&DisplayDenom>) -> Self {
+        let display_amount = display_denom
+            .and_then(|display| format_display_amount(&balance.amount, display.exponent));
+        let display_denom = display_amount
+            .as_ref()
+            .and(display_denom)
+            .map(|display| display.denom.clone());
+
+        Self {
+            balance,
+            display_amount,
+            display_denom,
+            denom_trace: None,
+        }
+    }
+
+    /// Resolves the trace of the denomination of the balance, if it is an IBC voucher.
+    fn resolve_denom_trace(&mut self, chain: &impl ChainHandle) -> Result<(), String> {
+        if let Some(hash) = self.balance.denom.strip_prefix("ibc/") {
+            let denom_trace = chain.query_denom_trace(hash.to_string()).map_err(|e| {
+                format!(
+                    "there was a problem querying the trace of denom `{}`: {}",
+                    self.balance.denom, e
+                )
+            })?;
+
+            self.denom_trace = Some(denom_trace);
+        }
+
+        Ok(())
+    }
+}
+
+impl core::fmt::Display for DisplayBalance {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match (&self.display_amount, &self.display_denom) {
+            (Some(display_amount), Some(display_denom)) => write!(
+                f,
+                "{} {} ({} {})",
+                display_amount, display_denom, self.balance.amount, self.balance.denom
+            ),
+            _ => write!(f, "{} {}", self.balance.amount, self.balance.denom),
+        }?;
+
+        match &self.denom_trace {
+            Some(trace) => write!(
+                f,
+                " [{} = {}/{}]",
+                self.balance.denom, trace.path, trace.base_denom
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The balance of a key read from the standard input in batch mode,
+/// or of the key configured for a chain with `--all-chains`.
+#[derive(Debug, Serialize)]
+struct BatchBalance {
+    chain_id: ChainId,
+    key_name: String,
+    #[serde(flatten)]
+    balance: DisplayBalance,
 }
 
 impl Runnable for KeyBalanceCmd {
     fn run(&self) {
         let config = app_config();
 
-        let chain = spawn_chain_runtime(&config, &self.chain_id)
-            .unwrap_or_else(exit_with_unrecoverable_error);
+        if self.batch {
+            run_batch(&config, self.resolve_traces);
+            return;
+        }
+
+        if self.all_chains {
+            run_all_chains(&config, self.resolve_traces, self.format);
+        }
+
+        // Ensured by clap, as the chain identifier is required unless `--batch` is given.
+        let chain_id = self.chain_id.as_ref().expect("missing chain identifier");
+
+        let chain =
+            spawn_chain_runtime(&config, chain_id).unwrap_or_else(exit_with_unrecoverable_error);
         let key_name = self.key_name.clone();
 
+        let chain_config = chain.config().unwrap_or_else(exit_with_unrecoverable_error);
+
         match chain.query_balance(key_name.clone()) {
-            Ok(balance) if json() => Output::success(balance).exit(),
             Ok(balance) => {
-                // Retrieve the key name string to output.
-                let key_name_str = match key_name {
-                    Some(name) => name,
-                    None => {
-                        let chain_config =
-                            chain.config().unwrap_or_else(exit_with_unrecoverable_error);
-                        chain_config.key_name
+                let display_denom = chain_config.display_denom_map.get(&balance.denom);
+                let mut balance = DisplayBalance::new(balance, display_denom);
+
+                if self.resolve_traces {
+                    if let Err(e) = balance.resolve_denom_trace(&chain) {
+                        Output::error(e).exit()
                     }
-                };
-                Output::success_msg(format!(
-                    "balance for key `{}`: {} {}",
-                    key_name_str, balance.amount, balance.denom
-                ))
-                .exit()
+                }
+
+                if self.format == BalanceFormat::Prometheus {
+                    print_prometheus_balances(&[(chain_id.clone(), balance)]);
+                    std::process::exit(0);
+                }
+
+                if json() {
+                    Output::success(balance).exit()
+                }
+
+                // Retrieve the key name string to output.
+                let key_name_str = key_name.unwrap_or(chain_config.key_name);
+
+                Output::success_msg(format!("balance for key `{}`: {}", key_name_str, balance))
+                    .exit()
             }
             Err(e) => Output::error(format!(
                 "there was a problem querying the chain balance: {}",
@@ -64,3 +252,319 @@ impl Runnable for KeyBalanceCmd {
         }
     }
 }
+
+/// Reads `<CHAIN_ID> [KEY_NAME]` pairs from the standard input and outputs the
+/// balance of each key, one line per input line.
+///
+/// The runtime of each chain is only spawned once, on its first occurrence.
+/// A line which cannot be parsed or whose balance cannot be queried is reported
+/// along with its line number, without aborting the batch.
+fn run_batch(config: &Config, resolve_traces: bool) {
+    let mut chains: HashMap<ChainId, Result<BaseChainHandle, String>> = HashMap::new();
+
+    for (index, line) in io::stdin().lock().lines().enumerate() {
+        let line_number = index + 1;
+
+        let output = match line {
+            Ok(line) if line.trim().is_empty() => continue,
+            Ok(line) => match batch_balance(config, &mut chains, &line, resolve_traces) {
+                Ok(balance) if json() => Output::success(balance),
+                Ok(balance) => Output::success_msg(format!(
+                    "balance for key `{}` on chain `{}`: {}",
+                    balance.key_name, balance.chain_id, balance.balance
+                )),
+                Err(e) => Output::error(e),
+            },
+            Err(e) => Output::error(format!("failed to read line: {}", e)),
+        };
+
+        print_batch_output(line_number, output);
+    }
+}
+
+fn batch_balance(
+    config: &Config,
+    chains: &mut HashMap<ChainId, Result<BaseChainHandle, String>>,
+    line: &str,
+    resolve_traces: bool,
+) -> Result<BatchBalance, String> {
+    let (chain_id, key_name) = parse_batch_line(line)?;
+
+    let chain = chains
+        .entry(chain_id.clone())
+        .or_insert_with(|| {
+            spawn_chain_runtime_generic::<BaseChainHandle>(config, &chain_id)
+                .map_err(|e| e.to_string())
+        })
+        .as_ref()
+        .map_err(Clone::clone)?;
+
+    key_balance(chain, chain_id, key_name, resolve_traces)
+}
+
+/// Outputs the balance of the key configured for each chain of the configuration,
+/// one line per chain, in the given `format`, then exits.
+///
+/// A chain whose balance cannot be queried is reported without aborting the others, in
+/// which case the command exits with an error. With the `prometheus` format, the command
+/// fails right away instead, without outputting any balance, so that no partial set of
+/// balances is exported.
+fn run_all_chains(config: &Config, resolve_traces: bool, format: BalanceFormat) -> ! {
+    let mut balances = Vec::new();
+    let mut failed = false;
+
+    for chain_config in config.chains.iter() {
+        let chain_id = chain_config.id.clone();
+
+        let result = spawn_chain_runtime_generic::<BaseChainHandle>(config, &chain_id)
+            .map_err(|e| e.to_string())
+            .and_then(|chain| key_balance(&chain, chain_id.clone(), None, resolve_traces))
+            .map_err(|e| format!("chain `{}`: {}", chain_id, e));
+
+        failed |= result.is_err();
+
+        match (format, result) {
+            (BalanceFormat::Prometheus, Ok(balance)) => {
+                balances.push((balance.chain_id, balance.balance));
+            }
+            (BalanceFormat::Prometheus, Err(e)) => Output::error(e).exit(),
+            (BalanceFormat::Text, result) => {
+                let output = match result {
+                    Ok(balance) if json() => Output::success(balance),
+                    Ok(balance) => Output::success_msg(format!(
+                        "balance for key `{}` on chain `{}`: {}",
+                        balance.key_name, balance.chain_id, balance.balance
+                    )),
+                    Err(e) => Output::error(e),
+                };
+
+                if output.stream().is_err() {
+                    // The reader of the output went away, eg. the other end of a pipe.
+                    std::process::exit(0);
+                }
+            }
+        }
+    }
+
+    if format == BalanceFormat::Prometheus {
+        print_prometheus_balances(&balances);
+    }
+
+    std::process::exit(if failed { 1 } else { 0 });
+}
+
+/// Queries the balance of the given key, or of the key configured for the chain.
+fn key_balance(
+    chain: &impl ChainHandle,
+    chain_id: ChainId,
+    key_name: Option<String>,
+    resolve_traces: bool,
+) -> Result<BatchBalance, String> {
+    let chain_config = chain.config().map_err(|e| e.to_string())?;
+
+    let balance = chain
+        .query_balance(key_name.clone())
+        .map_err(|e| format!("there was a problem querying the chain balance: {}", e))?;
+
+    let display_denom = chain_config.display_denom_map.get(&balance.denom);
+    let mut balance = DisplayBalance::new(balance, display_denom);
+
+    if resolve_traces {
+        balance.resolve_denom_trace(chain)?;
+    }
+
+    Ok(BatchBalance {
+        chain_id,
+        key_name: key_name.unwrap_or(chain_config.key_name),
+        balance,
+    })
+}
+
+/// Parses a `<CHAIN_ID> [KEY_NAME]` line of the batch input.
+fn parse_batch_line(line: &str) -> Result<(ChainId, Option<String>), String> {
+    let mut words = line.split_whitespace();
+
+    match (words.next(), words.next(), words.next()) {
+        (Some(chain_id), key_name, None) => Ok((
+            ChainId::from_string(chain_id),
+            key_name.map(ToString::to_string),
+        )),
+        _ => Err(format!(
+            "malformed line `{}`, expected `<CHAIN_ID> [KEY_NAME]`",
+            line.trim()
+        )),
+    }
+}
+
+/// The name of the metric of the balances output with `--format prometheus`.
+const BALANCE_METRIC: &str = "relayer_account_balance";
+
+/// Formats the given balances as the lines of a Prometheus text exposition,
+/// skipping the balances whose amount is not an integer.
+fn prometheus_balances(balances: &[(ChainId, DisplayBalance)]) -> Vec<String> {
+    let mut lines = vec![
+        format!(
+            "# HELP {} The balance of the relayer account on the chain, in the given denomination",
+            BALANCE_METRIC
+        ),
+        format!("# TYPE {} gauge", BALANCE_METRIC),
+    ];
+
+    lines.extend(
+        balances
+            .iter()
+            .filter(|(_, balance)| {
+                let amount = &balance.balance.amount;
+                !amount.is_empty() && amount.bytes().all(|b| b.is_ascii_digit())
+            })
+            .map(|(chain_id, balance)| {
+                format!(
+                    "{}{{chain=\"{}\",denom=\"{}\"}} {}",
+                    BALANCE_METRIC,
+                    escape_label_value(chain_id.as_str()),
+                    escape_label_value(&balance.balance.denom),
+                    balance.balance.amount
+                )
+            }),
+    );
+
+    lines
+}
+
+/// Escapes a label value of the Prometheus text exposition format.
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn print_prometheus_balances(balances: &[(ChainId, DisplayBalance)]) {
+    for line in prometheus_balances(balances) {
+        if write_line(&line).is_err() {
+            std::process::exit(0);
+        }
+    }
+}
+
+/// Prints the output for a line of the batch input, tagged with its line number,
+/// as soon as it is available.
+fn print_batch_output(line_number: usize, output: Output) {
+    let line = if json() {
+        let mut value = output.into_json();
+        if let Some(map) = value.as_object_mut() {
+            map.insert("line".to_string(), line_number.into());
+        }
+
+        serde_json::to_string(&value).unwrap()
+    } else {
+        format!("line {}: {}: {}", line_number, output.status, output.result)
+    };
+
+    if write_line(&line).is_err() {
+        // The reader of the output went away, eg. the other end of a pipe.
+        std::process::exit(0);
+    }
+}
+
+/// Scales down the given integer amount by `10^exponent`, eg. `1500000` with an
+/// exponent of 6 is formatted as `1.5`. The amount is handled as a string since
+/// balances may not fit into a primitive integer.
+///
+/// Returns `None` if the amount is not a non-negative integer.
+fn format_display_amount(amount: &str, exponent: u32) -> Option<String> {
+    if amount.is_empty() || !amount.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let exponent = exponent as usize;
+    let padded = format!("{:0>width$}", amount, width = exponent + 1);
+    let (integral, fractional) = padded.split_at(padded.len() - exponent);
+
+    let integral = integral.trim_start_matches('0');
+    let integral = if integral.is_empty() { "0" } else { integral };
+    let fractional = fractional.trim_end_matches('0');
+
+    if fractional.is_empty() {
+        Some(integral.to_string())
+    } else {
+        Some(format!("{}.{}", integral, fractional))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc::core::ics24_host::identifier::ChainId;
+    use ibc_relayer::account::Balance;
+
+    use super::{
+        escape_label_value, format_display_amount, parse_batch_line, prometheus_balances,
+        DisplayBalance,
+    };
+
+    #[test]
+    fn display_amount_is_scaled_by_exponent() {
+        assert_eq!(format_display_amount("1500000", 6).as_deref(), Some("1.5"));
+        assert_eq!(format_display_amount("1000000", 6).as_deref(), Some("1"));
+        assert_eq!(format_display_amount("42", 6).as_deref(), Some("0.000042"));
+        assert_eq!(format_display_amount("0", 6).as_deref(), Some("0"));
+        assert_eq!(format_display_amount("0042", 0).as_deref(), Some("42"));
+    }
+
+    #[test]
+    fn display_amount_rejects_non_integers() {
+        assert_eq!(format_display_amount("", 6), None);
+        assert_eq!(format_display_amount("1.5", 6), None);
+        assert_eq!(format_display_amount("-5", 6), None);
+    }
+
+    #[test]
+    fn batch_lines_are_parsed() {
+        let (chain_id, key_name) = parse_batch_line("  ibc-0   wallet ").unwrap();
+        assert_eq!(chain_id.as_str(), "ibc-0");
+        assert_eq!(key_name.as_deref(), Some("wallet"));
+
+        let (chain_id, key_name) = parse_batch_line("ibc-1").unwrap();
+        assert_eq!(chain_id.as_str(), "ibc-1");
+        assert_eq!(key_name, None);
+
+        assert!(parse_batch_line("ibc-0 wallet extra").is_err());
+        assert!(parse_batch_line("   ").is_err());
+    }
+
+    fn balance(amount: &str, denom: &str) -> DisplayBalance {
+        DisplayBalance::new(
+            Balance {
+                amount: amount.to_string(),
+                denom: denom.to_string(),
+            },
+            None,
+        )
+    }
+
+    #[test]
+    fn label_values_are_escaped() {
+        assert_eq!(escape_label_value("ibc-0"), "ibc-0");
+        assert_eq!(escape_label_value("a\\b\"c\nd"), "a\\\\b\\\"c\\nd");
+    }
+
+    #[test]
+    fn balances_are_formatted_as_prometheus_lines() {
+        let lines = prometheus_balances(&[
+            (ChainId::from_string("ibc-0"), balance("1000", "stake")),
+            (
+                ChainId::from_string("ibc-1"),
+                balance("invalid", "samoleans"),
+            ),
+        ]);
+
+        assert_eq!(
+            lines,
+            vec![
+                "# HELP relayer_account_balance The balance of the relayer account on the chain, in the given denomination",
+                "# TYPE relayer_account_balance gauge",
+                "relayer_account_balance{chain=\"ibc-0\",denom=\"stake\"} 1000",
+            ]
+        );
+    }
+}
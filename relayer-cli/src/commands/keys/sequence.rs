@@ -0,0 +1,93 @@
+use core::time::Duration;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc_relayer::account::AccountInfo;
+use ibc_relayer::rest::request::AccountSequenceParams;
+
+use crate::application::app_config;
+use crate::cli_utils::rest_post;
+use crate::conclude::{json, Output};
+use crate::error::Error;
+
+/// How long to wait for the running relayer to query the account.
+const SEQUENCE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The data structure that represents the arguments when invoking the `keys sequence` CLI command.
+///
+/// `keys sequence <chain_id> --key-name <KEY_NAME> [--reset]`
+///
+/// Displays the on-chain sequence of the account of the key, along with the sequence
+/// cached by the relayer started with `hermes start`, which is reached through its
+/// REST server, so that the `[rest]` section of the configuration must be enabled.
+///
+/// With the `--reset` flag, the cached sequence is cleared, so that the running
+/// relayer queries the account again before submitting its next transaction,
+/// eg. after it got stuck on account sequence mismatches.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct KeySequenceCmd {
+    #[clap(required = true, help = "identifier of the chain")]
+    chain_id: ChainId,
+
+    #[clap(
+        long,
+        short,
+        help = "(optional) name of the key (defaults to the `key_name` defined in the config)"
+    )]
+    key_name: Option<String>,
+
+    #[clap(
+        long,
+        help = "clear the account sequence cached by the running relayer"
+    )]
+    reset: bool,
+}
+
+impl KeySequenceCmd {
+    fn execute(&self) -> Result<AccountInfo, Error> {
+        let config = app_config();
+
+        let params = AccountSequenceParams {
+            key_name: self.key_name.clone(),
+            reset: self.reset,
+        };
+
+        rest_post(
+            &config,
+            &format!("/chain/{}/sequence", self.chain_id),
+            &params,
+            SEQUENCE_TIMEOUT,
+        )
+    }
+}
+
+impl Runnable for KeySequenceCmd {
+    fn run(&self) {
+        let account = match self.execute() {
+            Ok(account) => account,
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        };
+
+        if json() {
+            Output::success(account).exit()
+        }
+
+        let cached = match account.cached_sequence {
+            Some(cached) => cached.to_string(),
+            None => "none".to_string(),
+        };
+
+        let mut msg = format!(
+            "account {}: on-chain sequence {}, cached sequence {}",
+            account.address, account.sequence, cached
+        );
+
+        if self.reset {
+            msg.push_str(", the cached sequence was cleared");
+        }
+
+        Output::success_msg(msg).exit()
+    }
+}
@@ -1,18 +1,22 @@
 use alloc::sync::Arc;
 use core::{fmt, ops::Deref, str::FromStr};
+use std::path::{Path, PathBuf};
 use std::thread;
 
 use abscissa_core::clap::Parser;
 use abscissa_core::{application::fatal_error, Runnable};
 use itertools::Itertools;
 use tokio::runtime::Runtime as TokioRuntime;
-use tracing::{error, info};
+use tracing::{debug, error, info};
 
 use ibc::{core::ics24_host::identifier::ChainId, events::IbcEvent};
 
 use ibc_relayer::{
     config::ChainConfig,
-    event::monitor::{EventMonitor, EventReceiver},
+    event::{
+        dump::BatchDump,
+        monitor::{EventMonitor, EventReceiver},
+    },
 };
 
 use crate::prelude::*;
@@ -67,6 +71,12 @@ pub struct ListenCmd {
     /// Listen for all events by default (available: Tx, NewBlock).
     #[clap(short = 'e', long = "event", value_name = "EVENT")]
     events: Vec<EventFilter>,
+
+    /// Write each event batch received to a JSON file in the given directory,
+    /// to be replayed with `replay batch`. All the events of the batches are
+    /// written, regardless of the event types listened for.
+    #[clap(long = "dump-batches", value_name = "DIR")]
+    dump_batches: Option<PathBuf>,
 }
 
 impl ListenCmd {
@@ -83,7 +93,12 @@ impl ListenCmd {
             self.events.as_slice()
         };
 
-        listen(chain_config, events)
+        if let Some(dir) = &self.dump_batches {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("could not create directory '{}': {}", dir.display(), e))?;
+        }
+
+        listen(chain_config, events, self.dump_batches.as_deref())
     }
 }
 
@@ -94,10 +109,11 @@ impl Runnable for ListenCmd {
     }
 }
 
-/// Listen to events
+/// Listen to events, writing the event batches to `dump_dir` if given
 pub fn listen(
     config: &ChainConfig,
     filters: &[EventFilter],
+    dump_dir: Option<&Path>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let rt = Arc::new(TokioRuntime::new()?);
     let (event_monitor, rx) = subscribe(config, rt)?;
@@ -113,6 +129,13 @@ pub fn listen(
     while let Ok(event_batch) = rx.recv() {
         match event_batch {
             Ok(batch) => {
+                if let Some(dir) = dump_dir {
+                    match BatchDump::new(&batch).write_to_dir(dir) {
+                        Ok(path) => debug!("wrote event batch to '{}'", path.display()),
+                        Err(e) => error!("- could not write event batch: {}", e),
+                    }
+                }
+
                 let matching_events = batch
                     .events
                     .into_iter()
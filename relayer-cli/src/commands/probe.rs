@@ -0,0 +1,266 @@
+use core::time::Duration;
+use std::thread;
+use std::time::Instant;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc::applications::transfer::Amount;
+use ibc::core::ics04_channel::packet::{Packet, Sequence};
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::events::IbcEvent;
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::link::{Link, LinkParameters};
+use ibc_relayer::transfer::{build_and_send_transfer_messages, TransferOptions};
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// How long to wait between two clearing passes while waiting for the probe packet.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// `probe` subcommands
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum ProbeCmds {
+    /// Send a probe packet over a channel, relay it and its acknowledgement,
+    /// and report the round-trip time.
+    Path(ProbePathCmd),
+}
+
+/// Sends a minimal transfer from the relayer wallet on chain A to the relayer wallet
+/// on chain B, then relays it with one-shot clearing passes on both ends of the channel,
+/// until the acknowledgement of the probe packet is relayed back to chain A.
+///
+/// The clearing passes relay all the pending packets of the channel, not only the probe.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct ProbePathCmd {
+    #[clap(
+        required = true,
+        help = "identifier of the chain to send the probe packet from"
+    )]
+    chain_a_id: ChainId,
+
+    #[clap(
+        required = true,
+        help = "identifier of the chain to send the probe packet to"
+    )]
+    chain_b_id: ChainId,
+
+    #[clap(
+        required = true,
+        help = "identifier of the channel on the chain given by <CHAIN_A_ID>"
+    )]
+    channel_id: ChannelId,
+
+    #[clap(
+        long = "port",
+        default_value = "transfer",
+        help = "identifier of the port on the chain given by <CHAIN_A_ID>"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        long,
+        help = "denomination of the coins to send (defaults to the gas price denom of <CHAIN_A_ID>)"
+    )]
+    denom: Option<String>,
+
+    #[clap(long, default_value = "1", help = "amount of coins to send")]
+    amount: Amount,
+
+    #[clap(
+        long = "timeout",
+        default_value = "120",
+        value_name = "SECONDS",
+        help = "how long to wait for the acknowledgement of the probe packet, \
+                which is also the timeout of the packet"
+    )]
+    timeout_seconds: u64,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ProbeOutcome {
+    /// The acknowledgement of the probe packet was relayed back to chain A.
+    Acknowledged,
+    /// The probe packet timed out, and its timeout was relayed back to chain A.
+    TimedOut,
+    /// The probe packet was neither acknowledged nor timed out in time.
+    Unconfirmed,
+}
+
+/// The result of a probe of a channel.
+#[derive(Debug, Serialize)]
+struct ProbeReport {
+    chain_a: ChainId,
+    chain_b: ChainId,
+    port_id: PortId,
+    channel_id: ChannelId,
+    sequence: Sequence,
+    outcome: ProbeOutcome,
+    /// The time from sending the probe packet to its reception on chain B, if it was received.
+    received_after: Option<String>,
+    /// The time from sending the probe packet to the relaying of its acknowledgement
+    /// or timeout back to chain A, if it was relayed.
+    round_trip: Option<String>,
+    round_trip_ms: Option<u128>,
+}
+
+impl ProbePathCmd {
+    fn execute(&self) -> Result<ProbeReport, Error> {
+        let config = app_config();
+
+        if self.timeout_seconds == 0 {
+            return Err(Error::cli_arg(
+                "the probe timeout must be greater than zero".to_string(),
+            ));
+        }
+
+        let (chains, _) = spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.chain_a_id,
+            &self.port_id,
+            &self.channel_id,
+        )?;
+
+        if chains.dst.id() != self.chain_b_id {
+            return Err(Error::cli_arg(format!(
+                "channel {}/{} on chain {} does not lead to chain {} but to chain {}",
+                self.port_id,
+                self.channel_id,
+                self.chain_a_id,
+                self.chain_b_id,
+                chains.dst.id()
+            )));
+        }
+
+        let denom = match &self.denom {
+            Some(denom) => denom.clone(),
+            None => chains.src.config().map_err(Error::relayer)?.gas_price.denom,
+        };
+
+        let timeout = Duration::from_secs(self.timeout_seconds);
+
+        let opts = TransferOptions {
+            packet_src_port_id: self.port_id.clone(),
+            packet_src_channel_id: self.channel_id,
+            amount: self.amount,
+            denom,
+            receiver: None,
+            timeout_height_offset: 0,
+            timeout_duration: timeout,
+            number_msgs: 1,
+        };
+
+        let link_opts = LinkParameters {
+            src_port_id: self.port_id.clone(),
+            src_channel_id: self.channel_id,
+        };
+        let fwd_link =
+            Link::new_from_opts(chains.src.clone(), chains.dst.clone(), link_opts, false)
+                .map_err(Error::link)?;
+        let rev_link = fwd_link.reverse(false).map_err(Error::link)?;
+
+        let start = Instant::now();
+
+        let events = build_and_send_transfer_messages(&chains.src, &chains.dst, &opts)
+            .map_err(Error::transfer)?;
+
+        let sequence = events
+            .iter()
+            .find_map(|event| match event {
+                IbcEvent::SendPacket(send) => Some(send.packet.sequence),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                Error::cli_arg("the probe transfer did not emit a send packet event".to_string())
+            })?;
+
+        info!("sent probe packet with sequence {}", sequence);
+
+        let is_probe = |packet: &Packet| {
+            packet.sequence == sequence
+                && packet.source_port == self.port_id
+                && packet.source_channel == self.channel_id
+        };
+
+        let mut received_after = None;
+        let mut outcome = ProbeOutcome::Unconfirmed;
+
+        while start.elapsed() < timeout {
+            for event in fwd_link
+                .relay_recv_packet_and_timeout_messages()
+                .map_err(Error::link)?
+            {
+                match event {
+                    IbcEvent::WriteAcknowledgement(ev) if is_probe(&ev.packet) => {
+                        received_after.get_or_insert_with(|| start.elapsed());
+                    }
+                    IbcEvent::TimeoutPacket(ev) if is_probe(&ev.packet) => {
+                        outcome = ProbeOutcome::TimedOut;
+                    }
+                    _ => {}
+                }
+            }
+
+            if outcome == ProbeOutcome::Unconfirmed {
+                let acked = rev_link
+                    .relay_ack_packet_messages()
+                    .map_err(Error::link)?
+                    .iter()
+                    .any(|event| match event {
+                        IbcEvent::AcknowledgePacket(ev) => is_probe(&ev.packet),
+                        _ => false,
+                    });
+
+                if acked {
+                    outcome = ProbeOutcome::Acknowledged;
+                }
+            }
+
+            if outcome != ProbeOutcome::Unconfirmed {
+                break;
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+
+        let round_trip = match outcome {
+            ProbeOutcome::Unconfirmed => None,
+            _ => Some(start.elapsed()),
+        };
+
+        Ok(ProbeReport {
+            chain_a: self.chain_a_id.clone(),
+            chain_b: self.chain_b_id.clone(),
+            port_id: self.port_id.clone(),
+            channel_id: self.channel_id,
+            sequence,
+            outcome,
+            received_after: received_after.map(format_elapsed),
+            round_trip: round_trip.map(format_elapsed),
+            round_trip_ms: round_trip.map(|elapsed| elapsed.as_millis()),
+        })
+    }
+}
+
+impl Runnable for ProbePathCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(report) if report.outcome == ProbeOutcome::Acknowledged => {
+                Output::success(report).exit()
+            }
+            Ok(report) => Output::with_error().with_result(report).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+/// Formats the given duration, rounded down to the millisecond.
+fn format_elapsed(elapsed: Duration) -> String {
+    let millis = Duration::from_millis(elapsed.as_millis() as u64);
+    humantime::format_duration(millis).to_string()
+}
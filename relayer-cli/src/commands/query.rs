@@ -3,25 +3,41 @@
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 
+use crate::commands::query::chain::QueryChainCmds;
 use crate::commands::query::channel_client::QueryChannelClientCmd;
+use crate::commands::query::channel_drift::QueryChannelDriftCmd;
 use crate::commands::query::channel_ends::QueryChannelEndsCmd;
+use crate::commands::query::channel_escrow::QueryChannelEscrowCmd;
 use crate::commands::query::channels::QueryChannelsCmd;
 use crate::commands::query::packet::QueryPacketCmds;
 
+mod account;
+mod chain;
 mod channel;
 mod channel_client;
+mod channel_drift;
 mod channel_ends;
+mod channel_escrow;
 mod channels;
 mod client;
 mod clients;
 mod connection;
 mod connections;
+mod gas_price;
 mod packet;
+mod supply;
 mod tx;
 
 /// `query` subcommand
 #[derive(Command, Debug, Parser, Runnable)]
 pub enum QueryCmd {
+    /// Query the account number and sequence of the relayer account
+    Account(account::QueryAccountCmd),
+
+    /// Query information about a chain as a whole
+    #[clap(subcommand)]
+    Chain(QueryChainCmds),
+
     /// Query information about clients
     #[clap(subcommand)]
     Client(QueryClientCmds),
@@ -43,10 +59,16 @@ pub enum QueryCmd {
     /// Query the identifiers of all channels on a given chain
     Channels(QueryChannelsCmd),
 
+    /// Query the minimum gas prices of a chain node
+    GasPrice(gas_price::QueryGasPriceCmd),
+
     /// Query information about packets
     #[clap(subcommand)]
     Packet(QueryPacketCmds),
 
+    /// Query the total supply of a denomination
+    Supply(supply::QuerySupplyCmd),
+
     /// Query information about transactions
     #[clap(subcommand)]
     Tx(tx::QueryTxCmd),
@@ -57,6 +79,9 @@ pub enum QueryClientCmds {
     /// Query the client full state
     State(client::QueryClientStateCmd),
 
+    /// Query the client latest height, trusting period and expiry, and whether it is frozen
+    Status(client::QueryClientStatusCmd),
+
     /// Query the client consensus state
     Consensus(client::QueryClientConsensusCmd),
 
@@ -86,4 +111,10 @@ pub enum QueryChannelCmds {
 
     /// Query channel ends and underlying connection and client objects
     Ends(QueryChannelEndsCmd),
+
+    /// Compare the cached channel end with the channel end on chain
+    Drift(QueryChannelDriftCmd),
+
+    /// Query the escrow account of a channel and its balance
+    Escrow(QueryChannelEscrowCmd),
 }
@@ -0,0 +1,68 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc_relayer::account::AccountInfo;
+use ibc_relayer::chain::handle::ChainHandle;
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{json, Output};
+use crate::error::Error;
+use crate::prelude::*;
+
+/// Query the on-chain account number and sequence of the relayer account on a chain,
+/// along with the account sequence cached by the relayer, if any.
+///
+/// A cached sequence which differs from the on-chain sequence usually explains
+/// transactions failing with an account sequence mismatch. Note that this command
+/// spawns its own chain runtime, which only caches the account once it submits a
+/// transaction, so the cached sequence is only reported by long-lived chain handles.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct QueryAccountCmd {
+    #[clap(required = true, help = "identifier of the chain to query")]
+    chain_id: ChainId,
+
+    #[clap(
+        long,
+        short,
+        help = "(optional) name of the key (defaults to the `key_name` defined in the config)"
+    )]
+    key_name: Option<String>,
+}
+
+impl QueryAccountCmd {
+    fn execute(&self) -> Result<AccountInfo, Error> {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)?;
+
+        chain
+            .query_account_info(self.key_name.clone())
+            .map_err(Error::relayer)
+    }
+}
+
+impl Runnable for QueryAccountCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(account) if json() => Output::success(account).exit(),
+            Ok(account) => Output::success_msg(describe_account(&account)).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+fn describe_account(account: &AccountInfo) -> String {
+    let description = format!(
+        "account {}: number {}, sequence {}",
+        account.address, account.account_number, account.sequence
+    );
+
+    match account.cached_sequence {
+        Some(cached) if cached != account.sequence => format!(
+            "{} (the relayer has cached sequence {}, which differs from the on-chain sequence)",
+            description, cached
+        ),
+        _ => description,
+    }
+}
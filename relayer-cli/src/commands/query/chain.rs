@@ -0,0 +1,10 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+mod backlog;
+
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum QueryChainCmds {
+    /// List the channels of a chain along with their pending packets and acknowledgements
+    Backlog(backlog::QueryChainBacklogCmd),
+}
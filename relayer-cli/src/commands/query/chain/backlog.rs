@@ -0,0 +1,174 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc::core::ics02_client::client_state::ClientState;
+use ibc::core::ics04_channel::channel::State;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer::chain::counterparty::{channel_connection_client, pending_packet_summary};
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::chain::requests::{PageRequest, QueryChannelsRequest};
+use ibc_relayer::registry::Registry;
+
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// The backlog of a channel, ie. the packets sent on the queried chain which are
+/// not yet received by the counterparty chain, and the acknowledgements written by
+/// the counterparty chain which are not yet relayed back to the queried chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+struct ChannelBacklog {
+    port_id: PortId,
+    channel_id: ChannelId,
+    counterparty_chain_id: ChainId,
+    unreceived_packets: usize,
+    unreceived_acks: usize,
+}
+
+impl ChannelBacklog {
+    fn total(&self) -> usize {
+        self.unreceived_packets + self.unreceived_acks
+    }
+}
+
+/// Lists the open or closed channels of the given chain whose counterparty chain is
+/// present in the configuration, along with the number of their pending packets and
+/// acknowledgements, the channels with the largest backlog first.
+///
+/// The pending packets are queried as the packet workers query them when clearing
+/// packets. Nothing is relayed.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct QueryChainBacklogCmd {
+    #[clap(required = true, help = "identifier of the chain to query")]
+    chain_id: ChainId,
+}
+
+impl QueryChainBacklogCmd {
+    fn execute(&self) -> Result<Vec<ChannelBacklog>, Error> {
+        let config = app_config();
+
+        let mut registry = <Registry<BaseChainHandle>>::new((*config).clone());
+        let chain = registry
+            .get_or_spawn(&self.chain_id)
+            .map_err(Error::spawn)?;
+
+        let channels = chain
+            .query_channels(QueryChannelsRequest {
+                pagination: Some(PageRequest::all()),
+            })
+            .map_err(Error::relayer)?;
+
+        let mut backlogs = Vec::new();
+
+        for channel in channels {
+            let state = channel.channel_end.state();
+            if !matches!(state, State::Open | State::Closed) {
+                continue;
+            }
+
+            let chan_conn_cli =
+                match channel_connection_client(&chain, &channel.port_id, &channel.channel_id) {
+                    Ok(chan_conn_cli) => chan_conn_cli,
+                    Err(e) => {
+                        warn!(
+                            "skipping channel {}/{}: {}",
+                            channel.port_id, channel.channel_id, e
+                        );
+                        continue;
+                    }
+                };
+
+            let counterparty_chain_id = chan_conn_cli.client.client_state.chain_id();
+
+            if !config.has_chain(&counterparty_chain_id) {
+                debug!(
+                    "skipping channel {}/{} to chain {} missing from the configuration",
+                    channel.port_id, channel.channel_id, counterparty_chain_id
+                );
+                continue;
+            }
+
+            let counterparty_chain = registry
+                .get_or_spawn(&counterparty_chain_id)
+                .map_err(Error::spawn)?;
+
+            match pending_packet_summary(&chain, &counterparty_chain, &channel) {
+                Ok(pending) => backlogs.push(ChannelBacklog {
+                    port_id: channel.port_id,
+                    channel_id: channel.channel_id,
+                    counterparty_chain_id,
+                    unreceived_packets: pending.unreceived_packets.len(),
+                    unreceived_acks: pending.unreceived_acks.len(),
+                }),
+                Err(e) => warn!(
+                    "failed to query the pending packets of channel {}/{}: {}",
+                    channel.port_id, channel.channel_id, e
+                ),
+            }
+        }
+
+        sort_by_backlog(&mut backlogs);
+
+        Ok(backlogs)
+    }
+}
+
+/// Sorts the channels by decreasing backlog, then by channel and port identifiers.
+fn sort_by_backlog(backlogs: &mut [ChannelBacklog]) {
+    backlogs.sort_by(|a, b| {
+        b.total()
+            .cmp(&a.total())
+            .then_with(|| a.channel_id.cmp(&b.channel_id))
+            .then_with(|| a.port_id.cmp(&b.port_id))
+    });
+}
+
+impl Runnable for QueryChainBacklogCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(backlogs) => Output::success(backlogs).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+    use super::{sort_by_backlog, ChannelBacklog};
+
+    fn backlog(channel: u64, unreceived_packets: usize, unreceived_acks: usize) -> ChannelBacklog {
+        ChannelBacklog {
+            port_id: PortId::transfer(),
+            channel_id: ChannelId::new(channel),
+            counterparty_chain_id: ChainId::new("ibc".to_string(), 1),
+            unreceived_packets,
+            unreceived_acks,
+        }
+    }
+
+    #[test]
+    fn largest_backlogs_come_first() {
+        let mut backlogs = vec![
+            backlog(0, 1, 0),
+            backlog(1, 0, 0),
+            backlog(2, 2, 3),
+            backlog(3, 0, 1),
+        ];
+
+        sort_by_backlog(&mut backlogs);
+
+        let channels: Vec<_> = backlogs.iter().map(|b| b.channel_id).collect();
+        assert_eq!(
+            channels,
+            vec![
+                ChannelId::new(2),
+                ChannelId::new(0),
+                ChannelId::new(3),
+                ChannelId::new(1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use core::time::Duration;
+use std::thread;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc::core::ics04_channel::channel::ChannelEnd;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::Height;
+use ibc_relayer::chain::handle::{CachingChainHandle, ChainHandle};
+use ibc_relayer::chain::requests::{IncludeProof, QueryChannelRequest};
+
+use crate::cli_utils::spawn_chain_runtime_generic;
+use crate::conclude::{json, Output};
+use crate::error::Error;
+use crate::prelude::*;
+
+/// A field of a channel end whose cached value differs from its value on chain.
+#[derive(Debug, Serialize)]
+struct ChannelDrift {
+    field: &'static str,
+    cached: String,
+    on_chain: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ChannelDriftReport {
+    cached: ChannelEnd,
+    on_chain: ChannelEnd,
+    drift: Vec<ChannelDrift>,
+}
+
+/// Queries a channel end through the same cache the relayer queries channel ends through,
+/// then straight from the chain, and reports the fields whose values differ.
+///
+/// The cache only lives as long as the command, so the cached channel end is the one
+/// queried when the command starts. Use `--after` to let the cached channel end age before
+/// querying the chain again, up to the time-to-live of the cached channel ends.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct QueryChannelDriftCmd {
+    #[clap(required = true, help = "identifier of the chain to query")]
+    chain_id: ChainId,
+
+    #[clap(required = true, help = "identifier of the port to query")]
+    port_id: PortId,
+
+    #[clap(required = true, help = "identifier of the channel to query")]
+    channel_id: ChannelId,
+
+    #[clap(
+        long = "after",
+        default_value = "0",
+        value_name = "SECONDS",
+        help = "how long to wait after caching the channel end before querying it from the chain"
+    )]
+    after_seconds: u64,
+}
+
+impl QueryChannelDriftCmd {
+    fn execute(&self) -> Result<ChannelDriftReport, Error> {
+        let config = app_config();
+        debug!("Options: {:?}", self);
+
+        let chain = spawn_chain_runtime_generic::<CachingChainHandle>(&config, &self.chain_id)?;
+
+        let request = QueryChannelRequest {
+            port_id: self.port_id.clone(),
+            channel_id: self.channel_id,
+            height: Height::zero(),
+        };
+
+        let (cached, _) = chain
+            .query_channel(request.clone(), IncludeProof::No)
+            .map_err(Error::relayer)?;
+
+        thread::sleep(Duration::from_secs(self.after_seconds));
+
+        let on_chain = chain
+            .query_channel_uncached(request)
+            .map_err(Error::relayer)?;
+
+        let drift = channel_drift(&cached, &on_chain);
+
+        Ok(ChannelDriftReport {
+            cached,
+            on_chain,
+            drift,
+        })
+    }
+}
+
+impl Runnable for QueryChannelDriftCmd {
+    fn run(&self) {
+        let report = match self.execute() {
+            Ok(report) => report,
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        };
+
+        if json() || !report.drift.is_empty() {
+            Output::success(report).exit()
+        }
+
+        Output::success_msg(format!(
+            "the cached channel end of {}/{} matches the channel end on chain {}",
+            self.port_id, self.channel_id, self.chain_id
+        ))
+        .exit()
+    }
+}
+
+fn channel_drift(cached: &ChannelEnd, on_chain: &ChannelEnd) -> Vec<ChannelDrift> {
+    let mut drift = Vec::new();
+
+    let mut compare = |field, cached: String, on_chain: String| {
+        if cached != on_chain {
+            drift.push(ChannelDrift {
+                field,
+                cached,
+                on_chain,
+            });
+        }
+    };
+
+    compare(
+        "state",
+        cached.state.to_string(),
+        on_chain.state.to_string(),
+    );
+    compare(
+        "ordering",
+        cached.ordering.to_string(),
+        on_chain.ordering.to_string(),
+    );
+    compare(
+        "counterparty",
+        format!("{:?}", cached.remote),
+        format!("{:?}", on_chain.remote),
+    );
+    compare(
+        "connection_hops",
+        format!("{:?}", cached.connection_hops),
+        format!("{:?}", on_chain.connection_hops),
+    );
+    compare(
+        "version",
+        cached.version.to_string(),
+        on_chain.version.to_string(),
+    );
+
+    drift
+}
@@ -0,0 +1,80 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer::account::Balance;
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::transfer::escrow_address;
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::commands::query::supply::normalize_denom;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// The escrow account of a channel, and its balance of the queried denomination.
+#[derive(Debug, Serialize)]
+struct ChannelEscrow {
+    escrow_address: String,
+    balance: Balance,
+}
+
+/// Query the balance of the account in which the transfer module escrows the tokens sent
+/// over a channel. Comparing this balance with the supply of the vouchers minted for these
+/// tokens on the counterparty chain, given by `query supply`, helps spot supply drifts.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct QueryChannelEscrowCmd {
+    #[clap(required = true, help = "identifier of the chain to query")]
+    chain_id: ChainId,
+
+    #[clap(required = true, help = "identifier of the port of the channel")]
+    port_id: PortId,
+
+    #[clap(required = true, help = "identifier of the channel")]
+    channel_id: ChannelId,
+
+    #[clap(
+        required = true,
+        help = "denomination to query the escrowed balance of, eg. 'uatom'"
+    )]
+    denom: String,
+}
+
+impl QueryChannelEscrowCmd {
+    fn execute(&self) -> Result<ChannelEscrow, Error> {
+        let config = app_config();
+
+        let chain_config = config
+            .find_chain(&self.chain_id)
+            .ok_or_else(|| Error::missing_chain_config(self.chain_id.clone()))?;
+
+        let escrow_address = escrow_address(
+            &self.port_id,
+            &self.channel_id,
+            &chain_config.account_prefix,
+        )
+        .map_err(Error::relayer)?;
+
+        let denom = normalize_denom(&self.denom)?;
+        let chain = spawn_chain_runtime(&config, &self.chain_id)?;
+
+        let balance = chain
+            .query_balance_of_address(escrow_address.clone(), denom)
+            .map_err(Error::relayer)?;
+
+        Ok(ChannelEscrow {
+            escrow_address,
+            balance,
+        })
+    }
+}
+
+impl Runnable for QueryChannelEscrowCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(escrow) => Output::success(escrow).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
@@ -57,6 +57,33 @@ impl Runnable for QueryClientStateCmd {
     }
 }
 
+/// Query client status command
+#[derive(Clone, Command, Debug, Parser)]
+pub struct QueryClientStatusCmd {
+    #[clap(required = true, help = "identifier of the chain hosting the client")]
+    chain_id: ChainId,
+
+    #[clap(required = true, help = "identifier of the client to query")]
+    client_id: ClientId,
+}
+
+/// Command for querying the latest height, trusting period and time until expiry
+/// of a client, along with whether it is expired or frozen.
+/// hermes query client status ibc-1 07-tendermint-0
+impl Runnable for QueryClientStatusCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        match chain.query_client_status(self.client_id.clone()) {
+            Ok(status) => Output::success(status).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
 /// Query client consensus command
 #[derive(Clone, Command, Debug, Parser)]
 pub struct QueryClientConsensusCmd {
@@ -0,0 +1,40 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::config::GasPrice;
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// Query the minimum gas prices a chain node is configured with, one per denom, eg. to
+/// check that the `gas_price` configured for the chain is accepted by the node during
+/// congestion. Only nodes exposing the node configuration service of Cosmos SDK v0.46
+/// or later report their minimum gas prices.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct QueryGasPriceCmd {
+    #[clap(required = true, help = "identifier of the chain to query")]
+    chain_id: ChainId,
+}
+
+impl QueryGasPriceCmd {
+    fn execute(&self) -> Result<Vec<GasPrice>, Error> {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)?;
+
+        chain.query_min_gas_price().map_err(Error::relayer)
+    }
+}
+
+impl Runnable for QueryGasPriceCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(prices) => Output::success(prices).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
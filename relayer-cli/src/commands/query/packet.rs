@@ -5,7 +5,12 @@ mod ack;
 mod acks;
 mod commitment;
 mod commitments;
+mod lifecycle;
 mod pending;
+mod pending_acks;
+mod pending_gas;
+mod status;
+mod unreceived;
 mod unreceived_acks;
 mod unreceived_packets;
 
@@ -23,6 +28,9 @@ pub enum QueryPacketCmds {
     /// Query packet acknowledgment
     Ack(ack::QueryPacketAcknowledgmentCmd),
 
+    /// Query the sequences of the packets committed on the counterparty chain but not yet received, and their count
+    Unreceived(unreceived::QueryUnreceivedCmd),
+
     /// Query unreceived packets
     UnreceivedPackets(unreceived_packets::QueryUnreceivedPacketsCmd),
 
@@ -31,4 +39,16 @@ pub enum QueryPacketCmds {
 
     /// Output a summary of pending packets in both directions
     Pending(pending::QueryPendingPacketsCmd),
+
+    /// List the packets sent on a channel whose acknowledgement is pending
+    PendingAcks(pending_acks::QueryPendingAcksCmd),
+
+    /// Estimate the gas needed to relay the pending packets in both directions
+    PendingGas(pending_gas::QueryPendingGasCmd),
+
+    /// Output the lifecycle of a packet across both ends of its channel
+    Lifecycle(lifecycle::QueryPacketLifecycleCmd),
+
+    /// Query whether a packet is pending, received, acknowledged or timed out
+    Status(status::QueryPacketStatusCmd),
 }
@@ -0,0 +1,240 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc::core::ics04_channel::channel::QueryPacketEventDataRequest;
+use ibc::core::ics04_channel::packet::Sequence;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::events::WithBlockDataType;
+use ibc::Height;
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::path::PathIdentifiers;
+use tendermint::abci::transaction::Hash as TxHash;
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::{json, Output};
+use crate::error::Error;
+use crate::prelude::*;
+
+/// The state of a packet, as derived from the transactions found for it on both chains.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum PacketState {
+    /// No transaction sending the packet was found.
+    Unknown,
+    /// The packet was sent on the source chain but not yet received.
+    Committed,
+    /// The packet was received on the destination chain.
+    Received,
+    /// The acknowledgement of the packet was relayed back to the source chain.
+    Acknowledged,
+    /// The packet was timed out on the source chain.
+    TimedOut,
+}
+
+#[derive(Copy, Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum LifecycleStep {
+    Send,
+    Recv,
+    Ack,
+    Timeout,
+}
+
+#[derive(Debug, Serialize)]
+struct TimelineEntry {
+    step: LifecycleStep,
+    chain_id: ChainId,
+    tx_hash: TxHash,
+    height: Height,
+}
+
+/// The lifecycle of a packet, across both ends of its channel.
+#[derive(Debug, Serialize)]
+struct PacketLifecycle {
+    sequence: Sequence,
+    state: PacketState,
+    timeline: Vec<TimelineEntry>,
+}
+
+/// This command does the following:
+///
+/// 1. queries the source chain for the channel end, to get the counterparty channel and port.
+/// 2. searches the source chain for the transactions which sent the packet, and which
+///    relayed its acknowledgement or timeout.
+/// 3. searches the destination chain for the transaction which received the packet.
+/// 4. derives the current state of the packet from the transactions found.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct QueryPacketLifecycleCmd {
+    #[clap(
+        long = "src-chain",
+        required = true,
+        value_name = "SRC_CHAIN_ID",
+        help = "identifier of the chain the packet was sent from"
+    )]
+    src_chain_id: ChainId,
+
+    #[clap(
+        long = "dst-chain",
+        required = true,
+        value_name = "DST_CHAIN_ID",
+        help = "identifier of the chain the packet is sent to"
+    )]
+    dst_chain_id: ChainId,
+
+    #[clap(
+        long = "port",
+        required = true,
+        value_name = "PORT_ID",
+        help = "port identifier on the source chain"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        long = "channel",
+        required = true,
+        value_name = "CHANNEL_ID",
+        help = "channel identifier on the source chain"
+    )]
+    channel_id: ChannelId,
+
+    #[clap(
+        long = "sequence",
+        required = true,
+        value_name = "SEQUENCE",
+        help = "sequence of the packet"
+    )]
+    sequence: Sequence,
+}
+
+impl QueryPacketLifecycleCmd {
+    fn execute(&self) -> Result<PacketLifecycle, Error> {
+        let config = app_config();
+        debug!("Options: {:?}", self);
+
+        let (chains, chan_conn_cli) = spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.src_chain_id,
+            &self.port_id,
+            &self.channel_id,
+        )?;
+
+        if chains.dst.id() != self.dst_chain_id {
+            return Err(Error::cli_arg(format!(
+                "the counterparty chain of channel {}/{} on chain {} is {}, not {}",
+                self.port_id,
+                self.channel_id,
+                self.src_chain_id,
+                chains.dst.id(),
+                self.dst_chain_id
+            )));
+        }
+
+        let path: PathIdentifiers = (&chan_conn_cli.channel).into();
+
+        let query = |chain: &BaseChainHandle, step: LifecycleStep, event_id: WithBlockDataType| {
+            let request = QueryPacketEventDataRequest {
+                event_id,
+                source_channel_id: path.channel_id,
+                source_port_id: path.port_id.clone(),
+                destination_channel_id: path.counterparty_channel_id,
+                destination_port_id: path.counterparty_port_id.clone(),
+                sequences: vec![self.sequence],
+                height: Height::zero(),
+            };
+
+            let txs = chain
+                .query_packet_event_txs(request)
+                .map_err(Error::relayer)?;
+
+            Ok::<_, Error>(
+                txs.into_iter()
+                    .map(|tx| TimelineEntry {
+                        step,
+                        chain_id: chain.id(),
+                        tx_hash: tx.tx_hash,
+                        height: tx.height,
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        let send = query(
+            &chains.src,
+            LifecycleStep::Send,
+            WithBlockDataType::SendPacket,
+        )?;
+        let recv = query(
+            &chains.dst,
+            LifecycleStep::Recv,
+            WithBlockDataType::WriteAck,
+        )?;
+        let ack = query(
+            &chains.src,
+            LifecycleStep::Ack,
+            WithBlockDataType::AckPacket,
+        )?;
+        let timeout = query(
+            &chains.src,
+            LifecycleStep::Timeout,
+            WithBlockDataType::TimeoutPacket,
+        )?;
+
+        let state = if !timeout.is_empty() {
+            PacketState::TimedOut
+        } else if !ack.is_empty() {
+            PacketState::Acknowledged
+        } else if !recv.is_empty() {
+            PacketState::Received
+        } else if !send.is_empty() {
+            PacketState::Committed
+        } else {
+            PacketState::Unknown
+        };
+
+        let timeline = send
+            .into_iter()
+            .chain(recv)
+            .chain(ack)
+            .chain(timeout)
+            .collect();
+
+        Ok(PacketLifecycle {
+            sequence: self.sequence,
+            state,
+            timeline,
+        })
+    }
+}
+
+impl Runnable for QueryPacketLifecycleCmd {
+    fn run(&self) {
+        let lifecycle = match self.execute() {
+            Ok(lifecycle) => lifecycle,
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        };
+
+        if json() {
+            Output::success(lifecycle).exit()
+        }
+
+        let lines: Vec<String> = lifecycle
+            .timeline
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{:?} on {} at height {}: {}",
+                    entry.step, entry.chain_id, entry.height, entry.tx_hash
+                )
+            })
+            .collect();
+
+        Output::success_msg(format!(
+            "packet {} is {:?}\n{}",
+            lifecycle.sequence,
+            lifecycle.state,
+            lines.join("\n")
+        ))
+        .exit()
+    }
+}
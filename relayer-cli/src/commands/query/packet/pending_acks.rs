@@ -0,0 +1,98 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc::core::ics04_channel::packet::Sequence;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::Height;
+use ibc_relayer::chain::handle::BaseChainHandle;
+use ibc_relayer::link::{Link, LinkParameters};
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// A packet whose acknowledgement is pending.
+#[derive(Debug, Serialize)]
+struct PendingAck {
+    sequence: Sequence,
+    /// The height of the counterparty chain at which the
+    /// acknowledgement is expected to be proven.
+    counterparty_height: Height,
+}
+
+/// This command does the following:
+///
+/// 1. queries the chain to get its counterparty chain, channel and port identifiers
+/// 2. queries the counterparty chain for the acknowledgements written for the packets
+///    sent on the given channel, out of which it keeps those not yet received by the chain.
+/// 3. queries the counterparty chain for the events of these acknowledgements, as the
+///    packet workers do when scheduling the relaying of acknowledgements.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct QueryPendingAcksCmd {
+    #[clap(
+        required = true,
+        help = "identifier of the chain on which the packets were sent"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        required = true,
+        help = "port identifier on the chain given by <CHAIN_ID>"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        required = true,
+        help = "channel identifier on the chain given by <CHAIN_ID>"
+    )]
+    channel_id: ChannelId,
+}
+
+impl QueryPendingAcksCmd {
+    fn execute(&self) -> Result<Vec<PendingAck>, Error> {
+        let config = app_config();
+
+        let (chains, _) = spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.chain_id,
+            &self.port_id,
+            &self.channel_id,
+        )?;
+
+        let opts = LinkParameters {
+            src_port_id: self.port_id.clone(),
+            src_channel_id: self.channel_id,
+        };
+
+        let fwd_link =
+            Link::new_from_opts(chains.src, chains.dst, opts, false).map_err(Error::link)?;
+
+        // The acknowledgements of the packets sent on the chain are relayed
+        // from the counterparty chain, ie. on the reverse path.
+        let rev_link = fwd_link.reverse(false).map_err(Error::link)?;
+
+        let pending_acks = rev_link
+            .a_to_b
+            .pending_acknowledgements()
+            .map_err(Error::link)?
+            .into_iter()
+            .map(|(sequence, counterparty_height)| PendingAck {
+                sequence,
+                counterparty_height,
+            })
+            .collect();
+
+        Ok(pending_acks)
+    }
+}
+
+impl Runnable for QueryPendingAcksCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(pending_acks) => Output::success(pending_acks).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::chain::tracking::TrackedMsgs;
+use ibc_relayer::link::operational_data::{OperationalData, OperationalDataTarget};
+use ibc_relayer::link::{Link, LinkParameters, RelayPath};
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// The estimated gas for relaying all the pending packets on a channel.
+#[derive(Debug, Serialize)]
+struct PendingGas {
+    /// The total estimated gas, across all the batches.
+    total_gas: u64,
+    /// The estimated gas of each batch of messages.
+    batches: Vec<BatchGas>,
+}
+
+/// The estimated gas for a single transaction, ie. a batch of messages.
+#[derive(Debug, Serialize)]
+struct BatchGas {
+    /// The chain the batch would be submitted to.
+    chain_id: ChainId,
+    /// The gas estimated for the whole batch, including its client update.
+    gas: u64,
+    /// The gas estimated for the client update prepended to the batch, if any.
+    client_update_gas: Option<u64>,
+    /// The gas estimated for each message in the batch, excluding the client update.
+    messages: Vec<MessageGas>,
+}
+
+#[derive(Debug, Serialize)]
+struct MessageGas {
+    type_url: String,
+    gas: u64,
+}
+
+/// This command does the following:
+///
+/// 1. builds the relaying schedule for all the pending packets on the channel and its
+///    counterparty, as packet clearing would, but without submitting any transaction.
+/// 2. simulates each scheduled batch of messages on its target chain.
+/// 3. simulates each message individually with the batch client update, to estimate
+///    its own cost.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct QueryPendingGasCmd {
+    #[clap(
+        required = true,
+        help = "identifier of the chain at one end of the channel"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        required = true,
+        help = "port identifier on the chain given by <CHAIN_ID>"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        required = true,
+        help = "channel identifier on the chain given by <CHAIN_ID>"
+    )]
+    channel_id: ChannelId,
+}
+
+impl QueryPendingGasCmd {
+    fn execute(&self) -> Result<PendingGas, Error> {
+        let config = app_config();
+
+        let (chains, _) = spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.chain_id,
+            &self.port_id,
+            &self.channel_id,
+        )?;
+
+        let opts = LinkParameters {
+            src_port_id: self.port_id.clone(),
+            src_channel_id: self.channel_id,
+        };
+
+        let fwd_link = Link::new_from_opts(chains.src.clone(), chains.dst, opts, false)
+            .map_err(Error::link)?;
+        let rev_link = fwd_link.reverse(false).map_err(Error::link)?;
+
+        let mut batches = estimate_path_gas(&fwd_link.a_to_b)?;
+        batches.extend(estimate_path_gas(&rev_link.a_to_b)?);
+
+        Ok(PendingGas {
+            total_gas: batches.iter().map(|b| b.gas).sum(),
+            batches,
+        })
+    }
+}
+
+impl Runnable for QueryPendingGasCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(pending_gas) => Output::success(pending_gas).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+fn estimate_path_gas<Chain: ChainHandle>(
+    relay_path: &RelayPath<Chain, Chain>,
+) -> Result<Vec<BatchGas>, Error> {
+    relay_path
+        .take_pending_operational_data()
+        .map_err(Error::link)?
+        .iter()
+        .map(|odata| estimate_batch_gas(relay_path, odata))
+        .collect()
+}
+
+fn estimate_batch_gas<Chain: ChainHandle>(
+    relay_path: &RelayPath<Chain, Chain>,
+    odata: &OperationalData,
+) -> Result<BatchGas, Error> {
+    let chain = match odata.target {
+        OperationalDataTarget::Source => relay_path.src_chain(),
+        OperationalDataTarget::Destination => relay_path.dst_chain(),
+    };
+
+    let tracked_msgs = odata.assemble_msgs(relay_path).map_err(Error::link)?;
+    let tracking_id = tracked_msgs.tracking_id();
+
+    // The client update message, if any, is prepended to the batch messages.
+    let update_msgs_count = tracked_msgs
+        .messages()
+        .len()
+        .saturating_sub(odata.batch.len());
+    let update_msgs = tracked_msgs.messages()[..update_msgs_count].to_vec();
+
+    let estimate = |msgs: TrackedMsgs| -> Result<u64, Error> {
+        chain.estimate_gas(msgs).map_err(Error::relayer)
+    };
+
+    debug!(
+        chain = %chain.id(),
+        "estimating gas for batch of {} message(s)",
+        tracked_msgs.messages().len()
+    );
+
+    let client_update_gas = if update_msgs.is_empty() {
+        None
+    } else {
+        Some(estimate(TrackedMsgs::new(
+            update_msgs.clone(),
+            tracking_id,
+        ))?)
+    };
+
+    let messages = odata
+        .batch
+        .iter()
+        .map(|transit_msg| {
+            let mut msgs = update_msgs.clone();
+            msgs.push(transit_msg.msg.clone());
+
+            let gas = estimate(TrackedMsgs::new(msgs, tracking_id))?;
+
+            Ok(MessageGas {
+                type_url: transit_msg.msg.type_url.clone(),
+                gas: gas.saturating_sub(client_update_gas.unwrap_or(0)),
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(BatchGas {
+        chain_id: chain.id(),
+        gas: estimate(tracked_msgs)?,
+        client_update_gas,
+        messages,
+    })
+}
@@ -0,0 +1,161 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc::core::ics04_channel::packet::Sequence;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::Height;
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::chain::requests::{
+    IncludeProof, QueryPacketCommitmentRequest, QueryUnreceivedPacketsRequest,
+};
+use ibc_relayer::path::PathIdentifiers;
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// The status of a packet, as derived from the state of both ends of its channel.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum PacketStatus {
+    /// The packet is committed on the source chain but not yet received.
+    Pending,
+    /// The packet was received on the destination chain, but its
+    /// acknowledgement was not yet relayed back to the source chain.
+    Received,
+    /// The acknowledgement of the packet was relayed back to the source chain.
+    Acknowledged,
+    /// The packet was timed out on the source chain.
+    TimedOut,
+}
+
+impl PacketStatus {
+    /// The status of a packet whose commitment is, or is not, stored on the
+    /// source chain, and which was, or was not, received on the destination chain.
+    ///
+    /// The commitment of a packet is removed from the source chain once its
+    /// acknowledgement or timeout is relayed, a removed commitment of a packet
+    /// which was not received thus meaning that the packet was timed out.
+    fn new(committed: bool, received: bool) -> Self {
+        match (committed, received) {
+            (true, false) => Self::Pending,
+            (true, true) => Self::Received,
+            (false, true) => Self::Acknowledged,
+            (false, false) => Self::TimedOut,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct PacketStatusOutput {
+    sequence: Sequence,
+    status: PacketStatus,
+    /// The height of the source chain the commitment was queried at.
+    src_height: Height,
+    /// The height of the destination chain when the receipt was queried.
+    dst_height: Height,
+}
+
+/// Query the status of a packet from its commitment on the source chain and
+/// its receipt on the destination chain, without searching for the transactions
+/// of the packet as `query packet lifecycle` does.
+///
+/// A sequence which was never sent on the channel is reported as timed out.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct QueryPacketStatusCmd {
+    #[clap(
+        required = true,
+        help = "identifier of the chain the packet was sent from"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        required = true,
+        help = "port identifier on the chain given by <CHAIN_ID>"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        required = true,
+        help = "channel identifier on the chain given by <CHAIN_ID>"
+    )]
+    channel_id: ChannelId,
+
+    #[clap(required = true, help = "sequence of the packet")]
+    sequence: Sequence,
+}
+
+impl QueryPacketStatusCmd {
+    fn execute(&self) -> Result<PacketStatusOutput, Error> {
+        let config = app_config();
+        debug!("Options: {:?}", self);
+
+        let (chains, chan_conn_cli) = spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.chain_id,
+            &self.port_id,
+            &self.channel_id,
+        )?;
+
+        let path: PathIdentifiers = (&chan_conn_cli.channel).into();
+
+        // The source chain is queried first, so that a packet received and acknowledged
+        // in between the queries is not mistaken for a timed out packet.
+        let src_height = chains.src.query_latest_height().map_err(Error::relayer)?;
+
+        let (commitment, _) = chains
+            .src
+            .query_packet_commitment(
+                QueryPacketCommitmentRequest {
+                    port_id: path.port_id.clone(),
+                    channel_id: path.channel_id,
+                    sequence: self.sequence,
+                    height: src_height,
+                },
+                IncludeProof::No,
+            )
+            .map_err(Error::relayer)?;
+
+        let dst_height = chains.dst.query_latest_height().map_err(Error::relayer)?;
+
+        let unreceived = chains
+            .dst
+            .query_unreceived_packets(QueryUnreceivedPacketsRequest {
+                port_id: path.counterparty_port_id.clone(),
+                channel_id: path.counterparty_channel_id,
+                packet_commitment_sequences: vec![self.sequence],
+            })
+            .map_err(Error::relayer)?;
+
+        Ok(PacketStatusOutput {
+            sequence: self.sequence,
+            status: PacketStatus::new(!commitment.is_empty(), unreceived.is_empty()),
+            src_height,
+            dst_height,
+        })
+    }
+}
+
+impl Runnable for QueryPacketStatusCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(status) => Output::success(status).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PacketStatus;
+
+    #[test]
+    fn status_follows_the_commitment_and_the_receipt() {
+        assert_eq!(PacketStatus::new(true, false), PacketStatus::Pending);
+        assert_eq!(PacketStatus::new(true, true), PacketStatus::Received);
+        assert_eq!(PacketStatus::new(false, true), PacketStatus::Acknowledged);
+        assert_eq!(PacketStatus::new(false, false), PacketStatus::TimedOut);
+    }
+}
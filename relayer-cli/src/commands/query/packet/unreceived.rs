@@ -0,0 +1,86 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc::core::ics04_channel::packet::Sequence;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::Height;
+use ibc_relayer::chain::counterparty::unreceived_packets;
+use ibc_relayer::chain::handle::BaseChainHandle;
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// The sequences of the packets committed on the counterparty chain
+/// but not yet received by the chain.
+#[derive(Debug, Serialize)]
+struct UnreceivedSequences {
+    sequences: Vec<Sequence>,
+    count: usize,
+    /// The height of the counterparty chain at which the commitments were queried.
+    counterparty_height: Height,
+}
+
+/// This command does the following:
+///
+/// 1. queries the chain to get its counterparty chain, channel and port identifiers
+/// 2. queries the counterparty chain for the sequences of the packet commitments on the
+///    counterparty end of the channel.
+/// 3. queries the chain, with `ChainHandle::query_unreceived_packets`, for the sequences
+///    out of the ones obtained in 2. which were not yet received.
+///
+/// These are the packets which would be relayed by `clear packets`.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct QueryUnreceivedCmd {
+    #[clap(
+        required = true,
+        help = "identifier of the chain on which the packets are to be received"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        required = true,
+        help = "port identifier on the chain given by <CHAIN_ID>"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        required = true,
+        help = "channel identifier on the chain given by <CHAIN_ID>"
+    )]
+    channel_id: ChannelId,
+}
+
+impl QueryUnreceivedCmd {
+    fn execute(&self) -> Result<UnreceivedSequences, Error> {
+        let config = app_config();
+
+        let (chains, chan_conn_cli) = spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.chain_id,
+            &self.port_id,
+            &self.channel_id,
+        )?;
+
+        let (sequences, counterparty_height) =
+            unreceived_packets(&chains.src, &chains.dst, &(&chan_conn_cli.channel).into())
+                .map_err(Error::supervisor)?;
+
+        Ok(UnreceivedSequences {
+            count: sequences.len(),
+            sequences,
+            counterparty_height,
+        })
+    }
+}
+
+impl Runnable for QueryUnreceivedCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(unreceived) => Output::success(unreceived).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
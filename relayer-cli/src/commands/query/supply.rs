@@ -0,0 +1,82 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc_relayer::account::Balance;
+use ibc_relayer::chain::handle::ChainHandle;
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// Query the total supply of a denomination on a chain, eg. of the vouchers minted on the
+/// chain for the tokens received over a channel. Comparing the supply of these vouchers with
+/// the balance of the escrow account of the channel on the counterparty chain helps spot
+/// supply drifts.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct QuerySupplyCmd {
+    #[clap(required = true, help = "identifier of the chain to query")]
+    chain_id: ChainId,
+
+    #[clap(
+        required = true,
+        help = "denomination to query the supply of, eg. 'uatom' or 'ibc/<HASH>' for IBC vouchers"
+    )]
+    denom: String,
+}
+
+impl QuerySupplyCmd {
+    fn execute(&self) -> Result<Balance, Error> {
+        let config = app_config();
+
+        let denom = normalize_denom(&self.denom)?;
+        let chain = spawn_chain_runtime(&config, &self.chain_id)?;
+
+        chain.query_supply_of(denom).map_err(Error::relayer)
+    }
+}
+
+impl Runnable for QuerySupplyCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(supply) => Output::success(supply).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+/// Checks the hash of an `ibc/<HASH>` denomination, and normalizes it to upper case
+/// as the bank module expects, leaving the other denominations untouched.
+pub(crate) fn normalize_denom(denom: &str) -> Result<String, Error> {
+    let hash = match denom.strip_prefix("ibc/") {
+        Some(hash) => hash,
+        None => return Ok(denom.to_string()),
+    };
+
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(Error::cli_arg(format!(
+            "invalid IBC denomination '{}', expected 'ibc/' followed by a 64 characters hex hash",
+            denom
+        )));
+    }
+
+    Ok(format!("ibc/{}", hash.to_ascii_uppercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_denom;
+
+    #[test]
+    fn ibc_denoms_are_normalized() {
+        let hash = "27394fb092d2eccd56123c74f36e4c1f926001ceada9ca97ea622b25f41e5eb2";
+
+        assert_eq!(
+            normalize_denom(&format!("ibc/{}", hash)).unwrap(),
+            format!("ibc/{}", hash.to_ascii_uppercase())
+        );
+        assert_eq!(normalize_denom("uatom").unwrap(), "uatom");
+        assert!(normalize_denom("ibc/1234").is_err());
+    }
+}
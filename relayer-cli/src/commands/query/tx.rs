@@ -4,10 +4,14 @@ use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 
 mod events;
+mod pair_events;
 
 /// `query tx` subcommand
 #[derive(Command, Debug, Parser, Runnable)]
 pub enum QueryTxCmd {
     /// Query the events emitted by transaction
     Events(events::QueryTxEventsCmd),
+
+    /// Query the events emitted by a pair of transactions on two chains
+    PairEvents(pair_events::QueryTxPairEventsCmd),
 }
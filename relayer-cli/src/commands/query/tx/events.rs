@@ -1,21 +1,83 @@
+use alloc::collections::BTreeMap;
+use core::fmt;
 use core::str::FromStr;
 
 use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
+use serde::Serialize;
 use tracing::debug;
 
 use tendermint::abci::transaction::Hash;
 
 use ibc::core::ics24_host::identifier::ChainId;
-use ibc::query::{QueryTxHash, QueryTxRequest};
+use ibc::events::{IbcEvent, IbcEventType};
+use ibc::query::QueryTxHash;
+use ibc::Height;
 
+use ibc_relayer::chain::endpoint::TxEvent;
 use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::chain::requests::QueryHostConsensusStateRequest;
+use ibc_relayer::config::Config;
 
 use crate::cli_utils::spawn_chain_runtime;
 use crate::conclude::{exit_with_unrecoverable_error, Output};
 use crate::error::Error;
 use crate::prelude::app_config;
 
+/// The direction of the packets whose events are shown, relatively to the
+/// chain the transaction was submitted to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PacketDirection {
+    /// The packets sent by the chain: their `send_packet`, `acknowledge_packet`
+    /// and timeout events.
+    Send,
+    /// The packets received by the chain: their `receive_packet` and
+    /// `write_acknowledgement` events.
+    Recv,
+}
+
+impl PacketDirection {
+    /// The types of the events of the packets of this direction.
+    fn event_types(&self) -> &'static [IbcEventType] {
+        match self {
+            Self::Send => &[
+                IbcEventType::SendPacket,
+                IbcEventType::AckPacket,
+                IbcEventType::Timeout,
+                IbcEventType::TimeoutOnClose,
+            ],
+            Self::Recv => &[IbcEventType::ReceivePacket, IbcEventType::WriteAck],
+        }
+    }
+
+    fn includes(&self, event_type: &IbcEventType) -> bool {
+        self.event_types()
+            .iter()
+            .any(|t| t.as_str() == event_type.as_str())
+    }
+}
+
+impl fmt::Display for PacketDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Send => write!(f, "send"),
+            Self::Recv => write!(f, "recv"),
+        }
+    }
+}
+
+impl FromStr for PacketDirection {
+    type Err = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "send" => Ok(Self::Send),
+            "recv" => Ok(Self::Recv),
+            invalid => Err(format!("unrecognized packet direction: {}", invalid).into()),
+        }
+    }
+}
+
 /// Query the events emitted by transaction
 #[derive(Clone, Command, Debug, Parser)]
 pub struct QueryTxEventsCmd {
@@ -24,6 +86,73 @@ pub struct QueryTxEventsCmd {
 
     #[clap(required = true, help = "transaction hash to query")]
     hash: String,
+
+    #[clap(
+        long = "with-time",
+        help = "attach to each event the timestamp of the block at its height, at the cost of one block query per height"
+    )]
+    with_time: bool,
+
+    #[clap(
+        long = "event-type",
+        value_name = "EVENT_TYPE",
+        help = "only show the events of the given type, eg. send_packet, can be repeated"
+    )]
+    event_types: Vec<IbcEventType>,
+
+    #[clap(
+        long = "direction",
+        value_name = "DIRECTION",
+        help = "only show the events of the packets sent (send) or received (recv) by the chain"
+    )]
+    direction: Option<PacketDirection>,
+
+    #[clap(
+        long = "decode-only",
+        value_name = "EVENT_TYPE",
+        help = "only decode the events of the given type, eg. send_packet, showing the attributes \
+                of the other events as emitted, which is faster for large transactions, can be repeated"
+    )]
+    decode_only: Vec<IbcEventType>,
+}
+
+impl QueryTxEventsCmd {
+    /// The types of the events to show, if filtered, from the explicit event types,
+    /// which must all be of the packet direction if one is given as well, or else
+    /// from the packet direction.
+    fn event_filter(&self) -> Result<Option<Vec<IbcEventType>>, Error> {
+        match self.direction {
+            None if self.event_types.is_empty() => Ok(None),
+            None => Ok(Some(self.event_types.clone())),
+            Some(direction) => {
+                if let Some(event_type) = self
+                    .event_types
+                    .iter()
+                    .find(|event_type| !direction.includes(event_type))
+                {
+                    return Err(Error::cli_arg(format!(
+                        "event type '{}' is not an event of the '{}' packet direction",
+                        event_type.as_str(),
+                        direction
+                    )));
+                }
+
+                if self.event_types.is_empty() {
+                    Ok(Some(direction.event_types().to_vec()))
+                } else {
+                    Ok(Some(self.event_types.clone()))
+                }
+            }
+        }
+    }
+}
+
+/// An event along with the timestamp of the block in which it was emitted.
+#[derive(Debug, Serialize)]
+struct TimedEvent<E> {
+    event: E,
+    /// The block timestamp, in RFC 3339 format, if the event has a height.
+    time: Option<String>,
 }
 
 // cargo run --bin hermes -- query tx events ibc-0 B8E78AD83810239E21863AC7B5FC4F99396ABB39EB534F721EEF43A4979C2821
@@ -33,20 +162,227 @@ impl Runnable for QueryTxEventsCmd {
 
         debug!("Options: {:?}", self);
 
+        let event_filter = match self.event_filter() {
+            Ok(event_filter) => event_filter,
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        };
+
         let chain = spawn_chain_runtime(&config, &self.chain_id)
             .unwrap_or_else(exit_with_unrecoverable_error);
 
-        let res = Hash::from_str(self.hash.as_str())
-            .map_err(|e| Error::invalid_hash(self.hash.clone(), e))
-            .and_then(|h| {
-                chain
-                    .query_txs(QueryTxRequest::Transaction(QueryTxHash(h)))
-                    .map_err(Error::relayer)
-            });
+        if self.decode_only.is_empty() {
+            match query_tx_events(&chain, &self.hash) {
+                Ok(events) => self.conclude(
+                    &chain,
+                    events,
+                    event_filter,
+                    |event| event.event_type().as_str(),
+                    event_height,
+                ),
+                Err(e) => Output::error(format!("{}", e)).exit(),
+            }
+        }
 
-        match res {
-            Ok(res) => Output::success(res).exit(),
+        match query_tx_decoded_events(&chain, &self.hash, &self.decode_only) {
+            Ok(events) => self.conclude(
+                &chain,
+                events,
+                event_filter,
+                TxEvent::event_type,
+                TxEvent::height,
+            ),
             Err(e) => Output::error(format!("{}", e)).exit(),
         }
     }
 }
+
+impl QueryTxEventsCmd {
+    /// Outputs the given events of the transaction, filtered and timed as requested.
+    fn conclude<E>(
+        &self,
+        chain: &impl ChainHandle,
+        events: Vec<E>,
+        event_filter: Option<Vec<IbcEventType>>,
+        event_type: impl Fn(&E) -> &str,
+        height: impl Fn(&E) -> Option<Height>,
+    ) -> !
+    where
+        E: Serialize + fmt::Debug + 'static,
+    {
+        if events.is_empty() {
+            Output::success_msg(format!(
+                "transaction {} was found on chain '{}' but emitted no IBC events",
+                self.hash, self.chain_id
+            ))
+            .exit()
+        }
+
+        let events = match event_filter {
+            Some(event_types) => filter_events(events, &event_types, event_type),
+            None => events,
+        };
+
+        if events.is_empty() {
+            Output::success_msg(format!(
+                "transaction {} was found on chain '{}' but emitted no IBC events of the given types",
+                self.hash, self.chain_id
+            ))
+            .exit()
+        }
+
+        if !self.with_time {
+            Output::success(events).exit()
+        }
+
+        match with_block_times(chain, events, height) {
+            Ok(events) => Output::success(events).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+/// Query the events emitted by the transaction with the given hash on the given chain.
+///
+/// Fails with a [`TxNotFound`](crate::error::ErrorDetail::TxNotFound) error if there
+/// is no such transaction, and returns no events if the transaction was found but
+/// did not emit any IBC event.
+pub(super) fn query_tx_events(
+    chain: &impl ChainHandle,
+    hash: &str,
+) -> Result<Vec<IbcEvent>, Error> {
+    let h = Hash::from_str(hash).map_err(|e| Error::invalid_hash(hash.to_string(), e))?;
+
+    chain
+        .query_tx_by_hash(QueryTxHash(h))
+        .map_err(Error::relayer)?
+        .ok_or_else(|| Error::tx_not_found(chain.id(), hash.to_string()))
+}
+
+/// Query the events emitted by the transaction with the given hash on the given chain,
+/// like [`query_tx_events`], but only decoding the events of the given types.
+fn query_tx_decoded_events(
+    chain: &impl ChainHandle,
+    hash: &str,
+    decode_only: &[IbcEventType],
+) -> Result<Vec<TxEvent>, Error> {
+    let h = Hash::from_str(hash).map_err(|e| Error::invalid_hash(hash.to_string(), e))?;
+
+    chain
+        .query_tx_decoded_events(QueryTxHash(h), decode_only.to_vec())
+        .map_err(Error::relayer)?
+        .ok_or_else(|| Error::tx_not_found(chain.id(), hash.to_string()))
+}
+
+/// Keep only the events of the given types.
+fn filter_events<E>(
+    events: Vec<E>,
+    event_types: &[IbcEventType],
+    event_type: impl Fn(&E) -> &str,
+) -> Vec<E> {
+    events
+        .into_iter()
+        .filter(|event| {
+            let event_type = event_type(event);
+            event_types.iter().any(|t| t.as_str() == event_type)
+        })
+        .collect()
+}
+
+/// The height of the block including the given event, if it has one.
+fn event_height(event: &IbcEvent) -> Option<Height> {
+    match event {
+        IbcEvent::ChainError(_) | IbcEvent::Empty(_) | IbcEvent::AppModule(_) => None,
+        _ => Some(event.height()),
+    }
+}
+
+/// Spawn a runtime for the given chain, and query the events emitted by
+/// the transaction with the given hash on that chain.
+pub(super) fn spawn_and_query_tx_events(
+    config: &Config,
+    chain_id: &ChainId,
+    hash: &str,
+) -> Result<Vec<IbcEvent>, Error> {
+    let chain = spawn_chain_runtime(config, chain_id)?;
+    query_tx_events(&chain, hash)
+}
+
+/// Attaches the timestamp of the block at its height to each of the given events,
+/// querying each block only once.
+fn with_block_times<E>(
+    chain: &impl ChainHandle,
+    events: Vec<E>,
+    height: impl Fn(&E) -> Option<Height>,
+) -> Result<Vec<TimedEvent<E>>, Error> {
+    let mut times: BTreeMap<Height, Option<String>> = BTreeMap::new();
+
+    events
+        .into_iter()
+        .map(|event| {
+            let time = match height(&event) {
+                Some(height) => match times.get(&height) {
+                    Some(time) => time.clone(),
+                    None => {
+                        let time = chain
+                            .query_host_consensus_state(QueryHostConsensusStateRequest { height })
+                            .map_err(Error::relayer)?
+                            .timestamp()
+                            .into_tm_time()
+                            .map(|time| time.to_rfc3339());
+
+                        times.insert(height, time.clone());
+                        time
+                    }
+                },
+                None => None,
+            };
+
+            Ok(TimedEvent { event, time })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use abscissa_core::clap::Parser;
+
+    use ibc::events::IbcEventType;
+
+    use super::QueryTxEventsCmd;
+
+    fn event_filter(args: &[&str]) -> Option<Vec<&'static str>> {
+        let cmd = QueryTxEventsCmd::parse_from(["test", "chain_id", "hash"].iter().chain(args));
+
+        cmd.event_filter()
+            .unwrap()
+            .map(|types| types.iter().map(IbcEventType::as_str).collect())
+    }
+
+    #[test]
+    fn direction_selects_the_events_of_the_packets_in_that_direction() {
+        assert_eq!(event_filter(&[]), None);
+        assert_eq!(
+            event_filter(&["--direction", "recv"]),
+            Some(vec!["receive_packet", "write_acknowledgement"])
+        );
+        assert_eq!(
+            event_filter(&["--direction", "send", "--event-type", "send_packet"]),
+            Some(vec!["send_packet"])
+        );
+    }
+
+    #[test]
+    fn contradictory_direction_and_event_type_are_rejected() {
+        let cmd = QueryTxEventsCmd::parse_from([
+            "test",
+            "chain_id",
+            "hash",
+            "--direction",
+            "send",
+            "--event-type",
+            "receive_packet",
+        ]);
+
+        assert!(cmd.event_filter().is_err());
+    }
+}
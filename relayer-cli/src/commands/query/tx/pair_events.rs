@@ -0,0 +1,103 @@
+use std::thread;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+use tracing::debug;
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc::events::IbcEvent;
+
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::app_config;
+
+use super::events::spawn_and_query_tx_events;
+
+/// Query the events emitted by a pair of transactions on two chains,
+/// eg. the send-side and receive-side transactions of a packet.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct QueryTxPairEventsCmd {
+    #[clap(
+        long = "src-chain",
+        required = true,
+        help = "identifier of the chain of the first transaction"
+    )]
+    src_chain_id: ChainId,
+
+    #[clap(
+        long = "src-hash",
+        required = true,
+        help = "hash of the transaction to query on the chain given by --src-chain"
+    )]
+    src_hash: String,
+
+    #[clap(
+        long = "dst-chain",
+        required = true,
+        help = "identifier of the chain of the second transaction"
+    )]
+    dst_chain_id: ChainId,
+
+    #[clap(
+        long = "dst-hash",
+        required = true,
+        help = "hash of the transaction to query on the chain given by --dst-chain"
+    )]
+    dst_hash: String,
+}
+
+/// The events emitted by a transaction, or the error that occurred while querying them.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum TxEvents {
+    Events(Vec<IbcEvent>),
+    Error(String),
+}
+
+impl From<Result<Vec<IbcEvent>, Error>> for TxEvents {
+    fn from(result: Result<Vec<IbcEvent>, Error>) -> Self {
+        match result {
+            Ok(events) => Self::Events(events),
+            Err(e) => Self::Error(format!("{}", e)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct PairEvents {
+    src: TxEvents,
+    dst: TxEvents,
+}
+
+// cargo run --bin hermes -- query tx pair-events --src-chain ibc-0 --src-hash B8E78AD83810239E21863AC7B5FC4F99396ABB39EB534F721EEF43A4979C2821 --dst-chain ibc-1 --dst-hash 7B5FC4F99396ABB39EB534F721EEF43A4979C2821B8E78AD83810239E21863AC
+impl Runnable for QueryTxPairEventsCmd {
+    fn run(&self) {
+        let config = (*app_config()).clone();
+
+        debug!("Options: {:?}", self);
+
+        // Query both sides concurrently, each in its own thread,
+        // so that a failure on one side does not block the other.
+        let dst_handle = {
+            let config = config.clone();
+            let chain_id = self.dst_chain_id.clone();
+            let hash = self.dst_hash.clone();
+
+            thread::spawn(move || spawn_and_query_tx_events(&config, &chain_id, &hash))
+        };
+
+        let src = spawn_and_query_tx_events(&config, &self.src_chain_id, &self.src_hash);
+
+        let dst = match dst_handle.join() {
+            Ok(dst) => dst.into(),
+            Err(_) => TxEvents::Error("failed to query the destination chain".to_string()),
+        };
+
+        Output::success(PairEvents {
+            src: src.into(),
+            dst,
+        })
+        .exit()
+    }
+}
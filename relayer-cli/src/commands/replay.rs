@@ -0,0 +1,160 @@
+use core::time::Duration;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Instant;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::events::IbcEvent;
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::event::dump::BatchDump;
+use ibc_relayer::link::{Link, LinkParameters, Resubmit};
+use ibc_relayer::object::Packet;
+use ibc_relayer::worker::packet::replay_event_batch;
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// How long to wait between two checks of the transactions submitted for the replayed batch.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// `replay` subcommands
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum ReplayCmds {
+    /// Replay an event batch stored by `listen --dump-batches` to the packet worker of a channel
+    Batch(ReplayBatchCmd),
+}
+
+/// Feeds the stored event batch to a link over the given channel, as the packet
+/// worker of the channel would upon receiving the batch, and relays the packets
+/// it schedules, eg. to reproduce the scheduling and relaying of the batch.
+///
+/// The relayed messages are submitted to the chains given in the configuration,
+/// which must be the ones the batch was recorded from.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct ReplayBatchCmd {
+    #[clap(required = true, help = "path of the stored event batch")]
+    file: PathBuf,
+
+    #[clap(
+        required = true,
+        help = "identifier of the chain the batch was recorded from"
+    )]
+    src_chain_id: ChainId,
+
+    #[clap(required = true, help = "identifier of the counterparty chain")]
+    dst_chain_id: ChainId,
+
+    #[clap(
+        required = true,
+        help = "identifier of the channel on the chain given by <SRC_CHAIN_ID>"
+    )]
+    src_channel_id: ChannelId,
+
+    #[clap(
+        long = "port",
+        default_value = "transfer",
+        help = "identifier of the port on the chain given by <SRC_CHAIN_ID>"
+    )]
+    src_port_id: PortId,
+
+    #[clap(
+        long = "wait",
+        default_value = "30",
+        value_name = "SECONDS",
+        help = "how long to wait for the confirmation of the submitted transactions"
+    )]
+    wait_seconds: u64,
+}
+
+impl ReplayBatchCmd {
+    fn execute(&self) -> Result<Vec<IbcEvent>, Error> {
+        let config = app_config();
+
+        let dump = BatchDump::read(&self.file).map_err(Error::batch_dump)?;
+
+        if dump.chain_id != self.src_chain_id {
+            return Err(Error::cli_arg(format!(
+                "the batch was recorded from chain {}, not from chain {}",
+                dump.chain_id, self.src_chain_id
+            )));
+        }
+
+        let (chains, _) = spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.src_chain_id,
+            &self.src_port_id,
+            &self.src_channel_id,
+        )?;
+
+        if chains.dst.id() != self.dst_chain_id {
+            return Err(Error::cli_arg(format!(
+                "channel {}/{} on chain {} does not lead to chain {} but to chain {}",
+                self.src_port_id,
+                self.src_channel_id,
+                self.src_chain_id,
+                self.dst_chain_id,
+                chains.dst.id()
+            )));
+        }
+
+        let opts = LinkParameters {
+            src_port_id: self.src_port_id.clone(),
+            src_channel_id: self.src_channel_id,
+        };
+        let mut link =
+            Link::new_from_opts(chains.src, chains.dst, opts, true).map_err(Error::link)?;
+
+        let path = Packet {
+            dst_chain_id: self.dst_chain_id.clone(),
+            src_chain_id: self.src_chain_id.clone(),
+            src_channel_id: self.src_channel_id,
+            src_port_id: self.src_port_id.clone(),
+        };
+
+        info!(
+            "replaying {} event(s) recorded at height {} on {}",
+            dump.events.len(),
+            dump.height,
+            path.short_name()
+        );
+
+        let packets = config.mode.packets;
+
+        let mut summary = replay_event_batch(
+            &mut link,
+            packets.clear_interval,
+            packets.max_event_batch_size,
+            &path,
+            dump.into_batch(),
+        )
+        .map_err(Error::worker)?;
+
+        let start = Instant::now();
+        let wait = Duration::from_secs(self.wait_seconds);
+
+        while link.a_to_b.has_pending_txs() && start.elapsed() < wait {
+            thread::sleep(POLL_INTERVAL);
+            summary.merge(link.a_to_b.process_pending_txs(Resubmit::No));
+        }
+
+        if link.a_to_b.has_pending_txs() {
+            warn!("some of the submitted transactions were not confirmed in time");
+        }
+
+        Ok(summary.events)
+    }
+}
+
+impl Runnable for ReplayBatchCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(events) => Output::success(events).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
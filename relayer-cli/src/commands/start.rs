@@ -137,6 +137,11 @@ fn spawn_telemetry_server(config: &Config) -> Result<(), Box<dyn Error + Send +
     let state = ibc_telemetry::global();
 
     let telemetry = config.telemetry.clone();
+
+    if let Some(relayer_id) = telemetry.relayer_id {
+        state.set_relayer_id(relayer_id);
+    }
+
     if telemetry.enabled {
         match ibc_telemetry::spawn((telemetry.host, telemetry.port), state.clone()) {
             Ok((addr, _)) => {
@@ -185,6 +190,7 @@ fn make_supervisor<Chain: ChainHandle>(
         SupervisorOptions {
             health_check: true,
             force_full_scan,
+            on_relayed: None,
         },
     )?)
 }
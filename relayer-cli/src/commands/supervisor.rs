@@ -0,0 +1,73 @@
+use core::time::Duration;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer::supervisor::reload::ReloadReport;
+
+use crate::cli_utils::rest_post;
+use crate::conclude::{json, Output};
+use crate::error::Error;
+use crate::prelude::*;
+
+/// How long to wait for the running relayer to apply the configuration,
+/// which involves scanning the chains for newly allowed channels.
+const RELOAD_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// `supervisor` subcommands
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum SupervisorCmds {
+    /// Make a running relayer reload its configuration from the config file
+    /// given to this command, without restarting it.
+    Reload(SupervisorReloadCmd),
+}
+
+/// Sends the configuration to the REST server of the relayer started with
+/// `hermes start`, which must be enabled in the `[rest]` section.
+///
+/// The running relayer applies the updated packet relaying parameters and
+/// channel filters to its packet workers, shuts down the workers of the
+/// channels which are no longer allowed, and spawns workers for the newly
+/// allowed channels. The changes which require a restart, such as updated
+/// chain endpoints, are reported but not applied.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct SupervisorReloadCmd {}
+
+impl SupervisorReloadCmd {
+    fn execute(&self) -> Result<ReloadReport, Error> {
+        let config = app_config();
+
+        rest_post(&config, "/reload", &*config, RELOAD_TIMEOUT)
+    }
+}
+
+impl Runnable for SupervisorReloadCmd {
+    fn run(&self) {
+        let report = match self.execute() {
+            Ok(report) => report,
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        };
+
+        if json() {
+            Output::success(report).exit()
+        }
+
+        let mut lines = vec![format!(
+            "reconfigured {} packet worker(s), shut down {} worker(s), spawned {} worker(s)",
+            report.reconfigured.len(),
+            report.shut_down.len(),
+            report.spawned.len()
+        )];
+
+        lines.extend(report.shut_down.iter().map(|w| format!("shut down: {}", w)));
+        lines.extend(report.spawned.iter().map(|w| format!("spawned: {}", w)));
+        lines.extend(
+            report
+                .requires_restart
+                .iter()
+                .map(|change| format!("requires a restart: {}", change)),
+        );
+
+        Output::success_msg(lines.join("\n")).exit()
+    }
+}
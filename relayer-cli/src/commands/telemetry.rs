@@ -0,0 +1,85 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use crate::conclude::{json, Output};
+use crate::error::Error;
+
+/// `telemetry` subcommands
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum TelemetryCmds {
+    /// List all the metrics exported by the telemetry service
+    Metrics(TelemetryMetricsCmd),
+}
+
+#[derive(Clone, Command, Debug, Parser)]
+pub struct TelemetryMetricsCmd {}
+
+/// The description of a metric, as printed by the `telemetry metrics` command.
+#[derive(Debug, Serialize)]
+#[cfg_attr(not(feature = "telemetry"), allow(dead_code))]
+struct Metric {
+    name: &'static str,
+    #[serde(rename = "type")]
+    metric_type: &'static str,
+    labels: &'static [&'static str],
+    help: &'static str,
+}
+
+/// Fails with a [`TelemetryDisabled`](crate::error::ErrorDetail::TelemetryDisabled)
+/// error if Hermes was built without the `telemetry` feature, so that the commands
+/// depending on telemetry report it instead of printing empty results.
+pub(crate) fn ensure_telemetry_enabled() -> Result<(), Error> {
+    if ibc_relayer::telemetry::is_enabled() {
+        Ok(())
+    } else {
+        Err(Error::telemetry_disabled())
+    }
+}
+
+#[cfg(feature = "telemetry")]
+fn metrics() -> Vec<Metric> {
+    ibc_telemetry::metric_descriptors()
+        .into_iter()
+        .map(|descriptor| Metric {
+            name: descriptor.name,
+            metric_type: descriptor.metric_type.as_str(),
+            labels: descriptor.labels,
+            help: descriptor.help,
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "telemetry"))]
+fn metrics() -> Vec<Metric> {
+    Vec::new()
+}
+
+impl Runnable for TelemetryMetricsCmd {
+    fn run(&self) {
+        if let Err(e) = ensure_telemetry_enabled() {
+            Output::error(format!("{}", e)).exit()
+        }
+
+        let metrics = metrics();
+
+        if json() {
+            Output::success(metrics).exit()
+        }
+
+        let lines: Vec<String> = metrics
+            .iter()
+            .map(|metric| {
+                format!(
+                    "{} ({}) [{}]: {}",
+                    metric.name,
+                    metric.metric_type,
+                    metric.labels.join(", "),
+                    metric.help
+                )
+            })
+            .collect();
+
+        Output::success_msg(format!("\n{}", lines.join("\n"))).exit()
+    }
+}
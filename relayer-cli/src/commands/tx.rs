@@ -11,6 +11,7 @@ mod channel;
 pub(crate) mod client;
 mod connection;
 mod packet;
+mod simulate;
 mod transfer;
 mod upgrade;
 
@@ -76,6 +77,9 @@ pub enum TxRawCommands {
     /// Relay acknowledgment packets
     PacketAck(packet::TxRawPacketAckCmd),
 
+    /// Simulate the transactions relaying the pending packets on a channel, without broadcasting them
+    Simulate(simulate::TxRawSimulateCmd),
+
     /// Send an IBC upgrade plan
     UpgradeChain(upgrade::TxIbcUpgradeChainCmd),
 }
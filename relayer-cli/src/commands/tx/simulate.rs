@@ -0,0 +1,129 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer::chain::endpoint::TxSimulation;
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::link::operational_data::{OperationalData, OperationalDataTarget};
+use ibc_relayer::link::{Link, LinkParameters, RelayPath};
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// The simulation of a single transaction, ie. a batch of messages.
+#[derive(Debug, Serialize)]
+struct BatchSimulation {
+    /// The chain the batch would be submitted to.
+    chain_id: ChainId,
+    /// The type of each message in the batch, including its client update.
+    messages: Vec<String>,
+    simulation: TxSimulation,
+}
+
+/// Simulate the transactions relaying the pending packets on a channel and its
+/// counterparty, without broadcasting them, to find out why they would fail.
+///
+/// The schedule is built as packet clearing would build it, and the error of
+/// each rejected simulation is decoded into a human-readable reason.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct TxRawSimulateCmd {
+    #[clap(
+        required = true,
+        help = "identifier of the chain at one end of the channel"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        required = true,
+        help = "port identifier on the chain given by <CHAIN_ID>"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        required = true,
+        help = "channel identifier on the chain given by <CHAIN_ID>"
+    )]
+    channel_id: ChannelId,
+}
+
+impl TxRawSimulateCmd {
+    fn execute(&self) -> Result<Vec<BatchSimulation>, Error> {
+        let config = app_config();
+
+        let (chains, _) = spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.chain_id,
+            &self.port_id,
+            &self.channel_id,
+        )?;
+
+        let opts = LinkParameters {
+            src_port_id: self.port_id.clone(),
+            src_channel_id: self.channel_id,
+        };
+
+        let fwd_link = Link::new_from_opts(chains.src.clone(), chains.dst, opts, false)
+            .map_err(Error::link)?;
+        let rev_link = fwd_link.reverse(false).map_err(Error::link)?;
+
+        let mut simulations = simulate_path(&fwd_link.a_to_b)?;
+        simulations.extend(simulate_path(&rev_link.a_to_b)?);
+
+        Ok(simulations)
+    }
+}
+
+impl Runnable for TxRawSimulateCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(simulations) => Output::success(simulations).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+fn simulate_path<Chain: ChainHandle>(
+    relay_path: &RelayPath<Chain, Chain>,
+) -> Result<Vec<BatchSimulation>, Error> {
+    relay_path
+        .take_pending_operational_data()
+        .map_err(Error::link)?
+        .iter()
+        .map(|odata| simulate_batch(relay_path, odata))
+        .collect()
+}
+
+fn simulate_batch<Chain: ChainHandle>(
+    relay_path: &RelayPath<Chain, Chain>,
+    odata: &OperationalData,
+) -> Result<BatchSimulation, Error> {
+    let chain = match odata.target {
+        OperationalDataTarget::Source => relay_path.src_chain(),
+        OperationalDataTarget::Destination => relay_path.dst_chain(),
+    };
+
+    let tracked_msgs = odata.assemble_msgs(relay_path).map_err(Error::link)?;
+
+    let messages = tracked_msgs
+        .messages()
+        .iter()
+        .map(|msg| msg.type_url.clone())
+        .collect();
+
+    debug!(
+        chain = %chain.id(),
+        "simulating batch of {} message(s)",
+        tracked_msgs.messages().len()
+    );
+
+    let simulation = chain.simulate_tx(tracked_msgs).map_err(Error::relayer)?;
+
+    Ok(BatchSimulation {
+        chain_id: chain.id(),
+        messages,
+        simulation,
+    })
+}
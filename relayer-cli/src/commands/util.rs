@@ -0,0 +1,16 @@
+//! `util` subcommand
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use crate::commands::util::decode::DecodeCmds;
+
+mod decode;
+
+/// `util` subcommand
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum UtilCmds {
+    /// Decode raw IBC data, without connecting to any chain
+    #[clap(subcommand)]
+    Decode(DecodeCmds),
+}
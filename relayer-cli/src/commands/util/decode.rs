@@ -0,0 +1,14 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+mod events;
+mod packet;
+
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum DecodeCmds {
+    /// Decode the hex-encoded data of a packet
+    Packet(packet::DecodePacketCmd),
+
+    /// Decode the events of a stored event batch, or a list of events, into a table
+    Events(events::DecodeEventsCmd),
+}
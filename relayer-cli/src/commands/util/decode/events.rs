@@ -0,0 +1,193 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::Serialize;
+
+use ibc::core::ics04_channel::packet::Sequence;
+use ibc::events::IbcEvent;
+use ibc::Height;
+use ibc_relayer::event::dump::BatchDump;
+
+use crate::conclude::{json, Output};
+use crate::error::Error;
+
+use super::packet::{decode_packet_data, DecodedPacketData};
+
+/// An event, as described in a row of the table of decoded events.
+#[derive(Debug, Serialize)]
+struct DecodedEvent {
+    #[serde(rename = "type")]
+    event_type: &'static str,
+    height: Option<Height>,
+    /// The port and channel of the event, eg. the source port and channel of a packet.
+    channel: Option<String>,
+    sequence: Option<Sequence>,
+    /// The decoded data of the packet of the event, if it could be decoded.
+    data: Option<DecodedPacketData>,
+}
+
+impl DecodedEvent {
+    fn new(event: IbcEvent) -> Self {
+        let event_type = event.event_type().as_str();
+
+        let height = match event {
+            IbcEvent::ChainError(_) | IbcEvent::Empty(_) | IbcEvent::AppModule(_) => None,
+            _ => Some(event.height()),
+        };
+
+        match event.packet() {
+            Some(packet) => Self {
+                event_type,
+                height,
+                channel: Some(format!("{}/{}", packet.source_port, packet.source_channel)),
+                sequence: Some(packet.sequence),
+                data: decode_packet_data(&packet.data).ok(),
+            },
+            None => Self {
+                event_type,
+                height,
+                channel: event.channel_attributes().and_then(|attributes| {
+                    let channel_id = attributes.channel_id?;
+                    Some(format!("{}/{}", attributes.port_id, channel_id))
+                }),
+                sequence: None,
+                data: None,
+            },
+        }
+    }
+}
+
+/// Decodes the events of an event batch stored by `listen --dump-batches`, as
+/// consumed by `replay batch`, or a JSON list of events, eg. pasted from the logs,
+/// and prints them as a table, without connecting to any chain.
+///
+/// The data of the packets is decoded as by `util decode packet`.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct DecodeEventsCmd {
+    #[clap(
+        required = true,
+        help = "path of the stored event batch, or of a JSON list of events"
+    )]
+    file: PathBuf,
+}
+
+impl DecodeEventsCmd {
+    fn execute(&self) -> Result<Vec<DecodedEvent>, Error> {
+        let events = read_events(&self.file)?;
+
+        Ok(events.into_iter().map(DecodedEvent::new).collect())
+    }
+}
+
+impl Runnable for DecodeEventsCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(events) if json() => Output::success(events).exit(),
+            Ok(events) => Output::success_msg(format_table(&events)).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+/// Reads the events from the given file, holding either a stored event
+/// batch, or a list of events.
+fn read_events(path: &Path) -> Result<Vec<IbcEvent>, Error> {
+    let invalid = |e: &dyn core::fmt::Display| {
+        Error::cli_arg(format!("invalid events file '{}': {}", path.display(), e))
+    };
+
+    let json = fs::read_to_string(path).map_err(|e| invalid(&e))?;
+    let value: serde_json::Value = serde_json::from_str(&json).map_err(|e| invalid(&e))?;
+
+    if value.is_array() {
+        serde_json::from_value(value).map_err(|e| invalid(&e))
+    } else {
+        let dump = BatchDump::read(path).map_err(Error::batch_dump)?;
+        Ok(dump.events)
+    }
+}
+
+/// Formats the given events as a table, with one row per event, and columns
+/// aligned to their widest cell.
+fn format_table(events: &[DecodedEvent]) -> String {
+    let header = ["TYPE", "HEIGHT", "CHANNEL", "SEQUENCE", "DATA"].map(String::from);
+
+    let rows: Vec<[String; 5]> = events
+        .iter()
+        .map(|event| {
+            [
+                event.event_type.to_string(),
+                cell(&event.height),
+                cell(&event.channel),
+                cell(&event.sequence),
+                cell(&event.data),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.clone().map(|cell| cell.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    core::iter::once(&header)
+        .chain(&rows)
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect();
+
+            cells.join("  ").trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn cell(value: &Option<impl ToString>) -> String {
+    value
+        .as_ref()
+        .map_or_else(|| "-".to_string(), ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc::core::ics04_channel::events::SendPacket;
+    use ibc::core::ics04_channel::packet::{Packet, Sequence};
+    use ibc::core::ics24_host::identifier::{ChannelId, PortId};
+    use ibc::events::IbcEvent;
+    use ibc::Height;
+
+    use super::{format_table, DecodedEvent};
+
+    #[test]
+    fn packet_events_are_decoded_into_a_table() {
+        let event = IbcEvent::SendPacket(SendPacket {
+            height: Height::new(0, 42),
+            packet: Packet {
+                sequence: Sequence::from(7),
+                source_port: PortId::transfer(),
+                source_channel: ChannelId::new(3),
+                data: br#"{"amount":"1000","denom":"uatom","receiver":"cosmos1receiver","sender":"cosmos1sender"}"#.to_vec(),
+                ..Default::default()
+            },
+        });
+
+        let decoded = DecodedEvent::new(event);
+        assert_eq!(decoded.channel.as_deref(), Some("transfer/channel-3"));
+        assert_eq!(decoded.sequence, Some(Sequence::from(7)));
+
+        let table = format_table(&[decoded]);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("TYPE"));
+        assert!(lines[1].starts_with("send_packet"));
+        assert!(lines[1].ends_with("1000 uatom from cosmos1sender to cosmos1receiver"));
+    }
+}
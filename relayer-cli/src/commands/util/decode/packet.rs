@@ -0,0 +1,155 @@
+use core::convert::TryFrom;
+use core::fmt;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::{Deserialize, Serialize};
+
+use ibc::applications::transfer::packet::PacketData;
+use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+
+use crate::conclude::Output;
+use crate::error::Error;
+
+/// The packet data of an ICS-20 fungible token transfer, as encoded in JSON.
+#[derive(Debug, Deserialize, Serialize)]
+struct FungibleTokenPacketData {
+    denom: String,
+    amount: String,
+    sender: String,
+    receiver: String,
+}
+
+/// The decoded data of a packet.
+#[derive(Debug, Serialize)]
+#[serde(tag = "encoding", content = "data", rename_all = "snake_case")]
+pub(super) enum DecodedPacketData {
+    /// The data of a fungible token transfer packet.
+    FungibleToken(FungibleTokenPacketData),
+    /// Some other JSON data, eg. from an interchain accounts or custom application.
+    Json(serde_json::Value),
+    /// Some other UTF-8 text.
+    Utf8(String),
+}
+
+/// Decodes the hex-encoded data of a packet, eg. as found in the events
+/// returned by `query tx events`, without connecting to any chain.
+///
+/// The data is first decoded as the data of an ICS-20 fungible token transfer
+/// packet, and otherwise as arbitrary JSON or UTF-8 text.
+#[derive(Clone, Command, Debug, Parser)]
+pub struct DecodePacketCmd {
+    #[clap(
+        required = true,
+        help = "hex-encoded packet data, optionally prefixed with 0x"
+    )]
+    data: String,
+}
+
+impl DecodePacketCmd {
+    fn execute(&self) -> Result<DecodedPacketData, Error> {
+        let hex_data = self.data.trim();
+        let hex_data = hex_data.strip_prefix("0x").unwrap_or(hex_data);
+
+        let bytes = hex::decode(hex_data)
+            .map_err(|e| Error::cli_arg(format!("invalid hex-encoded packet data: {}", e)))?;
+
+        decode_packet_data(&bytes)
+    }
+}
+
+impl Runnable for DecodePacketCmd {
+    fn run(&self) {
+        match self.execute() {
+            Ok(decoded) => Output::success(decoded).exit(),
+            Err(e) => Output::error(format!("{}", e)).exit(),
+        }
+    }
+}
+
+impl fmt::Display for DecodedPacketData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodedPacketData::FungibleToken(data) => write!(
+                f,
+                "{} {} from {} to {}",
+                data.amount, data.denom, data.sender, data.receiver
+            ),
+            DecodedPacketData::Json(value) => write!(f, "{}", value),
+            DecodedPacketData::Utf8(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+pub(super) fn decode_packet_data(bytes: &[u8]) -> Result<DecodedPacketData, Error> {
+    if let Ok(data) = serde_json::from_slice::<FungibleTokenPacketData>(bytes) {
+        if is_valid_fungible_token_data(&data) {
+            return Ok(DecodedPacketData::FungibleToken(data));
+        }
+    }
+
+    if let Ok(value) = serde_json::from_slice::<serde_json::Value>(bytes) {
+        return Ok(DecodedPacketData::Json(value));
+    }
+
+    String::from_utf8(bytes.to_vec())
+        .map(DecodedPacketData::Utf8)
+        .map_err(|_| {
+            Error::cli_arg("packet data is neither valid JSON nor valid UTF-8 text".to_string())
+        })
+}
+
+/// Checks that the denomination, amount and addresses of the data are well-formed.
+fn is_valid_fungible_token_data(data: &FungibleTokenPacketData) -> bool {
+    let raw = RawPacketData {
+        denom: data.denom.clone(),
+        amount: data.amount.clone(),
+        sender: data.sender.clone(),
+        receiver: data.receiver.clone(),
+    };
+
+    PacketData::try_from(raw).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_packet_data, DecodedPacketData};
+
+    #[test]
+    fn decode_fungible_token_packet_data() {
+        let data = br#"{"amount":"1000","denom":"transfer/channel-0/uatom","receiver":"cosmos1receiver","sender":"cosmos1sender"}"#;
+
+        match decode_packet_data(data).unwrap() {
+            DecodedPacketData::FungibleToken(data) => {
+                assert_eq!(data.amount, "1000");
+                assert_eq!(data.denom, "transfer/channel-0/uatom");
+                assert_eq!(data.sender, "cosmos1sender");
+                assert_eq!(data.receiver, "cosmos1receiver");
+            }
+            decoded => panic!("expected fungible token packet data, got {:?}", decoded),
+        }
+    }
+
+    #[test]
+    fn decode_other_json_packet_data() {
+        let data = br#"{"type":"TYPE_EXECUTE_TX","data":"CgQ="}"#;
+
+        assert!(matches!(
+            decode_packet_data(data).unwrap(),
+            DecodedPacketData::Json(_)
+        ));
+    }
+
+    #[test]
+    fn decode_utf8_packet_data() {
+        assert!(matches!(
+            decode_packet_data(b"hello").unwrap(),
+            DecodedPacketData::Utf8(ref text) if text == "hello"
+        ));
+    }
+
+    #[test]
+    fn decode_binary_packet_data_fails() {
+        assert!(decode_packet_data(&[0xff, 0xfe, 0x00]).is_err());
+    }
+}
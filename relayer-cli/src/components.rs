@@ -1,3 +1,5 @@
+use core::{fmt, str::FromStr};
+
 use abscissa_core::{Component, FrameworkError, FrameworkErrorKind};
 use tracing_subscriber::{filter::EnvFilter, util::SubscriberInitExt, FmtSubscriber};
 
@@ -9,6 +11,36 @@ use crate::config::Error;
 /// the tracing filter built in [`build_tracing_filter`].
 const HERMES_LOG_VAR: &str = "RUST_LOG";
 
+/// The format of the log messages, set with the global `--log-format` option.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable log messages, handled by [`PrettyTracing`]
+    Text,
+    /// One JSON object per log message, handled by [`JsonTracing`]
+    Json,
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            invalid => Err(format!("unrecognized log format: {}", invalid).into()),
+        }
+    }
+}
+
 /// A custom component for parametrizing `tracing` in the relayer.
 /// Primarily used for:
 ///
@@ -32,7 +64,11 @@ impl JsonTracing {
             .with_writer(std::io::stderr)
             .with_ansi(use_color)
             .with_thread_ids(true)
-            .json();
+            .json()
+            // Report the fields of the spans the message was logged in, eg. the
+            // `src_chain` and `src_channel` of the span of a packet worker.
+            .with_current_span(true)
+            .with_span_list(true);
 
         let subscriber = builder.finish();
         subscriber.init();
@@ -54,8 +54,20 @@
 //! let end = ConnectionEnd::default();
 //! Output::success(h).with_result(end).exit();
 //! ```
+//!
+//! - Output a result on each iteration of a long-running command, without exiting,
+//! as one JSON object per line (NDJSON) with the `--json` flag:
+//!
+//! ```ignore
+//! for balance in balances {
+//!     if Output::success(balance).stream().is_err() {
+//!         break; // stdout was closed, eg. by the reader of a pipe
+//!     }
+//! }
+//! ```
 
 use core::fmt;
+use std::io::{self, Write};
 
 use serde::Serialize;
 use tracing::warn;
@@ -69,11 +81,7 @@ pub fn exit_with(out: Output) -> ! {
     let status = out.status;
 
     // Handle the output message
-    if json() {
-        println!("{}", serde_json::to_string(&out.into_json()).unwrap());
-    } else {
-        println!("{}: {}", out.status, out.result);
-    }
+    println!("{}", out.into_line());
 
     // The return code
     if status == Status::Error {
@@ -83,6 +91,16 @@ pub fn exit_with(out: Output) -> ! {
     }
 }
 
+/// Writes the given line to stdout and flushes it, so that the line reaches
+/// the reader right away even when stdout is not a terminal.
+pub fn write_line(line: &str) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+
+    writeln!(stdout, "{}", line)?;
+    stdout.flush()
+}
+
 /// Returns true if the application global json flag `-j` or `--json` is enabled.
 /// Returns false otherwise.
 pub fn json() -> bool {
@@ -208,6 +226,25 @@ impl Output {
         exit_with(self);
     }
 
+    /// Writes the current output as a single line to stdout and flushes it, without exiting,
+    /// so that long-running commands can output their results as they go. With the `--json`
+    /// flag, the output is written as a compact JSON object, the lines forming NDJSON.
+    ///
+    /// Fails if stdout was closed, eg. by the reader of a pipe, in which case the command
+    /// should stop.
+    pub fn stream(self) -> io::Result<()> {
+        write_line(&self.into_line())
+    }
+
+    /// Formats the current output as a single line, as JSON with the `--json` flag.
+    fn into_line(self) -> String {
+        if json() {
+            serde_json::to_string(&self.into_json()).unwrap()
+        } else {
+            format!("{}: {}", self.status, self.result)
+        }
+    }
+
     /// Convert this output value to a JSON value
     pub fn into_json(self) -> serde_json::Value {
         let mut map = serde_json::Map::new();
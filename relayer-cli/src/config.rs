@@ -9,7 +9,7 @@ use std::path::PathBuf;
 
 use flex_error::{define_error, TraceError};
 use ibc::core::ics24_host::identifier::ChainId;
-use ibc_relayer::config::{Config, ModeConfig};
+use ibc_relayer::config::{Config, ModeConfig, TxRateLimit};
 use tendermint_light_client_verifier::types::TrustThreshold;
 use tracing_subscriber::filter::ParseError;
 
@@ -70,6 +70,16 @@ define_error! {
                 format!("config file specifies an invalid `gas_adjustment` ({0}) for the chain with id {1}, caused by: {2}",
                     e.gas_adjustment, e.chain_id, e.reason)
             },
+
+        InvalidTxRateLimit
+            {
+                chain_id: ChainId,
+                reason: String
+            }
+            |e| {
+                format!("config file specifies an invalid `tx_rate_limit` for the chain with id {0}, caused by: {1}",
+                    e.chain_id, e.reason)
+            },
     }
 }
 
@@ -93,6 +103,10 @@ pub fn validate_config(config: &Config) -> Result<(), Diagnostic<Error>> {
 
         // Validate gas-related settings
         validate_gas_settings(&c.id, c.gas_adjustment)?;
+
+        if let Some(tx_rate_limit) = c.tx_rate_limit {
+            validate_tx_rate_limit(&c.id, tx_rate_limit)?;
+        }
     }
 
     // Check for invalid mode config
@@ -168,3 +182,24 @@ fn validate_gas_settings(
         _ => Ok(()),
     }
 }
+
+fn validate_tx_rate_limit(
+    id: &ChainId,
+    tx_rate_limit: TxRateLimit,
+) -> Result<(), Diagnostic<Error>> {
+    if tx_rate_limit.capacity == 0 {
+        return Err(Diagnostic::Error(Error::invalid_tx_rate_limit(
+            id.clone(),
+            "the capacity must be at least 1".to_string(),
+        )));
+    }
+
+    if !(tx_rate_limit.refill_per_second.is_finite() && tx_rate_limit.refill_per_second > 0.0) {
+        return Err(Diagnostic::Error(Error::invalid_tx_rate_limit(
+            id.clone(),
+            "the refill rate must be a positive number".to_string(),
+        )));
+    }
+
+    Ok(())
+}
@@ -7,6 +7,7 @@ use clap::IntoApp;
 use ibc_relayer::config::Config;
 
 use crate::commands::CliCmd;
+use crate::components::LogFormat;
 
 /// Entry point for Hermes CLI.
 #[derive(Command, Debug, Parser)]
@@ -20,6 +21,15 @@ pub struct EntryPoint {
     #[clap(short = 'j', long, help = "enable JSON output")]
     pub json: bool,
 
+    /// Format of the log messages, regardless of the output mode
+    #[clap(
+        long = "log-format",
+        value_name = "FORMAT",
+        possible_values = ["text", "json"],
+        help = "format of the log messages (default: json with --json, text otherwise)"
+    )]
+    pub log_format: Option<LogFormat>,
+
     /// Subcommand to execute.
     ///
     /// The `command` option will delegate option parsing to the command type,
@@ -8,12 +8,14 @@ use ibc::core::ics24_host::identifier::ChainId;
 use ibc_relayer::channel::ChannelError;
 use ibc_relayer::connection::ConnectionError;
 use ibc_relayer::error::Error as RelayerError;
+use ibc_relayer::event::dump::BatchDumpError;
 use ibc_relayer::foreign_client::ForeignClientError;
 use ibc_relayer::link::error::LinkError;
 use ibc_relayer::spawn::SpawnError;
 use ibc_relayer::supervisor::Error as SupervisorError;
 use ibc_relayer::transfer::TransferError;
 use ibc_relayer::upgrade_chain::UpgradeChainError;
+use ibc_relayer::worker::RunError;
 
 define_error! {
     /// An error raised within the relayer CLI
@@ -41,6 +43,13 @@ define_error! {
                     e.hash)
             },
 
+        TxNotFound
+            { chain_id: ChainId, hash: String }
+            | e | {
+                format_args!("transaction {} was not found on chain '{}'",
+                    e.hash, e.chain_id)
+            },
+
         CliArg
             { reason: String }
             | e | {
@@ -48,6 +57,13 @@ define_error! {
                     e.reason)
             },
 
+        RestRequest
+            { address: String, reason: String }
+            | e | {
+                format_args!("request to the REST server at '{}' failed: {}",
+                    e.address, e.reason)
+            },
+
         Keys
             |_| { "keys error" },
 
@@ -100,5 +116,19 @@ define_error! {
         UpgradeChain
             [ UpgradeChainError ]
             |_| { "upgrade chain error" },
+
+        Worker
+            [ RunError ]
+            |_| { "worker error" },
+
+        BatchDump
+            [ BatchDumpError ]
+            |_| { "batch dump error" },
+
+        TelemetryDisabled
+            |_| {
+                "Hermes was built without telemetry support, \
+                 build Hermes with --features=telemetry to enable telemetry support."
+            },
     }
 }
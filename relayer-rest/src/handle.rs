@@ -5,11 +5,12 @@ use tracing::error;
 use crossbeam_channel as channel;
 
 use ibc::core::ics24_host::identifier::ChainId;
-use ibc_relayer::supervisor::dump_state::SupervisorState;
+use ibc_relayer::supervisor::{dump_state::SupervisorState, reload::ReloadReport};
 use ibc_relayer::{
-    config::ChainConfig,
+    account::AccountInfo,
+    config::{ChainConfig, Config},
     rest::{
-        request::{reply_channel, ReplySender, Request, VersionInfo},
+        request::{reply_channel, AccountSequenceParams, ReplySender, Request, VersionInfo},
         RestApiError,
     },
 };
@@ -58,12 +59,34 @@ pub fn chain_config(
     })
 }
 
+pub fn account_sequence(
+    sender: &channel::Sender<Request>,
+    chain_id: &str,
+    params: AccountSequenceParams,
+) -> Result<AccountInfo, RestApiError> {
+    submit_request(sender, |reply_to| Request::AccountSequence {
+        chain_id: ChainId::from_string(chain_id),
+        params,
+        reply_to,
+    })
+}
+
 pub fn supervisor_state(
     sender: &channel::Sender<Request>,
 ) -> Result<SupervisorState, RestApiError> {
     submit_request(sender, |reply_to| Request::State { reply_to })
 }
 
+pub fn reload(
+    sender: &channel::Sender<Request>,
+    config: Config,
+) -> Result<ReloadReport, RestApiError> {
+    submit_request(sender, |reply_to| Request::Reload {
+        config: Box::new(config),
+        reply_to,
+    })
+}
+
 pub fn assemble_version_info(sender: &channel::Sender<Request>) -> Vec<VersionInfo> {
     // Fetch the relayer library version
     let lib_version = submit_request(sender, |reply_to| Request::Version { reply_to })
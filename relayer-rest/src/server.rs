@@ -4,10 +4,17 @@ use crossbeam_channel as channel;
 use serde::{Deserialize, Serialize};
 use tracing::{info, trace};
 
-use ibc_relayer::rest::request::Request;
+use ibc_relayer::config::Config as RelayerConfig;
+use ibc_relayer::rest::{
+    request::{AccountSequenceParams, Request},
+    RestApiError,
+};
 
 use crate::{
-    handle::{all_chain_ids, assemble_version_info, chain_config, supervisor_state},
+    handle::{
+        account_sequence, all_chain_ids, assemble_version_info, chain_config, reload,
+        supervisor_state,
+    },
     Config,
 };
 
@@ -76,12 +83,28 @@ fn run(config: Config, sender: channel::Sender<Request>) -> ServerHandle {
                 rouille::Response::json(&JsonResult::from(result))
             },
 
+            (POST) (/chain/{id: String}/sequence) => {
+                trace!("[rest] POST /chain/{}/sequence", id);
+                let result = rouille::input::json_input::<AccountSequenceParams>(request)
+                    .map_err(|e| RestApiError::InvalidRequestBody(e.to_string()))
+                    .and_then(|params| account_sequence(&sender, &id, params));
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
             (GET) (/state) => {
                 trace!("[rest] GET /state");
                 let result = supervisor_state(&sender);
                 rouille::Response::json(&JsonResult::from(result))
             },
 
+            (POST) (/reload) => {
+                trace!("[rest] POST /reload");
+                let result = rouille::input::json_input::<RelayerConfig>(request)
+                    .map_err(|e| RestApiError::InvalidConfig(e.to_string()))
+                    .and_then(|config| reload(&sender, config));
+                rouille::Response::json(&JsonResult::from(result))
+            },
+
             _ => rouille::Response::empty_404(),
         )
     })
@@ -4,9 +4,9 @@ use serde::{Deserialize, Serialize};
 
 use ibc::core::ics24_host::identifier::ChainId;
 use ibc_relayer::{
-    config::ChainConfig,
+    config::{ChainConfig, Config as RelayerConfig},
     rest::request::{Request, VersionInfo},
-    supervisor::dump_state::SupervisorState,
+    supervisor::{dump_state::SupervisorState, reload::ReloadReport},
 };
 
 use ibc_relayer_rest::{server::spawn, Config};
@@ -137,3 +137,40 @@ fn state() {
         req => TestResult::WrongRequest(req),
     });
 }
+
+#[test]
+fn reload() {
+    let chain_config: ChainConfig = toml::de::from_str(MOCK_CHAIN_CONFIG).unwrap();
+    let config = RelayerConfig {
+        chains: vec![chain_config],
+        ..RelayerConfig::default()
+    };
+
+    let report = ReloadReport {
+        reconfigured: vec!["packet::channel-0/transfer:mock-0->mock-1".to_string()],
+        ..ReloadReport::default()
+    };
+    let result: JsonResult<_, ()> = JsonResult::Success(report.clone());
+
+    let (handle, rx) = spawn(Config::new("127.0.0.1".to_string(), 19105));
+
+    std::thread::spawn(move || match rx.recv() {
+        Ok(Request::Reload { config, reply_to }) if config.chains.len() == 1 => {
+            reply_to.send(Ok(report)).unwrap();
+        }
+        Ok(req) => panic!("got the wrong request: {:?}", req),
+        Err(e) => panic!("got an error: {}", e),
+    });
+
+    let response = ureq::post("http://127.0.0.1:19105/reload")
+        .set("Content-Type", "application/json")
+        .send_string(&serde_json::to_string(&config).unwrap())
+        .unwrap()
+        .into_string()
+        .unwrap();
+
+    assert_eq!(response, serde_json::to_string(&result).unwrap());
+
+    handle.stop();
+    handle.join().unwrap();
+}
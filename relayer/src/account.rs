@@ -10,3 +10,28 @@ pub struct Balance {
     /// The denomination for that coin
     pub denom: String,
 }
+
+/// The trace of an IBC voucher denomination, ie. the denomination of the
+/// coins on their origin chain and the path they were transferred over.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DenomTrace {
+    /// The port/channel identifiers the coins were transferred over, eg. `transfer/channel-0`
+    pub path: String,
+    /// The denomination of the coins on their origin chain
+    pub base_denom: String,
+}
+
+/// The on-chain account number and sequence of the account of a key,
+/// along with the account sequence cached by the relayer, if any.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AccountInfo {
+    /// The address of the account
+    pub address: String,
+    /// The account number, as stored on chain
+    pub account_number: u64,
+    /// The account sequence, as stored on chain
+    pub sequence: u64,
+    /// The account sequence cached by the relayer for submitting transactions,
+    /// if the relayer has cached the account of this key
+    pub cached_sequence: Option<u64>,
+}
@@ -7,6 +7,7 @@ use core::{
     time::Duration,
 };
 use num_bigint::BigInt;
+use std::collections::HashMap;
 use std::thread;
 
 use bitcoin::hashes::hex::ToHex;
@@ -22,7 +23,7 @@ use tendermint_rpc::{
 };
 use tokio::runtime::Runtime as TokioRuntime;
 use tonic::codegen::http::Uri;
-use tracing::{error, span, warn, Level};
+use tracing::{error, info, span, warn, Level};
 
 use ibc::clients::ics07_tendermint::consensus_state::ConsensusState as TMConsensusState;
 use ibc::clients::ics07_tendermint::header::Header as TmHeader;
@@ -43,9 +44,9 @@ use ibc::core::ics24_host::path::{
     ConnectionsPath, ReceiptsPath, SeqRecvsPath,
 };
 use ibc::core::ics24_host::{ClientUpgradePath, Path, IBC_QUERY_PATH, SDK_UPGRADE_QUERY_PATH};
-use ibc::events::IbcEvent;
+use ibc::events::{IbcEvent, IbcEventType};
 use ibc::query::QueryBlockRequest;
-use ibc::query::QueryTxRequest;
+use ibc::query::{QueryTxHash, QueryTxRequest};
 use ibc::signer::Signer;
 use ibc::Height as ICSHeight;
 use ibc::{
@@ -53,27 +54,39 @@ use ibc::{
     core::ics23_commitment::merkle::MerkleProof,
 };
 use ibc_proto::cosmos::staking::v1beta1::Params as StakingParams;
+use ibc_proto::google::protobuf::Any;
 
-use crate::account::Balance;
+use crate::account::{AccountInfo, Balance, DenomTrace};
 use crate::chain::client::ClientSettings;
 use crate::chain::cosmos::batch::{
     send_batched_messages_and_wait_check_tx, send_batched_messages_and_wait_commit,
 };
 use crate::chain::cosmos::encode::encode_to_bech32;
+use crate::chain::cosmos::estimate::estimate_tx_gas;
 use crate::chain::cosmos::gas::{calculate_fee, mul_ceil};
-use crate::chain::cosmos::query::account::get_or_fetch_account;
+use crate::chain::cosmos::query::account::{get_or_fetch_account, query_account};
 use crate::chain::cosmos::query::balance::query_balance;
+use crate::chain::cosmos::query::denom_trace::query_denom_trace;
+use crate::chain::cosmos::query::gas_price::query_min_gas_price;
 use crate::chain::cosmos::query::status::query_status;
-use crate::chain::cosmos::query::tx::query_txs;
+use crate::chain::cosmos::query::supply::query_supply_of;
+use crate::chain::cosmos::query::tx::{
+    query_packet_txs, query_tx_by_hash, query_tx_decoded_events, query_tx_result, query_txs,
+};
 use crate::chain::cosmos::query::{abci_query, fetch_version_specs, packet_query, QueryResponse};
+use crate::chain::cosmos::signer::set_signer;
+use crate::chain::cosmos::simulate::simulate_tx;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::gas::{default_gas_from_config, max_gas_from_config};
-use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck};
+use crate::chain::endpoint::{
+    ChainEndpoint, ChainStatus, HealthCheck, PacketEventTx, TxEvent, TxResult, TxSimulation,
+};
 use crate::chain::tracking::TrackedMsgs;
-use crate::config::ChainConfig;
+use crate::config::{ChainConfig, EventSource, GasPrice};
 use crate::error::Error;
 use crate::event::monitor::{EventMonitor, EventReceiver, TxMonitorCmd};
+use crate::event::poll_monitor::PollMonitor;
 use crate::keyring::{KeyEntry, KeyRing};
 use crate::light_client::tendermint::LightClient as TmLightClient;
 use crate::light_client::{LightClient, Verified};
@@ -98,6 +111,7 @@ pub mod estimate;
 pub mod gas;
 pub mod query;
 pub mod retry;
+pub mod signer;
 pub mod simulate;
 pub mod tx;
 pub mod types;
@@ -117,6 +131,9 @@ pub struct CosmosSdkChain {
     keybase: KeyRing,
     /// A cached copy of the account information
     account: Option<Account>,
+    /// Cached copies of the account information of the keys other than
+    /// the one from the configuration, which sign the messages naming them
+    extra_accounts: HashMap<String, Option<Account>>,
 }
 
 impl CosmosSdkChain {
@@ -354,6 +371,28 @@ impl CosmosSdkChain {
             .map_err(Error::key_base)
     }
 
+    /// Returns the key which signs the given messages, along with the name of
+    /// that key if it is not the one from the configuration, and the messages,
+    /// whose signer is set to the account of any such other key.
+    fn signing_key(
+        &self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<(KeyEntry, Option<String>, Vec<Any>), Error> {
+        match tracked_msgs.key_name {
+            Some(key_name) if key_name != self.config.key_name => {
+                let key_entry = self
+                    .keybase()
+                    .get_key(&key_name)
+                    .map_err(|e| Error::key_not_found(key_name.clone(), e))?;
+
+                let msgs = set_signer(tracked_msgs.msgs, &key_entry.account)?;
+
+                Ok((key_entry, Some(key_name), msgs))
+            }
+            _ => Ok((self.key()?, None, tracked_msgs.msgs)),
+        }
+    }
+
     fn trusting_period(&self, unbonding_period: Duration) -> Duration {
         self.config
             .trusting_period
@@ -402,12 +441,15 @@ impl CosmosSdkChain {
         let _span =
             span!(Level::DEBUG, "send_tx_commit", id = %tracked_msgs.tracking_id()).entered();
 
-        let proto_msgs = tracked_msgs.msgs;
+        let memo = tracked_msgs.memo(&self.config.memo_prefix);
+        let (key_entry, extra_key_name, proto_msgs) = self.signing_key(tracked_msgs)?;
 
-        let key_entry = self.key()?;
+        let m_account = match extra_key_name {
+            Some(key_name) => self.extra_accounts.entry(key_name).or_default(),
+            None => &mut self.account,
+        };
 
-        let account =
-            get_or_fetch_account(&self.grpc_addr, &key_entry.account, &mut self.account).await?;
+        let account = get_or_fetch_account(&self.grpc_addr, &key_entry.account, m_account).await?;
 
         send_batched_messages_and_wait_commit(
             &self.tx_config,
@@ -415,7 +457,7 @@ impl CosmosSdkChain {
             self.config.max_tx_size,
             &key_entry,
             account,
-            &self.config.memo_prefix,
+            &memo,
             proto_msgs,
         )
         .await
@@ -430,12 +472,15 @@ impl CosmosSdkChain {
         let span = span!(Level::DEBUG, "send_tx_check", id = %tracked_msgs.tracking_id());
         let _enter = span.enter();
 
-        let proto_msgs = tracked_msgs.msgs;
+        let memo = tracked_msgs.memo(&self.config.memo_prefix);
+        let (key_entry, extra_key_name, proto_msgs) = self.signing_key(tracked_msgs)?;
 
-        let key_entry = self.key()?;
+        let m_account = match extra_key_name {
+            Some(key_name) => self.extra_accounts.entry(key_name).or_default(),
+            None => &mut self.account,
+        };
 
-        let account =
-            get_or_fetch_account(&self.grpc_addr, &key_entry.account, &mut self.account).await?;
+        let account = get_or_fetch_account(&self.grpc_addr, &key_entry.account, m_account).await?;
 
         send_batched_messages_and_wait_check_tx(
             &self.tx_config,
@@ -443,7 +488,7 @@ impl CosmosSdkChain {
             self.config.max_tx_size,
             &key_entry,
             account,
-            &self.config.memo_prefix,
+            &memo,
             proto_msgs,
         )
         .await
@@ -479,6 +524,7 @@ impl ChainEndpoint for CosmosSdkChain {
             rt,
             keybase,
             account: None,
+            extra_accounts: HashMap::new(),
             tx_config,
         };
 
@@ -507,18 +553,34 @@ impl ChainEndpoint for CosmosSdkChain {
     ) -> Result<(EventReceiver, TxMonitorCmd), Error> {
         crate::time!("init_event_monitor");
 
-        let (mut event_monitor, event_receiver, monitor_tx) = EventMonitor::new(
-            self.config.id.clone(),
-            self.config.websocket_addr.clone(),
-            rt,
-        )
-        .map_err(Error::event_monitor)?;
+        match self.config.event_source {
+            EventSource::WebSocket => {
+                let (mut event_monitor, event_receiver, monitor_tx) = EventMonitor::new(
+                    self.config.id.clone(),
+                    self.config.websocket_addr.clone(),
+                    rt,
+                )
+                .map_err(Error::event_monitor)?;
+
+                event_monitor.subscribe().map_err(Error::event_monitor)?;
 
-        event_monitor.subscribe().map_err(Error::event_monitor)?;
+                thread::spawn(move || event_monitor.run());
 
-        thread::spawn(move || event_monitor.run());
+                Ok((event_receiver, monitor_tx))
+            }
+            EventSource::Poll { interval } => {
+                let (poll_monitor, event_receiver, monitor_tx) = PollMonitor::new(
+                    self.config.id.clone(),
+                    self.rpc_client.clone(),
+                    interval,
+                    rt,
+                );
+
+                thread::spawn(move || poll_monitor.run());
 
-        Ok((event_receiver, monitor_tx))
+                Ok((event_receiver, monitor_tx))
+            }
+        }
     }
 
     fn shutdown(self) -> Result<(), Error> {
@@ -594,6 +656,50 @@ impl ChainEndpoint for CosmosSdkChain {
         runtime.block_on(self.do_send_messages_and_wait_check_tx(tracked_msgs))
     }
 
+    fn estimate_gas(&mut self, tracked_msgs: TrackedMsgs) -> Result<u64, Error> {
+        crate::time!("estimate_gas");
+
+        let key_entry = self.key()?;
+
+        let runtime = self.rt.clone();
+
+        let account = runtime.block_on(get_or_fetch_account(
+            &self.grpc_addr,
+            &key_entry.account,
+            &mut self.account,
+        ))?;
+
+        runtime.block_on(estimate_tx_gas(
+            &self.tx_config,
+            &key_entry,
+            account,
+            &self.config.memo_prefix,
+            tracked_msgs.msgs,
+        ))
+    }
+
+    fn simulate_tx(&mut self, tracked_msgs: TrackedMsgs) -> Result<TxSimulation, Error> {
+        crate::time!("simulate_tx");
+
+        let key_entry = self.key()?;
+
+        let runtime = self.rt.clone();
+
+        let account = runtime.block_on(get_or_fetch_account(
+            &self.grpc_addr,
+            &key_entry.account,
+            &mut self.account,
+        ))?;
+
+        runtime.block_on(simulate_tx(
+            &self.tx_config,
+            &key_entry,
+            account,
+            &self.config.memo_prefix,
+            tracked_msgs.msgs,
+        ))
+    }
+
     /// Get the account for the signer
     fn get_signer(&mut self) -> Result<Signer, Error> {
         crate::time!("get_signer");
@@ -664,6 +770,103 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(balance)
     }
 
+    fn query_balances(&self, key_names: Vec<String>) -> Result<Vec<Result<Balance, Error>>, Error> {
+        crate::time!("query_balances");
+
+        let denom = &self.config.gas_price.denom;
+
+        // Issue all the balance queries concurrently, `join_all`
+        // yields the results in the same order as the keys.
+        let queries = key_names.iter().map(|key_name| async move {
+            let key = self.keybase().get_key(key_name).map_err(Error::key_base)?;
+            query_balance(&self.grpc_addr, &key.account, denom).await
+        });
+
+        Ok(self.block_on(futures::future::join_all(queries)))
+    }
+
+    fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
+        crate::time!("query_denom_trace");
+        crate::telemetry!(query, self.id(), "query_denom_trace");
+
+        let denom_trace = self.block_on(query_denom_trace(&self.grpc_addr, &hash))?;
+
+        Ok(denom_trace)
+    }
+
+    fn query_supply_of(&self, denom: String) -> Result<Balance, Error> {
+        crate::time!("query_supply_of");
+        crate::telemetry!(query, self.id(), "query_supply_of");
+
+        let supply = self.block_on(query_supply_of(&self.grpc_addr, &denom))?;
+
+        Ok(supply)
+    }
+
+    fn query_balance_of_address(&self, address: String, denom: String) -> Result<Balance, Error> {
+        crate::time!("query_balance_of_address");
+        crate::telemetry!(query, self.id(), "query_balance_of_address");
+
+        let balance = self.block_on(query_balance(&self.grpc_addr, &address, &denom))?;
+
+        Ok(balance)
+    }
+
+    fn query_min_gas_price(&self) -> Result<Vec<GasPrice>, Error> {
+        crate::time!("query_min_gas_price");
+        crate::telemetry!(query, self.id(), "query_min_gas_price");
+
+        self.block_on(query_min_gas_price(self.id(), &self.grpc_addr))
+    }
+
+    fn query_account_info(&self, key_name: Option<String>) -> Result<AccountInfo, Error> {
+        crate::time!("query_account_info");
+
+        // The relayer only caches the accounts of the keys it has signed messages with.
+        let (key, cached_account) = match key_name {
+            Some(key_name) if key_name != self.config.key_name => {
+                let key = self.keybase().get_key(&key_name).map_err(Error::key_base)?;
+                let cached = self.extra_accounts.get(&key_name).and_then(Option::as_ref);
+                (key, cached)
+            }
+            _ => (self.key()?, self.account.as_ref()),
+        };
+
+        let account = self.block_on(query_account(&self.grpc_addr, &key.account))?;
+
+        let cached_sequence = cached_account.map(|cached| cached.sequence.to_u64());
+
+        Ok(AccountInfo {
+            address: key.account,
+            account_number: account.account_number,
+            sequence: account.sequence,
+            cached_sequence,
+        })
+    }
+
+    fn reset_account_sequence(&mut self, key_name: Option<String>) -> Result<(), Error> {
+        crate::time!("reset_account_sequence");
+
+        match key_name {
+            Some(key_name) if key_name != self.config.key_name => {
+                // Make sure the key exists, to report typos.
+                self.keybase().get_key(&key_name).map_err(Error::key_base)?;
+
+                if let Some(account) = self.extra_accounts.get_mut(&key_name) {
+                    *account = None;
+                }
+            }
+            _ => self.account = None,
+        }
+
+        info!(
+            chain = %self.id(),
+            "cleared the cached account sequence, the account will be queried again"
+        );
+
+        Ok(())
+    }
+
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
         crate::time!("query_commitment_prefix");
         crate::telemetry!(query, self.id(), "query_commitment_prefix");
@@ -1431,6 +1634,62 @@ impl ChainEndpoint for CosmosSdkChain {
         ))
     }
 
+    fn query_tx_by_hash(&self, hash: QueryTxHash) -> Result<Option<Vec<IbcEvent>>, Error> {
+        crate::time!("query_tx_by_hash");
+        crate::telemetry!(query, self.id(), "query_tx_by_hash");
+
+        self.block_on(query_tx_by_hash(
+            self.id(),
+            &self.rpc_client,
+            &self.config.rpc_addr,
+            &hash,
+        ))
+    }
+
+    fn query_tx_result(&self, hash: QueryTxHash) -> Result<Option<TxResult>, Error> {
+        crate::time!("query_tx_result");
+        crate::telemetry!(query, self.id(), "query_tx_result");
+
+        self.block_on(query_tx_result(
+            self.id(),
+            &self.rpc_client,
+            &self.config.rpc_addr,
+            &hash,
+        ))
+    }
+
+    fn query_tx_decoded_events(
+        &self,
+        hash: QueryTxHash,
+        decode_only: &[IbcEventType],
+    ) -> Result<Option<Vec<TxEvent>>, Error> {
+        crate::time!("query_tx_decoded_events");
+        crate::telemetry!(query, self.id(), "query_tx_decoded_events");
+
+        self.block_on(query_tx_decoded_events(
+            self.id(),
+            &self.rpc_client,
+            &self.config.rpc_addr,
+            &hash,
+            decode_only,
+        ))
+    }
+
+    fn query_packet_event_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<PacketEventTx>, Error> {
+        crate::time!("query_packet_event_txs");
+        crate::telemetry!(query, self.id(), "query_packet_event_txs");
+
+        self.block_on(query_packet_txs(
+            self.id(),
+            &self.rpc_client,
+            &self.config.rpc_addr,
+            &request,
+        ))
+    }
+
     fn query_blocks(
         &self,
         request: QueryBlockRequest,
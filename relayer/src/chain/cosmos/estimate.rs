@@ -6,7 +6,7 @@ use tracing::{debug, error, span, warn, Level};
 
 use crate::chain::cosmos::encode::sign_tx;
 use crate::chain::cosmos::gas::{gas_amount_to_fees, PrettyFee};
-use crate::chain::cosmos::simulate::send_tx_simulate;
+use crate::chain::cosmos::simulate::{send_tx_simulate, simulate_msgs};
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::gas::GasConfig;
@@ -49,6 +49,26 @@ pub async fn estimate_tx_fees(
     Ok(estimated_fee)
 }
 
+/// Simulates a tx made of the given messages, without broadcasting it, and
+/// returns the amount of gas used by the simulation.
+///
+/// Unlike [`estimate_tx_fees`], this does not fall back on the default gas
+/// when the simulation fails.
+pub async fn estimate_tx_gas(
+    config: &TxConfig,
+    key_entry: &KeyEntry,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: Vec<Any>,
+) -> Result<u64, Error> {
+    let response = simulate_msgs(config, key_entry, account, tx_memo, messages).await?;
+
+    response
+        .gas_info
+        .map(|gas_info| gas_info.gas_used)
+        .ok_or_else(Error::empty_response_value)
+}
+
 async fn estimate_fee_with_tx(
     gas_config: &GasConfig,
     grpc_address: &Uri,
@@ -17,7 +17,10 @@ use crate::error::Error;
 
 pub mod account;
 pub mod balance;
+pub mod denom_trace;
+pub mod gas_price;
 pub mod status;
+pub mod supply;
 pub mod tx;
 
 /// Generic query response type
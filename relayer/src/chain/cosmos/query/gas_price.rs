@@ -0,0 +1,104 @@
+use http::uri::{PathAndQuery, Uri};
+use tonic::codec::ProstCodec;
+use tonic::transport::Channel;
+use tonic::Code;
+
+use ibc::core::ics24_host::identifier::ChainId;
+
+use crate::config::GasPrice;
+use crate::error::Error;
+
+/// The node configuration service, which is not part of the Cosmos SDK version
+/// the protobuf definitions are generated from, and is thus called directly.
+const NODE_CONFIG_PATH: &str = "/cosmos.base.node.v1beta1.Service/Config";
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ConfigRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ConfigResponse {
+    #[prost(string, tag = "1")]
+    minimum_gas_price: String,
+}
+
+/// Uses the gRPC node service to retrieve the minimum gas prices the node is
+/// configured with, one per denom. Nodes which do not expose the service,
+/// which was introduced in Cosmos SDK v0.46, are reported as such.
+pub async fn query_min_gas_price(
+    chain_id: &ChainId,
+    grpc_address: &Uri,
+) -> Result<Vec<GasPrice>, Error> {
+    let channel = Channel::builder(grpc_address.clone())
+        .connect()
+        .await
+        .map_err(Error::grpc_transport)?;
+
+    let mut client = tonic::client::Grpc::new(channel);
+
+    client.ready().await.map_err(Error::grpc_transport)?;
+
+    let response: ConfigResponse = client
+        .unary(
+            tonic::Request::new(ConfigRequest {}),
+            PathAndQuery::from_static(NODE_CONFIG_PATH),
+            ProstCodec::default(),
+        )
+        .await
+        .map(|r| r.into_inner())
+        .map_err(|status| match status.code() {
+            Code::Unimplemented => Error::min_gas_price_unsupported(chain_id.clone()),
+            _ => Error::grpc_status(status),
+        })?;
+
+    parse_gas_prices(&response.minimum_gas_price)
+}
+
+/// Parses a list of decimal coins, eg. `0.025uatom,0.001stake`, into gas prices.
+/// A node without any minimum gas price reports an empty list.
+fn parse_gas_prices(prices: &str) -> Result<Vec<GasPrice>, Error> {
+    prices
+        .split(',')
+        .map(str::trim)
+        .filter(|price| !price.is_empty())
+        .map(|price| {
+            let denom_start = price
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(price.len());
+
+            let (amount, denom) = price.split_at(denom_start);
+
+            match amount.parse() {
+                Ok(amount) if !denom.is_empty() => Ok(GasPrice::new(amount, denom.to_string())),
+                _ => Err(Error::invalid_min_gas_price(price.to_string())),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_gas_prices;
+    use crate::config::GasPrice;
+
+    #[test]
+    fn gas_prices_are_parsed_per_denom() {
+        assert_eq!(
+            parse_gas_prices("0.025000000000000000uatom,0.001stake").unwrap(),
+            vec![
+                GasPrice::new(0.025, "uatom".to_string()),
+                GasPrice::new(0.001, "stake".to_string()),
+            ]
+        );
+        assert_eq!(
+            parse_gas_prices("1ibc/27394FB092D2ECCD").unwrap(),
+            vec![GasPrice::new(1.0, "ibc/27394FB092D2ECCD".to_string())]
+        );
+        assert_eq!(parse_gas_prices("").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn invalid_gas_prices_are_rejected() {
+        assert!(parse_gas_prices("uatom").is_err());
+        assert!(parse_gas_prices("0.025").is_err());
+    }
+}
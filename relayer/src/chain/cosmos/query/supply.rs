@@ -0,0 +1,31 @@
+use http::uri::Uri;
+
+use ibc_proto::cosmos::bank::v1beta1::{query_client::QueryClient, QuerySupplyOfRequest};
+
+use crate::{account::Balance, error::Error};
+
+/// Uses the GRPC client to retrieve the total supply of a specific denom
+pub async fn query_supply_of(grpc_address: &Uri, denom: &str) -> Result<Balance, Error> {
+    let mut client = QueryClient::connect(grpc_address.clone())
+        .await
+        .map_err(Error::grpc_transport)?;
+
+    let request = tonic::Request::new(QuerySupplyOfRequest {
+        denom: denom.to_string(),
+    });
+
+    let response = client
+        .supply_of(request)
+        .await
+        .map(|r| r.into_inner())
+        .map_err(Error::grpc_status)?;
+
+    let supply = response
+        .amount
+        .ok_or_else(|| Error::empty_supply(denom.to_string()))?;
+
+    Ok(Balance {
+        amount: supply.amount,
+        denom: supply.denom,
+    })
+}
@@ -1,17 +1,23 @@
+use core::str::FromStr;
+
 use ibc::core::ics02_client::client_consensus::QueryClientEventRequest;
 use ibc::core::ics02_client::events as ClientEvents;
 use ibc::core::ics04_channel::channel::QueryPacketEventDataRequest;
 use ibc::core::ics04_channel::events as ChannelEvents;
 use ibc::core::ics04_channel::packet::{Packet, Sequence};
 use ibc::core::ics24_host::identifier::ChainId;
-use ibc::events::{from_tx_response_event, IbcEvent};
-use ibc::query::QueryTxRequest;
+use ibc::events::{from_tx_response_event, IbcEvent, IbcEventType};
+use ibc::query::{QueryTxHash, QueryTxRequest};
 use ibc::Height as ICSHeight;
+use ibc_proto::cosmos::base::v1beta1::Coin;
+use ibc_proto::cosmos::tx::v1beta1::Tx;
+use prost::Message;
 use tendermint::abci::Event;
 use tendermint_rpc::endpoint::tx::Response as ResultTx;
 use tendermint_rpc::{Client, HttpClient, Order, Url};
 
 use crate::chain::cosmos::query::{header_query, packet_query, tx_hash_query};
+use crate::chain::endpoint::{PacketEventTx, TxCost, TxEvent, TxResult};
 use crate::error::Error;
 
 /// This function queries transactions for events matching certain criteria.
@@ -38,40 +44,9 @@ pub async fn query_txs(
         QueryTxRequest::Packet(request) => {
             crate::time!("query_txs: query packet events");
 
-            let mut result: Vec<IbcEvent> = vec![];
-
-            for seq in &request.sequences {
-                // query first (and only) Tx that includes the event specified in the query request
-                let response = rpc_client
-                    .tx_search(
-                        packet_query(&request, *seq),
-                        false,
-                        1,
-                        1, // get only the first Tx matching the query
-                        Order::Ascending,
-                    )
-                    .await
-                    .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
-
-                assert!(
-                    response.txs.len() <= 1,
-                    "packet_from_tx_search_response: unexpected number of txs"
-                );
-
-                if response.txs.is_empty() {
-                    continue;
-                }
-
-                if let Some(event) = packet_from_tx_search_response(
-                    chain_id,
-                    &request,
-                    *seq,
-                    response.txs[0].clone(),
-                ) {
-                    result.push(event);
-                }
-            }
-            Ok(result)
+            let txs = query_packet_txs(chain_id, rpc_client, rpc_address, &request).await?;
+
+            Ok(txs.into_iter().map(|tx| tx.event).collect())
         }
 
         QueryTxRequest::Client(request) => {
@@ -111,27 +86,197 @@ pub async fn query_txs(
         }
 
         QueryTxRequest::Transaction(tx) => {
-            let mut response = rpc_client
-                .tx_search(
-                    tx_hash_query(&tx),
-                    false,
-                    1,
-                    1, // get only the first Tx matching the query
-                    Order::Ascending,
-                )
-                .await
-                .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+            let events = query_tx_by_hash(chain_id, rpc_client, rpc_address, &tx).await?;
 
-            if response.txs.is_empty() {
-                Ok(vec![])
-            } else {
-                let tx = response.txs.remove(0);
-                Ok(all_ibc_events_from_tx_search_response(chain_id, tx))
-            }
+            Ok(events.unwrap_or_default())
         }
     }
 }
 
+/// Queries the IBC events emitted by the transaction with the given hash.
+///
+/// Returns `None` if the transaction is not found, and an empty vector if it
+/// was found but did not emit any IBC event.
+pub async fn query_tx_by_hash(
+    chain_id: &ChainId,
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    tx: &QueryTxHash,
+) -> Result<Option<Vec<IbcEvent>>, Error> {
+    let tx = search_tx_by_hash(rpc_client, rpc_address, tx).await?;
+
+    Ok(tx.map(|tx| all_ibc_events_from_tx_search_response(chain_id, tx)))
+}
+
+/// Queries the IBC events emitted by the transaction with the given hash, along
+/// with the gas it used and the fee it paid.
+///
+/// Returns `None` if the transaction is not found.
+pub async fn query_tx_result(
+    chain_id: &ChainId,
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    tx: &QueryTxHash,
+) -> Result<Option<TxResult>, Error> {
+    let tx = match search_tx_by_hash(rpc_client, rpc_address, tx).await? {
+        Some(tx) => tx,
+        None => return Ok(None),
+    };
+
+    let cost = TxCost {
+        gas_used: tx.tx_result.gas_used.value(),
+        fee: decode_fee(tx.tx.as_bytes()),
+    };
+
+    Ok(Some(TxResult {
+        events: all_ibc_events_from_tx_search_response(chain_id, tx),
+        cost,
+    }))
+}
+
+/// Queries the IBC events emitted by the transaction with the given hash, like
+/// [`query_tx_by_hash`], but only decodes the events of the given types, the
+/// other IBC events being returned with their attributes as emitted.
+///
+/// Returns `None` if the transaction is not found.
+pub async fn query_tx_decoded_events(
+    chain_id: &ChainId,
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    tx: &QueryTxHash,
+    decode_only: &[IbcEventType],
+) -> Result<Option<Vec<TxEvent>>, Error> {
+    let tx = match search_tx_by_hash(rpc_client, rpc_address, tx).await? {
+        Some(tx) => tx,
+        None => return Ok(None),
+    };
+
+    if let Some(error) = deliver_tx_error(&tx) {
+        return Ok(Some(vec![TxEvent::Decoded(error)]));
+    }
+
+    let height = ICSHeight::new(chain_id.version(), u64::from(tx.height));
+
+    let events = tx
+        .tx_result
+        .events
+        .iter()
+        .filter_map(|event| decode_tx_event(height, event, decode_only))
+        .collect();
+
+    Ok(Some(events))
+}
+
+/// Decodes the given event if it is an IBC event of one of the given types, or
+/// else keeps it undecoded if it is an IBC event, skipping the other events.
+fn decode_tx_event(
+    height: ICSHeight,
+    event: &Event,
+    decode_only: &[IbcEventType],
+) -> Option<TxEvent> {
+    let event_type = IbcEventType::from_str(&event.type_str).ok()?;
+
+    if decode_only
+        .iter()
+        .any(|t| t.as_str() == event_type.as_str())
+    {
+        return from_tx_response_event(height, event).map(TxEvent::Decoded);
+    }
+
+    Some(TxEvent::Raw {
+        height,
+        event_type: event.type_str.clone(),
+        attributes: event
+            .attributes
+            .iter()
+            .map(|tag| (tag.key.to_string(), tag.value.to_string()))
+            .collect(),
+    })
+}
+
+async fn search_tx_by_hash(
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    tx: &QueryTxHash,
+) -> Result<Option<ResultTx>, Error> {
+    let mut response = rpc_client
+        .tx_search(
+            tx_hash_query(tx),
+            false,
+            1,
+            1, // get only the first Tx matching the query
+            Order::Ascending,
+        )
+        .await
+        .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+    if response.txs.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(response.txs.remove(0)))
+    }
+}
+
+/// Decodes the fee paid by the given encoded transaction, which is only used
+/// for reporting, so that a transaction which cannot be decoded paid no fee.
+fn decode_fee(tx_bytes: &[u8]) -> Vec<Coin> {
+    Tx::decode(tx_bytes)
+        .ok()
+        .and_then(|tx| tx.auth_info)
+        .and_then(|auth_info| auth_info.fee)
+        .map(|fee| fee.amount)
+        .unwrap_or_default()
+}
+
+/// Queries the transactions which emitted the packet events matching the given
+/// request, along with the events themselves, returning at most one transaction
+/// for each sequence specified in the request. See [`query_txs`] for details.
+pub async fn query_packet_txs(
+    chain_id: &ChainId,
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    request: &QueryPacketEventDataRequest,
+) -> Result<Vec<PacketEventTx>, Error> {
+    let mut result = vec![];
+
+    for seq in &request.sequences {
+        // query first (and only) Tx that includes the event specified in the query request
+        let mut response = rpc_client
+            .tx_search(
+                packet_query(request, *seq),
+                false,
+                1,
+                1, // get only the first Tx matching the query
+                Order::Ascending,
+            )
+            .await
+            .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+        assert!(
+            response.txs.len() <= 1,
+            "packet_from_tx_search_response: unexpected number of txs"
+        );
+
+        if response.txs.is_empty() {
+            continue;
+        }
+
+        let tx = response.txs.remove(0);
+        let tx_hash = tx.hash;
+        let height = ICSHeight::new(chain_id.version(), u64::from(tx.height));
+
+        if let Some(event) = packet_from_tx_search_response(chain_id, request, *seq, tx) {
+            result.push(PacketEventTx {
+                event,
+                tx_hash,
+                height,
+            });
+        }
+    }
+
+    Ok(result)
+}
+
 // Extracts from the Tx the update client event for the requested client and height.
 // Note: in the Tx, there may have been multiple events, some of them may be
 // for update of other clients that are not relevant to the request.
@@ -226,25 +371,80 @@ fn filter_matching_event(
         {
             Some(ibc_event)
         }
+        IbcEvent::AcknowledgePacket(ref ack_ev) if matches_packet(request, seq, &ack_ev.packet) => {
+            Some(ibc_event)
+        }
+        IbcEvent::TimeoutPacket(ref timeout_ev)
+            if matches_packet(request, seq, &timeout_ev.packet) =>
+        {
+            Some(ibc_event)
+        }
         _ => None,
     }
 }
 
 fn all_ibc_events_from_tx_search_response(chain_id: &ChainId, response: ResultTx) -> Vec<IbcEvent> {
-    let height = ICSHeight::new(chain_id.version(), u64::from(response.height));
-    let deliver_tx_result = response.tx_result;
-    if deliver_tx_result.code.is_err() {
-        return vec![IbcEvent::ChainError(format!(
-            "deliver_tx for {} reports error: code={:?}, log={:?}",
-            response.hash, deliver_tx_result.code, deliver_tx_result.log
-        ))];
+    if let Some(error) = deliver_tx_error(&response) {
+        return vec![error];
     }
 
+    let height = ICSHeight::new(chain_id.version(), u64::from(response.height));
+
     let mut result = vec![];
-    for event in deliver_tx_result.events {
+    for event in response.tx_result.events {
         if let Some(ibc_ev) = from_tx_response_event(height, &event) {
             result.push(ibc_ev);
         }
     }
     result
 }
+
+/// The error reported by the deliver-tx result of the given transaction, if it failed.
+fn deliver_tx_error(response: &ResultTx) -> Option<IbcEvent> {
+    let deliver_tx_result = &response.tx_result;
+
+    if deliver_tx_result.code.is_err() {
+        Some(IbcEvent::ChainError(format!(
+            "deliver_tx for {} reports error: code={:?}, log={:?}",
+            response.hash, deliver_tx_result.code, deliver_tx_result.log
+        )))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tendermint::abci::tag::Tag;
+    use tendermint::abci::Event;
+
+    use ibc::events::IbcEventType;
+    use ibc::Height;
+
+    use super::decode_tx_event;
+    use crate::chain::endpoint::TxEvent;
+
+    fn event(type_str: &str) -> Event {
+        Event {
+            type_str: type_str.to_string(),
+            attributes: vec![Tag {
+                key: "packet_sequence".parse().unwrap(),
+                value: "7".parse().unwrap(),
+            }],
+        }
+    }
+
+    #[test]
+    fn events_of_other_types_are_left_undecoded() {
+        let height = Height::new(0, 42);
+
+        let decoded = decode_tx_event(height, &event("send_packet"), &[IbcEventType::WriteAck]);
+        assert!(matches!(
+            decoded,
+            Some(TxEvent::Raw { ref event_type, ref attributes, .. })
+                if event_type == "send_packet" && attributes == &[("packet_sequence".to_string(), "7".to_string())]
+        ));
+
+        assert!(decode_tx_event(height, &event("transfer"), &[]).is_none());
+    }
+}
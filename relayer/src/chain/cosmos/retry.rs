@@ -3,6 +3,7 @@ use core::pin::Pin;
 use core::time::Duration;
 use ibc_proto::google::protobuf::Any;
 use std::thread;
+use std::time::Instant;
 use tendermint::abci::Code;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use tracing::{debug, error, span, warn, Level};
@@ -41,7 +42,8 @@ const INCORRECT_ACCOUNT_SEQUENCE_ERR: u32 = 32;
 ///     the `broadcast_tx_sync` step.
 ///
 /// We treat both cases by re-fetching the account sequence number
-/// from the full node.
+/// from the full node, at most once per `account_resync_interval`,
+/// see [`resync_account`].
 /// Upon case #1, we do not retry submitting the same tx (retry happens
 /// nonetheless at the worker `step` level). Upon case #2, we retry
 /// submitting the same transaction.
@@ -101,7 +103,7 @@ fn do_send_tx_with_account_sequence_retry<'a>(
             // retry at the worker-level will handle retrying.
             Err(e) if mismatching_account_sequence_number(&e) => {
                 warn!("failed at estimate_gas step mismatching account sequence: dropping the tx & refreshing account sequence number");
-                refresh_account(&config.grpc_address, &key_entry.account, account).await?;
+                resync_account(config, key_entry, account).await?;
                 // Note: propagating error here can lead to bug & dropped packets:
                 // https://github.com/informalsystems/ibc-rs/issues/1153
                 // But periodic packet clearing will catch any dropped packets.
@@ -118,7 +120,12 @@ fn do_send_tx_with_account_sequence_retry<'a>(
                     let backoff = retry_counter * BACKOFF_MULTIPLIER_ACCOUNT_SEQUENCE_RETRY;
 
                     thread::sleep(Duration::from_millis(backoff));
-                    refresh_account(&config.grpc_address, &key_entry.account, account).await?;
+
+                    if !resync_account(config, key_entry, account).await? {
+                        // Retrying with the sequence which just failed would fail again.
+                        warn!("account sequence was resynced too recently, not retrying");
+                        return Ok(response);
+                    }
 
                     // Now retry.
                     do_send_tx_with_account_sequence_retry(
@@ -172,6 +179,39 @@ fn do_send_tx_with_account_sequence_retry<'a>(
     })
 }
 
+/// Resync the cached account sequence with the full node after a mismatch,
+/// unless it was already resynced less than `account_resync_interval` ago,
+/// in which case the last resynced sequence is reused, so that repeated
+/// mismatches do not flood the node with account queries.
+///
+/// Returns whether the account sequence was resynced.
+async fn resync_account(
+    config: &TxConfig,
+    key_entry: &KeyEntry,
+    account: &mut Account,
+) -> Result<bool, Error> {
+    let now = Instant::now();
+
+    if !account.is_resync_due(now, config.account_resync_interval) {
+        debug!(
+            sequence = %account.sequence,
+            "account sequence was resynced less than {:?} ago, reusing it",
+            config.account_resync_interval
+        );
+
+        telemetry!(account_resyncs, &config.chain_id, true);
+
+        return Ok(false);
+    }
+
+    refresh_account(&config.grpc_address, &key_entry.account, account).await?;
+    account.resynced_at = Some(now);
+
+    telemetry!(account_resyncs, &config.chain_id, false);
+
+    Ok(true)
+}
+
 /// Determine whether the given error yielded by `tx_simulate`
 /// indicates hat the current sequence number cached in Hermes
 /// may be out-of-sync with the full node's version of the s.n.
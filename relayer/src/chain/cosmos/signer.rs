@@ -0,0 +1,100 @@
+use ibc::core::ics02_client::msgs::update_client;
+use ibc::core::ics04_channel::msgs::{
+    acknowledgement, chan_close_confirm, recv_packet, timeout, timeout_on_close,
+};
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::channel::v1::{
+    MsgAcknowledgement, MsgChannelCloseConfirm, MsgRecvPacket, MsgTimeout, MsgTimeoutOnClose,
+};
+use ibc_proto::ibc::core::client::v1::MsgUpdateClient;
+use prost::Message;
+
+use crate::error::Error;
+
+/// Sets the signer of the given messages to the given account, so that the
+/// messages can be submitted in a transaction signed by that account.
+///
+/// Only the messages submitted by the packet workers are supported, ie.
+/// client updates, packet messages and channel close confirmations.
+pub fn set_signer(messages: Vec<Any>, signer: &str) -> Result<Vec<Any>, Error> {
+    messages
+        .into_iter()
+        .map(|message| set_message_signer(message, signer))
+        .collect()
+}
+
+fn set_message_signer(message: Any, signer: &str) -> Result<Any, Error> {
+    let type_url = message.type_url.clone();
+
+    match type_url.as_str() {
+        update_client::TYPE_URL => {
+            with_signer::<MsgUpdateClient>(message, signer, |m| &mut m.signer)
+        }
+        recv_packet::TYPE_URL => with_signer::<MsgRecvPacket>(message, signer, |m| &mut m.signer),
+        acknowledgement::TYPE_URL => {
+            with_signer::<MsgAcknowledgement>(message, signer, |m| &mut m.signer)
+        }
+        timeout::TYPE_URL => with_signer::<MsgTimeout>(message, signer, |m| &mut m.signer),
+        timeout_on_close::TYPE_URL => {
+            with_signer::<MsgTimeoutOnClose>(message, signer, |m| &mut m.signer)
+        }
+        chan_close_confirm::TYPE_URL => {
+            with_signer::<MsgChannelCloseConfirm>(message, signer, |m| &mut m.signer)
+        }
+        _ => Err(Error::unsupported_signer_message(message.type_url)),
+    }
+}
+
+fn with_signer<M: Message + Default>(
+    message: Any,
+    signer: &str,
+    signer_field: impl FnOnce(&mut M) -> &mut String,
+) -> Result<Any, Error> {
+    let mut raw = M::decode(message.value.as_slice())
+        .map_err(|e| Error::protobuf_decode(message.type_url.clone(), e))?;
+
+    *signer_field(&mut raw) = signer.to_string();
+
+    Ok(Any {
+        type_url: message.type_url,
+        value: raw.encode_to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc::core::ics04_channel::msgs::recv_packet;
+    use ibc_proto::google::protobuf::Any;
+    use ibc_proto::ibc::core::channel::v1::MsgRecvPacket;
+    use prost::Message;
+
+    use super::set_signer;
+
+    #[test]
+    fn signer_of_packet_messages_is_replaced() {
+        let msg = MsgRecvPacket {
+            signer: "cosmos1default".to_string(),
+            ..Default::default()
+        };
+
+        let any = Any {
+            type_url: recv_packet::TYPE_URL.to_string(),
+            value: msg.encode_to_vec(),
+        };
+
+        let msgs = set_signer(vec![any], "cosmos1extra").unwrap();
+        let msg = MsgRecvPacket::decode(msgs[0].value.as_slice()).unwrap();
+
+        assert_eq!(msg.signer, "cosmos1extra");
+    }
+
+    #[test]
+    fn unsupported_messages_are_rejected() {
+        let any = Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: vec![],
+        };
+
+        assert!(set_signer(vec![any], "cosmos1extra").is_err());
+    }
+}
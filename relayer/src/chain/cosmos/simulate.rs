@@ -1,8 +1,16 @@
 use ibc_proto::cosmos::tx::v1beta1::service_client::ServiceClient;
 use ibc_proto::cosmos::tx::v1beta1::{SimulateRequest, SimulateResponse, Tx};
+use ibc_proto::google::protobuf::Any;
 use tonic::codegen::http::Uri;
 
-use crate::error::Error;
+use crate::chain::cosmos::encode::sign_tx;
+use crate::chain::cosmos::types::account::Account;
+use crate::chain::cosmos::types::config::TxConfig;
+use crate::chain::endpoint::TxSimulation;
+use crate::config::types::Memo;
+use crate::error::{Error, ErrorDetail};
+use crate::keyring::KeyEntry;
+use crate::sdk_error::decode_simulation_error;
 
 pub async fn send_tx_simulate(grpc_address: &Uri, tx: Tx) -> Result<SimulateResponse, Error> {
     crate::time!("send_tx_simulate");
@@ -31,3 +39,64 @@ pub async fn send_tx_simulate(grpc_address: &Uri, tx: Tx) -> Result<SimulateResp
 
     Ok(response)
 }
+
+/// Signs a tx made of the given messages, paying the maximum fee, and simulates
+/// it, without broadcasting it.
+pub async fn simulate_msgs(
+    config: &TxConfig,
+    key_entry: &KeyEntry,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: Vec<Any>,
+) -> Result<SimulateResponse, Error> {
+    let signed_tx = sign_tx(
+        config,
+        key_entry,
+        account,
+        tx_memo,
+        messages,
+        &config.gas_config.max_fee,
+    )?;
+
+    let tx = Tx {
+        body: Some(signed_tx.body),
+        auth_info: Some(signed_tx.auth_info),
+        signatures: signed_tx.signatures,
+    };
+
+    send_tx_simulate(&config.grpc_address, tx).await
+}
+
+/// Simulates a tx made of the given messages, without broadcasting it, and
+/// returns the decoded result of the simulation.
+///
+/// The simulation being rejected by the node is reported in the returned
+/// result, along with the decoded error; an error is only returned if the
+/// simulation could not be requested at all.
+pub async fn simulate_tx(
+    config: &TxConfig,
+    key_entry: &KeyEntry,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: Vec<Any>,
+) -> Result<TxSimulation, Error> {
+    match simulate_msgs(config, key_entry, account, tx_memo, messages).await {
+        Ok(response) => Ok(TxSimulation {
+            gas_used: response.gas_info.map(|gas_info| gas_info.gas_used),
+            log: response.result.map(|result| result.log).unwrap_or_default(),
+            error: None,
+        }),
+        Err(e) => match e.detail() {
+            ErrorDetail::GrpcStatus(detail) => {
+                let message = detail.status.message();
+
+                Ok(TxSimulation {
+                    gas_used: None,
+                    log: message.to_string(),
+                    error: Some(decode_simulation_error(message)),
+                })
+            }
+            _ => Err(e),
+        },
+    }
+}
@@ -2,12 +2,14 @@ use ibc_proto::cosmos::tx::v1beta1::Fee;
 use ibc_proto::google::protobuf::Any;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use tendermint_rpc::{Client, HttpClient, Url};
+use tracing::trace;
 
 use crate::chain::cosmos::encode::sign_and_encode_tx;
 use crate::chain::cosmos::estimate::estimate_tx_fees;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::config::types::Memo;
+use crate::config::BroadcastMode;
 use crate::error::Error;
 use crate::keyring::KeyEntry;
 
@@ -33,11 +35,28 @@ async fn send_tx_with_fee(
 ) -> Result<Response, Error> {
     let tx_bytes = sign_and_encode_tx(config, key_entry, account, tx_memo, messages, fee)?;
 
-    let response = broadcast_tx_sync(&config.rpc_client, &config.rpc_address, tx_bytes).await?;
+    let response = broadcast_tx(config, tx_bytes).await?;
 
     Ok(response)
 }
 
+/// Broadcast the transaction with the `broadcast_mode` of the chain, and return
+/// the response in the form of a `broadcast_tx_sync` response, as expected by
+/// the confirmation of the pending transactions.
+async fn broadcast_tx(config: &TxConfig, data: Vec<u8>) -> Result<Response, Error> {
+    match config.broadcast_mode {
+        BroadcastMode::Sync => {
+            broadcast_tx_sync(&config.rpc_client, &config.rpc_address, data).await
+        }
+        BroadcastMode::Async => {
+            broadcast_tx_async(&config.rpc_client, &config.rpc_address, data).await
+        }
+        BroadcastMode::Commit => {
+            broadcast_tx_commit(&config.rpc_client, &config.rpc_address, data).await
+        }
+    }
+}
+
 /// Perform a `broadcast_tx_sync`, and return the corresponding deserialized response data.
 async fn broadcast_tx_sync(
     rpc_client: &HttpClient,
@@ -51,3 +70,49 @@ async fn broadcast_tx_sync(
 
     Ok(response)
 }
+
+/// Perform a `broadcast_tx_async`, which does not wait for `CheckTx`, so that
+/// a transaction failing it is reported as broadcast, and then never committed.
+async fn broadcast_tx_async(
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    data: Vec<u8>,
+) -> Result<Response, Error> {
+    let response = rpc_client
+        .broadcast_tx_async(data.into())
+        .await
+        .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+    Ok(Response {
+        code: response.code,
+        data: response.data,
+        log: response.log,
+        hash: response.hash,
+    })
+}
+
+/// Perform a `broadcast_tx_commit`, and return the `CheckTx` result of the
+/// transaction, its `DeliverTx` result being found when confirming it.
+async fn broadcast_tx_commit(
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    data: Vec<u8>,
+) -> Result<Response, Error> {
+    let response = rpc_client
+        .broadcast_tx_commit(data.into())
+        .await
+        .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+    trace!(
+        "broadcast_tx_commit: committed {} at height {}",
+        response.hash,
+        response.height
+    );
+
+    Ok(Response {
+        code: response.check_tx.code,
+        data: response.check_tx.data,
+        log: response.check_tx.log,
+        hash: response.hash,
+    })
+}
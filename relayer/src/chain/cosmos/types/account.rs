@@ -1,4 +1,7 @@
 use core::fmt;
+use core::time::Duration;
+use std::time::Instant;
+
 use ibc_proto::cosmos::auth::v1beta1::BaseAccount;
 
 /// Wrapper for account number and sequence number.
@@ -10,6 +13,17 @@ pub struct Account {
     // pub pub_key: Option<prost_types::Any>,
     pub number: AccountNumber,
     pub sequence: AccountSequence,
+    /// When the sequence was last resynced with the node after a mismatch.
+    pub resynced_at: Option<Instant>,
+}
+
+impl Account {
+    /// Whether the sequence may be resynced at `now`, ie. whether at least
+    /// `min_interval` elapsed since it was last resynced.
+    pub fn is_resync_due(&self, now: Instant, min_interval: Duration) -> bool {
+        self.resynced_at
+            .map_or(true, |at| now.saturating_duration_since(at) >= min_interval)
+    }
 }
 
 impl From<BaseAccount> for Account {
@@ -17,6 +31,7 @@ impl From<BaseAccount> for Account {
         Self {
             number: AccountNumber::new(value.account_number),
             sequence: AccountSequence::new(value.sequence),
+            resynced_at: None,
         }
     }
 }
@@ -68,3 +83,29 @@ impl fmt::Display for AccountSequence {
         write!(f, "{:?}", self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use super::{Account, AccountNumber, AccountSequence};
+
+    #[test]
+    fn resyncs_are_spaced_by_the_minimum_interval() {
+        let now = Instant::now();
+        let interval = Duration::from_secs(1);
+
+        let mut account = Account {
+            number: AccountNumber::new(1),
+            sequence: AccountSequence::new(5),
+            resynced_at: None,
+        };
+        assert!(account.is_resync_due(now, interval));
+
+        account.resynced_at = Some(now);
+        assert!(!account.is_resync_due(now + Duration::from_millis(500), interval));
+        assert!(account.is_resync_due(now + interval, interval));
+        assert!(account.is_resync_due(now, Duration::ZERO));
+    }
+}
@@ -5,7 +5,7 @@ use ibc::core::ics24_host::identifier::ChainId;
 use tendermint_rpc::{HttpClient, Url};
 
 use crate::chain::cosmos::types::gas::GasConfig;
-use crate::config::{AddressType, ChainConfig};
+use crate::config::{AddressType, BroadcastMode, ChainConfig};
 use crate::error::Error;
 
 #[derive(Debug, Clone)]
@@ -17,6 +17,8 @@ pub struct TxConfig {
     pub grpc_address: Uri,
     pub rpc_timeout: Duration,
     pub address_type: AddressType,
+    pub broadcast_mode: BroadcastMode,
+    pub account_resync_interval: Duration,
 }
 
 impl<'a> TryFrom<&'a ChainConfig> for TxConfig {
@@ -39,6 +41,8 @@ impl<'a> TryFrom<&'a ChainConfig> for TxConfig {
             grpc_address,
             rpc_timeout: config.rpc_timeout,
             address_type: config.address_type.clone(),
+            broadcast_mode: config.broadcast_mode,
+            account_resync_interval: config.account_resync_interval,
         })
     }
 }
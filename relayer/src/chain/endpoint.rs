@@ -1,5 +1,6 @@
 use alloc::sync::Arc;
 use core::convert::TryFrom;
+use core::time::Duration;
 use ibc::core::ics23_commitment::merkle::MerkleProof;
 
 use tokio::runtime::Runtime as TokioRuntime;
@@ -13,19 +14,24 @@ use ibc::core::ics02_client::client_state::{
 use ibc::core::ics02_client::header::Header;
 use ibc::core::ics03_connection::connection::{ConnectionEnd, IdentifiedConnectionEnd, State};
 use ibc::core::ics03_connection::version::{get_compatible_versions, Version};
-use ibc::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
+use ibc::core::ics04_channel::channel::{
+    ChannelEnd, IdentifiedChannelEnd, QueryPacketEventDataRequest,
+};
 use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
 use ibc::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
-use ibc::events::IbcEvent;
+use ibc::events::{IbcEvent, IbcEventType};
 use ibc::proofs::{ConsensusProof, Proofs};
-use ibc::query::{QueryBlockRequest, QueryTxRequest};
+use ibc::query::{QueryBlockRequest, QueryTxHash, QueryTxRequest};
 use ibc::signer::Signer;
 use ibc::timestamp::Timestamp;
 use ibc::Height as ICSHeight;
+use ibc_proto::cosmos::base::v1beta1::Coin;
+use serde::Serialize;
+use tendermint::abci::transaction::Hash as TxHash;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response as TxResponse;
 
-use crate::account::Balance;
+use crate::account::{AccountInfo, Balance, DenomTrace};
 use crate::chain::client::ClientSettings;
 use crate::chain::requests::{
     QueryChannelClientStateRequest, QueryChannelRequest, QueryChannelsRequest,
@@ -37,7 +43,7 @@ use crate::chain::requests::{
     QueryUpgradedClientStateRequest, QueryUpgradedConsensusStateRequest,
 };
 use crate::chain::tracking::TrackedMsgs;
-use crate::config::ChainConfig;
+use crate::config::{ChainConfig, GasPrice};
 use crate::connection::ConnectionMsgType;
 use crate::error::{Error, QUERY_PROOF_EXPECT_MSG};
 use crate::event::monitor::{EventReceiver, TxMonitorCmd};
@@ -63,6 +69,132 @@ pub struct ChainStatus {
     pub timestamp: Timestamp,
 }
 
+/// The status of a light client, as tracked by the chain hosting it.
+#[derive(Clone, Debug, Serialize)]
+pub struct ClientStatus {
+    pub client_id: ClientId,
+    /// The latest height of the counterparty chain trusted by the client.
+    pub latest_height: ICSHeight,
+    /// The trusting period of the client, if it expires.
+    #[serde(with = "humantime_serde")]
+    pub trusting_period: Option<Duration>,
+    /// How long until the client expires, if it does, zero once it expired.
+    #[serde(with = "humantime_serde")]
+    pub time_until_expiry: Option<Duration>,
+    /// Whether the latest consensus state of the client is no longer trusted,
+    /// as its trusting period elapsed, on the clock of the hosting chain.
+    pub expired: bool,
+    pub frozen: bool,
+}
+
+impl ClientStatus {
+    /// The status of the client with the given state, when `elapsed` passed
+    /// since the timestamp of its latest consensus state.
+    pub fn new(client_id: ClientId, client_state: &AnyClientState, elapsed: Duration) -> Self {
+        let trusting_period = client_state.trusting_period();
+
+        Self {
+            client_id,
+            latest_height: client_state.latest_height(),
+            trusting_period,
+            time_until_expiry: trusting_period.map(|period| period.saturating_sub(elapsed)),
+            expired: client_state.expired(elapsed),
+            frozen: client_state.is_frozen(),
+        }
+    }
+
+    /// Whether the client can no longer be updated, the operations on an expired
+    /// or frozen client failing with errors for which
+    /// [`HasExpiredOrFrozenError`](crate::foreign_client::HasExpiredOrFrozenError) holds.
+    pub fn is_expired_or_frozen(&self) -> bool {
+        self.expired || self.frozen
+    }
+}
+
+/// A packet event, along with the transaction which emitted it.
+#[derive(Clone, Debug, Serialize)]
+pub struct PacketEventTx {
+    pub event: IbcEvent,
+    pub tx_hash: TxHash,
+    /// The height of the block which includes the transaction.
+    pub height: ICSHeight,
+}
+
+/// The IBC events emitted by a committed transaction, along with its cost.
+#[derive(Clone, Debug)]
+pub struct TxResult {
+    pub events: Vec<IbcEvent>,
+    pub cost: TxCost,
+}
+
+/// An IBC event emitted by a transaction, decoded only if it is of one of the
+/// requested types, so that decoding can be skipped for the other events of
+/// large transactions.
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum TxEvent {
+    Decoded(IbcEvent),
+    /// An event left undecoded, with its attributes as emitted by the chain.
+    Raw {
+        height: ICSHeight,
+        #[serde(rename = "type")]
+        event_type: String,
+        attributes: Vec<(String, String)>,
+    },
+}
+
+impl TxEvent {
+    /// The type of the event, eg. `send_packet`.
+    pub fn event_type(&self) -> &str {
+        match self {
+            Self::Decoded(event) => event.event_type().as_str(),
+            Self::Raw { event_type, .. } => event_type,
+        }
+    }
+
+    /// The height of the block including the transaction, if known.
+    pub fn height(&self) -> Option<ICSHeight> {
+        match self {
+            Self::Decoded(
+                IbcEvent::ChainError(_) | IbcEvent::Empty(_) | IbcEvent::AppModule(_),
+            ) => None,
+            Self::Decoded(event) => Some(event.height()),
+            Self::Raw { height, .. } => Some(*height),
+        }
+    }
+}
+
+/// The cost of a committed transaction.
+#[derive(Clone, Debug, Default)]
+pub struct TxCost {
+    /// The gas used by the transaction, as reported by its deliver-tx result.
+    pub gas_used: u64,
+    /// The fee paid by the transaction, empty if the transaction could not be decoded.
+    pub fee: Vec<Coin>,
+}
+
+/// The result of simulating a transaction, without broadcasting it.
+#[derive(Clone, Debug, Serialize)]
+pub struct TxSimulation {
+    /// The gas used by the simulation, if it succeeded.
+    pub gas_used: Option<u64>,
+    /// The raw log of the simulation, or the error message if it failed.
+    pub log: String,
+    /// The error the simulation failed with, if any.
+    pub error: Option<SimulationError>,
+}
+
+/// The error a transaction simulation failed with, as decoded from the error message.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SimulationError {
+    /// The codespace of the error, eg. `sdk`, if recognized.
+    pub codespace: Option<String>,
+    /// The code of the error within its codespace, if recognized.
+    pub code: Option<u32>,
+    /// A human-readable reason for the failure.
+    pub reason: String,
+}
+
 /// Defines a blockchain as understood by the relayer
 pub trait ChainEndpoint: Sized {
     /// Type of light blocks for this chain
@@ -121,6 +253,15 @@ pub trait ChainEndpoint: Sized {
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<TxResponse>, Error>;
 
+    /// Simulate a transaction made of the given messages, without broadcasting it,
+    /// and return the amount of gas it would use.
+    fn estimate_gas(&mut self, tracked_msgs: TrackedMsgs) -> Result<u64, Error>;
+
+    /// Simulate a transaction made of the given messages, without broadcasting it,
+    /// and return the decoded result of the simulation, including the error the
+    /// simulation failed with, if any.
+    fn simulate_tx(&mut self, tracked_msgs: TrackedMsgs) -> Result<TxSimulation, Error>;
+
     fn get_signer(&mut self) -> Result<Signer, Error>;
 
     fn config(&self) -> ChainConfig;
@@ -138,6 +279,41 @@ pub trait ChainEndpoint: Sized {
     /// If no account is given, behavior must be specified, e.g. retrieve it from configuration file.
     fn query_balance(&self, key_name: Option<String>) -> Result<Balance, Error>;
 
+    /// Query the balances of the given keys for the denom used to pay tx fees,
+    /// returning the result for each key in the same order as the keys.
+    fn query_balances(&self, key_names: Vec<String>) -> Result<Vec<Result<Balance, Error>>, Error> {
+        Ok(key_names
+            .into_iter()
+            .map(|key_name| self.query_balance(Some(key_name)))
+            .collect())
+    }
+
+    /// Query the denomination trace of an IBC voucher denomination,
+    /// given the hash of the `ibc/<HASH>` denomination.
+    fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error>;
+
+    /// Query the total supply of the given denomination,
+    /// eg. of an IBC voucher denomination `ibc/<HASH>`.
+    fn query_supply_of(&self, denom: String) -> Result<Balance, Error>;
+
+    /// Query the balance of the given denomination held by the account with the
+    /// given address, eg. by the escrow account of a channel.
+    fn query_balance_of_address(&self, address: String, denom: String) -> Result<Balance, Error>;
+
+    /// Query the minimum gas prices the node is configured with, one per denom,
+    /// failing if the node does not expose them.
+    fn query_min_gas_price(&self) -> Result<Vec<GasPrice>, Error>;
+
+    /// Query the on-chain account number and sequence of the account of the given key,
+    /// along with the sequence cached by the relayer for that account, if any.
+    /// If no key is given, the key is retrieved from the configuration file.
+    fn query_account_info(&self, key_name: Option<String>) -> Result<AccountInfo, Error>;
+
+    /// Clear the account sequence cached for the given key, if any, so that the
+    /// account is queried again before submitting the next transaction.
+    /// If no key is given, the key is retrieved from the configuration file.
+    fn reset_account_sequence(&mut self, key_name: Option<String>) -> Result<(), Error>;
+
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error>;
 
     fn query_compatible_versions(&self) -> Result<Vec<Version>, Error> {
@@ -162,6 +338,39 @@ pub trait ChainEndpoint: Sized {
         include_proof: IncludeProof,
     ) -> Result<(AnyClientState, Option<MerkleProof>), Error>;
 
+    /// Performs a query to retrieve the status of the specified light client: its
+    /// latest height, its trusting period and how long until it expires, along with
+    /// whether it is expired or frozen.
+    ///
+    /// The expiry is measured on the clock of this chain, the hosting chain,
+    /// against the timestamp of the latest consensus state of the client.
+    fn query_client_status(&self, client_id: ClientId) -> Result<ClientStatus, Error> {
+        let (client_state, _) = self.query_client_state(
+            QueryClientStateRequest {
+                client_id: client_id.clone(),
+                height: ICSHeight::zero(),
+            },
+            IncludeProof::No,
+        )?;
+
+        let (consensus_state, _) = self.query_consensus_state(
+            QueryConsensusStateRequest {
+                client_id: client_id.clone(),
+                consensus_height: client_state.latest_height(),
+                query_height: ICSHeight::zero(),
+            },
+            IncludeProof::No,
+        )?;
+
+        let elapsed = self
+            .query_application_status()?
+            .timestamp
+            .duration_since(&consensus_state.timestamp())
+            .unwrap_or_default();
+
+        Ok(ClientStatus::new(client_id, &client_state, elapsed))
+    }
+
     /// Performs a query to retrieve the consensus state for a specified height
     /// `consensus_height` that the specified light client stores.
     fn query_consensus_state(
@@ -312,6 +521,30 @@ pub trait ChainEndpoint: Sized {
 
     fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEvent>, Error>;
 
+    /// Query the IBC events emitted by the transaction with the given hash,
+    /// or `None` if no such transaction is found.
+    fn query_tx_by_hash(&self, hash: QueryTxHash) -> Result<Option<Vec<IbcEvent>>, Error>;
+
+    /// Query the IBC events emitted by the transaction with the given hash, along
+    /// with the gas it used and the fee it paid, or `None` if no such transaction is found.
+    fn query_tx_result(&self, hash: QueryTxHash) -> Result<Option<TxResult>, Error>;
+
+    /// Query the IBC events emitted by the transaction with the given hash, only
+    /// decoding the events of the given types, the other IBC events being returned
+    /// undecoded, or `None` if no such transaction is found.
+    fn query_tx_decoded_events(
+        &self,
+        hash: QueryTxHash,
+        decode_only: &[IbcEventType],
+    ) -> Result<Option<Vec<TxEvent>>, Error>;
+
+    /// Query the packet events matching the given request, along with the
+    /// transactions which emitted them, at most one for each requested sequence.
+    fn query_packet_event_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<PacketEventTx>, Error>;
+
     fn query_blocks(
         &self,
         request: QueryBlockRequest,
@@ -18,22 +18,22 @@ use ibc::{
             version::Version,
         },
         ics04_channel::{
-            channel::{ChannelEnd, IdentifiedChannelEnd},
+            channel::{ChannelEnd, IdentifiedChannelEnd, QueryPacketEventDataRequest},
             packet::{PacketMsgType, Sequence},
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
     },
-    events::IbcEvent,
+    events::{IbcEvent, IbcEventType},
     proofs::Proofs,
-    query::{QueryBlockRequest, QueryTxRequest},
+    query::{QueryBlockRequest, QueryTxHash, QueryTxRequest},
     signer::Signer,
     Height,
 };
 
 use crate::{
-    account::Balance,
-    config::ChainConfig,
+    account::{AccountInfo, Balance, DenomTrace},
+    config::{ChainConfig, GasPrice},
     connection::ConnectionMsgType,
     error::Error,
     event::monitor::{EventBatch, Result as MonitorResult},
@@ -42,7 +42,9 @@ use crate::{
 
 use super::{
     client::ClientSettings,
-    endpoint::{ChainStatus, HealthCheck},
+    endpoint::{
+        ChainStatus, ClientStatus, HealthCheck, PacketEventTx, TxEvent, TxResult, TxSimulation,
+    },
     requests::{
         IncludeProof, QueryChannelClientStateRequest, QueryChannelRequest, QueryChannelsRequest,
         QueryClientConnectionsRequest, QueryClientStateRequest, QueryClientStatesRequest,
@@ -129,6 +131,16 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>>,
     },
 
+    EstimateGas {
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<u64>,
+    },
+
+    SimulateTx {
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<TxSimulation>,
+    },
+
     Config {
         reply_to: ReplyTo<ChainConfig>,
     },
@@ -156,6 +168,41 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Balance>,
     },
 
+    QueryBalances {
+        key_names: Vec<String>,
+        reply_to: ReplyTo<Vec<Result<Balance, Error>>>,
+    },
+
+    QueryDenomTrace {
+        hash: String,
+        reply_to: ReplyTo<DenomTrace>,
+    },
+
+    QuerySupplyOf {
+        denom: String,
+        reply_to: ReplyTo<Balance>,
+    },
+
+    QueryBalanceOfAddress {
+        address: String,
+        denom: String,
+        reply_to: ReplyTo<Balance>,
+    },
+
+    QueryMinGasPrice {
+        reply_to: ReplyTo<Vec<GasPrice>>,
+    },
+
+    QueryAccountInfo {
+        key_name: Option<String>,
+        reply_to: ReplyTo<AccountInfo>,
+    },
+
+    ResetAccountSequence {
+        key_name: Option<String>,
+        reply_to: ReplyTo<()>,
+    },
+
     QueryApplicationStatus {
         reply_to: ReplyTo<ChainStatus>,
     },
@@ -205,6 +252,11 @@ pub enum ChainRequest {
         reply_to: ReplyTo<(AnyClientState, Option<MerkleProof>)>,
     },
 
+    QueryClientStatus {
+        client_id: ClientId,
+        reply_to: ReplyTo<ClientStatus>,
+    },
+
     QueryClientConnections {
         request: QueryClientConnectionsRequest,
         reply_to: ReplyTo<Vec<ConnectionId>>,
@@ -336,6 +388,27 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Vec<IbcEvent>>,
     },
 
+    QueryTxByHash {
+        hash: QueryTxHash,
+        reply_to: ReplyTo<Option<Vec<IbcEvent>>>,
+    },
+
+    QueryTxResult {
+        hash: QueryTxHash,
+        reply_to: ReplyTo<Option<TxResult>>,
+    },
+
+    QueryTxDecodedEvents {
+        hash: QueryTxHash,
+        decode_only: Vec<IbcEventType>,
+        reply_to: ReplyTo<Option<Vec<TxEvent>>>,
+    },
+
+    QueryPacketEventTxs {
+        request: QueryPacketEventDataRequest,
+        reply_to: ReplyTo<Vec<PacketEventTx>>,
+    },
+
     QueryPacketEventDataFromBlocks {
         request: QueryBlockRequest,
         reply_to: ReplyTo<(Vec<IbcEvent>, Vec<IbcEvent>)>,
@@ -378,6 +451,14 @@ pub trait ChainHandle: Clone + Send + Sync + Serialize + Debug + 'static {
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>, Error>;
 
+    /// Simulate a transaction made of the given messages, without broadcasting it,
+    /// and return the amount of gas it would use.
+    fn estimate_gas(&self, tracked_msgs: TrackedMsgs) -> Result<u64, Error>;
+
+    /// Simulate a transaction made of the given messages, without broadcasting it,
+    /// and return the decoded result of the simulation.
+    fn simulate_tx(&self, tracked_msgs: TrackedMsgs) -> Result<TxSimulation, Error>;
+
     fn get_signer(&self) -> Result<Signer, Error>;
 
     fn config(&self) -> Result<ChainConfig, Error>;
@@ -393,6 +474,37 @@ pub trait ChainHandle: Clone + Send + Sync + Serialize + Debug + 'static {
     /// If no account is given, behavior must be specified, e.g. retrieve it from configuration file.
     fn query_balance(&self, key_name: Option<String>) -> Result<Balance, Error>;
 
+    /// Query the balances of the given keys for the denom used to pay tx fees.
+    /// The balances are returned in the same order as the keys, and the failure
+    /// to query the balance of one key does not affect the others.
+    fn query_balances(&self, key_names: Vec<String>) -> Result<Vec<Result<Balance, Error>>, Error>;
+
+    /// Query the denomination trace of an IBC voucher denomination,
+    /// given the hash of the `ibc/<HASH>` denomination.
+    fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error>;
+
+    /// Query the total supply of the given denomination,
+    /// eg. of an IBC voucher denomination `ibc/<HASH>`.
+    fn query_supply_of(&self, denom: String) -> Result<Balance, Error>;
+
+    /// Query the balance of the given denomination held by the account with the
+    /// given address, eg. by the escrow account of a channel.
+    fn query_balance_of_address(&self, address: String, denom: String) -> Result<Balance, Error>;
+
+    /// Query the minimum gas prices the node is configured with, one per denom,
+    /// failing if the node does not expose them.
+    fn query_min_gas_price(&self) -> Result<Vec<GasPrice>, Error>;
+
+    /// Query the on-chain account number and sequence of the account of the given key,
+    /// along with the sequence cached by the relayer for that account, if any.
+    /// If no key is given, the key is retrieved from the configuration file.
+    fn query_account_info(&self, key_name: Option<String>) -> Result<AccountInfo, Error>;
+
+    /// Clear the account sequence cached by the chain runtime for the given key,
+    /// so that the account is queried again before submitting the next transaction.
+    /// If no key is given, the key is retrieved from the configuration file.
+    fn reset_account_sequence(&self, key_name: Option<String>) -> Result<(), Error>;
+
     /// Query the latest height and timestamp the application is at
     fn query_application_status(&self) -> Result<ChainStatus, Error>;
 
@@ -414,6 +526,11 @@ pub trait ChainHandle: Clone + Send + Sync + Serialize + Debug + 'static {
         include_proof: IncludeProof,
     ) -> Result<(AnyClientState, Option<MerkleProof>), Error>;
 
+    /// Performs a query to retrieve the status of the specified light client: its
+    /// latest height, its trusting period and how long until it expires, along with
+    /// whether it is expired or frozen.
+    fn query_client_status(&self, client_id: ClientId) -> Result<ClientStatus, Error>;
+
     /// Performs a query to retrieve the identifiers of all connections.
     fn query_client_connections(
         &self,
@@ -493,6 +610,11 @@ pub trait ChainHandle: Clone + Send + Sync + Serialize + Debug + 'static {
         include_proof: IncludeProof,
     ) -> Result<(ChannelEnd, Option<MerkleProof>), Error>;
 
+    /// Performs a query to retrieve the channel associated with a given channel
+    /// identifier from the chain, bypassing any cache of the channel ends held
+    /// by the handle.
+    fn query_channel_uncached(&self, request: QueryChannelRequest) -> Result<ChannelEnd, Error>;
+
     /// Performs a query to retrieve the client state for the channel associated
     /// with a given channel identifier.
     fn query_channel_client_state(
@@ -620,6 +742,30 @@ pub trait ChainHandle: Clone + Send + Sync + Serialize + Debug + 'static {
 
     fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEvent>, Error>;
 
+    /// Query the IBC events emitted by the transaction with the given hash,
+    /// or `None` if no such transaction is found.
+    fn query_tx_by_hash(&self, hash: QueryTxHash) -> Result<Option<Vec<IbcEvent>>, Error>;
+
+    /// Query the IBC events emitted by the transaction with the given hash, along
+    /// with the gas it used and the fee it paid, or `None` if no such transaction is found.
+    fn query_tx_result(&self, hash: QueryTxHash) -> Result<Option<TxResult>, Error>;
+
+    /// Query the IBC events emitted by the transaction with the given hash, only
+    /// decoding the events of the given types, the other IBC events being returned
+    /// undecoded, or `None` if no such transaction is found.
+    fn query_tx_decoded_events(
+        &self,
+        hash: QueryTxHash,
+        decode_only: Vec<IbcEventType>,
+    ) -> Result<Option<Vec<TxEvent>>, Error>;
+
+    /// Query the packet events matching the given request, along with the
+    /// transactions which emitted them, at most one for each requested sequence.
+    fn query_packet_event_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<PacketEventTx>, Error>;
+
     fn query_blocks(
         &self,
         request: QueryBlockRequest,
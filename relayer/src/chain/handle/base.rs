@@ -12,25 +12,25 @@ use ibc::{
         ics02_client::misbehaviour::MisbehaviourEvidence,
         ics03_connection::connection::{ConnectionEnd, IdentifiedConnectionEnd},
         ics03_connection::version::Version,
-        ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd},
+        ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd, QueryPacketEventDataRequest},
         ics04_channel::packet::{PacketMsgType, Sequence},
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::ChainId,
         ics24_host::identifier::ChannelId,
         ics24_host::identifier::{ClientId, ConnectionId, PortId},
     },
-    events::IbcEvent,
+    events::{IbcEvent, IbcEventType},
     proofs::Proofs,
-    query::{QueryBlockRequest, QueryTxRequest},
+    query::{QueryBlockRequest, QueryTxHash, QueryTxRequest},
     signer::Signer,
     Height,
 };
 
 use crate::{
-    account::Balance,
+    account::{AccountInfo, Balance, DenomTrace},
     chain::{
         client::ClientSettings,
-        endpoint::ChainStatus,
+        endpoint::{ChainStatus, ClientStatus, PacketEventTx, TxEvent, TxResult, TxSimulation},
         requests::{
             IncludeProof, QueryChannelClientStateRequest, QueryChannelRequest,
             QueryChannelsRequest, QueryClientConnectionsRequest, QueryClientStateRequest,
@@ -44,7 +44,7 @@ use crate::{
         },
         tracking::TrackedMsgs,
     },
-    config::ChainConfig,
+    config::{ChainConfig, GasPrice},
     connection::ConnectionMsgType,
     error::Error,
     keyring::KeyEntry,
@@ -126,6 +126,20 @@ impl ChainHandle for BaseChainHandle {
         })
     }
 
+    fn estimate_gas(&self, tracked_msgs: TrackedMsgs) -> Result<u64, Error> {
+        self.send(|reply_to| ChainRequest::EstimateGas {
+            tracked_msgs,
+            reply_to,
+        })
+    }
+
+    fn simulate_tx(&self, tracked_msgs: TrackedMsgs) -> Result<TxSimulation, Error> {
+        self.send(|reply_to| ChainRequest::SimulateTx {
+            tracked_msgs,
+            reply_to,
+        })
+    }
+
     fn get_signer(&self) -> Result<Signer, Error> {
         self.send(|reply_to| ChainRequest::Signer { reply_to })
     }
@@ -154,6 +168,41 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::QueryBalance { key_name, reply_to })
     }
 
+    fn query_balances(&self, key_names: Vec<String>) -> Result<Vec<Result<Balance, Error>>, Error> {
+        self.send(|reply_to| ChainRequest::QueryBalances {
+            key_names,
+            reply_to,
+        })
+    }
+
+    fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
+        self.send(|reply_to| ChainRequest::QueryDenomTrace { hash, reply_to })
+    }
+
+    fn query_supply_of(&self, denom: String) -> Result<Balance, Error> {
+        self.send(|reply_to| ChainRequest::QuerySupplyOf { denom, reply_to })
+    }
+
+    fn query_balance_of_address(&self, address: String, denom: String) -> Result<Balance, Error> {
+        self.send(|reply_to| ChainRequest::QueryBalanceOfAddress {
+            address,
+            denom,
+            reply_to,
+        })
+    }
+
+    fn query_min_gas_price(&self) -> Result<Vec<GasPrice>, Error> {
+        self.send(|reply_to| ChainRequest::QueryMinGasPrice { reply_to })
+    }
+
+    fn query_account_info(&self, key_name: Option<String>) -> Result<AccountInfo, Error> {
+        self.send(|reply_to| ChainRequest::QueryAccountInfo { key_name, reply_to })
+    }
+
+    fn reset_account_sequence(&self, key_name: Option<String>) -> Result<(), Error> {
+        self.send(|reply_to| ChainRequest::ResetAccountSequence { key_name, reply_to })
+    }
+
     fn query_application_status(&self) -> Result<ChainStatus, Error> {
         self.send(|reply_to| ChainRequest::QueryApplicationStatus { reply_to })
     }
@@ -177,6 +226,13 @@ impl ChainHandle for BaseChainHandle {
         })
     }
 
+    fn query_client_status(&self, client_id: ClientId) -> Result<ClientStatus, Error> {
+        self.send(|reply_to| ChainRequest::QueryClientStatus {
+            client_id,
+            reply_to,
+        })
+    }
+
     fn query_client_connections(
         &self,
         request: QueryClientConnectionsRequest,
@@ -282,6 +338,11 @@ impl ChainHandle for BaseChainHandle {
         })
     }
 
+    fn query_channel_uncached(&self, request: QueryChannelRequest) -> Result<ChannelEnd, Error> {
+        self.query_channel(request, IncludeProof::No)
+            .map(|(channel_end, _)| channel_end)
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -459,6 +520,33 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::QueryPacketEventDataFromTxs { request, reply_to })
     }
 
+    fn query_tx_by_hash(&self, hash: QueryTxHash) -> Result<Option<Vec<IbcEvent>>, Error> {
+        self.send(|reply_to| ChainRequest::QueryTxByHash { hash, reply_to })
+    }
+
+    fn query_tx_result(&self, hash: QueryTxHash) -> Result<Option<TxResult>, Error> {
+        self.send(|reply_to| ChainRequest::QueryTxResult { hash, reply_to })
+    }
+
+    fn query_tx_decoded_events(
+        &self,
+        hash: QueryTxHash,
+        decode_only: Vec<IbcEventType>,
+    ) -> Result<Option<Vec<TxEvent>>, Error> {
+        self.send(|reply_to| ChainRequest::QueryTxDecodedEvents {
+            hash,
+            decode_only,
+            reply_to,
+        })
+    }
+
+    fn query_packet_event_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<PacketEventTx>, Error> {
+        self.send(|reply_to| ChainRequest::QueryPacketEventTxs { request, reply_to })
+    }
+
     fn query_blocks(
         &self,
         request: QueryBlockRequest,
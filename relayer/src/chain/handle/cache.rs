@@ -4,10 +4,10 @@ use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientS
 use ibc::core::ics02_client::events::UpdateClient;
 use ibc::core::ics02_client::misbehaviour::MisbehaviourEvidence;
 use ibc::core::ics03_connection::connection::IdentifiedConnectionEnd;
-use ibc::core::ics04_channel::channel::IdentifiedChannelEnd;
+use ibc::core::ics04_channel::channel::{IdentifiedChannelEnd, QueryPacketEventDataRequest};
 use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
 use ibc::core::ics23_commitment::merkle::MerkleProof;
-use ibc::query::QueryTxRequest;
+use ibc::query::{QueryTxHash, QueryTxRequest};
 use ibc::{
     core::ics02_client::header::AnyHeader,
     core::ics03_connection::connection::ConnectionEnd,
@@ -17,7 +17,7 @@ use ibc::{
     core::ics24_host::identifier::{
         ChainId, ChannelId, ClientId, ConnectionId, PortChannelId, PortId,
     },
-    events::IbcEvent,
+    events::{IbcEvent, IbcEventType},
     proofs::Proofs,
     query::QueryBlockRequest,
     signer::Signer,
@@ -25,10 +25,12 @@ use ibc::{
 };
 use serde::{Serialize, Serializer};
 
-use crate::account::Balance;
+use crate::account::{AccountInfo, Balance, DenomTrace};
 use crate::cache::{Cache, CacheStatus};
 use crate::chain::client::ClientSettings;
-use crate::chain::endpoint::{ChainStatus, HealthCheck};
+use crate::chain::endpoint::{
+    ChainStatus, ClientStatus, HealthCheck, PacketEventTx, TxEvent, TxResult, TxSimulation,
+};
 use crate::chain::handle::{ChainHandle, ChainRequest, Subscription};
 use crate::chain::requests::{
     IncludeProof, QueryChannelClientStateRequest, QueryChannelRequest, QueryChannelsRequest,
@@ -42,7 +44,7 @@ use crate::chain::requests::{
     QueryUpgradedConsensusStateRequest,
 };
 use crate::chain::tracking::TrackedMsgs;
-use crate::config::ChainConfig;
+use crate::config::{ChainConfig, GasPrice};
 use crate::connection::ConnectionMsgType;
 use crate::error::Error;
 use crate::keyring::KeyEntry;
@@ -113,6 +115,14 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().send_messages_and_wait_check_tx(tracked_msgs)
     }
 
+    fn estimate_gas(&self, tracked_msgs: TrackedMsgs) -> Result<u64, Error> {
+        self.inner().estimate_gas(tracked_msgs)
+    }
+
+    fn simulate_tx(&self, tracked_msgs: TrackedMsgs) -> Result<TxSimulation, Error> {
+        self.inner().simulate_tx(tracked_msgs)
+    }
+
     fn get_signer(&self) -> Result<Signer, Error> {
         self.inner().get_signer()
     }
@@ -137,6 +147,34 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().query_balance(key_name)
     }
 
+    fn query_balances(&self, key_names: Vec<String>) -> Result<Vec<Result<Balance, Error>>, Error> {
+        self.inner().query_balances(key_names)
+    }
+
+    fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
+        self.inner().query_denom_trace(hash)
+    }
+
+    fn query_supply_of(&self, denom: String) -> Result<Balance, Error> {
+        self.inner().query_supply_of(denom)
+    }
+
+    fn query_balance_of_address(&self, address: String, denom: String) -> Result<Balance, Error> {
+        self.inner().query_balance_of_address(address, denom)
+    }
+
+    fn query_min_gas_price(&self) -> Result<Vec<GasPrice>, Error> {
+        self.inner().query_min_gas_price()
+    }
+
+    fn query_account_info(&self, key_name: Option<String>) -> Result<AccountInfo, Error> {
+        self.inner().query_account_info(key_name)
+    }
+
+    fn reset_account_sequence(&self, key_name: Option<String>) -> Result<(), Error> {
+        self.inner().reset_account_sequence(key_name)
+    }
+
     fn query_application_status(&self) -> Result<ChainStatus, Error> {
         self.inner().query_application_status()
     }
@@ -193,6 +231,10 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         }
     }
 
+    fn query_client_status(&self, client_id: ClientId) -> Result<ClientStatus, Error> {
+        self.inner().query_client_status(client_id)
+    }
+
     fn query_client_connections(
         &self,
         request: QueryClientConnectionsRequest,
@@ -329,6 +371,10 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         }
     }
 
+    fn query_channel_uncached(&self, request: QueryChannelRequest) -> Result<ChannelEnd, Error> {
+        self.inner().query_channel_uncached(request)
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -468,6 +514,29 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().query_txs(request)
     }
 
+    fn query_tx_by_hash(&self, hash: QueryTxHash) -> Result<Option<Vec<IbcEvent>>, Error> {
+        self.inner().query_tx_by_hash(hash)
+    }
+
+    fn query_tx_result(&self, hash: QueryTxHash) -> Result<Option<TxResult>, Error> {
+        self.inner().query_tx_result(hash)
+    }
+
+    fn query_tx_decoded_events(
+        &self,
+        hash: QueryTxHash,
+        decode_only: Vec<IbcEventType>,
+    ) -> Result<Option<Vec<TxEvent>>, Error> {
+        self.inner().query_tx_decoded_events(hash, decode_only)
+    }
+
+    fn query_packet_event_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<PacketEventTx>, Error> {
+        self.inner().query_packet_event_txs(request)
+    }
+
     fn query_blocks(
         &self,
         request: QueryBlockRequest,
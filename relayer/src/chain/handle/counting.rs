@@ -4,10 +4,10 @@ use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientS
 use ibc::core::ics02_client::events::UpdateClient;
 use ibc::core::ics02_client::misbehaviour::MisbehaviourEvidence;
 use ibc::core::ics03_connection::connection::IdentifiedConnectionEnd;
-use ibc::core::ics04_channel::channel::IdentifiedChannelEnd;
+use ibc::core::ics04_channel::channel::{IdentifiedChannelEnd, QueryPacketEventDataRequest};
 use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
 use ibc::core::ics23_commitment::merkle::MerkleProof;
-use ibc::query::QueryTxRequest;
+use ibc::query::{QueryTxHash, QueryTxRequest};
 use ibc::{
     core::ics02_client::header::AnyHeader,
     core::ics03_connection::connection::ConnectionEnd,
@@ -15,7 +15,7 @@ use ibc::{
     core::ics04_channel::channel::ChannelEnd,
     core::ics23_commitment::commitment::CommitmentPrefix,
     core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId},
-    events::IbcEvent,
+    events::{IbcEvent, IbcEventType},
     proofs::Proofs,
     query::QueryBlockRequest,
     signer::Signer,
@@ -26,9 +26,11 @@ use std::collections::HashMap;
 use std::sync::{Arc, RwLock, RwLockReadGuard};
 use tracing::debug;
 
-use crate::account::Balance;
+use crate::account::{AccountInfo, Balance, DenomTrace};
 use crate::chain::client::ClientSettings;
-use crate::chain::endpoint::{ChainStatus, HealthCheck};
+use crate::chain::endpoint::{
+    ChainStatus, ClientStatus, HealthCheck, PacketEventTx, TxEvent, TxResult, TxSimulation,
+};
 use crate::chain::handle::{ChainHandle, ChainRequest, Subscription};
 use crate::chain::requests::{
     IncludeProof, QueryChannelClientStateRequest, QueryChannelRequest, QueryChannelsRequest,
@@ -42,7 +44,7 @@ use crate::chain::requests::{
     QueryUpgradedConsensusStateRequest,
 };
 use crate::chain::tracking::TrackedMsgs;
-use crate::config::ChainConfig;
+use crate::config::{ChainConfig, GasPrice};
 use crate::error::Error;
 use crate::util::lock::LockExt;
 use crate::{connection::ConnectionMsgType, keyring::KeyEntry};
@@ -133,6 +135,16 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().send_messages_and_wait_check_tx(tracked_msgs)
     }
 
+    fn estimate_gas(&self, tracked_msgs: TrackedMsgs) -> Result<u64, Error> {
+        self.inc_metric("estimate_gas");
+        self.inner().estimate_gas(tracked_msgs)
+    }
+
+    fn simulate_tx(&self, tracked_msgs: TrackedMsgs) -> Result<TxSimulation, Error> {
+        self.inc_metric("simulate_tx");
+        self.inner().simulate_tx(tracked_msgs)
+    }
+
     fn get_signer(&self) -> Result<Signer, Error> {
         self.inc_metric("get_signer");
         self.inner().get_signer()
@@ -163,6 +175,41 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().query_balance(key_name)
     }
 
+    fn query_balances(&self, key_names: Vec<String>) -> Result<Vec<Result<Balance, Error>>, Error> {
+        self.inc_metric("query_balances");
+        self.inner().query_balances(key_names)
+    }
+
+    fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
+        self.inc_metric("query_denom_trace");
+        self.inner().query_denom_trace(hash)
+    }
+
+    fn query_supply_of(&self, denom: String) -> Result<Balance, Error> {
+        self.inc_metric("query_supply_of");
+        self.inner().query_supply_of(denom)
+    }
+
+    fn query_balance_of_address(&self, address: String, denom: String) -> Result<Balance, Error> {
+        self.inc_metric("query_balance_of_address");
+        self.inner().query_balance_of_address(address, denom)
+    }
+
+    fn query_min_gas_price(&self) -> Result<Vec<GasPrice>, Error> {
+        self.inc_metric("query_min_gas_price");
+        self.inner().query_min_gas_price()
+    }
+
+    fn query_account_info(&self, key_name: Option<String>) -> Result<AccountInfo, Error> {
+        self.inc_metric("query_account_info");
+        self.inner().query_account_info(key_name)
+    }
+
+    fn reset_account_sequence(&self, key_name: Option<String>) -> Result<(), Error> {
+        self.inc_metric("reset_account_sequence");
+        self.inner().reset_account_sequence(key_name)
+    }
+
     fn query_application_status(&self) -> Result<ChainStatus, Error> {
         self.inc_metric("query_application_status");
         self.inner().query_application_status()
@@ -193,6 +240,11 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().query_client_state(request, include_proof)
     }
 
+    fn query_client_status(&self, client_id: ClientId) -> Result<ClientStatus, Error> {
+        self.inc_metric(&format!("query_client_status({})", client_id));
+        self.inner().query_client_status(client_id)
+    }
+
     fn query_client_connections(
         &self,
         request: QueryClientConnectionsRequest,
@@ -296,6 +348,11 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().query_channel(request, include_proof)
     }
 
+    fn query_channel_uncached(&self, request: QueryChannelRequest) -> Result<ChannelEnd, Error> {
+        self.inc_metric("query_channel_uncached");
+        self.inner().query_channel_uncached(request)
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -451,6 +508,33 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().query_txs(request)
     }
 
+    fn query_tx_by_hash(&self, hash: QueryTxHash) -> Result<Option<Vec<IbcEvent>>, Error> {
+        self.inc_metric("query_tx_by_hash");
+        self.inner().query_tx_by_hash(hash)
+    }
+
+    fn query_tx_result(&self, hash: QueryTxHash) -> Result<Option<TxResult>, Error> {
+        self.inc_metric("query_tx_result");
+        self.inner().query_tx_result(hash)
+    }
+
+    fn query_tx_decoded_events(
+        &self,
+        hash: QueryTxHash,
+        decode_only: Vec<IbcEventType>,
+    ) -> Result<Option<Vec<TxEvent>>, Error> {
+        self.inc_metric("query_tx_decoded_events");
+        self.inner().query_tx_decoded_events(hash, decode_only)
+    }
+
+    fn query_packet_event_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<PacketEventTx>, Error> {
+        self.inc_metric("query_packet_event_txs");
+        self.inner().query_packet_event_txs(request)
+    }
+
     fn query_blocks(
         &self,
         request: QueryBlockRequest,
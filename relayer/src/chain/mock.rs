@@ -15,27 +15,31 @@ use ibc::clients::ics07_tendermint::header::Header as TendermintHeader;
 use ibc::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
 use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientState};
 use ibc::core::ics03_connection::connection::{ConnectionEnd, IdentifiedConnectionEnd};
-use ibc::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
+use ibc::core::ics04_channel::channel::{
+    ChannelEnd, IdentifiedChannelEnd, QueryPacketEventDataRequest,
+};
 use ibc::core::ics04_channel::context::ChannelReader;
 use ibc::core::ics04_channel::packet::Sequence;
 use ibc::core::ics23_commitment::{commitment::CommitmentPrefix, specs::ProofSpecs};
 use ibc::core::ics24_host::identifier::{ChainId, ConnectionId};
-use ibc::events::IbcEvent;
+use ibc::events::{IbcEvent, IbcEventType};
 use ibc::mock::context::MockContext;
 use ibc::mock::host::HostType;
-use ibc::query::{QueryBlockRequest, QueryTxRequest};
+use ibc::query::{QueryBlockRequest, QueryTxHash, QueryTxRequest};
 use ibc::relayer::ics18_relayer::context::Ics18Context;
 use ibc::signer::Signer;
 use ibc::test_utils::get_dummy_account_id;
 use ibc::Height;
 
-use crate::account::Balance;
+use crate::account::{AccountInfo, Balance, DenomTrace};
 use crate::chain::client::ClientSettings;
-use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck};
+use crate::chain::endpoint::{
+    ChainEndpoint, ChainStatus, HealthCheck, PacketEventTx, TxEvent, TxResult, TxSimulation,
+};
 use crate::chain::requests::{
     QueryChannelClientStateRequest, QueryChannelRequest, QueryClientStatesRequest,
 };
-use crate::config::ChainConfig;
+use crate::config::{ChainConfig, GasPrice};
 use crate::error::Error;
 use crate::event::monitor::{EventReceiver, EventSender, TxMonitorCmd};
 use crate::keyring::{KeyEntry, KeyRing};
@@ -146,6 +150,14 @@ impl ChainEndpoint for MockChain {
         todo!()
     }
 
+    fn estimate_gas(&mut self, _tracked_msgs: TrackedMsgs) -> Result<u64, Error> {
+        unimplemented!()
+    }
+
+    fn simulate_tx(&mut self, _tracked_msgs: TrackedMsgs) -> Result<TxSimulation, Error> {
+        unimplemented!()
+    }
+
     fn get_signer(&mut self) -> Result<Signer, Error> {
         Ok(get_dummy_account_id())
     }
@@ -170,6 +182,30 @@ impl ChainEndpoint for MockChain {
         unimplemented!()
     }
 
+    fn query_denom_trace(&self, _hash: String) -> Result<DenomTrace, Error> {
+        unimplemented!()
+    }
+
+    fn query_supply_of(&self, _denom: String) -> Result<Balance, Error> {
+        unimplemented!()
+    }
+
+    fn query_balance_of_address(&self, _address: String, _denom: String) -> Result<Balance, Error> {
+        unimplemented!()
+    }
+
+    fn query_min_gas_price(&self) -> Result<Vec<GasPrice>, Error> {
+        unimplemented!()
+    }
+
+    fn query_account_info(&self, _key_name: Option<String>) -> Result<AccountInfo, Error> {
+        unimplemented!()
+    }
+
+    fn reset_account_sequence(&mut self, _key_name: Option<String>) -> Result<(), Error> {
+        unimplemented!()
+    }
+
     fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
         unimplemented!()
     }
@@ -324,6 +360,29 @@ impl ChainEndpoint for MockChain {
         unimplemented!()
     }
 
+    fn query_tx_by_hash(&self, _hash: QueryTxHash) -> Result<Option<Vec<IbcEvent>>, Error> {
+        unimplemented!()
+    }
+
+    fn query_tx_result(&self, _hash: QueryTxHash) -> Result<Option<TxResult>, Error> {
+        unimplemented!()
+    }
+
+    fn query_tx_decoded_events(
+        &self,
+        _hash: QueryTxHash,
+        _decode_only: &[IbcEventType],
+    ) -> Result<Option<Vec<TxEvent>>, Error> {
+        unimplemented!()
+    }
+
+    fn query_packet_event_txs(
+        &self,
+        _request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<PacketEventTx>, Error> {
+        unimplemented!()
+    }
+
     fn query_blocks(
         &self,
         _request: QueryBlockRequest,
@@ -462,6 +521,7 @@ pub mod test_utils {
             rpc_timeout: crate::config::default::rpc_timeout(),
             account_prefix: "".to_string(),
             key_name: "".to_string(),
+            extra_key_names: Vec::new(),
             store_prefix: "".to_string(),
             default_gas: None,
             key_store_type: Default::default(),
@@ -479,6 +539,11 @@ pub mod test_utils {
             address_type: AddressType::default(),
             memo_prefix: Default::default(),
             proof_specs: Default::default(),
+            tx_rate_limit: None,
+            display_denom_map: Default::default(),
+            event_source: Default::default(),
+            broadcast_mode: Default::default(),
+            account_resync_interval: Duration::from_secs(1),
         }
     }
 }
@@ -19,22 +19,22 @@ use ibc::{
             version::Version,
         },
         ics04_channel::{
-            channel::{ChannelEnd, IdentifiedChannelEnd},
+            channel::{ChannelEnd, IdentifiedChannelEnd, QueryPacketEventDataRequest},
             packet::{PacketMsgType, Sequence},
         },
         ics23_commitment::{commitment::CommitmentPrefix, merkle::MerkleProof},
         ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
     },
-    events::IbcEvent,
+    events::{IbcEvent, IbcEventType},
     proofs::Proofs,
-    query::{QueryBlockRequest, QueryTxRequest},
+    query::{QueryBlockRequest, QueryTxHash, QueryTxRequest},
     signer::Signer,
     Height,
 };
 
 use crate::{
-    account::Balance,
-    config::ChainConfig,
+    account::{AccountInfo, Balance, DenomTrace},
+    config::{ChainConfig, GasPrice},
     connection::ConnectionMsgType,
     error::Error,
     event::{
@@ -47,7 +47,10 @@ use crate::{
 
 use super::{
     client::ClientSettings,
-    endpoint::{ChainEndpoint, ChainStatus, HealthCheck},
+    endpoint::{
+        ChainEndpoint, ChainStatus, ClientStatus, HealthCheck, PacketEventTx, TxEvent, TxResult,
+        TxSimulation,
+    },
     handle::{ChainHandle, ChainRequest, ReplyTo, Subscription},
     requests::{
         IncludeProof, QueryChannelClientStateRequest, QueryChannelRequest, QueryChannelsRequest,
@@ -259,6 +262,14 @@ where
                             self.send_messages_and_wait_check_tx(tracked_msgs, reply_to)?
                         },
 
+                        Ok(ChainRequest::EstimateGas { tracked_msgs, reply_to }) => {
+                            self.estimate_gas(tracked_msgs, reply_to)?
+                        },
+
+                        Ok(ChainRequest::SimulateTx { tracked_msgs, reply_to }) => {
+                            self.simulate_tx(tracked_msgs, reply_to)?
+                        },
+
                         Ok(ChainRequest::Signer { reply_to }) => {
                             self.get_signer(reply_to)?
                         }
@@ -307,6 +318,34 @@ where
                             self.query_balance(key_name, reply_to)?
                         }
 
+                        Ok(ChainRequest::QueryBalances { key_names, reply_to }) => {
+                            self.query_balances(key_names, reply_to)?
+                        }
+
+                        Ok(ChainRequest::QueryDenomTrace { hash, reply_to }) => {
+                            self.query_denom_trace(hash, reply_to)?
+                        }
+
+                        Ok(ChainRequest::QuerySupplyOf { denom, reply_to }) => {
+                            self.query_supply_of(denom, reply_to)?
+                        }
+
+                        Ok(ChainRequest::QueryBalanceOfAddress { address, denom, reply_to }) => {
+                            self.query_balance_of_address(address, denom, reply_to)?
+                        }
+
+                        Ok(ChainRequest::QueryMinGasPrice { reply_to }) => {
+                            self.query_min_gas_price(reply_to)?
+                        }
+
+                        Ok(ChainRequest::QueryAccountInfo { key_name, reply_to }) => {
+                            self.query_account_info(key_name, reply_to)?
+                        }
+
+                        Ok(ChainRequest::ResetAccountSequence { key_name, reply_to }) => {
+                            self.reset_account_sequence(key_name, reply_to)?
+                        }
+
                         Ok(ChainRequest::QueryApplicationStatus { reply_to }) => {
                             self.query_application_status(reply_to)?
                         }
@@ -323,6 +362,10 @@ where
                             self.query_client_state(request, include_proof, reply_to)?
                         },
 
+                        Ok(ChainRequest::QueryClientStatus { client_id, reply_to }) => {
+                            self.query_client_status(client_id, reply_to)?
+                        },
+
                         Ok(ChainRequest::QueryConsensusStates { request, reply_to }) => {
                             self.query_consensus_states(request, reply_to)?
                         },
@@ -411,6 +454,22 @@ where
                             self.query_txs(request, reply_to)?
                         },
 
+                        Ok(ChainRequest::QueryTxByHash { hash, reply_to }) => {
+                            self.query_tx_by_hash(hash, reply_to)?
+                        },
+
+                        Ok(ChainRequest::QueryTxResult { hash, reply_to }) => {
+                            self.query_tx_result(hash, reply_to)?
+                        },
+
+                        Ok(ChainRequest::QueryTxDecodedEvents { hash, decode_only, reply_to }) => {
+                            self.query_tx_decoded_events(hash, decode_only, reply_to)?
+                        },
+
+                        Ok(ChainRequest::QueryPacketEventTxs { request, reply_to }) => {
+                            self.query_packet_event_txs(request, reply_to)?
+                        },
+
                         Ok(ChainRequest::QueryPacketEventDataFromBlocks { request, reply_to }) => {
                             self.query_blocks(request, reply_to)?
                         },
@@ -469,6 +528,24 @@ where
         reply_to.send(result).map_err(Error::send)
     }
 
+    fn estimate_gas(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<u64>,
+    ) -> Result<(), Error> {
+        let result = self.chain.estimate_gas(tracked_msgs);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn simulate_tx(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<TxSimulation>,
+    ) -> Result<(), Error> {
+        let result = self.chain.simulate_tx(tracked_msgs);
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn query_balance(
         &self,
         key_name: Option<String>,
@@ -478,6 +555,58 @@ where
         reply_to.send(balance).map_err(Error::send)
     }
 
+    fn query_balances(
+        &self,
+        key_names: Vec<String>,
+        reply_to: ReplyTo<Vec<Result<Balance, Error>>>,
+    ) -> Result<(), Error> {
+        let balances = self.chain.query_balances(key_names);
+        reply_to.send(balances).map_err(Error::send)
+    }
+
+    fn query_denom_trace(&self, hash: String, reply_to: ReplyTo<DenomTrace>) -> Result<(), Error> {
+        let denom_trace = self.chain.query_denom_trace(hash);
+        reply_to.send(denom_trace).map_err(Error::send)
+    }
+
+    fn query_supply_of(&self, denom: String, reply_to: ReplyTo<Balance>) -> Result<(), Error> {
+        let supply = self.chain.query_supply_of(denom);
+        reply_to.send(supply).map_err(Error::send)
+    }
+
+    fn query_balance_of_address(
+        &self,
+        address: String,
+        denom: String,
+        reply_to: ReplyTo<Balance>,
+    ) -> Result<(), Error> {
+        let balance = self.chain.query_balance_of_address(address, denom);
+        reply_to.send(balance).map_err(Error::send)
+    }
+
+    fn query_min_gas_price(&self, reply_to: ReplyTo<Vec<GasPrice>>) -> Result<(), Error> {
+        let prices = self.chain.query_min_gas_price();
+        reply_to.send(prices).map_err(Error::send)
+    }
+
+    fn query_account_info(
+        &self,
+        key_name: Option<String>,
+        reply_to: ReplyTo<AccountInfo>,
+    ) -> Result<(), Error> {
+        let account_info = self.chain.query_account_info(key_name);
+        reply_to.send(account_info).map_err(Error::send)
+    }
+
+    fn reset_account_sequence(
+        &mut self,
+        key_name: Option<String>,
+        reply_to: ReplyTo<()>,
+    ) -> Result<(), Error> {
+        let result = self.chain.reset_account_sequence(key_name);
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn query_application_status(&self, reply_to: ReplyTo<ChainStatus>) -> Result<(), Error> {
         let latest_timestamp = self.chain.query_application_status();
         reply_to.send(latest_timestamp).map_err(Error::send)
@@ -637,6 +766,15 @@ where
         reply_to.send(res).map_err(Error::send)
     }
 
+    fn query_client_status(
+        &self,
+        client_id: ClientId,
+        reply_to: ReplyTo<ClientStatus>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_client_status(client_id);
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn query_upgraded_client_state(
         &self,
         request: QueryUpgradedClientStateRequest,
@@ -868,6 +1006,43 @@ where
         reply_to.send(result).map_err(Error::send)
     }
 
+    fn query_tx_by_hash(
+        &self,
+        hash: QueryTxHash,
+        reply_to: ReplyTo<Option<Vec<IbcEvent>>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_tx_by_hash(hash);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_tx_result(
+        &self,
+        hash: QueryTxHash,
+        reply_to: ReplyTo<Option<TxResult>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_tx_result(hash);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_tx_decoded_events(
+        &self,
+        hash: QueryTxHash,
+        decode_only: Vec<IbcEventType>,
+        reply_to: ReplyTo<Option<Vec<TxEvent>>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_tx_decoded_events(hash, decode_only);
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    fn query_packet_event_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+        reply_to: ReplyTo<Vec<PacketEventTx>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_packet_event_txs(request);
+        reply_to.send(result).map_err(Error::send)
+    }
+
     fn query_blocks(
         &self,
         request: QueryBlockRequest,
@@ -1,8 +1,11 @@
 use core::fmt;
 
+use ibc::Height;
 use ibc_proto::google::protobuf::Any;
 use uuid::Uuid;
 
+use crate::config::types::Memo;
+
 /// Identifier used to track an `EventBatch` along
 /// the relaying pipeline until the corresponding
 /// transactions are submitted and/or confirmed.
@@ -48,21 +51,36 @@ impl fmt::Display for TrackingId {
 /// A [`TrackedMsgs`] correlates with a
 /// [`TrackedEvents`](crate::link::operational_data::TrackedEvents)
 /// by sharing the same `tracking_id`.
+///
+/// The messages are signed with the key given by `key_name`,
+/// or with the key from the chain configuration if unset.
+///
+/// The `source_heights` of the events relayed by the messages,
+/// if set, are recorded in the memo of the transactions.
 #[derive(Debug, Clone)]
 pub struct TrackedMsgs {
     pub msgs: Vec<Any>,
     pub tracking_id: TrackingId,
+    pub key_name: Option<String>,
+    pub source_heights: Vec<Height>,
 }
 
 impl TrackedMsgs {
     pub fn new(msgs: Vec<Any>, tracking_id: TrackingId) -> Self {
-        Self { msgs, tracking_id }
+        Self {
+            msgs,
+            tracking_id,
+            key_name: None,
+            source_heights: vec![],
+        }
     }
 
     pub fn new_static(msgs: Vec<Any>, tracking_id: &'static str) -> Self {
         Self {
             msgs,
             tracking_id: TrackingId::Static(tracking_id),
+            key_name: None,
+            source_heights: vec![],
         }
     }
 
@@ -70,6 +88,8 @@ impl TrackedMsgs {
         Self {
             msgs,
             tracking_id: TrackingId::Uuid(tracking_id),
+            key_name: None,
+            source_heights: vec![],
         }
     }
 
@@ -77,6 +97,8 @@ impl TrackedMsgs {
         Self {
             msgs: vec![msg],
             tracking_id: TrackingId::Static(tracking_id),
+            key_name: None,
+            source_heights: vec![],
         }
     }
 
@@ -84,6 +106,8 @@ impl TrackedMsgs {
         Self {
             msgs: vec![msg],
             tracking_id: TrackingId::Uuid(tracking_id),
+            key_name: None,
+            source_heights: vec![],
         }
     }
 
@@ -94,4 +118,71 @@ impl TrackedMsgs {
     pub fn tracking_id(&self) -> TrackingId {
         self.tracking_id
     }
+
+    /// Sets the name of the key used to sign the messages.
+    pub fn with_key_name(mut self, key_name: Option<String>) -> Self {
+        self.key_name = key_name;
+        self
+    }
+
+    /// Sets the heights of the events relayed by the messages,
+    /// to be recorded in the memo of the transactions.
+    pub fn with_source_heights(mut self, source_heights: Vec<Height>) -> Self {
+        self.source_heights = source_heights;
+        self
+    }
+
+    /// The memo of the transactions submitting the messages: the given
+    /// memo prefix, followed by the range of the source heights, if set,
+    /// eg. `src_heights: 0-120..0-135`.
+    pub fn memo(&self, memo_prefix: &Memo) -> Memo {
+        let lowest = self.source_heights.iter().min();
+        let highest = self.source_heights.iter().max();
+
+        let mut memo = memo_prefix.clone();
+
+        match (lowest, highest) {
+            (Some(lowest), Some(highest)) if lowest == highest => {
+                memo.apply_suffix(&format!("src_heights: {}", lowest))
+            }
+            (Some(lowest), Some(highest)) => {
+                memo.apply_suffix(&format!("src_heights: {}..{}", lowest, highest))
+            }
+            _ => {}
+        }
+
+        memo
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc::Height;
+
+    use super::{TrackedMsgs, TrackingId};
+    use crate::config::types::Memo;
+
+    fn msgs(heights: &[u64]) -> TrackedMsgs {
+        TrackedMsgs::new(vec![], TrackingId::new_static("test"))
+            .with_source_heights(heights.iter().map(|h| Height::new(0, *h)).collect())
+    }
+
+    #[test]
+    fn memo_records_the_range_of_source_heights() {
+        let prefix = Memo::new("relayer".to_string()).unwrap();
+
+        assert_eq!(msgs(&[]).memo(&prefix).as_str(), "relayer");
+        assert_eq!(
+            msgs(&[120, 120]).memo(&prefix).as_str(),
+            "relayer | src_heights: 0-120"
+        );
+        assert_eq!(
+            msgs(&[135, 120, 128]).memo(&prefix).as_str(),
+            "relayer | src_heights: 0-120..0-135"
+        );
+        assert_eq!(
+            msgs(&[7]).memo(&Memo::default()).as_str(),
+            "src_heights: 0-7"
+        );
+    }
 }
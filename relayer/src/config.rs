@@ -7,8 +7,14 @@ pub mod types;
 
 use alloc::collections::BTreeMap;
 use core::{fmt, time::Duration};
-use std::{fs, fs::File, io::Write, path::Path};
-
+use std::{
+    fs,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use itertools::Itertools;
 use serde_derive::{Deserialize, Serialize};
 use tendermint_light_client_verifier::types::TrustThreshold;
 
@@ -58,6 +64,30 @@ pub mod default {
         100
     }
 
+    pub fn relay_acks() -> bool {
+        true
+    }
+
+    pub fn relay_timeouts() -> bool {
+        true
+    }
+
+    pub fn max_event_batch_size() -> usize {
+        500
+    }
+
+    pub fn execute_schedule_timeout() -> Duration {
+        ZERO_DURATION
+    }
+
+    pub fn circuit_breaker_window() -> Duration {
+        Duration::from_secs(60)
+    }
+
+    pub fn circuit_breaker_cooldown() -> Duration {
+        Duration::from_secs(300)
+    }
+
     pub fn rpc_timeout() -> Duration {
         Duration::from_secs(10)
     }
@@ -70,9 +100,17 @@ pub mod default {
         Duration::from_secs(30)
     }
 
+    pub fn account_resync_interval() -> Duration {
+        Duration::from_secs(1)
+    }
+
     pub fn connection_delay() -> Duration {
         ZERO_DURATION
     }
+
+    pub fn max_packet_data_bytes() -> usize {
+        1024 * 1024
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -86,6 +124,10 @@ pub struct Config {
     pub rest: RestConfig,
     #[serde(default)]
     pub telemetry: TelemetryConfig,
+    /// The directions relayed between pairs of chains, both being relayed
+    /// between the chains which are not listed.
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub relay_directions: Vec<ChainPairDirection>,
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub chains: Vec<ChainConfig>,
 }
@@ -118,6 +160,45 @@ impl Config {
         }
     }
 
+    /// Returns true if the packets sent from `src_chain_id` to `dst_chain_id`
+    /// are relayed, ie. unless the `relay_directions` of the pair of chains
+    /// exclude that direction.
+    pub fn relays_direction(&self, src_chain_id: &ChainId, dst_chain_id: &ChainId) -> bool {
+        self.relay_directions.iter().all(|pair| {
+            if pair.chain_a == *src_chain_id && pair.chain_b == *dst_chain_id {
+                pair.relay_direction != RelayDirection::BToA
+            } else if pair.chain_a == *dst_chain_id && pair.chain_b == *src_chain_id {
+                pair.relay_direction != RelayDirection::AToB
+            } else {
+                true
+            }
+        })
+    }
+
+    /// The identifiers of the configured chains, in the order their runtimes are
+    /// spawned: first the chains listed in the `spawn_priority`, in that order,
+    /// then the other chains, in configuration order.
+    ///
+    /// The chains listed in the `spawn_priority` but not configured are skipped.
+    pub fn chains_in_spawn_order(&self) -> (Vec<&ChainId>, Vec<&ChainId>) {
+        let prioritized = self
+            .global
+            .spawn_priority
+            .iter()
+            .unique()
+            .filter(|chain_id| self.has_chain(chain_id))
+            .collect();
+
+        let others = self
+            .chains
+            .iter()
+            .map(|chain_config| &chain_config.id)
+            .filter(|chain_id| !self.global.spawn_priority.contains(chain_id))
+            .collect();
+
+        (prioritized, others)
+    }
+
     pub fn chains_map(&self) -> BTreeMap<&ChainId, &ChainConfig> {
         self.chains.iter().map(|c| (&c.id, c)).collect()
     }
@@ -156,6 +237,38 @@ impl Default for ModeConfig {
                 clear_interval: default::clear_packets_interval(),
                 clear_on_start: true,
                 tx_confirmation: true,
+                relay_acks: default::relay_acks(),
+                relay_timeouts: default::relay_timeouts(),
+                attempt_client_recovery_on_frozen: false,
+                max_event_batch_size: default::max_event_batch_size(),
+                observe_only: false,
+                forward_events: PacketEventFilter::default(),
+                max_command_retries: 0,
+                execute_schedule_timeout: default::execute_schedule_timeout(),
+                circuit_breaker_threshold: 0,
+                circuit_breaker_window: default::circuit_breaker_window(),
+                circuit_breaker_cooldown: default::circuit_breaker_cooldown(),
+                confirmation_blocks: 0,
+                reconcile_schedule: false,
+                tx_aggregation_window: Duration::ZERO,
+                client_refresh_margin: Duration::ZERO,
+                cost_aware_direction: false,
+                relay_grace_period: Duration::ZERO,
+                startup_jitter: false,
+                aggregate_in_commit_order: false,
+                clear_on_start_max_packets: 0,
+                verify_acks: false,
+                max_in_flight_txs: 0,
+                ack_flush_count: 0,
+                ack_flush_interval: Duration::ZERO,
+                clear_max_duration: Duration::ZERO,
+                separate_client_updates: false,
+                memo_source_heights: false,
+                idle_backoff_max_interval: Duration::ZERO,
+                max_packet_data_bytes: default::max_packet_data_bytes(),
+                collect_timings: false,
+                persist_schedule: false,
+                proof_query_retries: 0,
             },
         }
     }
@@ -193,6 +306,77 @@ pub struct Packets {
     pub clear_on_start: bool,
     #[serde(default = "default::tx_confirmation")]
     pub tx_confirmation: bool,
+    #[serde(default = "default::relay_acks")]
+    pub relay_acks: bool,
+    #[serde(default = "default::relay_timeouts")]
+    pub relay_timeouts: bool,
+    #[serde(default)]
+    pub attempt_client_recovery_on_frozen: bool,
+    #[serde(default = "default::max_event_batch_size")]
+    pub max_event_batch_size: usize,
+    #[serde(default)]
+    pub observe_only: bool,
+    #[serde(default)]
+    pub max_command_retries: u64,
+    #[serde(
+        default = "default::execute_schedule_timeout",
+        with = "humantime_serde"
+    )]
+    pub execute_schedule_timeout: Duration,
+    #[serde(default)]
+    pub circuit_breaker_threshold: u64,
+    #[serde(default = "default::circuit_breaker_window", with = "humantime_serde")]
+    pub circuit_breaker_window: Duration,
+    #[serde(
+        default = "default::circuit_breaker_cooldown",
+        with = "humantime_serde"
+    )]
+    pub circuit_breaker_cooldown: Duration,
+    #[serde(default)]
+    pub confirmation_blocks: u64,
+    #[serde(default)]
+    pub reconcile_schedule: bool,
+    #[serde(default, with = "humantime_serde")]
+    pub tx_aggregation_window: Duration,
+    #[serde(default, with = "humantime_serde")]
+    pub client_refresh_margin: Duration,
+    #[serde(default)]
+    pub cost_aware_direction: bool,
+    #[serde(default, with = "humantime_serde")]
+    pub relay_grace_period: Duration,
+    #[serde(default)]
+    pub startup_jitter: bool,
+    #[serde(default)]
+    pub aggregate_in_commit_order: bool,
+    #[serde(default)]
+    pub clear_on_start_max_packets: usize,
+    #[serde(default)]
+    pub verify_acks: bool,
+    #[serde(default)]
+    pub max_in_flight_txs: usize,
+    #[serde(default)]
+    pub ack_flush_count: usize,
+    #[serde(default, with = "humantime_serde")]
+    pub ack_flush_interval: Duration,
+    #[serde(default, with = "humantime_serde")]
+    pub clear_max_duration: Duration,
+    #[serde(default)]
+    pub separate_client_updates: bool,
+    #[serde(default)]
+    pub memo_source_heights: bool,
+    #[serde(default, with = "humantime_serde")]
+    pub idle_backoff_max_interval: Duration,
+    #[serde(default = "default::max_packet_data_bytes")]
+    pub max_packet_data_bytes: usize,
+    #[serde(default)]
+    pub collect_timings: bool,
+    #[serde(default)]
+    pub persist_schedule: bool,
+    #[serde(default)]
+    pub proof_query_retries: u64,
+    // Must be the last field, as it is serialized as a TOML table.
+    #[serde(default)]
+    pub forward_events: PacketEventFilter,
 }
 
 impl Default for Packets {
@@ -202,10 +386,93 @@ impl Default for Packets {
             clear_interval: default::clear_packets_interval(),
             clear_on_start: false,
             tx_confirmation: default::tx_confirmation(),
+            relay_acks: default::relay_acks(),
+            relay_timeouts: default::relay_timeouts(),
+            attempt_client_recovery_on_frozen: false,
+            max_event_batch_size: default::max_event_batch_size(),
+            observe_only: false,
+            forward_events: PacketEventFilter::default(),
+            max_command_retries: 0,
+            execute_schedule_timeout: default::execute_schedule_timeout(),
+            circuit_breaker_threshold: 0,
+            circuit_breaker_window: default::circuit_breaker_window(),
+            circuit_breaker_cooldown: default::circuit_breaker_cooldown(),
+            confirmation_blocks: 0,
+            reconcile_schedule: false,
+            tx_aggregation_window: Duration::ZERO,
+            client_refresh_margin: Duration::ZERO,
+            cost_aware_direction: false,
+            relay_grace_period: Duration::ZERO,
+            startup_jitter: false,
+            aggregate_in_commit_order: false,
+            clear_on_start_max_packets: 0,
+            verify_acks: false,
+            max_in_flight_txs: 0,
+            ack_flush_count: 0,
+            ack_flush_interval: Duration::ZERO,
+            clear_max_duration: Duration::ZERO,
+            separate_client_updates: false,
+            memo_source_heights: false,
+            idle_backoff_max_interval: Duration::ZERO,
+            max_packet_data_bytes: default::max_packet_data_bytes(),
+            collect_timings: false,
+            persist_schedule: false,
+            proof_query_retries: 0,
         }
     }
 }
 
+/// The types of packet events that are forwarded to the packet workers.
+/// `NewBlock` events are always forwarded, as they drive packet clearing.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PacketEventFilter {
+    pub send_packet: bool,
+    pub write_acknowledgement: bool,
+    pub timeout_packet: bool,
+    pub close_init_channel: bool,
+}
+
+impl Default for PacketEventFilter {
+    fn default() -> Self {
+        Self {
+            send_packet: true,
+            write_acknowledgement: true,
+            timeout_packet: true,
+            close_init_channel: true,
+        }
+    }
+}
+
+/// The directions of the channels between two chains which are relayed,
+/// ie. for which packet workers are spawned.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelayDirection {
+    Both,
+    /// Only relay the packets sent from `chain_a` to `chain_b`.
+    AToB,
+    /// Only relay the packets sent from `chain_b` to `chain_a`.
+    BToA,
+}
+
+impl Default for RelayDirection {
+    fn default() -> Self {
+        Self::Both
+    }
+}
+
+/// The directions relayed between `chain_a` and `chain_b`, eg. to split
+/// the two directions across separate relayer processes.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChainPairDirection {
+    pub chain_a: ChainId,
+    pub chain_b: ChainId,
+    #[serde(default)]
+    pub relay_direction: RelayDirection,
+}
+
 /// Log levels are wrappers over [`tracing_core::Level`].
 ///
 /// [`tracing_core::Level`]: https://docs.rs/tracing-core/0.1.17/tracing_core/struct.Level.html
@@ -237,18 +504,43 @@ impl fmt::Display for LogLevel {
     }
 }
 
+/// The default directory of the state of the relayer, relative to the home directory.
+const DEFAULT_STATE_FOLDER: &str = ".hermes/state";
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct GlobalConfig {
     pub log_level: LogLevel,
+    /// The file to which the relayed packets are appended, as JSON lines.
+    pub relay_log_path: Option<PathBuf>,
+    /// The chains whose runtimes are spawned first, in this order, when
+    /// spawning the runtimes of all the chains at once.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub spawn_priority: Vec<ChainId>,
+    /// The directory in which the relayer keeps its state across restarts,
+    /// defaults to `~/.hermes/state`.
+    pub state_dir: Option<PathBuf>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+impl GlobalConfig {
+    /// The directory in which the relayer keeps its state, if it is
+    /// configured or the home directory can be located.
+    pub fn state_dir(&self) -> Option<PathBuf> {
+        self.state_dir
+            .clone()
+            .or_else(|| dirs_next::home_dir().map(|home| home.join(DEFAULT_STATE_FOLDER)))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct TelemetryConfig {
     pub enabled: bool,
     pub host: String,
     pub port: u16,
+    /// Identifies this relayer instance in the packet metrics, defaults to the hostname.
+    #[serde(default)]
+    pub relayer_id: Option<String>,
 }
 
 impl Default for TelemetryConfig {
@@ -257,11 +549,12 @@ impl Default for TelemetryConfig {
             enabled: false,
             host: "127.0.0.1".to_string(),
             port: 3001,
+            relayer_id: None,
         }
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RestConfig {
     pub enabled: bool,
@@ -310,6 +603,48 @@ impl fmt::Display for AddressType {
     }
 }
 
+/// Where the event monitor of a chain discovers the IBC events of the chain.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase", tag = "mode", deny_unknown_fields)]
+pub enum EventSource {
+    /// Subscribe to the events pushed by the node over `websocket_addr`.
+    WebSocket,
+    /// Poll the results of the new blocks from the node over `rpc_addr`,
+    /// every `interval`.
+    Poll {
+        #[serde(with = "humantime_serde")]
+        interval: Duration,
+    },
+}
+
+impl Default for EventSource {
+    fn default() -> Self {
+        EventSource::WebSocket
+    }
+}
+
+/// How the transactions submitted to a chain are broadcast to its node.
+///
+/// Whichever the mode, the relayer then waits for the transactions to be
+/// committed before confirming them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BroadcastMode {
+    /// Return once the transaction passed `CheckTx`.
+    Sync,
+    /// Return as soon as the transaction is received by the node,
+    /// without waiting for `CheckTx`.
+    Async,
+    /// Return once the transaction is committed in a block.
+    Commit,
+}
+
+impl Default for BroadcastMode {
+    fn default() -> Self {
+        BroadcastMode::Sync
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ChainConfig {
@@ -323,6 +658,10 @@ pub struct ChainConfig {
     pub rpc_timeout: Duration,
     pub account_prefix: String,
     pub key_name: String,
+    /// Additional keys which sign the transactions submitted by the packet
+    /// workers in turn with `key_name`, each with its own account sequence.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_key_names: Vec<String>,
     #[serde(default)]
     pub key_store_type: Store,
     pub store_prefix: String,
@@ -354,6 +693,13 @@ pub struct ChainConfig {
 
     #[serde(default)]
     pub memo_prefix: Memo,
+    #[serde(default)]
+    pub broadcast_mode: BroadcastMode,
+    /// The minimum interval between two resyncs of the cached account sequence
+    /// of a key after a mismatch, the mismatches happening in between reusing
+    /// the last resynced sequence. Zero to resync on every mismatch.
+    #[serde(default = "default::account_resync_interval", with = "humantime_serde")]
+    pub account_resync_interval: Duration,
     #[serde(default, with = "self::proof_specs")]
     pub proof_specs: ProofSpecs,
 
@@ -367,6 +713,33 @@ pub struct ChainConfig {
     pub packet_filter: PacketFilter,
     #[serde(default)]
     pub address_type: AddressType,
+    #[serde(default)]
+    pub event_source: EventSource,
+    /// Limits the rate of the transactions submitted to this chain by all the workers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_rate_limit: Option<TxRateLimit>,
+    /// Maps base denominations to the denominations and exponents
+    /// used when displaying balances to users.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub display_denom_map: BTreeMap<String, DisplayDenom>,
+}
+
+/// A token bucket limiting the rate of the transactions submitted to a chain,
+/// allowing bursts of up to `capacity` transactions.
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TxRateLimit {
+    pub capacity: u64,
+    pub refill_per_second: f64,
+}
+
+/// How to display amounts of a base denomination, eg. `uatom`
+/// is displayed as `ATOM` with an exponent of 6.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DisplayDenom {
+    pub denom: String,
+    pub exponent: u32,
 }
 
 /// Attempt to load and parse the TOML config file as a `Config`.
@@ -401,7 +774,13 @@ pub(crate) fn store_writer(config: &Config, mut writer: impl Write) -> Result<()
 
 #[cfg(test)]
 mod tests {
-    use super::{load, store_writer};
+    use core::time::Duration;
+
+    use ibc::core::ics24_host::identifier::ChainId;
+
+    use super::{
+        load, store_writer, BroadcastMode, ChainPairDirection, EventSource, Packets, RelayDirection,
+    };
     use test_log::test;
 
     #[test]
@@ -428,4 +807,92 @@ mod tests {
         let mut buffer = Vec::new();
         store_writer(&config, &mut buffer).unwrap();
     }
+
+    #[test]
+    fn parse_partial_packet_event_filter() {
+        let packets: Packets = toml::from_str(
+            r#"
+            enabled = true
+
+            [forward_events]
+            send_packet = false
+            "#,
+        )
+        .expect("could not parse packets config");
+
+        assert!(!packets.forward_events.send_packet);
+        assert!(packets.forward_events.write_acknowledgement);
+        assert!(packets.forward_events.timeout_packet);
+        assert!(packets.forward_events.close_init_channel);
+    }
+
+    #[test]
+    fn parse_event_source() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let config = load(path).expect("could not parse config");
+
+        assert_eq!(config.chains[0].event_source, EventSource::WebSocket);
+        assert_eq!(
+            config.chains[1].event_source,
+            EventSource::Poll {
+                interval: Duration::from_secs(2)
+            }
+        );
+    }
+
+    #[test]
+    fn parse_broadcast_mode() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let config = load(path).expect("could not parse config");
+
+        assert_eq!(config.chains[0].broadcast_mode, BroadcastMode::Sync);
+        assert_eq!(config.chains[1].broadcast_mode, BroadcastMode::Commit);
+    }
+
+    #[test]
+    fn relay_directions_exclude_the_other_direction() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let mut config = load(path).expect("could not parse config");
+        let (a, b) = (config.chains[0].id.clone(), config.chains[1].id.clone());
+
+        assert!(config.relays_direction(&a, &b) && config.relays_direction(&b, &a));
+
+        config.relay_directions = vec![ChainPairDirection {
+            chain_a: a.clone(),
+            chain_b: b.clone(),
+            relay_direction: RelayDirection::BToA,
+        }];
+
+        assert!(!config.relays_direction(&a, &b));
+        assert!(config.relays_direction(&b, &a));
+    }
+
+    #[test]
+    fn prioritized_chains_are_spawned_first() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        let mut config = load(path).expect("could not parse config");
+        let (a, b) = (config.chains[0].id.clone(), config.chains[1].id.clone());
+
+        assert_eq!(config.chains_in_spawn_order(), (vec![], vec![&a, &b]));
+
+        config.global.spawn_priority = vec![b.clone(), ChainId::from_string("unknown"), b.clone()];
+
+        assert_eq!(config.chains_in_spawn_order(), (vec![&b], vec![&a]));
+    }
 }
@@ -481,6 +481,22 @@ define_error! {
             { address: String }
             |e| { format!("Query/Account RPC returned an empty account for address: {}", e.address) },
 
+        EmptyDenomTrace
+            { hash: String }
+            |e| { format!("Query/DenomTrace RPC returned an empty denom trace for hash: {}", e.hash) },
+
+        EmptySupply
+            { denom: String }
+            |e| { format!("Query/SupplyOf RPC returned an empty supply for denom: {}", e.denom) },
+
+        MinGasPriceUnsupported
+            { chain_id: ChainId }
+            |e| { format!("querying the minimum gas price is unsupported by node of chain {}", e.chain_id) },
+
+        InvalidMinGasPrice
+            { price: String }
+            |e| { format!("node returned an invalid minimum gas price: {}", e.price) },
+
         NoHistoricalEntries
             { chain_id: ChainId }
             |e| {
@@ -500,6 +516,12 @@ define_error! {
                     e.chain_id
                 )
             },
+
+        UnsupportedSignerMessage
+            { type_url: String }
+            |e| {
+                format!("cannot change the signer of a message of type: {}", e.type_url)
+            },
     }
 }
 
@@ -1,3 +1,5 @@
 pub mod bus;
+pub mod dump;
 pub mod monitor;
+pub mod poll_monitor;
 pub mod rpc;
@@ -0,0 +1,167 @@
+//! Storage of the event batches received by an event monitor, so that
+//! they can later be replayed to a packet worker, eg. to reproduce a bug.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use flex_error::{define_error, TraceError};
+use serde::{Deserialize, Serialize};
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc::events::IbcEvent;
+use ibc::Height;
+
+use crate::chain::tracking::TrackingId;
+use crate::event::monitor::EventBatch;
+
+/// The version of the format of the batch dumps written by this relayer,
+/// to be bumped on any change of the format of [`BatchDump`].
+pub const BATCH_DUMP_VERSION: u32 = 1;
+
+define_error! {
+    BatchDumpError {
+        Io
+            { path: PathBuf }
+            [ TraceError<std::io::Error> ]
+            | e | { format_args!("I/O error on batch dump '{}'", e.path.display()) },
+
+        Json
+            { path: PathBuf }
+            [ TraceError<serde_json::Error> ]
+            | e | { format_args!("invalid batch dump '{}'", e.path.display()) },
+
+        UnsupportedVersion
+            { version: u32 }
+            | e | {
+                format_args!("unsupported batch dump version {}, expected version {}",
+                    e.version, BATCH_DUMP_VERSION)
+            },
+    }
+}
+
+/// An [`EventBatch`] as stored on disk, as JSON.
+///
+/// The tracking id of the batch is not stored, the replayed
+/// batch being given a new one.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BatchDump {
+    pub version: u32,
+    pub chain_id: ChainId,
+    pub height: Height,
+    pub events: Vec<IbcEvent>,
+}
+
+impl BatchDump {
+    pub fn new(batch: &EventBatch) -> Self {
+        Self {
+            version: BATCH_DUMP_VERSION,
+            chain_id: batch.chain_id.clone(),
+            height: batch.height,
+            events: batch.events.clone(),
+        }
+    }
+
+    /// The name of the file the batch is written to, unique per chain and height.
+    pub fn file_name(&self) -> String {
+        format!(
+            "{}-{}-{}.json",
+            self.chain_id, self.height.revision_number, self.height.revision_height
+        )
+    }
+
+    /// Writes the batch to a file in the given directory, and returns its path.
+    pub fn write_to_dir(&self, dir: &Path) -> Result<PathBuf, BatchDumpError> {
+        let path = dir.join(self.file_name());
+
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| BatchDumpError::json(path.clone(), e))?;
+
+        fs::write(&path, json).map_err(|e| BatchDumpError::io(path.clone(), e))?;
+
+        Ok(path)
+    }
+
+    /// Reads a batch from the given file, checking the version of its format.
+    pub fn read(path: &Path) -> Result<Self, BatchDumpError> {
+        let json = fs::read_to_string(path).map_err(|e| BatchDumpError::io(path.into(), e))?;
+
+        Self::from_json(path, &json)
+    }
+
+    fn from_json(path: &Path, json: &str) -> Result<Self, BatchDumpError> {
+        #[derive(Deserialize)]
+        struct Versioned {
+            version: u32,
+        }
+
+        // Check the version first, as a dump in another format may not deserialize.
+        let Versioned { version } =
+            serde_json::from_str(json).map_err(|e| BatchDumpError::json(path.into(), e))?;
+
+        if version != BATCH_DUMP_VERSION {
+            return Err(BatchDumpError::unsupported_version(version));
+        }
+
+        serde_json::from_str(json).map_err(|e| BatchDumpError::json(path.into(), e))
+    }
+
+    /// Turns the dump back into an event batch, with a new tracking id.
+    pub fn into_batch(self) -> EventBatch {
+        EventBatch {
+            chain_id: self.chain_id,
+            tracking_id: TrackingId::new_uuid(),
+            height: self.height,
+            events: self.events,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use ibc::core::ics02_client::events::{Attributes, CreateClient};
+    use ibc::core::ics24_host::identifier::ChainId;
+    use ibc::events::IbcEvent;
+    use ibc::Height;
+
+    use super::{BatchDump, BatchDumpErrorDetail, BATCH_DUMP_VERSION};
+
+    fn dump() -> BatchDump {
+        BatchDump {
+            version: BATCH_DUMP_VERSION,
+            chain_id: ChainId::new("ibc".to_string(), 0),
+            height: Height::new(0, 42),
+            events: vec![IbcEvent::CreateClient(CreateClient::from(Attributes {
+                height: Height::new(0, 42),
+                ..Default::default()
+            }))],
+        }
+    }
+
+    #[test]
+    fn dump_round_trips_through_json() {
+        let json = serde_json::to_string(&dump()).unwrap();
+
+        assert_eq!(
+            BatchDump::from_json(Path::new("batch.json"), &json).unwrap(),
+            dump()
+        );
+    }
+
+    #[test]
+    fn other_versions_are_rejected() {
+        let json = serde_json::to_string(&BatchDump {
+            version: BATCH_DUMP_VERSION + 1,
+            ..dump()
+        })
+        .unwrap();
+
+        let e = BatchDump::from_json(Path::new("batch.json"), &json).unwrap_err();
+
+        assert!(matches!(
+            e.detail(),
+            BatchDumpErrorDetail::UnsupportedVersion(_)
+        ));
+    }
+}
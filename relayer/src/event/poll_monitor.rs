@@ -0,0 +1,208 @@
+//! An event monitor discovering the IBC events of a chain by polling the
+//! results of its blocks over RPC, for nodes whose WebSocket subscriptions
+//! are unreliable.
+
+use alloc::sync::Arc;
+use core::time::Duration;
+use std::thread;
+
+use crossbeam_channel as channel;
+use tokio::runtime::Runtime as TokioRuntime;
+use tracing::{debug, trace, warn};
+
+use tendermint::abci::Event as AbciEvent;
+use tendermint::block::Height as TmHeight;
+use tendermint_rpc::{Client, HttpClient};
+
+use ibc::core::ics02_client::events::NewBlock;
+use ibc::core::ics02_client::height::Height;
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc::events::{from_tx_response_event, IbcEvent};
+
+use crate::chain::tracking::TrackingId;
+use crate::event::monitor::{Error, EventBatch, EventReceiver, MonitorCmd, Result, TxMonitorCmd};
+
+/// The maximum number of blocks whose events are fetched in a single poll,
+/// so that a monitor lagging far behind still checks for shutdown commands.
+const MAX_BLOCKS_PER_POLL: u64 = 100;
+
+/// Polls a Tendermint node for new blocks, and sends the IBC events of each
+/// block to the event handler as a batch, as the [`EventMonitor`] does with
+/// the events pushed over its WebSocket subscriptions.
+///
+/// As with the subscriptions, each batch starts with a `NewBlock` event,
+/// followed by the IBC events of the block, and only the events of the
+/// transactions which succeeded are collected.
+///
+/// [`EventMonitor`]: crate::event::monitor::EventMonitor
+pub struct PollMonitor {
+    chain_id: ChainId,
+    /// RPC client to poll the blocks from
+    client: HttpClient,
+    /// How long to wait between two polls
+    interval: Duration,
+    /// Channel to handler where the monitor for this chain sends the events
+    tx_batch: channel::Sender<Result<EventBatch>>,
+    /// Channel where to receive commands
+    rx_cmd: channel::Receiver<MonitorCmd>,
+    /// The height of the next block to collect the events of, if known
+    next_height: Option<TmHeight>,
+    /// Tokio runtime
+    rt: Arc<TokioRuntime>,
+}
+
+impl PollMonitor {
+    /// Create a poll monitor, starting from the latest block of the chain.
+    pub fn new(
+        chain_id: ChainId,
+        client: HttpClient,
+        interval: Duration,
+        rt: Arc<TokioRuntime>,
+    ) -> (Self, EventReceiver, TxMonitorCmd) {
+        let (tx_batch, rx_batch) = channel::unbounded();
+        let (tx_cmd, rx_cmd) = channel::unbounded();
+
+        let monitor = Self {
+            chain_id,
+            client,
+            interval,
+            tx_batch,
+            rx_cmd,
+            next_height: None,
+            rt,
+        };
+
+        (monitor, rx_batch, tx_cmd)
+    }
+
+    /// Poll monitor loop
+    pub fn run(mut self) {
+        debug!(chain = %self.chain_id, interval = ?self.interval, "starting poll monitor");
+
+        loop {
+            if let Ok(MonitorCmd::Shutdown) = self.rx_cmd.try_recv() {
+                break;
+            }
+
+            // The blocks are polled again from the same height, so no event is missed.
+            if let Err(e) = self.poll() {
+                warn!(
+                    "[{}] failed to poll blocks, will retry: {}",
+                    self.chain_id, e
+                );
+            }
+
+            thread::sleep(self.interval);
+        }
+
+        debug!("[{}] poll monitor is shutting down", self.chain_id);
+    }
+
+    /// Collect and send the events of the blocks committed since the last poll.
+    fn poll(&mut self) -> Result<()> {
+        let latest_height = self
+            .rt
+            .block_on(self.client.status())
+            .map_err(Error::rpc)?
+            .sync_info
+            .latest_block_height;
+
+        let next_height = *self.next_height.get_or_insert(latest_height);
+        let last_height = latest_height
+            .value()
+            .min(next_height.value() + MAX_BLOCKS_PER_POLL - 1);
+
+        for height in next_height.value()..=last_height {
+            // SAFETY: below the height returned by the node.
+            let height = TmHeight::try_from(height).expect("invalid block height");
+
+            let batch = self.collect_batch(height)?;
+
+            trace!(
+                "[{}] collected {} event(s) at height {}",
+                self.chain_id,
+                batch.events.len(),
+                batch.height
+            );
+
+            self.tx_batch
+                .send(Ok(batch))
+                .map_err(|_| Error::channel_send_failed())?;
+
+            self.next_height = Some(height.increment());
+        }
+
+        Ok(())
+    }
+
+    /// Collect the IBC events of the block at the given height.
+    fn collect_batch(&self, height: TmHeight) -> Result<EventBatch> {
+        let response = self
+            .rt
+            .block_on(self.client.block_results(height))
+            .map_err(Error::rpc)?;
+
+        let height = Height::new(self.chain_id.version(), height.value());
+
+        let tx_events = response
+            .txs_results
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|tx_result| tx_result.code.is_ok())
+            .flat_map(|tx_result| tx_result.events);
+
+        let block_events = response
+            .begin_block_events
+            .unwrap_or_default()
+            .into_iter()
+            .chain(tx_events)
+            .chain(response.end_block_events.unwrap_or_default());
+
+        let events = collect_events(height, block_events);
+
+        Ok(EventBatch {
+            chain_id: self.chain_id.clone(),
+            tracking_id: TrackingId::new_uuid(),
+            height,
+            events,
+        })
+    }
+}
+
+/// The `NewBlock` event of the block at the given height, followed by
+/// the IBC events among the given events of the block.
+fn collect_events(height: Height, events: impl IntoIterator<Item = AbciEvent>) -> Vec<IbcEvent> {
+    let ibc_events = events
+        .into_iter()
+        .filter_map(|event| from_tx_response_event(height, &event));
+
+    core::iter::once(NewBlock::new(height).into())
+        .chain(ibc_events)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use tendermint::abci::Event as AbciEvent;
+
+    use ibc::core::ics02_client::height::Height;
+    use ibc::events::IbcEvent;
+
+    use super::collect_events;
+
+    #[test]
+    fn batch_starts_with_the_new_block_event() {
+        let height = Height::new(0, 42);
+
+        let events = collect_events(
+            height,
+            vec![AbciEvent {
+                type_str: "transfer".to_string(),
+                attributes: vec![],
+            }],
+        );
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], IbcEvent::NewBlock(block) if block.height == height));
+    }
+}
@@ -10,6 +10,7 @@ use std::time::Instant;
 
 use ibc_proto::google::protobuf::Any;
 use itertools::Itertools;
+use serde::Serialize;
 use tracing::{debug, error, info, span, trace, warn, Level};
 
 use flex_error::define_error;
@@ -317,6 +318,19 @@ pub enum ConsensusStateTrusted {
     },
 }
 
+/// The consensus states stored for a client, as counted by
+/// [`ForeignClient::consensus_states_summary`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ConsensusStatesSummary {
+    /// The number of consensus states stored for the client.
+    pub stored: usize,
+    /// The number of stored consensus states which are outside of the trusting
+    /// period of the client, and which the host chain may thus prune.
+    pub expired: usize,
+    /// The height of the oldest stored consensus state, if any.
+    pub oldest_height: Option<Height>,
+}
+
 #[derive(Clone, Debug)]
 pub struct ForeignClient<DstChain: ChainHandle, SrcChain: ChainHandle> {
     /// The identifier of this client. The host chain determines this id upon client creation,
@@ -1238,6 +1252,52 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         Ok(consensus_state_heights)
     }
 
+    /// Counts the consensus states stored for this client on the destination chain,
+    /// and the ones among them which are outside of the trusting period of the client,
+    /// according to the current time of the source chain.
+    pub fn consensus_states_summary(&self) -> Result<ConsensusStatesSummary, ForeignClientError> {
+        let (client_state, _) = self
+            .dst_chain
+            .query_client_state(
+                QueryClientStateRequest {
+                    client_id: self.id.clone(),
+                    height: Height::zero(),
+                },
+                IncludeProof::No,
+            )
+            .map_err(|e| {
+                ForeignClientError::client_query(self.id().clone(), self.src_chain.id(), e)
+            })?;
+
+        let current_src_network_time = self
+            .src_chain
+            .query_application_status()
+            .map_err(|e| {
+                ForeignClientError::client_query(self.id().clone(), self.src_chain.id(), e)
+            })?
+            .timestamp;
+
+        let consensus_states = self.consensus_states()?;
+
+        let expired = consensus_states
+            .iter()
+            .filter(|cs| {
+                let elapsed = current_src_network_time
+                    .duration_since(&cs.consensus_state.timestamp())
+                    .unwrap_or_default();
+
+                client_state.expired(elapsed)
+            })
+            .count();
+
+        Ok(ConsensusStatesSummary {
+            stored: consensus_states.len(),
+            expired,
+            // The consensus states are sorted in descending height order.
+            oldest_height: consensus_states.last().map(|cs| cs.height),
+        })
+    }
+
     /// Checks for evidence of misbehaviour.
     /// The check starts with and `update_event` emitted by chain B (`dst_chain`) for a client update
     /// with a header from chain A (`src_chain`). The algorithm goes backwards through the headers
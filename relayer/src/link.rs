@@ -13,23 +13,30 @@ use crate::chain::{handle::ChainHandle, requests::IncludeProof};
 use crate::channel::{Channel, ChannelSide};
 use crate::link::error::LinkError;
 
+pub mod ack_coalescer;
+pub mod ack_verifier;
 pub mod cli;
+pub mod direction_priority;
 pub mod error;
 pub mod operational_data;
+pub mod schedule_snapshot;
 
+mod key_rotation;
 mod packet_events;
 mod pending;
 mod relay_path;
 mod relay_sender;
 mod relay_summary;
+mod tx_aggregator;
 mod tx_hashes;
 
 use tx_hashes::TxHashes;
 
 // Re-export the telemetries summary
-pub use relay_summary::RelaySummary;
+pub use relay_summary::{RelaySummary, RelayTimings};
 
-pub use relay_path::{RelayPath, Resubmit};
+pub use relay_path::{HeightWindow, RelayPath, Resubmit};
+pub use tx_aggregator::TxAggregator;
 
 #[derive(Clone, Debug)]
 pub struct LinkParameters {
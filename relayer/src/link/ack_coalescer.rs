@@ -0,0 +1,277 @@
+use core::time::Duration;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use ibc::events::IbcEvent;
+
+use crate::link::operational_data::OperationalData;
+
+/// When the acknowledgements held back by an [`AckCoalescer`] are flushed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AckFlushPolicy {
+    /// Flush once this many acknowledgements are held, if non-zero.
+    pub max_acks: usize,
+    /// Flush once the oldest held acknowledgement was held for this long, if non-zero.
+    pub max_delay: Duration,
+}
+
+impl AckFlushPolicy {
+    /// Whether acknowledgements are held at all, rather than flushed immediately.
+    pub fn is_enabled(&self) -> bool {
+        self.max_acks > 1 || !self.max_delay.is_zero()
+    }
+
+    /// Whether the given number of held acknowledgements, the oldest of which
+    /// was held for `oldest_held_for`, should be flushed.
+    pub fn should_flush(&self, held_acks: usize, oldest_held_for: Duration) -> bool {
+        if held_acks == 0 {
+            return false;
+        }
+
+        let count_reached = self.max_acks != 0 && held_acks >= self.max_acks;
+        let delay_elapsed = !self.max_delay.is_zero() && oldest_held_for >= self.max_delay;
+
+        count_reached || delay_elapsed
+    }
+}
+
+/// Holds back the operational data which only relays acknowledgements, over
+/// several schedule executions, until the [`AckFlushPolicy`] says they should
+/// be flushed, so that they can be relayed together in fewer transactions.
+pub struct AckCoalescer {
+    policy: AckFlushPolicy,
+    held: Mutex<Vec<(Instant, OperationalData)>>,
+}
+
+impl AckCoalescer {
+    pub fn new(policy: AckFlushPolicy) -> Self {
+        Self {
+            policy,
+            held: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Holds the given operational data, held since `now`.
+    pub fn hold(&self, od: OperationalData, now: Instant) {
+        self.held.lock().unwrap().push((now, od));
+    }
+
+    /// The number of acknowledgements held so far.
+    pub fn held_acks(&self) -> usize {
+        self.held
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, od)| od.batch.len())
+            .sum()
+    }
+
+    /// The held operational data, in the order it was held, still held.
+    pub fn held(&self) -> Vec<OperationalData> {
+        self.held
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, od)| od.clone())
+            .collect()
+    }
+
+    /// Returns the held operational data, in the order it was held and along with
+    /// when it was held, if it should be flushed at `now`, and no longer holds it.
+    pub fn take_if_due(&self, now: Instant) -> Option<Vec<(Instant, OperationalData)>> {
+        let mut held = self.held.lock().unwrap();
+
+        let held_acks = held.iter().map(|(_, od)| od.batch.len()).sum();
+        let oldest_held_for = held.first().map_or(Duration::ZERO, |(since, _)| {
+            now.saturating_duration_since(*since)
+        });
+
+        if !self.policy.should_flush(held_acks, oldest_held_for) {
+            return None;
+        }
+
+        Some(held.drain(..).collect())
+    }
+
+    /// Holds again the operational data returned by [`AckCoalescer::take_if_due`]
+    /// which could not be flushed, ahead of the data held since, so that it keeps
+    /// its age.
+    pub fn restore(&self, taken: Vec<(Instant, OperationalData)>) {
+        let mut held = self.held.lock().unwrap();
+
+        let since = core::mem::replace(&mut *held, taken);
+        held.extend(since);
+    }
+}
+
+/// Whether the given operational data only relays acknowledgements.
+pub fn is_ack_only(od: &OperationalData) -> bool {
+    !od.batch.is_empty()
+        && od
+            .batch
+            .iter()
+            .all(|msg| matches!(msg.event, IbcEvent::WriteAcknowledgement(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use ibc::core::ics04_channel::events::{SendPacket, WriteAcknowledgement};
+    use ibc::core::ics04_channel::packet::{Packet, Sequence};
+    use ibc::events::IbcEvent;
+    use ibc::Height;
+    use ibc_proto::google::protobuf::Any;
+
+    use crate::chain::tracking::TrackingId;
+    use crate::link::operational_data::{OperationalData, OperationalDataTarget, TransitMessage};
+
+    use super::{is_ack_only, AckCoalescer, AckFlushPolicy};
+
+    fn packet(sequence: u64) -> Packet {
+        Packet {
+            sequence: Sequence::from(sequence),
+            ..Default::default()
+        }
+    }
+
+    fn od(events: Vec<IbcEvent>) -> OperationalData {
+        let mut od = OperationalData::new(
+            Height::new(0, 10),
+            OperationalDataTarget::Destination,
+            TrackingId::new_static("test"),
+            Duration::ZERO,
+        );
+
+        for event in events {
+            od.push(TransitMessage {
+                event,
+                msg: Any::default(),
+            });
+        }
+
+        od
+    }
+
+    fn write_ack(sequence: u64) -> IbcEvent {
+        IbcEvent::WriteAcknowledgement(WriteAcknowledgement {
+            height: Height::new(0, 10),
+            packet: packet(sequence),
+            ack: vec![1],
+        })
+    }
+
+    fn acks(sequences: &[u64]) -> OperationalData {
+        od(sequences
+            .iter()
+            .map(|sequence| write_ack(*sequence))
+            .collect())
+    }
+
+    const POLICY: AckFlushPolicy = AckFlushPolicy {
+        max_acks: 3,
+        max_delay: Duration::from_secs(10),
+    };
+
+    #[test]
+    fn flushes_once_the_count_is_reached() {
+        assert!(!POLICY.should_flush(0, Duration::ZERO));
+        assert!(!POLICY.should_flush(2, Duration::ZERO));
+        assert!(POLICY.should_flush(3, Duration::ZERO));
+        assert!(POLICY.should_flush(4, Duration::ZERO));
+    }
+
+    #[test]
+    fn flushes_once_the_delay_elapsed() {
+        assert!(!POLICY.should_flush(1, Duration::from_secs(9)));
+        assert!(POLICY.should_flush(1, Duration::from_secs(10)));
+
+        // Nothing to flush, however long ago.
+        assert!(!POLICY.should_flush(0, Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn immediate_flush_disables_coalescing() {
+        let immediate = AckFlushPolicy {
+            max_acks: 0,
+            max_delay: Duration::ZERO,
+        };
+        assert!(!immediate.is_enabled());
+
+        let single = AckFlushPolicy {
+            max_acks: 1,
+            max_delay: Duration::ZERO,
+        };
+        assert!(!single.is_enabled());
+
+        assert!(POLICY.is_enabled());
+    }
+
+    #[test]
+    fn held_acks_are_taken_at_the_batching_boundary() {
+        let coalescer = AckCoalescer::new(POLICY);
+        let start = Instant::now();
+
+        coalescer.hold(acks(&[1, 2]), start);
+        assert_eq!(coalescer.held_acks(), 2);
+        assert!(coalescer.take_if_due(start).is_none());
+
+        coalescer.hold(acks(&[3]), start);
+        let flushed = coalescer.take_if_due(start).unwrap();
+
+        assert_eq!(flushed.len(), 2);
+        assert_eq!(coalescer.held_acks(), 0);
+        assert!(coalescer.take_if_due(start).is_none());
+    }
+
+    #[test]
+    fn restored_acks_are_held_again_with_their_age() {
+        let coalescer = AckCoalescer::new(POLICY);
+        let start = Instant::now();
+
+        coalescer.hold(acks(&[1]), start);
+        let due = start + Duration::from_secs(10);
+        let taken = coalescer.take_if_due(due).unwrap();
+
+        // The flush failed, while another acknowledgement was held meanwhile.
+        coalescer.hold(acks(&[2]), due);
+        coalescer.restore(taken);
+        assert_eq!(coalescer.held_acks(), 2);
+
+        // The restored acknowledgement is still the oldest one, and thus still due.
+        let flushed = coalescer.take_if_due(due).unwrap();
+        assert_eq!(flushed[0].0, start);
+        assert_eq!(flushed.len(), 2);
+    }
+
+    #[test]
+    fn held_acks_are_taken_once_the_oldest_is_due() {
+        let coalescer = AckCoalescer::new(POLICY);
+        let start = Instant::now();
+
+        coalescer.hold(acks(&[1]), start);
+        coalescer.hold(acks(&[2]), start + Duration::from_secs(5));
+
+        assert!(coalescer
+            .take_if_due(start + Duration::from_secs(9))
+            .is_none());
+        assert!(coalescer
+            .take_if_due(start + Duration::from_secs(10))
+            .is_some());
+    }
+
+    #[test]
+    fn only_ack_operational_data_is_coalesced() {
+        assert!(is_ack_only(&acks(&[1, 2])));
+        assert!(!is_ack_only(&od(vec![])));
+
+        let recv = IbcEvent::SendPacket(SendPacket {
+            height: Height::new(0, 10),
+            packet: packet(3),
+        });
+        let mixed = od(vec![write_ack(1), recv]);
+
+        assert!(!is_ack_only(&mixed));
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use ibc::core::ics04_channel::packet::{Packet, Sequence};
+use ibc::events::IbcEvent;
+
+/// How many relayed packets are remembered, the ones with the lowest sequences being
+/// forgotten first, eg. when their reception was relayed by another relayer.
+const MAX_RELAYED_PACKETS: usize = 10_000;
+
+/// An acknowledgement written by the destination chain for a packet which
+/// differs from the packet relayed to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AckMismatch {
+    /// The packet relayed to the destination chain.
+    pub relayed: Packet,
+    /// The packet as reported by the `WriteAcknowledgement` event.
+    pub acknowledged: Packet,
+}
+
+/// Remembers the packets of the `MsgRecvPacket` messages relayed on a path,
+/// so that the `WriteAcknowledgement` events of the destination chain can be
+/// checked against them.
+///
+/// The acknowledgements of the packets which were not relayed by this path,
+/// or were forgotten, are not checked.
+#[derive(Debug, Default)]
+pub struct AckVerifier {
+    relayed: Mutex<BTreeMap<Sequence, Packet>>,
+}
+
+impl AckVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remembers the given packet, relayed to the destination chain.
+    pub fn record_relayed(&self, packet: &Packet) {
+        let mut relayed = self.relayed.lock().unwrap();
+
+        relayed.insert(packet.sequence, packet.clone());
+
+        while relayed.len() > MAX_RELAYED_PACKETS {
+            let lowest = *relayed.keys().next().unwrap();
+            relayed.remove(&lowest);
+        }
+    }
+
+    /// Checks the packets of the `WriteAcknowledgement` events among the given
+    /// events against the relayed packets with the same sequence, which are
+    /// forgotten afterwards, and returns the mismatches.
+    pub fn verify(&self, events: &[IbcEvent]) -> Vec<AckMismatch> {
+        let mut relayed = self.relayed.lock().unwrap();
+
+        events
+            .iter()
+            .filter_map(|event| match event {
+                IbcEvent::WriteAcknowledgement(write_ack) => Some(&write_ack.packet),
+                _ => None,
+            })
+            .filter_map(|acknowledged| {
+                let relayed = relayed.remove(&acknowledged.sequence)?;
+
+                (relayed != *acknowledged).then(|| AckMismatch {
+                    relayed,
+                    acknowledged: acknowledged.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc::core::ics04_channel::events::WriteAcknowledgement;
+    use ibc::core::ics04_channel::packet::{Packet, Sequence};
+    use ibc::events::IbcEvent;
+    use ibc::Height;
+
+    use super::AckVerifier;
+
+    fn packet(sequence: u64, data: &[u8]) -> Packet {
+        Packet {
+            sequence: Sequence::from(sequence),
+            data: data.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    fn write_ack(packet: Packet) -> IbcEvent {
+        IbcEvent::WriteAcknowledgement(WriteAcknowledgement {
+            height: Height::new(0, 10),
+            packet,
+            ack: vec![1],
+        })
+    }
+
+    #[test]
+    fn matching_acks_are_verified() {
+        let verifier = AckVerifier::new();
+        verifier.record_relayed(&packet(1, b"transfer"));
+
+        assert!(verifier
+            .verify(&[write_ack(packet(1, b"transfer"))])
+            .is_empty());
+    }
+
+    #[test]
+    fn forged_ack_is_flagged() {
+        let verifier = AckVerifier::new();
+        verifier.record_relayed(&packet(1, b"transfer"));
+        verifier.record_relayed(&packet(2, b"transfer"));
+
+        let mismatches = verifier.verify(&[
+            write_ack(packet(1, b"transfer")),
+            write_ack(packet(2, b"forged")),
+        ]);
+
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].relayed, packet(2, b"transfer"));
+        assert_eq!(mismatches[0].acknowledged, packet(2, b"forged"));
+    }
+
+    #[test]
+    fn acks_of_packets_not_relayed_are_ignored() {
+        let verifier = AckVerifier::new();
+        verifier.record_relayed(&packet(1, b"transfer"));
+
+        assert!(verifier
+            .verify(&[write_ack(packet(2, b"other"))])
+            .is_empty());
+
+        // Verified packets are forgotten.
+        assert!(verifier
+            .verify(&[write_ack(packet(1, b"transfer"))])
+            .is_empty());
+        assert!(verifier
+            .verify(&[write_ack(packet(1, b"forged"))])
+            .is_empty());
+    }
+}
@@ -16,7 +16,7 @@ use crate::link::operational_data::{OperationalData, TrackedEvents};
 use crate::link::packet_events::{
     query_packet_events_with, query_send_packet_events, query_write_ack_events,
 };
-use crate::link::relay_path::RelayPath;
+use crate::link::relay_path::{HeightWindow, RelayPath};
 use crate::link::relay_sender::SyncSender;
 use crate::link::Link;
 use crate::path::PathIdentifiers;
@@ -47,6 +47,65 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         }
     }
 
+    /// Schedules the relaying of all the pending packets on this path, then
+    /// takes and returns all the scheduled operational data, without
+    /// submitting any of it.
+    pub fn take_pending_operational_data(&self) -> Result<Vec<OperationalData>, LinkError> {
+        self.schedule_packet_clearing(None)?;
+
+        let mut odata = Vec::from(self.src_operational_data.take());
+        odata.extend(self.dst_operational_data.take());
+
+        Ok(odata)
+    }
+
+    /// Counts the packets sent on the source chain which the destination chain did
+    /// not receive yet, and the acknowledgements written on the source chain which
+    /// were not relayed yet to the destination chain.
+    ///
+    /// Only the sequences of the pending packets are queried, neither their
+    /// events nor their proofs.
+    pub fn count_pending_packets(&self) -> Result<(u64, u64), LinkError> {
+        let (packets, _) = unreceived_packets(self.dst_chain(), self.src_chain(), &self.path_id)
+            .map_err(LinkError::supervisor)?;
+
+        let (acks, _) =
+            unreceived_acknowledgements(self.dst_chain(), self.src_chain(), &self.path_id)
+                .map_err(LinkError::supervisor)?;
+
+        Ok((packets.len() as u64, acks.len() as u64))
+    }
+
+    /// Returns the sequences of the packets sent on the destination chain whose
+    /// acknowledgement was written on the source chain, but not yet relayed back
+    /// to the destination chain. Each sequence comes with the source chain height
+    /// at which its acknowledgement is expected to be proven.
+    ///
+    /// This performs the same queries as the scheduling of acknowledgements
+    /// when the schedule is refreshed, but without scheduling anything.
+    pub fn pending_acknowledgements(&self) -> Result<Vec<(Sequence, Height)>, LinkError> {
+        let (sequences, src_response_height) =
+            unreceived_acknowledgements(self.dst_chain(), self.src_chain(), &self.path_id)
+                .map_err(LinkError::supervisor)?;
+
+        let pending = query_packet_events_with(
+            &sequences,
+            src_response_height,
+            self.src_chain(),
+            &self.path_id,
+            query_write_ack_events,
+        )
+        .flatten()
+        .filter_map(|event| {
+            event
+                .packet()
+                .map(|packet| (packet.sequence, event.height()))
+        })
+        .collect();
+
+        Ok(pending)
+    }
+
     /// Given a vector of [`OperationalData`], this method proceeds to relaying
     /// all the messages therein. It accumulates all events generated in the
     /// mutable vector of [`IbcEvent`]s.
@@ -67,6 +126,15 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
     /// Implements the `packet-recv` CLI
     pub fn relay_recv_packet_and_timeout_messages(&self) -> Result<Vec<IbcEvent>, LinkError> {
+        self.relay_recv_packet_and_timeout_messages_within(HeightWindow::default())
+    }
+
+    /// Relays the pending packets, like [`Self::relay_recv_packet_and_timeout_messages`],
+    /// but only the ones sent within the given `window` on the source chain.
+    pub fn relay_recv_packet_and_timeout_messages_within(
+        &self,
+        window: HeightWindow,
+    ) -> Result<Vec<IbcEvent>, LinkError> {
         let _span = error_span!(
             "PacketRecvCmd",
             src_chain = %self.a_to_b.src_chain().id(),
@@ -94,12 +162,22 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
             sequences,
             src_response_height,
             query_send_packet_events,
+            window,
             TrackingId::new_static("packet-recv"),
         )
     }
 
     /// Implements the `packet-ack` CLI
     pub fn relay_ack_packet_messages(&self) -> Result<Vec<IbcEvent>, LinkError> {
+        self.relay_ack_packet_messages_within(HeightWindow::default())
+    }
+
+    /// Relays the pending acknowledgements, like [`Self::relay_ack_packet_messages`],
+    /// but only the ones written within the given `window` on the source chain.
+    pub fn relay_ack_packet_messages_within(
+        &self,
+        window: HeightWindow,
+    ) -> Result<Vec<IbcEvent>, LinkError> {
         let _span = error_span!(
             "PacketAckCmd",
             src_chain = %self.a_to_b.src_chain().id(),
@@ -127,6 +205,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
             sequences,
             src_response_height,
             query_write_ack_events,
+            window,
             TrackingId::new_static("packet-ack"),
         )
     }
@@ -141,6 +220,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
             Vec<Sequence>,
             Height,
         ) -> Result<Vec<IbcEvent>, LinkError>,
+        window: HeightWindow,
         tracking_id: TrackingId,
     ) -> Result<Vec<IbcEvent>, LinkError> {
         dbg!(src_response_height);
@@ -152,6 +232,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Link<ChainA, ChainB> {
             &self.a_to_b.path_id,
             query_fn,
         ) {
+            let events_chunk = events_chunk
+                .into_iter()
+                .filter(|event| window.contains(event.height()))
+                .collect();
+
             let tracked_events = TrackedEvents::new(events_chunk, tracking_id);
             self.a_to_b.events_to_operational_data(tracked_events)?;
 
@@ -0,0 +1,97 @@
+use core::time::Duration;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use once_cell::sync::Lazy;
+
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+/// How long a relay path may defer the execution of its schedule in a row, so that the
+/// costlier direction still makes progress while the cheaper one is always busy.
+const MAX_DEFERRAL: Duration = Duration::from_secs(30);
+
+/// The source channels of the relay paths with scheduled operational data,
+/// per direction, ie. per pair of source and destination chains.
+static PENDING_PATHS: Lazy<Mutex<HashMap<(ChainId, ChainId), HashSet<(PortId, ChannelId)>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Prioritizes relaying in the cheaper of the two directions between a pair of chains,
+/// by letting the relay paths towards the costlier destination defer the execution of
+/// their schedule while the relay paths in the reverse direction have operational data
+/// scheduled.
+///
+/// The cost of a direction is the gas price of its destination chain, as configured.
+pub struct DirectionPriority {
+    src_chain: ChainId,
+    dst_chain: ChainId,
+    src_channel: (PortId, ChannelId),
+    costlier: bool,
+    deferred_since: Option<Instant>,
+}
+
+impl DirectionPriority {
+    pub fn new(
+        src_chain: ChainId,
+        dst_chain: ChainId,
+        src_port_id: PortId,
+        src_channel_id: ChannelId,
+        src_gas_price: f64,
+        dst_gas_price: f64,
+    ) -> Self {
+        Self {
+            src_chain,
+            dst_chain,
+            src_channel: (src_port_id, src_channel_id),
+            costlier: dst_gas_price > src_gas_price,
+            deferred_since: None,
+        }
+    }
+
+    /// Records whether the relay path has operational data scheduled, and returns whether
+    /// it should defer the execution of its schedule, which is the case if it relays towards
+    /// the costlier destination and operational data is scheduled in the reverse direction.
+    pub fn should_defer(&mut self, has_pending: bool) -> bool {
+        let mut pending_paths = PENDING_PATHS.lock().unwrap();
+
+        let paths = pending_paths
+            .entry((self.src_chain.clone(), self.dst_chain.clone()))
+            .or_default();
+
+        if has_pending {
+            paths.insert(self.src_channel.clone());
+        } else {
+            paths.remove(&self.src_channel);
+        }
+
+        let reverse_pending = pending_paths
+            .get(&(self.dst_chain.clone(), self.src_chain.clone()))
+            .map_or(false, |paths| !paths.is_empty());
+
+        if !self.costlier || !has_pending || !reverse_pending {
+            self.deferred_since = None;
+            return false;
+        }
+
+        let deferred_since = *self.deferred_since.get_or_insert_with(Instant::now);
+
+        if deferred_since.elapsed() < MAX_DEFERRAL {
+            true
+        } else {
+            self.deferred_since = None;
+            false
+        }
+    }
+}
+
+impl Drop for DirectionPriority {
+    fn drop(&mut self) {
+        let mut pending_paths = PENDING_PATHS.lock().unwrap();
+
+        if let Some(paths) =
+            pending_paths.get_mut(&(self.src_chain.clone(), self.dst_chain.clone()))
+        {
+            paths.remove(&self.src_channel);
+        }
+    }
+}
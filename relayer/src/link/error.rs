@@ -1,3 +1,5 @@
+use core::time::Duration;
+
 use flex_error::define_error;
 use ibc::core::ics02_client::error::Error as Ics02Error;
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
@@ -8,6 +10,7 @@ use crate::channel::ChannelError;
 use crate::connection::ConnectionError;
 use crate::error::Error;
 use crate::foreign_client::{ForeignClientError, HasExpiredOrFrozenError};
+use crate::link::schedule_snapshot::ScheduleSnapshotError;
 use crate::supervisor::Error as SupervisorError;
 use crate::transfer::TransferError;
 
@@ -145,6 +148,32 @@ define_error! {
 
         UpdateClientFailed
              |_| { "failed to update client" },
+
+        ExecuteScheduleTimeout
+            { timeout: Duration }
+            |e| {
+                format!("schedule execution did not complete within {:?}, \
+                    the remaining operational data will be relayed later",
+                    e.timeout)
+            },
+
+        SubmissionAborted
+            { chain_id: ChainId }
+            |e| {
+                format!("the submission of the messages to chain {} was aborted before completing",
+                    e.chain_id)
+            },
+
+        AggregatedSubmit
+            { reason: String }
+            |e| {
+                format!("failed to submit the aggregated batch of messages: {}",
+                    e.reason)
+            },
+
+        ScheduleSnapshot
+            [ ScheduleSnapshotError ]
+            |_| { "failed to restore the stored schedule" },
    }
 }
 
@@ -0,0 +1,65 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Round-robin selection of the keys which sign the transactions
+/// submitted to a chain, so that consecutive transactions use
+/// different accounts, each with its own account sequence.
+#[derive(Debug, Default)]
+pub struct KeyRotation {
+    /// The keys used in turn with the key from the chain configuration.
+    extra_key_names: Vec<String>,
+    next: AtomicUsize,
+}
+
+impl KeyRotation {
+    pub fn new(extra_key_names: Vec<String>) -> Self {
+        Self {
+            extra_key_names,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the name of the key which signs the next transaction,
+    /// or `None` for the key from the chain configuration.
+    pub fn next_key_name(&self) -> Option<String> {
+        if self.extra_key_names.is_empty() {
+            return None;
+        }
+
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % (self.extra_key_names.len() + 1);
+
+        index
+            .checked_sub(1)
+            .map(|index| self.extra_key_names[index].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeyRotation;
+
+    #[test]
+    fn keys_are_selected_in_turn() {
+        let rotation = KeyRotation::new(vec!["extra-1".to_string(), "extra-2".to_string()]);
+
+        let keys: Vec<_> = (0..5).map(|_| rotation.next_key_name()).collect();
+
+        assert_eq!(
+            keys,
+            vec![
+                None,
+                Some("extra-1".to_string()),
+                Some("extra-2".to_string()),
+                None,
+                Some("extra-1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn configured_key_is_used_without_extra_keys() {
+        let rotation = KeyRotation::default();
+
+        assert_eq!(rotation.next_key_name(), None);
+        assert_eq!(rotation.next_key_name(), None);
+    }
+}
@@ -1,5 +1,6 @@
 use core::fmt;
 use core::iter;
+use std::collections::{HashSet, VecDeque};
 use std::time::{Duration, Instant};
 
 use ibc_proto::google::protobuf::Any;
@@ -16,7 +17,7 @@ use crate::chain::requests::QueryClientStateRequest;
 use crate::chain::tracking::TrackedMsgs;
 use crate::chain::tracking::TrackingId;
 use crate::link::error::LinkError;
-use crate::link::RelayPath;
+use crate::link::{RelayPath, RelayTimings};
 
 /// The chain that the events associated with a piece of [`OperationalData`] are bound for.
 #[derive(Clone, Copy, PartialEq)]
@@ -98,6 +99,11 @@ pub struct OperationalData {
     pub tracking_id: TrackingId,
     /// Stores `Some(ConnectionDelay)` if the delay is non-zero and `None` otherwise
     connection_delay: Option<ConnectionDelay>,
+    /// The time spent building the messages, if collected by the relay path.
+    pub timings: Option<RelayTimings>,
+    /// The number of times the transactions relaying the messages were submitted,
+    /// incremented whenever they are resubmitted after failing to be confirmed.
+    pub attempts: u64,
 }
 
 impl OperationalData {
@@ -119,6 +125,8 @@ impl OperationalData {
             target,
             connection_delay,
             tracking_id,
+            timings: None,
+            attempts: 1,
         }
     }
 
@@ -146,6 +154,12 @@ impl OperationalData {
         }
     }
 
+    /// Returns the heights of the events relayed by the messages in this
+    /// operational data.
+    pub fn source_heights(&self) -> Vec<Height> {
+        self.batch.iter().map(|gm| gm.event.height()).collect()
+    }
+
     /// Returns all the messages in this operational
     /// data, plus prepending the client update message
     /// if necessary.
@@ -153,6 +167,29 @@ impl OperationalData {
         &self,
         relay_path: &RelayPath<ChainA, ChainB>,
     ) -> Result<TrackedMsgs, LinkError> {
+        let (client_update_msg, tm) = self.assemble_msgs_apart(relay_path)?;
+
+        let tm = match client_update_msg {
+            Some(client_update) => TrackedMsgs::new(
+                iter::once(client_update).chain(tm.msgs).collect(),
+                self.tracking_id,
+            ),
+            None => tm,
+        };
+
+        info!("assembled batch of {} message(s)", tm.messages().len());
+
+        Ok(tm)
+    }
+
+    /// Returns the client update message needed by the messages in
+    /// this operational data, if any, apart from the messages, so that
+    /// it can be submitted on its own. No message is returned if the
+    /// client targeted by the messages is frozen.
+    pub fn assemble_msgs_apart<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        relay_path: &RelayPath<ChainA, ChainB>,
+    ) -> Result<(Option<Any>, TrackedMsgs), LinkError> {
         // For zero delay we prepend the client update msgs.
         let client_update_msg = if !self.conn_delay_needed() {
             let update_height = self.proofs_height.increment();
@@ -200,24 +237,15 @@ impl OperationalData {
             };
 
             if client_state.is_frozen() {
-                return Ok(TrackedMsgs::new(vec![], self.tracking_id));
+                return Ok((None, TrackedMsgs::new(vec![], self.tracking_id)));
             } else {
                 None
             }
         };
 
-        let msgs: Vec<Any> = match client_update_msg {
-            Some(client_update) => iter::once(client_update)
-                .chain(self.batch.iter().map(|gm| gm.msg.clone()))
-                .collect(),
-            None => self.batch.iter().map(|gm| gm.msg.clone()).collect(),
-        };
-
-        let tm = TrackedMsgs::new(msgs, self.tracking_id);
-
-        info!("assembled batch of {} message(s)", tm.messages().len());
+        let msgs = self.batch.iter().map(|gm| gm.msg.clone()).collect();
 
-        Ok(tm)
+        Ok((client_update_msg, TrackedMsgs::new(msgs, self.tracking_id)))
     }
 
     /// Returns true iff the batch contains a packet event
@@ -319,6 +347,29 @@ impl OperationalData {
     }
 }
 
+/// Drops the messages of the given operational data which relay the same packet event,
+/// ie. an event of the same type for the same packet sequence, as a previous message,
+/// or for whose event `is_pending` returns `false`.
+/// The operational data left without any message are then dropped.
+pub fn retain_pending_messages(
+    odata: &mut VecDeque<OperationalData>,
+    is_pending: impl Fn(&IbcEvent) -> bool,
+) {
+    let mut relayed = HashSet::new();
+
+    for od in odata.iter_mut() {
+        od.batch.retain(|msg| match msg.event.packet() {
+            Some(packet) => {
+                relayed.insert((msg.event.event_type().as_str(), packet.sequence))
+                    && is_pending(&msg.event)
+            }
+            None => true,
+        });
+    }
+
+    odata.retain(|od| !od.batch.is_empty());
+}
+
 /// A struct that holds everything that is required to calculate and deal with the connection-delay
 /// feature.
 #[derive(Clone)]
@@ -399,3 +450,70 @@ impl fmt::Display for OperationalInfo {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::time::Duration;
+
+    use ibc::core::ics04_channel::events::SendPacket;
+    use ibc::core::ics04_channel::packet::{Packet, Sequence};
+    use ibc::events::IbcEvent;
+    use ibc::Height;
+    use ibc_proto::google::protobuf::Any;
+
+    use super::{retain_pending_messages, OperationalData, OperationalDataTarget, TransitMessage};
+    use crate::chain::tracking::TrackingId;
+
+    fn odata(sequences: &[u64]) -> OperationalData {
+        let mut od = OperationalData::new(
+            Height::new(0, 10),
+            OperationalDataTarget::Destination,
+            TrackingId::new_static("test"),
+            Duration::ZERO,
+        );
+
+        for &sequence in sequences {
+            od.push(TransitMessage {
+                event: IbcEvent::SendPacket(SendPacket {
+                    height: Height::new(0, 10),
+                    packet: Packet {
+                        sequence: Sequence::from(sequence),
+                        ..Packet::default()
+                    },
+                }),
+                msg: Any::default(),
+            });
+        }
+
+        od
+    }
+
+    fn sequences(odata: &VecDeque<OperationalData>) -> Vec<Vec<u64>> {
+        odata
+            .iter()
+            .map(|od| {
+                od.batch
+                    .iter()
+                    .map(|msg| u64::from(msg.event.packet().unwrap().sequence))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn overlapping_batches_are_deduplicated_and_reconciled() {
+        // A catch-up batch from packet clearing, followed by batches from incoming
+        // events referencing some of the same packets.
+        let mut scheduled = VecDeque::from(vec![odata(&[1, 2, 3]), odata(&[2, 3, 4]), odata(&[1])]);
+
+        // Packet 1 was already received on the destination chain.
+        retain_pending_messages(&mut scheduled, |event| {
+            event
+                .packet()
+                .map_or(true, |packet| packet.sequence != Sequence::from(1))
+        });
+
+        assert_eq!(sequences(&scheduled), vec![vec![2, 3], vec![4]]);
+    }
+}
@@ -53,6 +53,52 @@ where
         })
 }
 
+/// Sorts the given packet events by increasing sequence number and drops
+/// every event that follows the first of the expected `sequences` for which
+/// no event was found.
+///
+/// Returns `true` if such a gap was found.
+pub fn retain_contiguous_packet_events(events: &mut Vec<IbcEvent>, sequences: &[Sequence]) -> bool {
+    events.sort_by_key(|ev| ev.packet().map(|p| p.sequence));
+
+    let gap = sequences.iter().find(|seq| {
+        !events
+            .iter()
+            .any(|ev| ev.packet().map(|p| p.sequence) == Some(**seq))
+    });
+
+    match gap {
+        Some(gap) => {
+            events.retain(|ev| ev.packet().map_or(true, |p| p.sequence < *gap));
+            true
+        }
+        None => false,
+    }
+}
+
+/// Keeps at most `max_packets` of the given pending sequences, unless it is zero:
+/// the highest ones, as the most recent packets are the likeliest to still be relayable,
+/// or the lowest ones if the channel is `ordered`, as its packets must be delivered in order.
+///
+/// Returns how many sequences were dropped.
+pub fn cap_sequences(sequences: &mut Vec<Sequence>, max_packets: usize, ordered: bool) -> usize {
+    if max_packets == 0 || sequences.len() <= max_packets {
+        return 0;
+    }
+
+    let skipped = sequences.len() - max_packets;
+
+    sequences.sort_unstable();
+
+    if ordered {
+        sequences.truncate(max_packets);
+    } else {
+        sequences.drain(..skipped);
+    }
+
+    skipped
+}
+
 /// Returns relevant packet events for building RecvPacket and timeout messages
 /// for the given vector of packet [`Sequence`] numbers.
 pub fn query_send_packet_events<ChainA: ChainHandle>(
@@ -133,3 +179,39 @@ pub fn query_write_ack_events<ChainA: ChainHandle>(
 
     Ok(events_result)
 }
+
+#[cfg(test)]
+mod tests {
+    use ibc::core::ics04_channel::packet::Sequence;
+
+    use super::cap_sequences;
+
+    fn sequences(seqs: &[u64]) -> Vec<Sequence> {
+        seqs.iter().copied().map(Sequence::from).collect()
+    }
+
+    #[test]
+    fn cap_keeps_most_recent_sequences_on_unordered_channels() {
+        let mut seqs = sequences(&[4, 1, 5, 2, 3]);
+
+        assert_eq!(cap_sequences(&mut seqs, 2, false), 3);
+        assert_eq!(seqs, sequences(&[4, 5]));
+    }
+
+    #[test]
+    fn cap_keeps_oldest_sequences_on_ordered_channels() {
+        let mut seqs = sequences(&[4, 1, 5, 2, 3]);
+
+        assert_eq!(cap_sequences(&mut seqs, 2, true), 3);
+        assert_eq!(seqs, sequences(&[1, 2]));
+    }
+
+    #[test]
+    fn zero_or_unreached_cap_keeps_all_sequences() {
+        let mut seqs = sequences(&[3, 1, 2]);
+
+        assert_eq!(cap_sequences(&mut seqs, 0, false), 0);
+        assert_eq!(cap_sequences(&mut seqs, 3, false), 0);
+        assert_eq!(seqs, sequences(&[3, 1, 2]));
+    }
+}
@@ -6,7 +6,7 @@ use tracing::{debug, error, trace, trace_span};
 
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc::events::IbcEvent;
-use ibc::query::{QueryTxHash, QueryTxRequest};
+use ibc::query::QueryTxHash;
 
 use crate::chain::tracking::TrackingId;
 use crate::error::Error as RelayerError;
@@ -15,7 +15,10 @@ use crate::telemetry;
 use crate::util::queue::Queue;
 use crate::{
     chain::handle::ChainHandle,
-    link::{operational_data::OperationalData, relay_sender::AsyncReply, RelaySummary, TxHashes},
+    link::{
+        operational_data::OperationalData, relay_sender::AsyncReply, RelaySummary, RelayTimings,
+        TxHashes,
+    },
 };
 
 pub const TIMEOUT: Duration = Duration::from_secs(300);
@@ -33,6 +36,8 @@ pub struct PendingData {
     pub tx_hashes: TxHashes,
     pub submit_time: Instant,
     pub error_events: Vec<IbcEvent>,
+    /// The time spent submitting the transactions, if collected.
+    pub timings: Option<RelayTimings>,
 }
 
 impl PendingData {
@@ -73,6 +78,15 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
         self.chain.id()
     }
 
+    /// The number of transactions awaiting confirmation, across all the pending data.
+    pub fn pending_tx_count(&self) -> usize {
+        self.pending_queue
+            .clone_vec()
+            .iter()
+            .map(|pending| pending.tx_hashes.0.len())
+            .sum()
+    }
+
     /// Insert a new pending transaction to the back of the queue.
     pub fn insert_new_pending_tx(&self, r: AsyncReply, od: OperationalData) {
         let mut tx_hashes = Vec::new();
@@ -121,36 +135,90 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
             tx_hashes: TxHashes(tx_hashes),
             submit_time: Instant::now(),
             error_events,
+            timings: r.timings,
         };
 
         self.pending_queue.push_back(u);
     }
 
-    fn check_tx_events(&self, tx_hashes: &TxHashes) -> Result<Option<Vec<IbcEvent>>, RelayerError> {
-        let mut all_events = Vec::new();
+    fn check_tx_events(&self, tx_hashes: &TxHashes) -> Result<Option<RelaySummary>, RelayerError> {
+        let mut summary = RelaySummary::empty();
         for hash in &tx_hashes.0 {
-            let mut events = self
-                .chain
-                .query_txs(QueryTxRequest::Transaction(QueryTxHash(*hash)))?;
+            let result = match self.chain.query_tx_result(QueryTxHash(*hash))? {
+                Some(result) if !result.events.is_empty() => result,
+                _ => return Ok(None),
+            };
 
-            if events.is_empty() {
-                return Ok(None);
-            } else {
-                all_events.append(&mut events)
+            // The transaction may aggregate the messages of other channels.
+            let events = result
+                .events
+                .into_iter()
+                .filter(|event| self.is_channel_event(event))
+                .collect();
+
+            summary.merge(RelaySummary::from_tx_result(*hash, events, result.cost))
+        }
+        Ok(Some(summary))
+    }
+
+    /// Returns whether the given event is about a packet sent or received on the channel
+    /// of these pending transactions, or is not about a packet at all.
+    fn is_channel_event(&self, event: &IbcEvent) -> bool {
+        match event.packet() {
+            Some(packet) => {
+                (packet.source_channel == self.channel_id && packet.source_port == self.port_id)
+                    || (packet.destination_channel == self.channel_id
+                        && packet.destination_port == self.port_id)
             }
+            None => true,
+        }
+    }
+
+    /// Returns whether `confirmation_blocks` blocks have been committed on top of the
+    /// highest block in which the given events were emitted.
+    fn has_confirmations(
+        &self,
+        events: &[IbcEvent],
+        confirmation_blocks: u64,
+    ) -> Result<bool, RelayerError> {
+        if confirmation_blocks == 0 {
+            return Ok(true);
+        }
+
+        let inclusion_height = events
+            .iter()
+            .filter(|event| {
+                !matches!(
+                    event,
+                    IbcEvent::ChainError(_) | IbcEvent::Empty(_) | IbcEvent::AppModule(_)
+                )
+            })
+            .map(|event| event.height())
+            .max();
+
+        match inclusion_height {
+            Some(inclusion_height) => {
+                let latest_height = self.chain.query_latest_height()?;
+                Ok(latest_height >= inclusion_height.add(confirmation_blocks))
+            }
+            None => Ok(true),
         }
-        Ok(Some(all_events))
     }
 
     /// Try and process one pending transaction within the given timeout duration if one
     /// is available.
     ///
+    /// A transaction which has been committed is only confirmed once `confirmation_blocks`
+    /// blocks have been committed on top of the block including it. Until then, it stays
+    /// in the queue and is not resubmitted.
+    ///
     /// A `resubmit` closure is provided when the clear interval for packets is 0. If this closure
     /// is provided, the pending transactions that fail to process within the given timeout duration
     /// are resubmitted following the logic specified by the closure.
     pub fn process_pending<ChainA: ChainHandle, ChainB: ChainHandle>(
         &self,
         timeout: Duration,
+        confirmation_blocks: u64,
         relay_path: &RelayPath<ChainA, ChainB>,
         resubmit: Option<impl FnOnce(OperationalData) -> Result<AsyncReply, LinkError>>,
     ) -> Result<Option<RelaySummary>, LinkError> {
@@ -204,7 +272,19 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
 
                                 match new_od.map(f) {
                                     Some(Ok(reply)) => {
-                                        self.insert_new_pending_tx(reply, pending.original_od);
+                                        telemetry!(
+                                            ibc_packet_resubmissions,
+                                            &self.chain.id(),
+                                            &self.channel_id,
+                                            &self.port_id,
+                                            &self.counterparty_chain_id,
+                                            1
+                                        );
+
+                                        let mut od = pending.original_od;
+                                        od.attempts += 1;
+
+                                        self.insert_new_pending_tx(reply, od);
                                         Ok(None)
                                     }
                                     Some(Err(e)) => {
@@ -230,32 +310,120 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                         Ok(None)
                     }
                 }
-                Ok(Some(events)) => {
-                    // We get a list of events for the transaction hashes,
-                    // Meaning the transaction has been committed successfully
-                    // to the chain.
-
-                    debug!(
-                        tracking_id = %pending.tracking_id(),
-                        elapsed = ?pending.submit_time.elapsed(),
-                        tx_hashes = %tx_hashes,
-                        "transactions confirmed",
-                    );
+                Ok(Some(mut summary)) => {
+                    match self.has_confirmations(&summary.events, confirmation_blocks) {
+                        Ok(false) => {
+                            // The transaction has been committed, but not enough blocks have been
+                            // committed on top of it yet. Keep it pending, without resubmitting it.
+                            trace!(
+                                "transaction is awaiting {} confirmation(s): {}",
+                                confirmation_blocks,
+                                tx_hashes
+                            );
+
+                            self.pending_queue.push_back(pending);
+                            Ok(None)
+                        }
+                        Ok(true) => {
+                            // We get a list of events for the transaction hashes,
+                            // Meaning the transaction has been committed successfully
+                            // to the chain.
+
+                            let attempts = pending.original_od.attempts;
+
+                            debug!(
+                                tracking_id = %pending.tracking_id(),
+                                elapsed = ?pending.submit_time.elapsed(),
+                                tx_hashes = %tx_hashes,
+                                attempts,
+                                "transactions confirmed",
+                            );
+
+                            telemetry!(
+                                tx_confirmed,
+                                pending.tracking_id(),
+                                &self.chain.id(),
+                                &self.channel_id,
+                                &self.port_id,
+                                &self.counterparty_chain_id
+                            );
+
+                            telemetry!({
+                                for (tx_hash, cost) in summary.tx_costs() {
+                                    let fee: Vec<_> = cost
+                                        .fee
+                                        .iter()
+                                        .filter_map(|coin| {
+                                            let amount = coin.amount.parse().ok()?;
+                                            Some((amount, coin.denom.as_str()))
+                                        })
+                                        .collect();
+
+                                    ibc_telemetry::global().tx_cost(
+                                        &self.chain.id(),
+                                        tx_hash,
+                                        cost.gas_used,
+                                        &fee,
+                                    );
+                                }
+                            });
+
+                            summary = summary.with_attempts(attempts);
+
+                            telemetry!({
+                                for attempts in summary.attempts() {
+                                    ibc_telemetry::global().ibc_packet_attempts(
+                                        &self.chain.id(),
+                                        &self.channel_id,
+                                        &self.port_id,
+                                        &self.counterparty_chain_id,
+                                        *attempts,
+                                    );
+                                }
+                            });
 
-                    telemetry!(
-                        tx_confirmed,
-                        pending.tracking_id(),
-                        &self.chain.id(),
-                        &self.channel_id,
-                        &self.port_id,
-                        &self.counterparty_chain_id
-                    );
+                            if relay_path.memo_source_heights() {
+                                summary = summary.with_source_heights(&pending.original_od);
+                            }
 
-                    // Convert the events to RelaySummary and return them.
-                    let mut summary = RelaySummary::from_events(events);
-                    summary.extend(RelaySummary::from_events(pending.error_events));
+                            if let Some(timings) = pending.timings {
+                                let timings = RelayTimings {
+                                    confirmation: pending.submit_time.elapsed(),
+                                    ..timings
+                                };
+
+                                telemetry!({
+                                    for (phase, elapsed) in timings.phases() {
+                                        ibc_telemetry::global().ibc_relay_phase_seconds(
+                                            &self.chain.id(),
+                                            &self.channel_id,
+                                            &self.port_id,
+                                            &self.counterparty_chain_id,
+                                            phase,
+                                            elapsed,
+                                        );
+                                    }
+                                });
 
-                    Ok(Some(summary))
+                                summary = summary.with_timings(timings);
+                            }
+
+                            // Append the error events to the summary of the committed events.
+                            summary.merge(RelaySummary::from_events(pending.error_events));
+
+                            Ok(Some(summary))
+                        }
+                        Err(e) => {
+                            error!(
+                            "error querying the latest height to confirm {}: {}. will retry again later",
+                            tx_hashes, e
+                        );
+
+                            self.pending_queue.push_back(pending);
+
+                            Err(LinkError::relayer(e))
+                        }
+                    }
                 }
                 Err(e) => {
                     // There are errors querying for the transaction hashes.
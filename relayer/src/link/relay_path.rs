@@ -1,8 +1,14 @@
 use alloc::collections::BTreeMap as HashMap;
 use alloc::collections::VecDeque;
+use std::collections::HashSet;
 use std::ops::Sub;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
 use std::time::{Duration, Instant};
 
+use crossbeam_channel::RecvTimeoutError;
 use ibc_proto::google::protobuf::Any;
 use itertools::Itertools;
 use tracing::{debug, error, info, span, trace, warn, Level};
@@ -24,24 +30,34 @@ use crate::channel::error::ChannelError;
 use crate::channel::Channel;
 use crate::event::monitor::EventBatch;
 use crate::foreign_client::{ForeignClient, ForeignClientError};
+use crate::link::ack_coalescer::{is_ack_only, AckCoalescer, AckFlushPolicy};
+use crate::link::ack_verifier::{AckMismatch, AckVerifier};
+use crate::link::direction_priority::DirectionPriority;
 use crate::link::error::{self, LinkError};
+use crate::link::key_rotation::KeyRotation;
 use crate::link::operational_data::{
-    OperationalData, OperationalDataTarget, TrackedEvents, TransitMessage,
+    retain_pending_messages, OperationalData, OperationalDataTarget, TrackedEvents, TransitMessage,
 };
 use crate::link::packet_events::query_packet_events_with;
 use crate::link::packet_events::query_send_packet_events;
 use crate::link::packet_events::query_write_ack_events;
+use crate::link::packet_events::QUERY_RESULT_LIMIT;
+use crate::link::packet_events::{cap_sequences, retain_contiguous_packet_events};
 use crate::link::pending::PendingTxs;
 use crate::link::relay_sender::{AsyncReply, SubmitReply};
-use crate::link::relay_summary::RelaySummary;
+use crate::link::relay_summary::{RelaySummary, RelayTimings};
+use crate::link::schedule_snapshot::{ScheduleSnapshot, SCHEDULE_SNAPSHOT_INTERVAL};
+use crate::link::tx_aggregator::TxAggregator;
 use crate::link::{pending, relay_sender};
 use crate::path::PathIdentifiers;
 use crate::telemetry;
 use crate::util::queue::Queue;
+use crate::util::rate_limit::TxRateLimiter;
+use crate::util::retry::{clamp_total, ConstantGrowth};
 use ibc::{
     core::{
         ics02_client::{
-            client_consensus::QueryClientEventRequest,
+            client_consensus::QueryClientEventRequest, client_state::AnyClientState,
             events::ClientMisbehaviour as ClientMisbehaviourEvent,
             events::UpdateClient as UpdateClientEvent,
         },
@@ -53,11 +69,13 @@ use ibc::{
                 recv_packet::MsgRecvPacket, timeout::MsgTimeout,
                 timeout_on_close::MsgTimeoutOnClose,
             },
-            packet::{Packet, PacketMsgType},
+            packet::{Packet, PacketMsgType, Sequence},
         },
         ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId},
     },
+    downcast,
     events::{IbcEvent, PrettyEvents, WithBlockDataType},
+    proofs::Proofs,
     query::QueryTxRequest,
     signer::Signer,
     timestamp::Timestamp,
@@ -67,6 +85,20 @@ use ibc::{
 
 const MAX_RETRIES: usize = 5;
 
+/// The tracking id of the operational data relaying coalesced acknowledgements.
+const COALESCED_ACKS: &str = "coalesced-acks";
+
+/// The backoff between the retries of a failed query of the proofs of a message,
+/// starting at 100ms and growing by 100ms at each retry, capped at 500ms.
+/// Since the link is held while retrying, the overall amount of time spent
+/// backing off is capped to 2 seconds, whatever the number of retries.
+fn proof_query_retry_strategy(max_retries: u64) -> impl Iterator<Item = Duration> {
+    let strategy = ConstantGrowth::new(Duration::from_millis(100), Duration::from_millis(100));
+
+    clamp_total(strategy, Duration::from_millis(500), Duration::from_secs(2))
+        .take(max_retries as usize)
+}
+
 /// Whether or not to resubmit packets when pending transactions
 /// fail to process within the given timeout duration.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -88,6 +120,35 @@ impl Resubmit {
     }
 }
 
+/// A range of heights on the source chain of a relay path, inclusive on both ends,
+/// used to only relay the packets whose events were emitted within that range.
+/// A missing bound leaves the range unbounded on that end.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct HeightWindow {
+    pub from_height: Option<Height>,
+    pub to_height: Option<Height>,
+}
+
+impl HeightWindow {
+    pub fn new(from_height: Option<Height>, to_height: Option<Height>) -> Self {
+        Self {
+            from_height,
+            to_height,
+        }
+    }
+
+    /// Whether or not the given height is within this window.
+    pub fn contains(&self, height: Height) -> bool {
+        self.from_height.map_or(true, |from| from <= height)
+            && self.to_height.map_or(true, |to| height <= to)
+    }
+
+    /// The given query height, lowered to the upper bound of this window if it exceeds it.
+    pub fn bound(&self, height: Height) -> Height {
+        self.to_height.map_or(height, |to| to.min(height))
+    }
+}
+
 pub struct RelayPath<ChainA: ChainHandle, ChainB: ChainHandle> {
     channel: Channel<ChainA, ChainB>,
 
@@ -111,6 +172,103 @@ pub struct RelayPath<ChainA: ChainHandle, ChainB: ChainHandle> {
     // transactions if [`confirm_txes`] is true.
     pending_txs_src: PendingTxs<ChainA>,
     pending_txs_dst: PendingTxs<ChainB>,
+
+    // Toggles for relaying receive packets, acknowledgements and timeouts on this path.
+    relay_recv_packets: bool,
+    relay_acks: bool,
+    relay_timeouts: bool,
+
+    // How long a schedule execution may submit operational data for,
+    // before deferring the remaining operational data to the next execution.
+    execute_schedule_timeout: Option<Duration>,
+
+    // The instant the submissions of the ongoing schedule execution must
+    // complete by, if its execution is bounded by the timeout above.
+    submission_deadline: Option<Instant>,
+
+    // Whether a submission cut short by the deadline above is still running in
+    // the background, no other submission being made by this path until it returns.
+    submission_in_flight: Arc<AtomicBool>,
+
+    // How long a packet clearing may keep scheduling pending packets for,
+    // before leaving the remaining ones to the next clearing.
+    clear_max_duration: Option<Duration>,
+
+    // Whether the client update needed by the relayed messages is submitted,
+    // and its commit awaited, before the messages, rather than along with them.
+    separate_client_updates: bool,
+
+    // Whether the heights of the relayed events are recorded in the memo of the
+    // relay transactions, and in the relay log.
+    memo_source_heights: bool,
+
+    // The maximum size of the data of the packets relayed on this path, if set,
+    // the larger packets being skipped.
+    max_packet_data_bytes: Option<usize>,
+
+    // Whether the time spent in each phase of the relaying is collected
+    // into the relay summaries.
+    collect_timings: bool,
+
+    // How many times a failed query of the proofs of a message is retried.
+    proof_query_retries: u64,
+
+    // The number of blocks to wait for on top of the block including a
+    // pending transaction, before considering the transaction confirmed.
+    confirmation_blocks: u64,
+
+    // Whether to reconcile the scheduled operational data against the
+    // destination chain before executing the schedule.
+    reconcile_schedule: bool,
+
+    // Aggregates the messages submitted by this path with the ones
+    // of other paths bound for the same chain, if set.
+    tx_aggregator: Option<TxAggregator>,
+
+    // How long before the end of their trusting period the clients of this
+    // path are updated ahead of a schedule execution, if set, along with when
+    // the clients may next have entered that margin.
+    client_refresh_margin: Option<Duration>,
+    next_client_refresh_check: Option<Instant>,
+
+    // Defers the execution of the schedule while the reverse direction,
+    // which is cheaper to relay to, has operational data scheduled, if set.
+    direction_priority: Option<DirectionPriority>,
+
+    // Limit the rate of the transactions submitted to the source and destination
+    // chains, if set, along with how long to wait before the operational data
+    // deferred because of these limits may be submitted.
+    src_tx_rate_limiter: Option<TxRateLimiter>,
+    dst_tx_rate_limiter: Option<TxRateLimiter>,
+    tx_rate_limit_delay: Option<Duration>,
+
+    // How long to leave the packets committed on the source chain to other
+    // relayers, if set, along with the batches of events deferred until then,
+    // by commit time.
+    relay_grace_period: Option<Duration>,
+    grace_deferred_batches: VecDeque<(Instant, EventBatch)>,
+
+    // Remembers the relayed packets to check the acknowledgements
+    // written for them by the destination chain, if set.
+    ack_verifier: Option<AckVerifier>,
+
+    // How many of the transactions submitted by this path may be
+    // awaiting confirmation at once, if set.
+    max_in_flight_txs: Option<usize>,
+
+    // Holds back the operational data only relaying acknowledgements,
+    // to relay them in fewer transactions, if set.
+    ack_coalescer: Option<AckCoalescer>,
+
+    // The directory in which the schedule of this path is stored across
+    // restarts, if set, along with when it was last stored.
+    schedule_snapshot_dir: Option<PathBuf>,
+    last_schedule_snapshot: Option<Instant>,
+
+    // The keys signing the transactions submitted to the source
+    // and destination chains, in turn.
+    src_keys: KeyRotation,
+    dst_keys: KeyRotation,
 }
 
 impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
@@ -153,9 +311,385 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             confirm_txes: with_tx_confirmation,
             pending_txs_src: PendingTxs::new(src_chain, src_channel_id, src_port_id, dst_chain_id),
             pending_txs_dst: PendingTxs::new(dst_chain, dst_channel_id, dst_port_id, src_chain_id),
+
+            relay_recv_packets: true,
+            relay_acks: true,
+            relay_timeouts: true,
+
+            execute_schedule_timeout: None,
+            submission_deadline: None,
+            submission_in_flight: Arc::new(AtomicBool::new(false)),
+            clear_max_duration: None,
+            separate_client_updates: false,
+            memo_source_heights: false,
+            max_packet_data_bytes: None,
+            collect_timings: false,
+            proof_query_retries: 0,
+
+            confirmation_blocks: 0,
+
+            reconcile_schedule: false,
+
+            tx_aggregator: None,
+
+            client_refresh_margin: None,
+            next_client_refresh_check: None,
+
+            direction_priority: None,
+
+            src_tx_rate_limiter: None,
+            dst_tx_rate_limiter: None,
+            tx_rate_limit_delay: None,
+
+            relay_grace_period: None,
+            grace_deferred_batches: VecDeque::new(),
+
+            ack_verifier: None,
+
+            max_in_flight_txs: None,
+
+            ack_coalescer: None,
+
+            schedule_snapshot_dir: None,
+            last_schedule_snapshot: None,
+
+            src_keys: KeyRotation::default(),
+            dst_keys: KeyRotation::default(),
         })
     }
 
+    /// Enables or disables the relaying of receive packets on this path.
+    ///
+    /// With receive packets disabled, the pending packets which did not time out
+    /// are left pending, which allows relaying only the timeouts of the packets
+    /// of a channel being closed, refunding their senders.
+    pub fn set_relay_recv_packets(&mut self, relay_recv_packets: bool) {
+        self.relay_recv_packets = relay_recv_packets;
+    }
+
+    /// Enables or disables the relaying of acknowledgements on this path.
+    pub fn set_relay_acks(&mut self, relay_acks: bool) {
+        if !relay_acks && self.ordered_channel() {
+            warn!(
+                "relaying of acknowledgements is disabled on ordered channel {}/{} on chain {}, \
+                the channel may stall unless acknowledgements are relayed by another relayer",
+                self.src_port_id(),
+                self.src_channel_id(),
+                self.src_chain().id(),
+            );
+        }
+
+        self.relay_acks = relay_acks;
+    }
+
+    /// Enables or disables the relaying of timeouts on this path.
+    pub fn set_relay_timeouts(&mut self, relay_timeouts: bool) {
+        self.relay_timeouts = relay_timeouts;
+    }
+
+    /// Bounds how long [`RelayPath::execute_schedule`] may keep submitting
+    /// operational data for, or removes the bound if `None`.
+    pub fn set_execute_schedule_timeout(&mut self, timeout: Option<Duration>) {
+        self.execute_schedule_timeout = timeout;
+    }
+
+    /// Bounds how long the packet clearings of this path may keep scheduling
+    /// pending packets and acknowledgements for, or removes the bound if `None`.
+    pub fn set_clear_max_duration(&mut self, max_duration: Option<Duration>) {
+        self.clear_max_duration = max_duration;
+    }
+
+    /// Enables or disables submitting the client update needed by the relayed
+    /// messages in a transaction of its own, whose commit is awaited before the
+    /// messages are submitted, see [`Self::send_client_update`].
+    pub fn set_separate_client_updates(&mut self, enabled: bool) {
+        self.separate_client_updates = enabled;
+    }
+
+    /// Enables or disables recording the heights of the relayed events in the
+    /// memo of the relay transactions, and in the relay log.
+    pub fn set_memo_source_heights(&mut self, enabled: bool) {
+        self.memo_source_heights = enabled;
+    }
+
+    /// Whether the heights of the relayed events are recorded in the memo of
+    /// the relay transactions, and in the relay log.
+    pub fn memo_source_heights(&self) -> bool {
+        self.memo_source_heights
+    }
+
+    /// Bounds the size, in bytes, of the data of the packets relayed on this path,
+    /// or removes the bound if `None`. The events of the larger packets are skipped
+    /// when generating the operational data, so that no message is built for them.
+    pub fn set_max_packet_data_bytes(&mut self, max_packet_data_bytes: Option<usize>) {
+        self.max_packet_data_bytes = max_packet_data_bytes;
+    }
+
+    /// Enables or disables the collection of the time spent building, assembling,
+    /// broadcasting and confirming the relayed messages, into the [`RelaySummary`]
+    /// of the confirmed transactions. Only the transactions which are confirmed
+    /// carry the confirmation time.
+    pub fn set_collect_timings(&mut self, enabled: bool) {
+        self.collect_timings = enabled;
+    }
+
+    /// Sets how many times a failed query of the proofs of a packet, acknowledgement
+    /// or timeout message is retried, before failing the generation of the
+    /// operational data the message belongs to.
+    pub fn set_proof_query_retries(&mut self, retries: u64) {
+        self.proof_query_retries = retries;
+    }
+
+    /// Sets the directory in which the schedule of this path is stored by
+    /// [`Self::persist_schedule_if_due`] and restored from by
+    /// [`Self::restore_schedule`], or disables storing it if `None`.
+    pub fn set_schedule_snapshot_dir(&mut self, dir: Option<PathBuf>) {
+        self.schedule_snapshot_dir = dir;
+        self.last_schedule_snapshot = None;
+    }
+
+    /// Sets the number of blocks which must be committed on top of the block
+    /// including a pending transaction before the transaction is confirmed.
+    pub fn set_confirmation_blocks(&mut self, confirmation_blocks: u64) {
+        self.confirmation_blocks = confirmation_blocks;
+    }
+
+    /// Enables or disables the reconciliation of the schedule against the
+    /// destination chain before each execution, see [`RelayPath::reconcile_schedule`].
+    pub fn set_reconcile_schedule(&mut self, reconcile_schedule: bool) {
+        self.reconcile_schedule = reconcile_schedule;
+    }
+
+    /// Sets the aggregator through which the scheduled operational data is submitted,
+    /// along with the operational data of other paths bound for the same chain,
+    /// or submits the operational data of this path on its own if `None`.
+    pub fn set_tx_aggregator(&mut self, tx_aggregator: Option<TxAggregator>) {
+        self.tx_aggregator = tx_aggregator;
+    }
+
+    /// Sets how long before the end of their trusting period the clients of this path
+    /// are updated by [`RelayPath::refresh_clients_within_margin`], or disables these
+    /// updates if `None`.
+    pub fn set_client_refresh_margin(&mut self, margin: Option<Duration>) {
+        self.client_refresh_margin = margin;
+        self.next_client_refresh_check = None;
+    }
+
+    /// Sets the [`DirectionPriority`] deciding whether to defer the execution of the
+    /// schedule to the reverse direction, or never defers it if `None`.
+    pub fn set_direction_priority(&mut self, direction_priority: Option<DirectionPriority>) {
+        self.direction_priority = direction_priority;
+    }
+
+    /// Sets the rate limiters of the transactions submitted to the source and
+    /// destination chains, or does not limit the rate for a chain if `None`.
+    pub fn set_tx_rate_limiters(
+        &mut self,
+        src_tx_rate_limiter: Option<TxRateLimiter>,
+        dst_tx_rate_limiter: Option<TxRateLimiter>,
+    ) {
+        self.src_tx_rate_limiter = src_tx_rate_limiter;
+        self.dst_tx_rate_limiter = dst_tx_rate_limiter;
+    }
+
+    /// Returns how long to wait before executing the schedule again, if operational
+    /// data was deferred by the last executions because of the transaction rate limits.
+    pub fn take_tx_rate_limit_delay(&mut self) -> Option<Duration> {
+        self.tx_rate_limit_delay.take()
+    }
+
+    /// Sets how long to leave the packets committed on the source chain to other relayers
+    /// before relaying them, or relays them as soon as possible if `None`.
+    pub fn set_relay_grace_period(&mut self, relay_grace_period: Option<Duration>) {
+        self.relay_grace_period = relay_grace_period;
+    }
+
+    /// Bounds how many of the transactions submitted by this path may be awaiting
+    /// confirmation at once, or removes the bound if `None`. Once the bound is reached,
+    /// [`RelayPath::execute_schedule`] defers the remaining operational data until some
+    /// of the pending transactions are confirmed.
+    ///
+    /// The bound is only enforced when the transactions are confirmed.
+    pub fn set_max_in_flight_txs(&mut self, max_in_flight_txs: Option<usize>) {
+        self.max_in_flight_txs = max_in_flight_txs;
+    }
+
+    /// Sets when the acknowledgements held back by [`RelayPath::coalesce_acks`] are
+    /// flushed, the acknowledgements being relayed immediately unless the policy
+    /// [is enabled](AckFlushPolicy::is_enabled).
+    pub fn set_ack_flush_policy(&mut self, policy: AckFlushPolicy) {
+        self.ack_coalescer = policy.is_enabled().then(|| AckCoalescer::new(policy));
+    }
+
+    /// Holds back the scheduled operational data which only relays acknowledgements,
+    /// and, once the flush policy says so, schedules all the held acknowledgements
+    /// as a single piece of operational data, with proofs at the height of the most
+    /// recent one, so that they are relayed in fewer transactions. Does nothing unless
+    /// a flush policy is set.
+    pub fn coalesce_acks(&self) -> Result<(), LinkError> {
+        let coalescer = match &self.ack_coalescer {
+            Some(coalescer) => coalescer,
+            None => return Ok(()),
+        };
+
+        let now = Instant::now();
+        let mut kept = VecDeque::new();
+
+        for od in self.dst_operational_data.take() {
+            let coalesced =
+                matches!(od.tracking_id, TrackingId::Static(id) if id == COALESCED_ACKS);
+
+            if !coalesced && is_ack_only(&od) {
+                coalescer.hold(od, now);
+            } else {
+                kept.push_back(od);
+            }
+        }
+
+        self.dst_operational_data.replace(kept);
+
+        let held = match coalescer.take_if_due(now) {
+            Some(held) => held,
+            None => return Ok(()),
+        };
+
+        let mut events: Vec<IbcEvent> = held
+            .iter()
+            .flat_map(|(_, od)| od.batch.iter().map(|msg| msg.event.clone()))
+            .collect();
+
+        // The same acknowledgement may have been scheduled more than once, eg. when refreshed.
+        let mut sequences = HashSet::new();
+        events.retain(|event| match event {
+            IbcEvent::WriteAcknowledgement(write_ack) => {
+                sequences.insert(write_ack.packet.sequence)
+            }
+            _ => true,
+        });
+
+        // The acknowledgements written at a given height can be proven at any later height.
+        if let Some(height) = events.iter().map(|event| event.height()).max() {
+            for event in events.iter_mut() {
+                event.set_height(height);
+            }
+        }
+
+        debug!("flushing {} coalesced acknowledgement(s)", events.len());
+
+        let tracked_events = TrackedEvents::new(events, TrackingId::new_static(COALESCED_ACKS));
+
+        let result = self
+            .generate_operational_data(tracked_events)
+            .and_then(|(_, dst_od)| match dst_od {
+                Some(od) => self.schedule_operational_data(od),
+                None => Ok(()),
+            });
+
+        if result.is_err() {
+            // Keep holding the acknowledgements, to be flushed by a later schedule
+            // execution, rather than dropping them until the next packet clearing.
+            coalescer.restore(held);
+        }
+
+        result
+    }
+
+    /// Enables or disables the verification of the acknowledgements written by the
+    /// destination chain against the packets relayed to it, see [`Self::verify_acks`].
+    pub fn set_ack_verification(&mut self, enabled: bool) {
+        self.ack_verifier = enabled.then(AckVerifier::new);
+    }
+
+    /// Checks the packets of the `WriteAcknowledgement` events of the given summary
+    /// against the packets relayed by this path, and returns the mismatches, which
+    /// may indicate a misbehaving counterparty. Returns no mismatch unless the
+    /// verification is enabled.
+    pub fn verify_acks(&self, summary: &RelaySummary) -> Vec<AckMismatch> {
+        match &self.ack_verifier {
+            Some(verifier) => verifier.verify(&summary.events),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the given batch of events if the relay grace period elapsed since the
+    /// events were committed on the source chain, or defers the batch until then.
+    ///
+    /// The deferred batches are returned by [`RelayPath::take_batches_past_grace_period`]
+    /// once their grace period elapsed, in order.
+    pub fn defer_within_grace_period(
+        &mut self,
+        batch: EventBatch,
+    ) -> Result<Option<EventBatch>, LinkError> {
+        let grace_period = match self.relay_grace_period {
+            Some(grace_period) => grace_period,
+            None => return Ok(Some(batch)),
+        };
+
+        let committed_at = self.src_time_at_height(batch.height)?;
+
+        // Batches are never handed out of order, so a batch is
+        // deferred as long as any earlier batch is deferred.
+        if self.grace_deferred_batches.is_empty() && committed_at.elapsed() >= grace_period {
+            return Ok(Some(batch));
+        }
+
+        self.grace_deferred_batches.push_back((committed_at, batch));
+
+        Ok(None)
+    }
+
+    /// Returns the batches of events deferred by [`RelayPath::defer_within_grace_period`]
+    /// whose relay grace period elapsed, in order.
+    pub fn take_batches_past_grace_period(&mut self) -> Vec<EventBatch> {
+        let grace_period = match self.relay_grace_period {
+            Some(grace_period) => grace_period,
+            None => {
+                return self
+                    .grace_deferred_batches
+                    .drain(..)
+                    .map(|(_, b)| b)
+                    .collect()
+            }
+        };
+
+        let mut batches = Vec::new();
+
+        while let Some((committed_at, _)) = self.grace_deferred_batches.front() {
+            if committed_at.elapsed() < grace_period {
+                break;
+            }
+
+            if let Some((_, batch)) = self.grace_deferred_batches.pop_front() {
+                batches.push(batch);
+            }
+        }
+
+        batches
+    }
+
+    /// Whether the execution of the schedule should be deferred to a later step, to give
+    /// way to the relay paths in the cheaper, reverse direction, see [`DirectionPriority`].
+    pub fn should_defer_execution(&mut self) -> bool {
+        let has_pending =
+            !self.src_operational_data.is_empty() || !self.dst_operational_data.is_empty();
+
+        match self.direction_priority.as_mut() {
+            Some(direction_priority) => direction_priority.should_defer(has_pending),
+            None => false,
+        }
+    }
+
+    /// Sets the keys which sign the transactions submitted to the source and
+    /// destination chains in turn with the keys from the chain configurations.
+    pub fn set_extra_key_names(
+        &mut self,
+        src_extra_key_names: Vec<String>,
+        dst_extra_key_names: Vec<String>,
+    ) {
+        self.src_keys = KeyRotation::new(src_extra_key_names);
+        self.dst_keys = KeyRotation::new(dst_extra_key_names);
+    }
+
     pub fn src_chain(&self) -> &ChainA {
         self.channel.src_chain()
     }
@@ -395,13 +929,37 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         TrackedEvents::new(result, tracking_id)
     }
 
-    fn relay_pending_packets(&self, height: Option<Height>) -> Result<(), LinkError> {
+    fn relay_pending_packets(
+        &self,
+        height: Option<Height>,
+        window: HeightWindow,
+        max_packets: usize,
+    ) -> Result<(), LinkError> {
         let tracking_id = TrackingId::new_static("relay pending packets");
 
+        // The budget covers the whole clearing, retries included.
+        let deadline = self
+            .clear_max_duration
+            .map(|max_duration| Instant::now() + max_duration);
+
         for i in 1..=MAX_RETRIES {
             let cleared = self
-                .schedule_recv_packet_and_timeout_msgs(height, tracking_id)
-                .and_then(|_| self.schedule_packet_ack_msgs(height, tracking_id));
+                .schedule_recv_packet_and_timeout_msgs(
+                    height,
+                    window,
+                    max_packets,
+                    deadline,
+                    tracking_id,
+                )
+                .and_then(|_| {
+                    self.schedule_packet_ack_msgs(
+                        height,
+                        window,
+                        max_packets,
+                        deadline,
+                        tracking_id,
+                    )
+                });
 
             match cleared {
                 Ok(()) => return Ok(()),
@@ -418,14 +976,48 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     /// Clears any packets that were sent before `height`.
     /// If no height is passed in, then the latest height of the source chain is used.
     pub fn schedule_packet_clearing(&self, height: Option<Height>) -> Result<(), LinkError> {
-        let span = span!(Level::DEBUG, "clear");
+        self.schedule_packet_clearing_within(height, HeightWindow::default())
+    }
+
+    /// Clears the packets that were sent before `height`, like [`Self::schedule_packet_clearing`],
+    /// but only the ones whose events were emitted on the source chain within the given `window`.
+    /// The pending packets outside of the window are left untouched.
+    pub fn schedule_packet_clearing_within(
+        &self,
+        height: Option<Height>,
+        window: HeightWindow,
+    ) -> Result<(), LinkError> {
+        self.schedule_packet_clearing_with(height, window, 0)
+    }
+
+    /// Clears the packets that were sent before `height`, like [`Self::schedule_packet_clearing`],
+    /// but at most `max_packets` of the pending packets, and as many of the pending
+    /// acknowledgements, the others being skipped. Zero leaves the clearing uncapped.
+    ///
+    /// The most recent pending packets are cleared, except on ordered channels, where
+    /// the oldest ones are cleared as the packets must be delivered in order.
+    pub fn schedule_packet_clearing_capped(
+        &self,
+        height: Option<Height>,
+        max_packets: usize,
+    ) -> Result<(), LinkError> {
+        self.schedule_packet_clearing_with(height, HeightWindow::default(), max_packets)
+    }
+
+    fn schedule_packet_clearing_with(
+        &self,
+        height: Option<Height>,
+        window: HeightWindow,
+        max_packets: usize,
+    ) -> Result<(), LinkError> {
+        let span = span!(Level::DEBUG, "clear", window = ?window);
         let _enter = span.enter();
 
         let clear_height = height
             .map(|h| h.decrement().map_err(|e| LinkError::decrement_height(h, e)))
             .transpose()?;
 
-        self.relay_pending_packets(clear_height)?;
+        self.relay_pending_packets(clear_height, window, max_packets)?;
 
         debug!(height = ?clear_height, "done scheduling");
         Ok(())
@@ -503,8 +1095,35 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             self.channel.connection_delay,
         );
 
+        if self.collect_timings {
+            src_od.timings = Some(RelayTimings::default());
+            dst_od.timings = Some(RelayTimings::default());
+        }
+
         for event in input {
             trace!("processing event: {}", event);
+
+            let started = Instant::now();
+
+            if let Some(packet) = oversized_packet(event, self.max_packet_data_bytes) {
+                warn!(
+                    "skipping packet {} with {} bytes of data, above the maximum of {} bytes",
+                    packet,
+                    packet.data.len(),
+                    self.max_packet_data_bytes.unwrap_or_default(),
+                );
+
+                telemetry!(
+                    ibc_oversized_packets,
+                    &self.src_chain().id(),
+                    self.src_channel_id(),
+                    self.src_port_id(),
+                    1
+                );
+
+                continue;
+            }
+
             let (dst_msg, src_msg) = match event {
                 IbcEvent::CloseInitChannel(_) => {
                     (Some(self.build_chan_close_confirm_from_event(event)?), None)
@@ -537,7 +1156,13 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                     }
                 }
                 IbcEvent::WriteAcknowledgement(ref write_ack_ev) => {
-                    if self
+                    if !self.relay_acks {
+                        debug!(
+                            "{} skipped, relaying of acknowledgements is disabled",
+                            write_ack_ev
+                        );
+                        (None, None)
+                    } else if self
                         .dst_channel(Height::zero())?
                         .state_matches(&ChannelState::Closed)
                     {
@@ -552,6 +1177,15 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 _ => (None, None),
             };
 
+            // Attribute the time spent building the messages to the operational data they go to.
+            if let Some(timings) = match (&dst_msg, &src_msg) {
+                (Some(_), _) => dst_od.timings.as_mut(),
+                (None, Some(_)) => src_od.timings.as_mut(),
+                (None, None) => None,
+            } {
+                timings.proof_construction += started.elapsed();
+            }
+
             // Collect messages to be sent to the destination chain (e.g., RecvPacket)
             if let Some(msg) = dst_msg {
                 debug!("{} from {}", msg.type_url, event);
@@ -730,7 +1364,31 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             return Ok(S::Reply::empty());
         }
 
-        let msgs = odata.assemble_msgs(self)?;
+        let key_name = match odata.target {
+            OperationalDataTarget::Source => self.src_keys.next_key_name(),
+            OperationalDataTarget::Destination => self.dst_keys.next_key_name(),
+        };
+
+        let assembly_started = Instant::now();
+
+        let msgs = if self.separate_client_updates {
+            let (client_update, msgs) = odata.assemble_msgs_apart(self)?;
+
+            if let Some(client_update) = client_update {
+                self.send_client_update(odata, client_update, key_name.clone())?;
+            }
+
+            msgs
+        } else {
+            odata.assemble_msgs(self)?
+        }
+        .with_key_name(key_name);
+
+        let msgs = if self.memo_source_heights {
+            msgs.with_source_heights(odata.source_heights())
+        } else {
+            msgs
+        };
 
         telemetry!({
             let (chain, counterparty, channel_id, port_id) = self.target_info(odata.target);
@@ -744,10 +1402,120 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             );
         });
 
-        match odata.target {
-            OperationalDataTarget::Source => S::submit(self.src_chain(), msgs),
-            OperationalDataTarget::Destination => S::submit(self.dst_chain(), msgs),
+        let commit_height = odata
+            .batch
+            .iter()
+            .map(|gm| gm.event.height())
+            .min()
+            .unwrap_or(odata.proofs_height);
+
+        let message_assembly = assembly_started.elapsed();
+        let broadcast_started = Instant::now();
+
+        let aggregator = self.tx_aggregator.as_ref();
+        let deadline = self.submission_deadline.zip(self.execute_schedule_timeout);
+        let in_flight = &self.submission_in_flight;
+
+        let result = match odata.target {
+            OperationalDataTarget::Source => submit_until::<S, _>(
+                aggregator,
+                self.src_chain(),
+                msgs,
+                commit_height,
+                deadline,
+                in_flight,
+            ),
+            OperationalDataTarget::Destination => submit_until::<S, _>(
+                aggregator,
+                self.dst_chain(),
+                msgs,
+                commit_height,
+                deadline,
+                in_flight,
+            ),
+        };
+
+        if result.is_err() {
+            telemetry!({
+                let (chain, counterparty, channel_id, port_id) = self.target_info(odata.target);
+
+                ibc_telemetry::global().ibc_packet_relay_failures(
+                    &chain,
+                    channel_id,
+                    port_id,
+                    &counterparty,
+                    1,
+                );
+            });
+        }
+
+        let broadcast = broadcast_started.elapsed();
+
+        result.map(|reply| match odata.timings {
+            Some(timings) if self.collect_timings => reply.with_timings(RelayTimings {
+                message_assembly,
+                broadcast,
+                ..timings
+            }),
+            _ => reply,
+        })
+    }
+
+    /// Submits the given client update message, needed by the messages of the
+    /// operational data, in a transaction of its own to the chain targeted by the
+    /// operational data, and waits for its commit, so that the messages are only
+    /// relayed against a client which was successfully updated.
+    ///
+    /// A client update failing with a `ChainError` event is reported as a
+    /// [`Send`](error::LinkErrorDetail::Send) error, so that it is retried along
+    /// with the messages of the operational data.
+    fn send_client_update(
+        &self,
+        odata: &OperationalData,
+        client_update: Any,
+        key_name: Option<String>,
+    ) -> Result<(), LinkError> {
+        let tm = TrackedMsgs::new(vec![client_update], odata.tracking_id).with_key_name(key_name);
+
+        let result = match odata.target {
+            OperationalDataTarget::Source => self.src_chain().send_messages_and_wait_commit(tm),
+            OperationalDataTarget::Destination => {
+                self.dst_chain().send_messages_and_wait_commit(tm)
+            }
+        }
+        .map_err(LinkError::relayer)
+        .and_then(|events| {
+            match events
+                .into_iter()
+                .find(|event| matches!(event, IbcEvent::ChainError(_)))
+            {
+                Some(event) => Err(LinkError::send(event)),
+                None => Ok(()),
+            }
+        });
+
+        if let Err(e) = &result {
+            error!(
+                "client update on {} failed, not relaying the messages: {}",
+                odata.target, e
+            );
+
+            telemetry!({
+                let (chain, counterparty, channel_id, port_id) = self.target_info(odata.target);
+
+                ibc_telemetry::global().ibc_client_update_failures(
+                    &chain,
+                    channel_id,
+                    port_id,
+                    &counterparty,
+                    1,
+                );
+            });
+        } else {
+            debug!("client update on {} committed", odata.target);
         }
+
+        result
     }
 
     fn enqueue_pending_tx(&self, reply: AsyncReply, odata: OperationalData) {
@@ -1036,10 +1804,17 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     /// chain where to query for packet data. If `None`, the latest available
     /// height on the source chain is used.
     ///
-    /// Blocks until _all_ outstanding messages have been scheduled.
+    /// Only the packets sent within the given `window` on the source chain are scheduled,
+    /// and at most `max_packets` of them unless it is zero, see [`cap_sequences`].
+    ///
+    /// Blocks until _all_ outstanding messages have been scheduled, or until the
+    /// `deadline` is reached, if any, the remaining packets being left unscheduled.
     pub fn schedule_recv_packet_and_timeout_msgs(
         &self,
         opt_query_height: Option<Height>,
+        window: HeightWindow,
+        max_packets: usize,
+        deadline: Option<Instant>,
         tracking_id: TrackingId,
     ) -> Result<(), LinkError> {
         let _span =
@@ -1047,11 +1822,19 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 .entered();
 
         // Pull the s.n. of all packets that the destination chain has not yet received.
-        let (sequences, src_response_height) =
+        let (mut sequences, src_response_height) =
             unreceived_packets(self.dst_chain(), self.src_chain(), &self.path_id)
                 .map_err(LinkError::supervisor)?;
 
-        let query_height = opt_query_height.unwrap_or(src_response_height);
+        // Packets on ordered channels must be delivered contiguously,
+        // so make sure we schedule them in increasing sequence order.
+        if self.ordered_channel() {
+            sequences.sort_unstable();
+        }
+
+        self.cap_pending_sequences(&mut sequences, max_packets, "packets");
+
+        let query_height = window.bound(opt_query_height.unwrap_or(src_response_height));
 
         // Skip: no relevant events found.
         if sequences.is_empty() {
@@ -1067,14 +1850,69 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         // Chunk-up the list of sequence nrs. into smaller parts,
         // and schedule operational data incrementally across each chunk.
-        for events_chunk in query_packet_events_with(
+        let mut events_chunks = query_packet_events_with(
             &sequences,
             query_height,
             self.src_chain(),
             &self.path_id,
             query_send_packet_events,
-        ) {
+        )
+        .zip(sequences.chunks(QUERY_RESULT_LIMIT));
+
+        let mut scheduled = 0;
+
+        // The deadline is checked before the query of each chunk, so that a query is never cut short.
+        while !self.clear_deadline_reached(deadline, sequences.len() - scheduled, "packets") {
+            let (mut events_chunk, sequences_chunk) = match events_chunks.next() {
+                Some(chunk) => chunk,
+                None => break,
+            };
+
+            scheduled += sequences_chunk.len();
+
+            let (in_window, outside_window): (Vec<_>, Vec<_>) = events_chunk
+                .drain(..)
+                .partition(|event| window.contains(event.height()));
+
+            events_chunk = in_window;
+
+            let outside_sequences: Vec<Sequence> = outside_window
+                .iter()
+                .filter_map(|event| event.packet().map(|p| p.sequence))
+                .collect();
+
+            if !outside_sequences.is_empty() {
+                debug!(
+                    "skipping the packets {} sent outside of the window {:?}",
+                    outside_sequences.iter().format(", "),
+                    window
+                );
+            }
+
+            // On ordered channels, a missing packet event leaves a gap that the destination
+            // chain would reject every subsequent packet for. Stop scheduling at the gap,
+            // the remaining packets will be picked up by the next packet clearing.
+            // The packets sent outside of the window are not missing, so they leave no gap.
+            let found_gap = self.ordered_channel() && {
+                let window_sequences: Vec<Sequence> = sequences_chunk
+                    .iter()
+                    .filter(|seq| !outside_sequences.contains(seq))
+                    .copied()
+                    .collect();
+
+                retain_contiguous_packet_events(&mut events_chunk, &window_sequences)
+            };
+
             self.events_to_operational_data(TrackedEvents::new(events_chunk, tracking_id))?;
+
+            if found_gap {
+                warn!(
+                    "missing packet data for some of the sequences {} on ordered channel, \
+                    deferring the relaying of subsequent packets",
+                    sequences_chunk.iter().format(", ")
+                );
+                break;
+            }
         }
 
         Ok(())
@@ -1085,18 +1923,33 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     /// The `opt_query_height` parameter allows to optionally use a specific height on the source
     /// chain where to query for packet data. If `None`, the latest available height on the source
     /// chain is used.
+    ///
+    /// Only the acknowledgements written within the given `window` on the source chain are
+    /// scheduled, and at most `max_packets` of them unless it is zero, see [`cap_sequences`].
+    ///
+    /// The acknowledgements are no longer scheduled once the `deadline` is reached, if any.
     pub fn schedule_packet_ack_msgs(
         &self,
         opt_query_height: Option<Height>,
+        window: HeightWindow,
+        max_packets: usize,
+        deadline: Option<Instant>,
         tracking_id: TrackingId,
     ) -> Result<(), LinkError> {
         let _span = span!(Level::DEBUG, "build_packet_ack_msgs", h = ?opt_query_height).entered();
 
-        let (sequences, src_response_height) =
+        if !self.relay_acks {
+            debug!("relaying of acknowledgements is disabled, skipping");
+            return Ok(());
+        }
+
+        let (mut sequences, src_response_height) =
             unreceived_acknowledgements(self.dst_chain(), self.src_chain(), &self.path_id)
                 .map_err(LinkError::supervisor)?;
 
-        let query_height = opt_query_height.unwrap_or(src_response_height);
+        self.cap_pending_sequences(&mut sequences, max_packets, "acknowledgements");
+
+        let query_height = window.bound(opt_query_height.unwrap_or(src_response_height));
 
         // Skip: no relevant events found.
         if sequences.is_empty() {
@@ -1111,32 +1964,161 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         );
 
         // Incrementally process all the available sequence numbers in chunks
-        for events_chunk in query_packet_events_with(
+        let mut events_chunks = query_packet_events_with(
             &sequences,
             query_height,
             self.src_chain(),
             &self.path_id,
             query_write_ack_events,
+        )
+        .zip(sequences.chunks(QUERY_RESULT_LIMIT));
+
+        let mut scheduled = 0;
+
+        while !self.clear_deadline_reached(
+            deadline,
+            sequences.len() - scheduled,
+            "acknowledgements",
         ) {
+            let (events_chunk, sequences_chunk) = match events_chunks.next() {
+                Some(chunk) => chunk,
+                None => break,
+            };
+
+            scheduled += sequences_chunk.len();
+
+            let events_chunk = events_chunk
+                .into_iter()
+                .filter(|event| window.contains(event.height()))
+                .collect();
+
             self.events_to_operational_data(TrackedEvents::new(events_chunk, tracking_id))?;
         }
 
         Ok(())
     }
 
-    fn build_recv_packet(&self, packet: &Packet, height: Height) -> Result<Option<Any>, LinkError> {
-        let proofs = self
-            .src_chain()
-            .build_packet_proofs(
-                PacketMsgType::Recv,
-                &packet.source_port,
-                &packet.source_channel,
-                packet.sequence,
-                height,
-            )
-            .map_err(|e| LinkError::packet_proofs_constructor(self.src_chain().id(), e))?;
+    /// Whether the `deadline` of a packet clearing, if any, was reached while `unscheduled`
+    /// of the pending `kind` are left to schedule, logging that they are deferred if so.
+    fn clear_deadline_reached(
+        &self,
+        deadline: Option<Instant>,
+        unscheduled: usize,
+        kind: &str,
+    ) -> bool {
+        let reached = unscheduled > 0 && deadline.map_or(false, |d| Instant::now() >= d);
+
+        if reached {
+            warn!(
+                "packet clearing ran out of time, deferring {} pending {} to the next clearing",
+                unscheduled, kind
+            );
+        }
 
-        let msg = MsgRecvPacket::new(packet.clone(), proofs.clone(), self.dst_signer()?);
+        reached
+    }
+
+    /// Caps the given pending sequences to `max_packets`, see [`cap_sequences`],
+    /// logging how many of the pending `kind` were skipped.
+    fn cap_pending_sequences(&self, sequences: &mut Vec<Sequence>, max_packets: usize, kind: &str) {
+        let pending = sequences.len();
+        let skipped = cap_sequences(sequences, max_packets, self.ordered_channel());
+
+        if skipped > 0 {
+            warn!(
+                "clearing only {} of the {} pending {} on {}/{}, \
+                the {} others must be cleared manually, eg. with `hermes clear packets`",
+                sequences.len(),
+                pending,
+                kind,
+                self.src_port_id(),
+                self.src_channel_id(),
+                skipped
+            );
+        }
+    }
+
+    /// Builds the proofs of a packet message on the given chain, one of the chains of
+    /// this path, retrying the query of the proofs up to `proof_query_retries` times
+    /// with a backoff bounded overall, so that a transient failure of the node does
+    /// not fail the whole generation of the operational data.
+    fn build_packet_proofs<Chain: ChainHandle>(
+        &self,
+        chain: &Chain,
+        packet_type: PacketMsgType,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        height: Height,
+    ) -> Result<Proofs, LinkError> {
+        let mut delays = proof_query_retry_strategy(self.proof_query_retries);
+        let mut retries = 0;
+
+        loop {
+            let result = chain.build_packet_proofs(
+                packet_type.clone(),
+                port_id,
+                channel_id,
+                sequence,
+                height,
+            );
+
+            let e = match result {
+                Ok(proofs) => return Ok(proofs),
+                Err(e) => e,
+            };
+
+            match delays.next() {
+                Some(delay) => {
+                    retries += 1;
+
+                    warn!(
+                        "failed to query the proofs of packet {} on chain {}, retrying ({}/{}): {}",
+                        sequence,
+                        chain.id(),
+                        retries,
+                        self.proof_query_retries,
+                        e
+                    );
+
+                    telemetry!({
+                        let counterparty_chain_id = if chain.id() == self.src_chain().id() {
+                            self.dst_chain().id()
+                        } else {
+                            self.src_chain().id()
+                        };
+
+                        ibc_telemetry::global().ibc_proof_query_retries(
+                            &chain.id(),
+                            channel_id,
+                            port_id,
+                            &counterparty_chain_id,
+                            1,
+                        );
+                    });
+
+                    thread::sleep(delay);
+                }
+                None => return Err(LinkError::packet_proofs_constructor(chain.id(), e)),
+            }
+        }
+    }
+
+    fn build_recv_packet(&self, packet: &Packet, height: Height) -> Result<Option<Any>, LinkError> {
+        let proofs = self.build_packet_proofs(
+            self.src_chain(),
+            PacketMsgType::Recv,
+            &packet.source_port,
+            &packet.source_channel,
+            packet.sequence,
+            height,
+        )?;
+
+        let msg = MsgRecvPacket::new(packet.clone(), proofs.clone(), self.dst_signer()?);
+
+        if let Some(verifier) = &self.ack_verifier {
+            verifier.record_relayed(packet);
+        }
 
         trace!(
             "built recv_packet msg {}, proofs at height {}",
@@ -1153,16 +2135,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     ) -> Result<Option<Any>, LinkError> {
         let packet = event.packet.clone();
 
-        let proofs = self
-            .src_chain()
-            .build_packet_proofs(
-                PacketMsgType::Ack,
-                &packet.destination_port,
-                &packet.destination_channel,
-                packet.sequence,
-                event.height,
-            )
-            .map_err(|e| LinkError::packet_proofs_constructor(self.src_chain().id(), e))?;
+        let proofs = self.build_packet_proofs(
+            self.src_chain(),
+            PacketMsgType::Ack,
+            &packet.destination_port,
+            &packet.destination_channel,
+            packet.sequence,
+            event.height,
+        )?;
 
         let msg = MsgAcknowledgement::new(
             packet,
@@ -1206,16 +2186,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             (PacketMsgType::TimeoutUnordered, packet.sequence)
         };
 
-        let proofs = self
-            .dst_chain()
-            .build_packet_proofs(
-                packet_type,
-                &packet.destination_port,
-                &packet.destination_channel,
-                next_sequence_received,
-                height,
-            )
-            .map_err(|e| LinkError::packet_proofs_constructor(self.dst_chain().id(), e))?;
+        let proofs = self.build_packet_proofs(
+            self.dst_chain(),
+            packet_type,
+            &packet.destination_port,
+            &packet.destination_channel,
+            next_sequence_received,
+            height,
+        )?;
 
         let msg = MsgTimeout::new(
             packet.clone(),
@@ -1238,16 +2216,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         packet: &Packet,
         height: Height,
     ) -> Result<Option<Any>, LinkError> {
-        let proofs = self
-            .dst_chain()
-            .build_packet_proofs(
-                PacketMsgType::TimeoutOnClose,
-                &packet.destination_port,
-                &packet.destination_channel,
-                packet.sequence,
-                height,
-            )
-            .map_err(|e| LinkError::packet_proofs_constructor(self.dst_chain().id(), e))?;
+        let proofs = self.build_packet_proofs(
+            self.dst_chain(),
+            PacketMsgType::TimeoutOnClose,
+            &packet.destination_port,
+            &packet.destination_channel,
+            packet.sequence,
+            height,
+        )?;
 
         let msg = MsgTimeoutOnClose::new(
             packet.clone(),
@@ -1290,9 +2266,17 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     ) -> Result<(Option<Any>, Option<Any>), LinkError> {
         let timeout = self.build_timeout_from_send_packet_event(event, dst_info)?;
         if timeout.is_some() {
-            Ok((None, timeout))
-        } else {
+            if self.relay_timeouts {
+                Ok((None, timeout))
+            } else {
+                debug!("{} skipped, relaying of timeouts is disabled", event);
+                Ok((None, None))
+            }
+        } else if self.relay_recv_packets {
             Ok((self.build_recv_packet(&event.packet, event.height)?, None))
+        } else {
+            debug!("{} skipped, relaying of receive packets is disabled", event);
+            Ok((None, None))
         }
     }
 
@@ -1324,10 +2308,23 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         &mut self,
         mut operations: I,
         target_chain: OperationalDataTarget,
+        started: Instant,
     ) -> Result<VecDeque<OperationalData>, (VecDeque<OperationalData>, LinkError)> {
         let mut unprocessed = VecDeque::new();
 
         while let Some(od) = operations.next() {
+            if let Some(timeout) = self.execute_schedule_timeout {
+                if started.elapsed() >= timeout {
+                    // The schedule execution took too long; defer the current piece of
+                    // operational data, along with all of the subsequent pieces, to the
+                    // next schedule execution.
+                    unprocessed.push_back(od);
+                    unprocessed.extend(operations);
+
+                    return Err((unprocessed, LinkError::execute_schedule_timeout(timeout)));
+                }
+            }
+
             let elapsed_result = match target_chain {
                 OperationalDataTarget::Source => od.has_conn_delay_elapsed(
                     &|| self.src_time_latest(),
@@ -1344,6 +2341,66 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             match elapsed_result {
                 Ok(elapsed) => {
                     if elapsed {
+                        if let Some(max) = self.max_in_flight_txs {
+                            let in_flight = self.in_flight_txs();
+
+                            if in_flight >= max {
+                                // Too many transactions are awaiting confirmation; defer the
+                                // current piece of operational data, along with all of the
+                                // subsequent pieces, until some of them are confirmed.
+                                trace!(
+                                    "{} transaction(s) in flight, deferring the submission to the {} chain",
+                                    in_flight,
+                                    target_chain
+                                );
+
+                                unprocessed.push_back(od);
+                                unprocessed.extend(operations);
+
+                                return Ok(unprocessed);
+                            }
+                        }
+
+                        let rate_limiter = match target_chain {
+                            OperationalDataTarget::Source => &self.src_tx_rate_limiter,
+                            OperationalDataTarget::Destination => &self.dst_tx_rate_limiter,
+                        };
+
+                        if self.submission_in_flight.load(Ordering::SeqCst) {
+                            // A submission cut short by a previous schedule execution is still
+                            // running; defer the current piece of operational data, along with
+                            // all of the subsequent pieces, until it returns, so that the
+                            // submissions of this path do not race for the account sequence.
+                            trace!(
+                                "a submission is still in flight, deferring the submission to the {} chain",
+                                target_chain
+                            );
+
+                            unprocessed.push_back(od);
+                            unprocessed.extend(operations);
+
+                            return Ok(unprocessed);
+                        }
+
+                        if let Some(Err(delay)) = rate_limiter.as_ref().map(|l| l.try_acquire()) {
+                            // No transaction may be submitted to the target chain for now; defer
+                            // the current piece of operational data, along with all of the
+                            // subsequent pieces, to a later schedule execution.
+                            trace!(
+                                "transaction rate limit reached for {} chain, deferring for {:?}",
+                                target_chain,
+                                delay
+                            );
+
+                            self.tx_rate_limit_delay =
+                                Some(self.tx_rate_limit_delay.map_or(delay, |d| d.min(delay)));
+
+                            unprocessed.push_back(od);
+                            unprocessed.extend(operations);
+
+                            return Ok(unprocessed);
+                        }
+
                         // The current piece of operational data has elapsed; we can go ahead and
                         // attempt to relay it.
                         match self
@@ -1354,6 +2411,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                             // The relaying process failed; return all of the subsequent pieces of operational
                             // data along with the underlying error that occurred.
                             Err(e) => {
+                                // A submission cut short by the timeout may still be committed,
+                                // so its operational data is dropped as any other failing one
+                                // rather than rebroadcast, its packets being picked up again
+                                // by the next packet clearing if it is not.
                                 unprocessed.extend(operations);
 
                                 return Err((unprocessed, e));
@@ -1389,10 +2450,38 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     /// Any operational data items that do not get successfully relayed are
     /// dropped. Subsequent pending operational data items that went unprocessed
     /// are queued up again for re-submission.
+    ///
+    /// If an execute schedule timeout is set and elapses, the pending operational
+    /// data items which were not yet submitted are queued up again, and an
+    /// [`ExecuteScheduleTimeout`](error::LinkErrorDetail::ExecuteScheduleTimeout)
+    /// error is returned. A submission still running when the timeout elapses is
+    /// not waited for, but left to complete in the background, its operational
+    /// data being dropped, and the next schedule executions deferring all the
+    /// operational data until it returns.
     pub fn execute_schedule(&mut self) -> Result<(), LinkError> {
+        let started = Instant::now();
+
+        self.submission_deadline = self
+            .execute_schedule_timeout
+            .map(|timeout| started + timeout);
+        let result = self.execute_schedule_from(started);
+        self.submission_deadline = None;
+
+        result
+    }
+
+    fn execute_schedule_from(&mut self, started: Instant) -> Result<(), LinkError> {
+        if self.reconcile_schedule {
+            self.reconcile_schedule()?;
+        }
+
         let src_od_iter = self.src_operational_data.take().into_iter();
 
-        match self.execute_schedule_for_target_chain(src_od_iter, OperationalDataTarget::Source) {
+        match self.execute_schedule_for_target_chain(
+            src_od_iter,
+            OperationalDataTarget::Source,
+            started,
+        ) {
             Ok(unprocessed_src_data) => self.src_operational_data = unprocessed_src_data.into(),
             Err((unprocessed_src_data, e)) => {
                 self.src_operational_data = unprocessed_src_data.into();
@@ -1402,9 +2491,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         let dst_od_iter = self.dst_operational_data.take().into_iter();
 
-        match self
-            .execute_schedule_for_target_chain(dst_od_iter, OperationalDataTarget::Destination)
-        {
+        match self.execute_schedule_for_target_chain(
+            dst_od_iter,
+            OperationalDataTarget::Destination,
+            started,
+        ) {
             Ok(unprocessed_dst_data) => self.dst_operational_data = unprocessed_dst_data.into(),
             Err((unprocessed_dst_data, e)) => {
                 self.dst_operational_data = unprocessed_dst_data.into();
@@ -1415,6 +2506,174 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok(())
     }
 
+    /// Whether some of the transactions submitted by this path are yet to be confirmed.
+    pub fn has_pending_txs(&self) -> bool {
+        !self.pending_txs_src.pending_queue.is_empty()
+            || !self.pending_txs_dst.pending_queue.is_empty()
+    }
+
+    /// Whether this path has nothing left to relay for now: no operational data
+    /// scheduled, no transaction pending confirmation, and no event batch nor
+    /// acknowledgement held back.
+    pub fn is_idle(&self) -> bool {
+        self.src_operational_data.is_empty()
+            && self.dst_operational_data.is_empty()
+            && !self.has_pending_txs()
+            && self.grace_deferred_batches.is_empty()
+            && self
+                .ack_coalescer
+                .as_ref()
+                .map_or(true, |coalescer| coalescer.held_acks() == 0)
+    }
+
+    /// Counts the packets, to be received or timed out, and the acknowledgements
+    /// scheduled for relaying on this path, including the ones in flight.
+    pub fn count_scheduled_packets(&self) -> (u64, u64) {
+        self.scheduled_events()
+            .iter()
+            .fold((0, 0), |(packets, acks), event| match event {
+                IbcEvent::SendPacket(_) => (packets + 1, acks),
+                IbcEvent::WriteAcknowledgement(_) => (packets, acks + 1),
+                _ => (packets, acks),
+            })
+    }
+
+    /// The packet and channel events of the operational data scheduled, pending
+    /// confirmation or held back on this path, and of the event batches deferred
+    /// by the relay grace period, oldest first.
+    fn scheduled_events(&self) -> Vec<IbcEvent> {
+        let pending_od = |pending_txs_queue: VecDeque<pending::PendingData>| {
+            pending_txs_queue
+                .into_iter()
+                .map(|pending| pending.original_od)
+        };
+
+        let held_od = self
+            .ack_coalescer
+            .as_ref()
+            .map(|coalescer| coalescer.held())
+            .unwrap_or_default();
+
+        let od_events = pending_od(self.pending_txs_src.pending_queue.clone_vec())
+            .chain(pending_od(self.pending_txs_dst.pending_queue.clone_vec()))
+            .chain(held_od)
+            .chain(self.src_operational_data.clone_vec())
+            .chain(self.dst_operational_data.clone_vec())
+            .flat_map(|od| od.batch.into_iter().map(|msg| msg.event));
+
+        let deferred_events = self
+            .grace_deferred_batches
+            .iter()
+            .flat_map(|(_, batch)| batch.events.iter().cloned());
+
+        od_events
+            .chain(deferred_events)
+            .filter(is_restorable)
+            .collect()
+    }
+
+    /// Stores the events of the schedule of this path in the directory set by
+    /// [`Self::set_schedule_snapshot_dir`], at most once every
+    /// [`SCHEDULE_SNAPSHOT_INTERVAL`], so that [`Self::restore_schedule`] can resume
+    /// relaying them after a restart. Failing to store them is only logged, the
+    /// schedule being stored again at the next interval.
+    pub fn persist_schedule_if_due(&mut self) {
+        let dir = match &self.schedule_snapshot_dir {
+            Some(dir) => dir,
+            None => return,
+        };
+
+        let now = Instant::now();
+
+        let due = self.last_schedule_snapshot.map_or(true, |last| {
+            now.saturating_duration_since(last) >= SCHEDULE_SNAPSHOT_INTERVAL
+        });
+
+        if !due {
+            return;
+        }
+
+        let snapshot = ScheduleSnapshot::new(
+            self.src_chain().id(),
+            self.src_port_id().clone(),
+            *self.src_channel_id(),
+            self.scheduled_events(),
+        );
+
+        match snapshot.write_to_dir(dir) {
+            Ok(path) => trace!(
+                "stored the {} events of the schedule in '{}'",
+                snapshot.events.len(),
+                path.display()
+            ),
+            Err(e) => warn!("failed to store the schedule, will retry: {}", e),
+        }
+
+        self.last_schedule_snapshot = Some(now);
+    }
+
+    /// Schedules the operational data of the events stored by
+    /// [`Self::persist_schedule_if_due`] before a restart, if any.
+    ///
+    /// The operational data is generated as if the events were emitted at the latest
+    /// height of the source chain, so that the packets and acknowledgements which
+    /// were relayed in the meantime are dropped. The events deferred by the relay
+    /// grace period are scheduled at once, as their grace period was already waited.
+    ///
+    /// Returns the number of messages scheduled.
+    pub fn restore_schedule(&self) -> Result<usize, LinkError> {
+        let dir = match &self.schedule_snapshot_dir {
+            Some(dir) => dir,
+            None => return Ok(0),
+        };
+
+        let snapshot = ScheduleSnapshot::read_from_dir(
+            dir,
+            &self.src_chain().id(),
+            self.src_port_id(),
+            self.src_channel_id(),
+        )
+        .map_err(LinkError::schedule_snapshot)?;
+
+        let mut events: Vec<IbcEvent> = match snapshot {
+            Some(snapshot) => snapshot.events.into_iter().filter(is_restorable).collect(),
+            None => return Ok(0),
+        };
+
+        if events.is_empty() {
+            return Ok(0);
+        }
+
+        let height = self.src_latest_height()?;
+        for event in events.iter_mut() {
+            event.set_height(height);
+        }
+
+        let scheduled_msgs = |path: &Self| {
+            path.src_operational_data
+                .clone_vec()
+                .iter()
+                .chain(path.dst_operational_data.clone_vec().iter())
+                .map(|od| od.batch.len())
+                .sum::<usize>()
+        };
+
+        let before = scheduled_msgs(self);
+
+        self.events_to_operational_data(TrackedEvents::new(
+            events,
+            TrackingId::new_static("restored-schedule"),
+        ))?;
+
+        Ok(scheduled_msgs(self) - before)
+    }
+
+    /// The number of transactions submitted by this path to either chain
+    /// which are yet to be confirmed.
+    pub fn in_flight_txs(&self) -> usize {
+        self.pending_txs_src.pending_tx_count() + self.pending_txs_dst.pending_tx_count()
+    }
+
     /// Kicks off the process of relaying pending txs to the source and destination chains.
     ///
     /// See [`Resubmit::from_clear_interval`] for more info about the `resubmit` parameter.
@@ -1423,7 +2682,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             return RelaySummary::empty();
         }
 
-        let mut summary_src = self.process_pending_txs_src(resubmit).unwrap_or_else(|e| {
+        let summary_src = self.process_pending_txs_src(resubmit).unwrap_or_else(|e| {
             error!("error processing pending events in source chain: {}", e);
             RelaySummary::empty()
         });
@@ -1436,8 +2695,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             RelaySummary::empty()
         });
 
-        summary_src.extend(summary_dst);
-        summary_src
+        [summary_src, summary_dst].into_iter().collect()
     }
 
     fn process_pending_txs_src(&self, resubmit: Resubmit) -> Result<RelaySummary, LinkError> {
@@ -1450,7 +2708,12 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         let res = self
             .pending_txs_src
-            .process_pending(pending::TIMEOUT, self, do_resubmit)?
+            .process_pending(
+                pending::TIMEOUT,
+                self.confirmation_blocks,
+                self,
+                do_resubmit,
+            )?
             .unwrap_or_else(RelaySummary::empty);
 
         Ok(res)
@@ -1466,7 +2729,12 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         let res = self
             .pending_txs_dst
-            .process_pending(pending::TIMEOUT, self, do_resubmit)?
+            .process_pending(
+                pending::TIMEOUT,
+                self.confirmation_blocks,
+                self,
+                do_resubmit,
+            )?
             .unwrap_or_else(RelaySummary::empty);
 
         Ok(res)
@@ -1574,6 +2842,88 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok(())
     }
 
+    /// Drops the messages of the scheduled operational data which relay a packet
+    /// event already relayed by a previous message, eg. when the same packet was
+    /// scheduled both by packet clearing and from an incoming event.
+    ///
+    /// The messages targeting the destination chain are also reconciled against it,
+    /// dropping the packets which were already received and the acknowledgements
+    /// which were already relayed, with a single query for each.
+    pub fn reconcile_schedule(&self) -> Result<(), LinkError> {
+        let mut src_odata = self.src_operational_data.take();
+        retain_pending_messages(&mut src_odata, |_| true);
+        self.src_operational_data.replace(src_odata);
+
+        let mut dst_odata = self.dst_operational_data.take();
+
+        let mut packet_sequences = vec![];
+        let mut ack_sequences = vec![];
+        for msg in dst_odata.iter().flat_map(|od| od.batch.iter()) {
+            match &msg.event {
+                IbcEvent::SendPacket(e) => packet_sequences.push(e.packet.sequence),
+                IbcEvent::WriteAcknowledgement(e) => ack_sequences.push(e.packet.sequence),
+                _ => {}
+            }
+        }
+
+        let unreceived = |sequences: Vec<Sequence>, query_acks: bool| {
+            if sequences.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let result = if query_acks {
+                self.dst_chain()
+                    .query_unreceived_acknowledgements(QueryUnreceivedAcksRequest {
+                        port_id: self.dst_port_id().clone(),
+                        channel_id: *self.dst_channel_id(),
+                        packet_ack_sequences: sequences,
+                    })
+            } else {
+                self.dst_chain()
+                    .query_unreceived_packets(QueryUnreceivedPacketsRequest {
+                        port_id: self.dst_port_id().clone(),
+                        channel_id: *self.dst_channel_id(),
+                        packet_commitment_sequences: sequences,
+                    })
+            };
+
+            result.map_err(LinkError::relayer)
+        };
+
+        let (unreceived_packets, unreceived_acks) = match (
+            unreceived(packet_sequences, false),
+            unreceived(ack_sequences, true),
+        ) {
+            (Ok(packets), Ok(acks)) => (packets, acks),
+            (Err(e), _) | (_, Err(e)) => {
+                // Keep the schedule as it is, the messages are still valid.
+                self.dst_operational_data.replace(dst_odata);
+                return Err(e);
+            }
+        };
+
+        let before = dst_odata.iter().map(|od| od.batch.len()).sum::<usize>();
+
+        retain_pending_messages(&mut dst_odata, |event| match event {
+            IbcEvent::SendPacket(e) => unreceived_packets.contains(&e.packet.sequence),
+            IbcEvent::WriteAcknowledgement(e) => unreceived_acks.contains(&e.packet.sequence),
+            _ => true,
+        });
+
+        let after = dst_odata.iter().map(|od| od.batch.len()).sum::<usize>();
+        if after < before {
+            debug!(
+                "reconciled the schedule for {}, dropped {} messages already relayed",
+                self.dst_chain().id(),
+                before - after
+            );
+        }
+
+        self.dst_operational_data.replace(dst_odata);
+
+        Ok(())
+    }
+
     /// Adds a new operational data item for this relaying path to process later.
     /// If the relaying path has non-zero packet delays, this method also updates the client on the
     /// target chain with the appropriate headers.
@@ -1672,6 +3022,124 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok((elapsed_src_ods, elapsed_dst_ods))
     }
 
+    /// Attempts to recover the clients of this path that are expired or frozen,
+    /// by updating them with a header from the latest height of their source chain.
+    ///
+    /// Fails if any of the clients cannot be updated, or is still expired or frozen
+    /// after the update.
+    pub fn attempt_client_recovery(&self) -> Result<(), LinkError> {
+        let src_client = self.restore_src_client();
+        if src_client.is_expired_or_frozen() {
+            warn!(
+                "attempting recovery of expired or frozen client {} on source chain {}",
+                src_client.id(),
+                self.src_chain().id(),
+            );
+
+            src_client.update().map_err(LinkError::client)?;
+            src_client
+                .validated_client_state()
+                .map_err(LinkError::client)?;
+
+            info!(
+                "recovered client {} on source chain {}",
+                src_client.id(),
+                self.src_chain().id(),
+            );
+        }
+
+        let dst_client = self.restore_dst_client();
+        if dst_client.is_expired_or_frozen() {
+            warn!(
+                "attempting recovery of expired or frozen client {} on destination chain {}",
+                dst_client.id(),
+                self.dst_chain().id(),
+            );
+
+            dst_client.update().map_err(LinkError::client)?;
+            dst_client
+                .validated_client_state()
+                .map_err(LinkError::client)?;
+
+            info!(
+                "recovered client {} on destination chain {}",
+                dst_client.id(),
+                self.dst_chain().id(),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Re-fetches the latest client states of the clients of this path, along with
+    /// their consensus states at the latest client heights, so that the next messages
+    /// are built against the current state of the clients, eg. after they were
+    /// updated out-of-band.
+    ///
+    /// Fails if any of the client states cannot be fetched, or if any of the clients
+    /// is expired or frozen.
+    pub fn refresh_clients(&self) -> Result<(), LinkError> {
+        let src_client = self.restore_src_client();
+        let (src_client_state, _) = src_client
+            .validated_client_state()
+            .map_err(LinkError::client)?;
+
+        info!(
+            "refreshed client {} on source chain {} at height {}",
+            src_client.id(),
+            self.src_chain().id(),
+            src_client_state.latest_height(),
+        );
+
+        let dst_client = self.restore_dst_client();
+        let (dst_client_state, _) = dst_client
+            .validated_client_state()
+            .map_err(LinkError::client)?;
+
+        info!(
+            "refreshed client {} on destination chain {} at height {}",
+            dst_client.id(),
+            self.dst_chain().id(),
+            dst_client_state.latest_height(),
+        );
+
+        Ok(())
+    }
+
+    /// Updates the clients of this path whose trusting period ends within the client
+    /// refresh margin, if one is set and operational data is scheduled, so that no
+    /// client expires while the messages built against it are being relayed.
+    ///
+    /// The clients are only checked again once they may have entered the margin,
+    /// based on what was left of their trusting periods at the last check.
+    ///
+    /// Fails if any of the clients is expired or frozen, or cannot be updated.
+    pub fn refresh_clients_within_margin(&mut self) -> Result<(), LinkError> {
+        let margin = match self.client_refresh_margin {
+            Some(margin) => margin,
+            None => return Ok(()),
+        };
+
+        if self.src_operational_data.is_empty() && self.dst_operational_data.is_empty() {
+            return Ok(());
+        }
+
+        if matches!(self.next_client_refresh_check, Some(next) if Instant::now() < next) {
+            return Ok(());
+        }
+
+        let src_remaining = refresh_client_within_margin(&self.restore_src_client(), margin)?;
+        let dst_remaining = refresh_client_within_margin(&self.restore_dst_client(), margin)?;
+
+        self.next_client_refresh_check = src_remaining
+            .into_iter()
+            .chain(dst_remaining)
+            .min()
+            .map(|remaining| Instant::now() + remaining.saturating_sub(margin));
+
+        Ok(())
+    }
+
     fn restore_src_client(&self) -> ForeignClient<ChainA, ChainB> {
         ForeignClient::restore(
             self.src_client_id().clone(),
@@ -1715,3 +3183,485 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         }
     }
 }
+
+/// Updates the given client if its trusting period ends within the given `margin`,
+/// and returns what is left of its trusting period, if known.
+fn refresh_client_within_margin<DstChain: ChainHandle, SrcChain: ChainHandle>(
+    client: &ForeignClient<DstChain, SrcChain>,
+    margin: Duration,
+) -> Result<Option<Duration>, LinkError> {
+    let (client_state, elapsed) = client.validated_client_state().map_err(LinkError::client)?;
+
+    match trusting_period_remaining(&client_state, elapsed) {
+        Some(remaining) if remaining <= margin => {
+            warn!(
+                "client {} on chain {} expires in {:?}, within the refresh margin of {:?}: updating it",
+                client.id(),
+                client.dst_chain().id(),
+                remaining,
+                margin,
+            );
+
+            client.update().map_err(LinkError::client)?;
+
+            let (client_state, elapsed) =
+                client.validated_client_state().map_err(LinkError::client)?;
+
+            Ok(trusting_period_remaining(&client_state, elapsed))
+        }
+        remaining => Ok(remaining),
+    }
+}
+
+fn trusting_period_remaining(
+    client_state: &AnyClientState,
+    elapsed: Option<Duration>,
+) -> Option<Duration> {
+    let client_state = downcast!(client_state => AnyClientState::Tendermint)?;
+
+    Some(client_state.trusting_period.saturating_sub(elapsed?))
+}
+
+/// Whether the given event is one of the events from which operational data is
+/// generated, and can thus be stored with the schedule and restored from it.
+fn is_restorable(event: &IbcEvent) -> bool {
+    matches!(
+        event,
+        IbcEvent::SendPacket(_)
+            | IbcEvent::WriteAcknowledgement(_)
+            | IbcEvent::TimeoutPacket(_)
+            | IbcEvent::CloseInitChannel(_)
+    )
+}
+
+/// The packet of the given event, if it is to be relayed and its data
+/// exceeds `max_data_bytes`.
+///
+/// Only the packets of the `SendPacket` and `WriteAcknowledgement` events are
+/// checked, the timeout events being needed to close ordered channels.
+fn oversized_packet(event: &IbcEvent, max_data_bytes: Option<usize>) -> Option<&Packet> {
+    let max_data_bytes = max_data_bytes?;
+
+    let packet = match event {
+        IbcEvent::SendPacket(ev) => &ev.packet,
+        IbcEvent::WriteAcknowledgement(ev) => &ev.packet,
+        _ => return None,
+    };
+
+    if packet.data.len() > max_data_bytes {
+        Some(packet)
+    } else {
+        None
+    }
+}
+
+/// Submits the messages to the given chain, through the aggregator if any, waiting
+/// for the submission at most until the given deadline, if any, which is the one of
+/// a schedule execution bounded by the given timeout.
+///
+/// A submission still running at the deadline is left to complete in the background,
+/// and reported as an [`ExecuteScheduleTimeout`](error::LinkErrorDetail::ExecuteScheduleTimeout)
+/// error, so that the schedule execution returns and releases its link instead of
+/// stalling on a hung node. The `in_flight` flag is raised until the submission
+/// returns, the caller making no other submission meanwhile, so that at most one
+/// submission per path is left blocked on a hung node.
+fn submit_until<S: relay_sender::Submit, Chain: ChainHandle>(
+    aggregator: Option<&TxAggregator>,
+    chain: &Chain,
+    msgs: TrackedMsgs,
+    commit_height: Height,
+    deadline: Option<(Instant, Duration)>,
+    in_flight: &Arc<AtomicBool>,
+) -> Result<S::Reply, LinkError> {
+    let (deadline, timeout) = match deadline {
+        Some(deadline) => deadline,
+        None => return relay_sender::submit::<S>(aggregator, chain, msgs, commit_height),
+    };
+
+    let (sender, receiver) = crossbeam_channel::bounded(1);
+    let aggregator = aggregator.cloned();
+    let chain_id = chain.id();
+    let chain = chain.clone();
+
+    in_flight.store(true, Ordering::SeqCst);
+    let in_flight = in_flight.clone();
+
+    thread::spawn(move || {
+        let result = relay_sender::submit::<S>(aggregator.as_ref(), &chain, msgs, commit_height);
+        in_flight.store(false, Ordering::SeqCst);
+
+        // The schedule execution stops waiting for the result once past its deadline.
+        let _ = sender.send(result);
+    });
+
+    match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(LinkError::execute_schedule_timeout(timeout)),
+        Err(RecvTimeoutError::Disconnected) => Err(LinkError::submission_aborted(chain_id)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc::core::ics04_channel::events::{SendPacket, WriteAcknowledgement};
+    use ibc::core::ics04_channel::packet::Packet;
+    use ibc::events::IbcEvent;
+    use ibc::Height;
+
+    use core::time::Duration;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Instant;
+
+    use crossbeam_channel::Receiver;
+    use tendermint::abci::transaction::Hash as TxHash;
+    use tendermint::abci::Code;
+    use tendermint_rpc::endpoint::broadcast::tx_sync;
+
+    use ibc::core::ics04_channel::channel::Order;
+    use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
+    use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+    use ibc::proofs::Proofs;
+    use ibc_proto::google::protobuf::Any;
+
+    use crate::chain::handle::{BaseChainHandle, ChainRequest};
+    use crate::chain::tracking::{TrackedMsgs, TrackingId};
+    use crate::channel::{Channel, ChannelSide};
+    use crate::error::Error;
+    use crate::link::ack_coalescer::AckFlushPolicy;
+    use crate::link::error::{LinkError, LinkErrorDetail};
+    use crate::link::operational_data::{OperationalData, OperationalDataTarget, TransitMessage};
+    use crate::link::relay_sender::{AsyncReply, AsyncSender};
+
+    use super::{
+        oversized_packet, proof_query_retry_strategy, submit_until, HeightWindow, RelayPath,
+    };
+
+    fn send_packet(data_bytes: usize) -> IbcEvent {
+        IbcEvent::SendPacket(SendPacket {
+            height: Height::new(0, 1),
+            packet: Packet {
+                data: vec![0; data_bytes],
+                ..Default::default()
+            },
+        })
+    }
+
+    #[test]
+    fn packets_above_the_maximum_size_are_skipped() {
+        assert!(oversized_packet(&send_packet(1025), Some(1024)).is_some());
+        assert!(oversized_packet(&send_packet(1024), Some(1024)).is_none());
+        assert!(oversized_packet(&send_packet(1 << 20), None).is_none());
+    }
+
+    #[test]
+    fn query_heights_are_bounded_by_the_window() {
+        let window = HeightWindow::new(Some(Height::new(0, 5)), Some(Height::new(0, 10)));
+
+        assert_eq!(window.bound(Height::new(0, 20)), Height::new(0, 10));
+        assert_eq!(window.bound(Height::new(0, 7)), Height::new(0, 7));
+        assert_eq!(
+            HeightWindow::default().bound(Height::new(0, 20)),
+            Height::new(0, 20)
+        );
+    }
+
+    #[test]
+    fn stalled_submissions_are_cut_short_at_the_deadline() {
+        // A chain whose runtime never answers, as if its node hung.
+        let (sender, _receiver) = crossbeam_channel::unbounded();
+        let chain = BaseChainHandle::new(ChainId::new("ibc".to_string(), 0), sender);
+
+        let timeout = Duration::from_millis(200);
+        let started = Instant::now();
+        let in_flight = Arc::new(AtomicBool::new(false));
+
+        let result = submit_until::<AsyncSender, _>(
+            None,
+            &chain,
+            TrackedMsgs::new_static(vec![], "stalled"),
+            Height::new(0, 1),
+            Some((started + timeout, timeout)),
+            &in_flight,
+        );
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(matches!(
+            result.map(|_| ()).unwrap_err().detail(),
+            LinkErrorDetail::ExecuteScheduleTimeout(_)
+        ));
+
+        // The stalled submission is still running in the background.
+        assert!(in_flight.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn operational_data_is_deferred_while_a_submission_is_in_flight() {
+        let (mut path, _receiver) = relay_path(0);
+        path.submission_in_flight.store(true, Ordering::SeqCst);
+
+        let unprocessed = path
+            .execute_schedule_for_target_chain(
+                vec![operational_data(), operational_data()].into_iter(),
+                OperationalDataTarget::Destination,
+                Instant::now(),
+            )
+            .map_err(|(_, e)| e)
+            .unwrap();
+
+        assert_eq!(unprocessed.len(), 2);
+
+        // Once the submission returns, the operational data is submitted again.
+        path.submission_in_flight.store(false, Ordering::SeqCst);
+
+        let unprocessed = path
+            .execute_schedule_for_target_chain(
+                unprocessed.into_iter(),
+                OperationalDataTarget::Destination,
+                Instant::now(),
+            )
+            .map_err(|(_, e)| e)
+            .unwrap();
+
+        assert!(unprocessed.is_empty());
+    }
+
+    /// A relay path between two chains whose runtimes are served by the
+    /// returned receiver, retrying the failed proof queries `retries` times.
+    fn relay_path(
+        retries: u64,
+    ) -> (
+        RelayPath<BaseChainHandle, BaseChainHandle>,
+        Receiver<ChainRequest>,
+    ) {
+        relay_path_with(retries, false)
+    }
+
+    fn relay_path_with(
+        retries: u64,
+        with_tx_confirmation: bool,
+    ) -> (
+        RelayPath<BaseChainHandle, BaseChainHandle>,
+        Receiver<ChainRequest>,
+    ) {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+
+        let side = |chain_id: &str| {
+            ChannelSide::new(
+                BaseChainHandle::new(ChainId::new(chain_id.to_string(), 0), sender.clone()),
+                ClientId::default(),
+                ConnectionId::default(),
+                PortId::transfer(),
+                Some(ChannelId::default()),
+                None,
+            )
+        };
+
+        let channel = Channel {
+            ordering: Order::Unordered,
+            a_side: side("ibc-a"),
+            b_side: side("ibc-b"),
+            connection_delay: Duration::ZERO,
+        };
+
+        let mut path = RelayPath::new(channel, with_tx_confirmation).unwrap();
+        path.set_proof_query_retries(retries);
+
+        (path, receiver)
+    }
+
+    /// Serves the proof queries sent to the given receiver, failing the first
+    /// `failures` ones, and returns the number of queries once all the chain
+    /// handles are dropped.
+    fn serve_proof_queries(
+        receiver: Receiver<ChainRequest>,
+        failures: usize,
+    ) -> thread::JoinHandle<usize> {
+        thread::spawn(move || {
+            let mut queries = 0;
+
+            while let Ok(request) = receiver.recv() {
+                if let ChainRequest::BuildPacketProofs {
+                    height, reply_to, ..
+                } = request
+                {
+                    queries += 1;
+
+                    let reply = if queries <= failures {
+                        Err(Error::grpc())
+                    } else {
+                        Ok(
+                            Proofs::new(vec![1u8].try_into().unwrap(), None, None, None, height)
+                                .unwrap(),
+                        )
+                    };
+
+                    reply_to.send(reply).unwrap();
+                }
+            }
+
+            queries
+        })
+    }
+
+    fn build_recv_proofs(
+        path: &RelayPath<BaseChainHandle, BaseChainHandle>,
+    ) -> Result<Proofs, LinkError> {
+        path.build_packet_proofs(
+            path.src_chain(),
+            PacketMsgType::Recv,
+            &PortId::transfer(),
+            &ChannelId::default(),
+            Sequence::from(1),
+            Height::new(0, 1),
+        )
+    }
+
+    #[test]
+    fn failed_proof_queries_are_retried() {
+        let (path, receiver) = relay_path(3);
+        let runtime = serve_proof_queries(receiver, 2);
+
+        assert!(build_recv_proofs(&path).is_ok());
+
+        drop(path);
+        assert_eq!(runtime.join().unwrap(), 3);
+    }
+
+    #[test]
+    fn proof_queries_fail_once_the_retries_are_exhausted() {
+        let (path, receiver) = relay_path(2);
+        let runtime = serve_proof_queries(receiver, 5);
+
+        assert!(matches!(
+            build_recv_proofs(&path).unwrap_err().detail(),
+            LinkErrorDetail::PacketProofsConstructor(_)
+        ));
+
+        drop(path);
+        assert_eq!(runtime.join().unwrap(), 3);
+    }
+
+    #[test]
+    fn proof_query_backoff_is_bounded_overall() {
+        let delays = proof_query_retry_strategy(100).collect::<Vec<_>>();
+
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(300),
+                Duration::from_millis(400),
+                Duration::from_millis(500),
+                Duration::from_millis(500),
+            ]
+        );
+        assert_eq!(proof_query_retry_strategy(2).count(), 2);
+    }
+
+    fn operational_data() -> OperationalData {
+        OperationalData::new(
+            Height::new(0, 1),
+            OperationalDataTarget::Destination,
+            TrackingId::new_static("test"),
+            Duration::ZERO,
+        )
+    }
+
+    /// A relay path with a single transaction submitted to the destination
+    /// chain and awaiting confirmation, bounding the transactions in flight
+    /// to `max_in_flight_txs`.
+    fn relay_path_with_tx_in_flight(
+        max_in_flight_txs: usize,
+    ) -> (
+        RelayPath<BaseChainHandle, BaseChainHandle>,
+        Receiver<ChainRequest>,
+    ) {
+        let (mut path, receiver) = relay_path_with(0, true);
+        path.set_max_in_flight_txs(Some(max_in_flight_txs));
+
+        let reply = AsyncReply {
+            responses: vec![tx_sync::Response {
+                code: Code::Ok,
+                data: Default::default(),
+                log: Default::default(),
+                hash: TxHash::new([1; 32]),
+            }],
+            timings: None,
+        };
+
+        path.enqueue_pending_tx(reply, operational_data());
+        assert_eq!(path.in_flight_txs(), 1);
+
+        (path, receiver)
+    }
+
+    #[test]
+    fn operational_data_is_deferred_at_the_maximum_of_txs_in_flight() {
+        let (mut path, _receiver) = relay_path_with_tx_in_flight(1);
+
+        let unprocessed = path
+            .execute_schedule_for_target_chain(
+                vec![operational_data(), operational_data()].into_iter(),
+                OperationalDataTarget::Destination,
+                Instant::now(),
+            )
+            .map_err(|(_, e)| e)
+            .unwrap();
+
+        // Neither piece of operational data is submitted, nor made pending.
+        assert_eq!(unprocessed.len(), 2);
+        assert_eq!(path.in_flight_txs(), 1);
+    }
+
+    #[test]
+    fn operational_data_is_submitted_below_the_maximum_of_txs_in_flight() {
+        let (mut path, _receiver) = relay_path_with_tx_in_flight(2);
+
+        let unprocessed = path
+            .execute_schedule_for_target_chain(
+                vec![operational_data()].into_iter(),
+                OperationalDataTarget::Destination,
+                Instant::now(),
+            )
+            .map_err(|(_, e)| e)
+            .unwrap();
+
+        assert!(unprocessed.is_empty());
+    }
+
+    #[test]
+    fn held_acks_are_kept_when_their_flush_fails() {
+        let (mut path, receiver) = relay_path(0);
+        path.set_ack_flush_policy(AckFlushPolicy {
+            max_acks: 2,
+            max_delay: Duration::ZERO,
+        });
+
+        let mut od = operational_data();
+        for sequence in 1..=2 {
+            od.push(TransitMessage {
+                event: IbcEvent::WriteAcknowledgement(WriteAcknowledgement {
+                    height: Height::new(0, 1),
+                    packet: Packet {
+                        sequence: Sequence::from(sequence),
+                        ..Default::default()
+                    },
+                    ack: vec![1],
+                }),
+                msg: Any::default(),
+            });
+        }
+        path.dst_operational_data.push_back(od);
+
+        // The chain runtimes are gone, failing the generation of the coalesced
+        // operational data.
+        drop(receiver);
+
+        assert!(path.coalesce_acks().is_err());
+        assert!(path.dst_operational_data.is_empty());
+        assert_eq!(path.ack_coalescer.as_ref().unwrap().held_acks(), 2);
+    }
+}
@@ -4,27 +4,61 @@ use tendermint_rpc::endpoint::broadcast::tx_sync;
 use tracing::info;
 
 use ibc::events::{IbcEvent, PrettyEvents};
+use ibc::Height;
 
 use crate::chain::handle::ChainHandle;
 use crate::chain::tracking::TrackedMsgs;
 use crate::link::error::LinkError;
-use crate::link::RelaySummary;
+use crate::link::{RelaySummary, RelayTimings, TxAggregator};
 
 pub trait SubmitReply {
     fn empty() -> Self;
+
+    /// Records the time spent submitting the messages of the reply.
+    fn with_timings(self, timings: RelayTimings) -> Self;
 }
 
 impl SubmitReply for RelaySummary {
     fn empty() -> Self {
         RelaySummary::empty()
     }
+
+    fn with_timings(self, timings: RelayTimings) -> Self {
+        RelaySummary::with_timings(self, timings)
+    }
 }
 
 /// Captures the ability to submit messages to a chain.
-pub trait Submit {
-    type Reply: SubmitReply;
+pub trait Submit: 'static {
+    type Reply: SubmitReply + Send + 'static;
 
     fn submit(target: &impl ChainHandle, msgs: TrackedMsgs) -> Result<Self::Reply, LinkError>;
+
+    /// Submits the messages through the given aggregator, along with the messages
+    /// of other relay paths bound for the same chain. Senders which do not support
+    /// aggregation submit the messages on their own.
+    fn submit_aggregated(
+        _aggregator: &TxAggregator,
+        target: &impl ChainHandle,
+        msgs: TrackedMsgs,
+        _commit_height: Height,
+    ) -> Result<Self::Reply, LinkError> {
+        Self::submit(target, msgs)
+    }
+}
+
+/// Submits the messages to the `target` chain with the sender `S`,
+/// through the given aggregator, if any.
+pub fn submit<S: Submit>(
+    aggregator: Option<&TxAggregator>,
+    target: &impl ChainHandle,
+    msgs: TrackedMsgs,
+    commit_height: Height,
+) -> Result<S::Reply, LinkError> {
+    match aggregator {
+        Some(aggregator) => S::submit_aggregated(aggregator, target, msgs, commit_height),
+        None => S::submit(target, msgs),
+    }
 }
 
 /// Synchronous sender
@@ -61,11 +95,30 @@ impl Submit for SyncSender {
 
 pub struct AsyncReply {
     pub responses: Vec<tx_sync::Response>,
+    /// The time spent submitting the messages, if collected, the transactions
+    /// being yet to be confirmed.
+    pub timings: Option<RelayTimings>,
+}
+
+impl AsyncReply {
+    pub fn new(responses: Vec<tx_sync::Response>) -> Self {
+        Self {
+            responses,
+            timings: None,
+        }
+    }
 }
 
 impl SubmitReply for AsyncReply {
     fn empty() -> Self {
-        Self { responses: vec![] }
+        Self::new(vec![])
+    }
+
+    fn with_timings(self, timings: RelayTimings) -> Self {
+        Self {
+            timings: Some(timings),
+            ..self
+        }
     }
 }
 
@@ -80,11 +133,23 @@ impl Submit for AsyncSender {
         let a = target
             .send_messages_and_wait_check_tx(msgs)
             .map_err(LinkError::relayer)?;
-        let reply = AsyncReply { responses: a };
+        let reply = AsyncReply::new(a);
         info!("[Async~>{}] {}\n", target.id(), reply);
 
         Ok(reply)
     }
+
+    fn submit_aggregated(
+        aggregator: &TxAggregator,
+        target: &impl ChainHandle,
+        msgs: TrackedMsgs,
+        commit_height: Height,
+    ) -> Result<Self::Reply, LinkError> {
+        let reply = aggregator.submit(target, msgs, commit_height)?;
+        info!("[Async~>{}] aggregated {}\n", target.id(), reply);
+
+        Ok(reply)
+    }
 }
 
 impl fmt::Display for AsyncReply {
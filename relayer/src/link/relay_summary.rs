@@ -1,17 +1,91 @@
 use core::fmt;
+use core::ops::AddAssign;
+use core::time::Duration;
 
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId};
 use ibc::events::IbcEvent;
+use ibc::Height;
+use tendermint::abci::transaction::Hash as TxHash;
+
+use crate::chain::endpoint::TxCost;
+use crate::link::operational_data::OperationalData;
+
+/// The time spent in each phase of relaying the events of a [`RelaySummary`],
+/// summed over the transactions which emitted them.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RelayTimings {
+    /// Building the messages out of the events, including querying their proofs.
+    pub proof_construction: Duration,
+    /// Assembling the messages into transactions, along with the client updates.
+    pub message_assembly: Duration,
+    /// Submitting the transactions to the chain.
+    pub broadcast: Duration,
+    /// Waiting for the submitted transactions to be confirmed.
+    pub confirmation: Duration,
+}
+
+impl RelayTimings {
+    /// The phases, along with their name, in the order in which they happen.
+    pub fn phases(&self) -> [(&'static str, Duration); 4] {
+        [
+            ("proof_construction", self.proof_construction),
+            ("message_assembly", self.message_assembly),
+            ("broadcast", self.broadcast),
+            ("confirmation", self.confirmation),
+        ]
+    }
+}
+
+impl AddAssign for RelayTimings {
+    fn add_assign(&mut self, other: Self) {
+        self.proof_construction += other.proof_construction;
+        self.message_assembly += other.message_assembly;
+        self.broadcast += other.broadcast;
+        self.confirmation += other.confirmation;
+    }
+}
+
+impl fmt::Display for RelayTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let phases: Vec<String> = self
+            .phases()
+            .iter()
+            .map(|(phase, elapsed)| format!("{} {:?}", phase, elapsed))
+            .collect();
+
+        write!(f, "{}", phases.join(", "))
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct RelaySummary {
     pub events: Vec<IbcEvent>,
+    // The hash of the transaction which emitted each event, if known,
+    // at the same index as the event in `events`.
+    tx_hashes: Vec<Option<TxHash>>,
+    // The height of the event which triggered the relaying of each event, if
+    // recorded, at the same index as the event in `events`.
+    source_heights: Vec<Option<Height>>,
+    // The cost of each committed transaction which emitted the events.
+    tx_costs: Vec<(TxHash, TxCost)>,
+    // The time spent relaying the events, if collected.
+    timings: Option<RelayTimings>,
+    // The number of times the transactions relaying each packet of the events
+    // were submitted, if recorded.
+    attempts: Vec<u64>,
     // errors: todo!(),
-    // timings: todo!(),
 }
 
 impl RelaySummary {
     pub fn empty() -> Self {
-        Self { events: vec![] }
+        Self {
+            events: vec![],
+            tx_hashes: vec![],
+            source_heights: vec![],
+            tx_costs: vec![],
+            timings: None,
+            attempts: vec![],
+        }
     }
 
     pub fn is_empty(&self) -> bool {
@@ -19,11 +93,203 @@ impl RelaySummary {
     }
 
     pub fn from_events(events: Vec<IbcEvent>) -> Self {
-        Self { events }
+        Self {
+            events,
+            tx_hashes: vec![],
+            source_heights: vec![],
+            tx_costs: vec![],
+            timings: None,
+            attempts: vec![],
+        }
+    }
+
+    /// A summary of the events emitted by the transaction with the given hash.
+    pub fn from_tx_events(tx_hash: TxHash, events: Vec<IbcEvent>) -> Self {
+        Self {
+            tx_hashes: vec![Some(tx_hash); events.len()],
+            events,
+            source_heights: vec![],
+            tx_costs: vec![],
+            timings: None,
+            attempts: vec![],
+        }
+    }
+
+    /// A summary of the events emitted by the transaction with the given hash,
+    /// along with the cost of the transaction.
+    pub fn from_tx_result(tx_hash: TxHash, events: Vec<IbcEvent>, cost: TxCost) -> Self {
+        Self {
+            tx_costs: vec![(tx_hash, cost)],
+            ..Self::from_tx_events(tx_hash, events)
+        }
+    }
+
+    /// The cost of the committed transactions which emitted the events of this summary.
+    pub fn tx_costs(&self) -> &[(TxHash, TxCost)] {
+        &self.tx_costs
+    }
+
+    /// Records the time spent relaying the events of this summary.
+    pub fn with_timings(mut self, timings: RelayTimings) -> Self {
+        self.timings = Some(timings);
+        self
     }
 
-    pub fn extend(&mut self, other: RelaySummary) {
-        self.events.extend(other.events)
+    /// The time spent relaying the events of this summary, if collected.
+    pub fn timings(&self) -> Option<RelayTimings> {
+        self.timings
+    }
+
+    /// Records that the transactions relaying the packets of this summary were
+    /// submitted `attempts` times, once per packet event.
+    pub fn with_attempts(mut self, attempts: u64) -> Self {
+        let packets = self.events.iter().filter(|e| e.packet().is_some()).count();
+
+        self.attempts = vec![attempts; packets];
+        self
+    }
+
+    /// The number of times the transactions relaying each packet of this
+    /// summary were submitted, if recorded.
+    pub fn attempts(&self) -> &[u64] {
+        &self.attempts
+    }
+
+    /// The maximum number of times the transactions relaying a packet of this
+    /// summary were submitted, if recorded.
+    pub fn max_attempts(&self) -> Option<u64> {
+        self.attempts.iter().copied().max()
+    }
+
+    /// The average number of times the transactions relaying the packets of this
+    /// summary were submitted, if recorded.
+    pub fn average_attempts(&self) -> Option<f64> {
+        if self.attempts.is_empty() {
+            return None;
+        }
+
+        Some(self.attempts.iter().sum::<u64>() as f64 / self.attempts.len() as f64)
+    }
+
+    /// Iterates over the events of this summary, along with the hash
+    /// of the transaction which emitted them, if known.
+    pub fn events_with_tx_hashes(&self) -> impl Iterator<Item = (&IbcEvent, Option<TxHash>)> {
+        self.events
+            .iter()
+            .enumerate()
+            .map(|(i, event)| (event, self.tx_hashes.get(i).copied().flatten()))
+    }
+
+    /// Records the height of the event of the given operational data which
+    /// triggered the relaying of each packet event of this summary, ie. the
+    /// event of the operational data with the same packet.
+    pub fn with_source_heights(mut self, od: &OperationalData) -> Self {
+        let source_height = |event: &IbcEvent| {
+            let packet = event.packet()?;
+
+            od.batch.iter().find_map(|gm| {
+                let source = gm.event.packet()?;
+
+                let same_packet = source.sequence == packet.sequence
+                    && source.source_channel == packet.source_channel
+                    && source.source_port == packet.source_port;
+
+                same_packet.then(|| gm.event.height())
+            })
+        };
+
+        self.source_heights = self.events.iter().map(source_height).collect();
+        self
+    }
+
+    /// Iterates over the height of the event which triggered the relaying of
+    /// each event of this summary, if recorded, in the order of the events.
+    pub fn source_heights(&self) -> impl Iterator<Item = Option<Height>> + '_ {
+        (0..self.events.len()).map(|i| self.source_heights.get(i).copied().flatten())
+    }
+
+    /// The height of the most recent packet event in this summary, if any.
+    pub fn latest_packet_height(&self) -> Option<Height> {
+        self.events
+            .iter()
+            .filter(|e| e.packet().is_some())
+            .map(|e| e.height())
+            .max()
+    }
+
+    /// A compact, one-line description of the packets relayed in this summary,
+    /// eg. `relayed 3 recv, 2 ack, 0 timeout on ibc-0/channel-5`.
+    ///
+    /// Received packets are counted by their `WriteAcknowledgement` events, and
+    /// timeouts include timeouts on channel close. The number of attempts is
+    /// appended if some packet needed more than one, and the time spent in each
+    /// phase of the relaying if collected.
+    pub fn describe(&self, chain_id: &ChainId, channel_id: &ChannelId) -> String {
+        let count = |f: fn(&IbcEvent) -> bool| self.events.iter().filter(|e| f(e)).count();
+
+        let recv = count(|e| matches!(e, IbcEvent::WriteAcknowledgement(_)));
+        let ack = count(|e| matches!(e, IbcEvent::AcknowledgePacket(_)));
+        let timeout = count(|e| {
+            matches!(
+                e,
+                IbcEvent::TimeoutPacket(_) | IbcEvent::TimeoutOnClosePacket(_)
+            )
+        });
+
+        let mut description = format!(
+            "relayed {} recv, {} ack, {} timeout on {}/{}",
+            recv, ack, timeout, chain_id, channel_id
+        );
+
+        if let (Some(max), Some(average)) = (self.max_attempts(), self.average_attempts()) {
+            if max > 1 {
+                description = format!(
+                    "{} after up to {} attempts, {:.1} on average",
+                    description, max, average
+                );
+            }
+        }
+
+        match self.timings {
+            Some(timings) => format!("{} ({})", description, timings),
+            None => description,
+        }
+    }
+
+    /// Merges the `other` summary into this one, appending
+    /// its events after the events already in this summary.
+    pub fn merge(&mut self, mut other: RelaySummary) {
+        // Align the transaction hashes with the events before appending the other ones.
+        self.tx_hashes.resize(self.events.len(), None);
+        other.tx_hashes.resize(other.events.len(), None);
+        self.source_heights.resize(self.events.len(), None);
+        other.source_heights.resize(other.events.len(), None);
+
+        self.events.extend(other.events);
+        self.tx_hashes.extend(other.tx_hashes);
+        self.source_heights.extend(other.source_heights);
+        self.tx_costs.extend(other.tx_costs);
+        self.attempts.extend(other.attempts);
+
+        self.timings = match (self.timings, other.timings) {
+            (Some(mut timings), Some(other)) => {
+                timings += other;
+                Some(timings)
+            }
+            (timings, None) => timings,
+            (None, other) => other,
+        };
+    }
+}
+
+impl FromIterator<RelaySummary> for RelaySummary {
+    /// Merges all the given summaries, in order, into a single summary.
+    fn from_iter<I: IntoIterator<Item = RelaySummary>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(RelaySummary::empty(), |mut acc, summary| {
+                acc.merge(summary);
+                acc
+            })
     }
 }
 
@@ -36,3 +302,302 @@ impl fmt::Display for RelaySummary {
         write!(f, "total events = {}", self.events.len())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::{RelaySummary, RelayTimings};
+    use crate::chain::endpoint::TxCost;
+    use crate::chain::tracking::TrackingId;
+    use crate::link::operational_data::{OperationalData, OperationalDataTarget, TransitMessage};
+
+    use ibc::core::ics02_client::events::NewBlock;
+    use ibc::core::ics04_channel::events::{
+        AcknowledgePacket, SendPacket, TimeoutOnClosePacket, TimeoutPacket, WriteAcknowledgement,
+    };
+    use ibc::core::ics04_channel::packet::{Packet, Sequence};
+    use ibc::core::ics24_host::identifier::{ChainId, ChannelId};
+    use ibc::events::IbcEvent;
+    use ibc::Height;
+    use ibc_proto::google::protobuf::Any;
+    use tendermint::abci::transaction::Hash as TxHash;
+
+    fn new_block_events(heights: &[u64]) -> Vec<IbcEvent> {
+        heights
+            .iter()
+            .map(|h| IbcEvent::NewBlock(NewBlock::new(Height::new(0, *h))))
+            .collect()
+    }
+
+    fn heights(summary: &RelaySummary) -> Vec<u64> {
+        summary
+            .events
+            .iter()
+            .map(|e| e.height().revision_height)
+            .collect()
+    }
+
+    #[test]
+    fn merge_empty_summaries() {
+        let mut summary = RelaySummary::empty();
+        summary.merge(RelaySummary::empty());
+
+        assert!(summary.is_empty());
+    }
+
+    #[test]
+    fn merge_into_empty_summary() {
+        let mut summary = RelaySummary::empty();
+        summary.merge(RelaySummary::from_events(new_block_events(&[1, 2])));
+
+        assert_eq!(heights(&summary), vec![1, 2]);
+    }
+
+    #[test]
+    fn merge_empty_into_summary() {
+        let mut summary = RelaySummary::from_events(new_block_events(&[1, 2]));
+        summary.merge(RelaySummary::empty());
+
+        assert_eq!(heights(&summary), vec![1, 2]);
+    }
+
+    #[test]
+    fn merge_preserves_event_order() {
+        let mut summary = RelaySummary::from_events(new_block_events(&[3, 1]));
+        summary.merge(RelaySummary::from_events(new_block_events(&[2, 4])));
+
+        assert_eq!(heights(&summary), vec![3, 1, 2, 4]);
+    }
+
+    #[test]
+    fn from_iter_merges_in_order() {
+        let summary: RelaySummary = vec![
+            RelaySummary::from_events(new_block_events(&[5])),
+            RelaySummary::empty(),
+            RelaySummary::from_events(new_block_events(&[2, 7])),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(heights(&summary), vec![5, 2, 7]);
+    }
+
+    #[test]
+    fn from_iter_of_nothing_is_empty() {
+        let summary: RelaySummary = core::iter::empty().collect();
+
+        assert!(summary.is_empty());
+    }
+
+    #[test]
+    fn latest_packet_height_ignores_non_packet_events() {
+        let summary = RelaySummary::from_events(new_block_events(&[1, 2]));
+
+        assert_eq!(summary.latest_packet_height(), None);
+    }
+
+    #[test]
+    fn latest_packet_height_is_highest_packet_event_height() {
+        let timeout = |h| {
+            IbcEvent::TimeoutPacket(TimeoutPacket {
+                height: Height::new(0, h),
+                packet: Default::default(),
+            })
+        };
+
+        let mut events = new_block_events(&[9]);
+        events.extend([timeout(3), timeout(5), timeout(4)]);
+
+        let summary = RelaySummary::from_events(events);
+
+        assert_eq!(summary.latest_packet_height(), Some(Height::new(0, 5)));
+    }
+
+    #[test]
+    fn describe_counts_relayed_packets() {
+        let mut events = new_block_events(&[1]);
+        events.extend([
+            IbcEvent::WriteAcknowledgement(WriteAcknowledgement {
+                height: Height::new(0, 2),
+                packet: Default::default(),
+                ack: vec![],
+            }),
+            IbcEvent::AcknowledgePacket(AcknowledgePacket {
+                height: Height::new(0, 3),
+                packet: Default::default(),
+            }),
+            IbcEvent::AcknowledgePacket(AcknowledgePacket {
+                height: Height::new(0, 4),
+                packet: Default::default(),
+            }),
+            IbcEvent::TimeoutPacket(TimeoutPacket {
+                height: Height::new(0, 5),
+                packet: Default::default(),
+            }),
+            IbcEvent::TimeoutOnClosePacket(TimeoutOnClosePacket {
+                height: Height::new(0, 6),
+                packet: Default::default(),
+            }),
+        ]);
+
+        let summary = RelaySummary::from_events(events);
+
+        assert_eq!(
+            summary.describe(&ChainId::from_string("ibc-0"), &ChannelId::new(5)),
+            "relayed 1 recv, 2 ack, 2 timeout on ibc-0/channel-5"
+        );
+    }
+
+    #[test]
+    fn timings_are_summed_on_merge_and_described() {
+        let timings = RelayTimings {
+            proof_construction: Duration::from_millis(120),
+            message_assembly: Duration::from_millis(3),
+            broadcast: Duration::from_millis(40),
+            confirmation: Duration::from_secs(5),
+        };
+
+        let mut summary = RelaySummary::from_events(new_block_events(&[1])).with_timings(timings);
+        summary.merge(RelaySummary::from_events(new_block_events(&[2])));
+        assert_eq!(summary.timings(), Some(timings));
+
+        summary.merge(RelaySummary::empty().with_timings(timings));
+        assert_eq!(
+            summary.timings().map(|timings| timings.broadcast),
+            Some(Duration::from_millis(80))
+        );
+
+        assert_eq!(
+            summary.describe(&ChainId::from_string("ibc-0"), &ChannelId::new(5)),
+            "relayed 0 recv, 0 ack, 0 timeout on ibc-0/channel-5 \
+             (proof_construction 240ms, message_assembly 6ms, broadcast 80ms, confirmation 10s)"
+        );
+    }
+
+    #[test]
+    fn merge_keeps_tx_hashes_aligned_with_events() {
+        let hash = TxHash::new([1; 32]);
+
+        let mut summary = RelaySummary::from_events(new_block_events(&[1, 2]));
+        summary.merge(RelaySummary::from_tx_events(hash, new_block_events(&[3])));
+        summary.merge(RelaySummary::from_events(new_block_events(&[4])));
+
+        let tx_hashes: Vec<_> = summary
+            .events_with_tx_hashes()
+            .map(|(_, tx_hash)| tx_hash)
+            .collect();
+
+        assert_eq!(tx_hashes, vec![None, None, Some(hash), None]);
+    }
+
+    #[test]
+    fn merge_collects_tx_costs() {
+        let cost = |gas_used| TxCost {
+            gas_used,
+            fee: vec![],
+        };
+
+        let mut summary = RelaySummary::from_events(new_block_events(&[1]));
+        summary.merge(RelaySummary::from_tx_result(
+            TxHash::new([1; 32]),
+            new_block_events(&[2]),
+            cost(100),
+        ));
+        summary.merge(RelaySummary::from_tx_result(
+            TxHash::new([2; 32]),
+            vec![],
+            cost(200),
+        ));
+
+        let gas_used: Vec<_> = summary
+            .tx_costs()
+            .iter()
+            .map(|(tx_hash, cost)| (*tx_hash, cost.gas_used))
+            .collect();
+
+        assert_eq!(
+            gas_used,
+            vec![(TxHash::new([1; 32]), 100), (TxHash::new([2; 32]), 200)]
+        );
+    }
+
+    #[test]
+    fn describe_empty_summary() {
+        assert_eq!(
+            RelaySummary::empty().describe(&ChainId::from_string("ibc-1"), &ChannelId::new(0)),
+            "relayed 0 recv, 0 ack, 0 timeout on ibc-1/channel-0"
+        );
+    }
+
+    #[test]
+    fn source_heights_are_those_of_the_events_with_the_same_packet() {
+        let packet = |sequence: u64| Packet {
+            sequence: Sequence::from(sequence),
+            ..Default::default()
+        };
+
+        let mut od = OperationalData::new(
+            Height::new(0, 10),
+            OperationalDataTarget::Destination,
+            TrackingId::new_static("test"),
+            Duration::ZERO,
+        );
+
+        for (sequence, height) in [(1, 7), (2, 9)] {
+            od.push(TransitMessage {
+                event: IbcEvent::SendPacket(SendPacket {
+                    height: Height::new(0, height),
+                    packet: packet(sequence),
+                }),
+                msg: Any::default(),
+            });
+        }
+
+        let write_ack = |sequence| {
+            IbcEvent::WriteAcknowledgement(WriteAcknowledgement {
+                height: Height::new(0, 12),
+                packet: packet(sequence),
+                ack: vec![],
+            })
+        };
+
+        let mut events = new_block_events(&[12]);
+        events.extend([write_ack(2), write_ack(3)]);
+
+        let mut summary = RelaySummary::from_events(events).with_source_heights(&od);
+        summary.merge(RelaySummary::from_events(vec![write_ack(1)]));
+
+        assert_eq!(
+            summary.source_heights().collect::<Vec<_>>(),
+            vec![None, Some(Height::new(0, 9)), None, None]
+        );
+    }
+
+    #[test]
+    fn attempts_are_summarized_over_the_relayed_packets() {
+        let ack = |h| {
+            IbcEvent::AcknowledgePacket(AcknowledgePacket {
+                height: Height::new(0, h),
+                packet: Default::default(),
+            })
+        };
+
+        let mut summary = RelaySummary::from_events(new_block_events(&[1]));
+        assert_eq!(summary.max_attempts(), None);
+        assert_eq!(summary.average_attempts(), None);
+
+        summary.merge(RelaySummary::from_events(vec![ack(2)]).with_attempts(1));
+        summary.merge(RelaySummary::from_events(vec![ack(3), ack(4), ack(5)]).with_attempts(3));
+
+        assert_eq!(summary.attempts(), &[1, 3, 3, 3]);
+        assert_eq!(summary.max_attempts(), Some(3));
+        assert_eq!(summary.average_attempts(), Some(2.5));
+
+        assert_eq!(
+            summary.describe(&ChainId::from_string("ibc-0"), &ChannelId::new(5)),
+            "relayed 0 recv, 4 ack, 0 timeout on ibc-0/channel-5 \
+             after up to 3 attempts, 2.5 on average"
+        );
+    }
+}
@@ -0,0 +1,216 @@
+//! Storage of the schedule of a relay path, ie. of the events of its scheduled
+//! and pending operational data, so that a packet worker restarting on the path
+//! resumes where it left off instead of catching up with a full packet clearing.
+
+use core::time::Duration;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use flex_error::{define_error, TraceError};
+use serde::{Deserialize, Serialize};
+
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::events::IbcEvent;
+
+/// The version of the format of the schedule snapshots written by this relayer,
+/// to be bumped on any change of the format of [`ScheduleSnapshot`].
+pub const SCHEDULE_SNAPSHOT_VERSION: u32 = 1;
+
+/// The minimum interval between two snapshots of the schedule of a relay path.
+pub const SCHEDULE_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The subdirectory of the state directory in which the schedules are stored.
+pub const SCHEDULE_SNAPSHOT_FOLDER: &str = "schedules";
+
+define_error! {
+    ScheduleSnapshotError {
+        Io
+            { path: PathBuf }
+            [ TraceError<std::io::Error> ]
+            | e | { format_args!("I/O error on schedule snapshot '{}'", e.path.display()) },
+
+        Json
+            { path: PathBuf }
+            [ TraceError<serde_json::Error> ]
+            | e | { format_args!("invalid schedule snapshot '{}'", e.path.display()) },
+
+        UnsupportedVersion
+            { version: u32 }
+            | e | {
+                format_args!("unsupported schedule snapshot version {}, expected version {}",
+                    e.version, SCHEDULE_SNAPSHOT_VERSION)
+            },
+    }
+}
+
+/// The events of the operational data of a relay path, as stored on disk, as JSON.
+///
+/// The operational data themselves are not stored, as their messages carry proofs
+/// which may no longer be valid once restored. The events are instead turned into
+/// operational data again on restore, which checks that they are still pending.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleSnapshot {
+    pub version: u32,
+    pub src_chain_id: ChainId,
+    pub src_port_id: PortId,
+    pub src_channel_id: ChannelId,
+    pub events: Vec<IbcEvent>,
+}
+
+impl ScheduleSnapshot {
+    pub fn new(
+        src_chain_id: ChainId,
+        src_port_id: PortId,
+        src_channel_id: ChannelId,
+        events: Vec<IbcEvent>,
+    ) -> Self {
+        Self {
+            version: SCHEDULE_SNAPSHOT_VERSION,
+            src_chain_id,
+            src_port_id,
+            src_channel_id,
+            events,
+        }
+    }
+
+    /// The path of the file storing the schedule of the given relay path in the
+    /// given directory, unique per source chain, port and channel.
+    pub fn path(
+        dir: &Path,
+        src_chain_id: &ChainId,
+        src_port_id: &PortId,
+        src_channel_id: &ChannelId,
+    ) -> PathBuf {
+        dir.join(format!(
+            "{}-{}-{}.json",
+            src_chain_id, src_port_id, src_channel_id
+        ))
+    }
+
+    /// Writes the snapshot to its file in the given directory, creating the directory
+    /// if needed, and replacing the previous snapshot of the relay path at once.
+    pub fn write_to_dir(&self, dir: &Path) -> Result<PathBuf, ScheduleSnapshotError> {
+        let path = Self::path(
+            dir,
+            &self.src_chain_id,
+            &self.src_port_id,
+            &self.src_channel_id,
+        );
+
+        fs::create_dir_all(dir).map_err(|e| ScheduleSnapshotError::io(dir.into(), e))?;
+
+        let json = serde_json::to_string(self)
+            .map_err(|e| ScheduleSnapshotError::json(path.clone(), e))?;
+
+        // Write to a temporary file first, so that a crash never leaves a truncated snapshot.
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, json).map_err(|e| ScheduleSnapshotError::io(tmp_path.clone(), e))?;
+        fs::rename(&tmp_path, &path).map_err(|e| ScheduleSnapshotError::io(path.clone(), e))?;
+
+        Ok(path)
+    }
+
+    /// Reads the snapshot of the given relay path from the given directory,
+    /// if there is one, checking the version of its format.
+    pub fn read_from_dir(
+        dir: &Path,
+        src_chain_id: &ChainId,
+        src_port_id: &PortId,
+        src_channel_id: &ChannelId,
+    ) -> Result<Option<Self>, ScheduleSnapshotError> {
+        let path = Self::path(dir, src_chain_id, src_port_id, src_channel_id);
+
+        let json = match fs::read_to_string(&path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(ScheduleSnapshotError::io(path, e)),
+        };
+
+        Self::from_json(&path, &json).map(Some)
+    }
+
+    fn from_json(path: &Path, json: &str) -> Result<Self, ScheduleSnapshotError> {
+        #[derive(Deserialize)]
+        struct Versioned {
+            version: u32,
+        }
+
+        // Check the version first, as a snapshot in another format may not deserialize.
+        let Versioned { version } =
+            serde_json::from_str(json).map_err(|e| ScheduleSnapshotError::json(path.into(), e))?;
+
+        if version != SCHEDULE_SNAPSHOT_VERSION {
+            return Err(ScheduleSnapshotError::unsupported_version(version));
+        }
+
+        serde_json::from_str(json).map_err(|e| ScheduleSnapshotError::json(path.into(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use ibc::core::ics04_channel::events::SendPacket;
+    use ibc::core::ics04_channel::packet::{Packet, Sequence};
+    use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+    use ibc::events::IbcEvent;
+    use ibc::Height;
+
+    use super::{ScheduleSnapshot, ScheduleSnapshotErrorDetail, SCHEDULE_SNAPSHOT_VERSION};
+
+    fn snapshot() -> ScheduleSnapshot {
+        ScheduleSnapshot::new(
+            ChainId::new("ibc".to_string(), 0),
+            PortId::transfer(),
+            ChannelId::new(3),
+            vec![IbcEvent::SendPacket(SendPacket {
+                height: Height::new(0, 42),
+                packet: Packet {
+                    sequence: Sequence::from(7),
+                    ..Default::default()
+                },
+            })],
+        )
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_json() {
+        let json = serde_json::to_string(&snapshot()).unwrap();
+
+        assert_eq!(
+            ScheduleSnapshot::from_json(Path::new("schedule.json"), &json).unwrap(),
+            snapshot()
+        );
+    }
+
+    #[test]
+    fn snapshots_are_stored_per_path() {
+        let snapshot = snapshot();
+        let path = ScheduleSnapshot::path(
+            Path::new("/state"),
+            &snapshot.src_chain_id,
+            &snapshot.src_port_id,
+            &snapshot.src_channel_id,
+        );
+
+        assert_eq!(path, Path::new("/state/ibc-0-transfer-channel-3.json"));
+    }
+
+    #[test]
+    fn other_versions_are_rejected() {
+        let json = serde_json::to_string(&ScheduleSnapshot {
+            version: SCHEDULE_SNAPSHOT_VERSION + 1,
+            ..snapshot()
+        })
+        .unwrap();
+
+        let e = ScheduleSnapshot::from_json(Path::new("schedule.json"), &json).unwrap_err();
+
+        assert!(matches!(
+            e.detail(),
+            ScheduleSnapshotErrorDetail::UnsupportedVersion(_)
+        ));
+    }
+}
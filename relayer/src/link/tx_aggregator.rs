@@ -0,0 +1,453 @@
+use core::time::Duration;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crossbeam_channel::{RecvTimeoutError, Sender};
+use ibc_proto::google::protobuf::Any;
+use once_cell::sync::Lazy;
+use tendermint_rpc::endpoint::broadcast::tx_sync;
+use tracing::{debug, warn};
+
+use ibc::core::ics24_host::identifier::ChainId;
+use ibc::Height;
+
+use crate::chain::handle::ChainHandle;
+use crate::chain::tracking::{TrackedMsgs, TrackingId};
+use crate::error::Error;
+use crate::link::error::LinkError;
+use crate::link::relay_sender::AsyncReply;
+
+/// The aggregators created so far, by aggregation window and ordering, so that
+/// all the relay paths configured with the same window share an aggregator.
+static TX_AGGREGATORS: Lazy<Mutex<HashMap<(Duration, bool), TxAggregator>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// How long a relay path waits for the submission of its batch once the
+/// aggregation window has elapsed, before giving up on the batch.
+const BATCH_SUBMIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The responses to the submission of the messages of a contribution,
+/// as handed to the relay path which contributed them.
+type BatchResult = Result<Vec<tx_sync::Response>, Error>;
+
+/// The chain and the key the messages of a batch are submitted to and signed with.
+type BatchKey = (ChainId, Option<String>);
+
+/// The messages submitted by a relay path to an aggregated batch, which
+/// are kept together, as they may start with the update of a client.
+#[derive(Debug)]
+struct Contribution {
+    /// The lowest height at which the events the messages relay were committed.
+    commit_height: Height,
+    msgs: Vec<Any>,
+    /// The heights of the events the messages relay, to be recorded in the memo.
+    source_heights: Vec<Height>,
+}
+
+/// The messages bound for the same chain and signed with the same key, collected
+/// so far from the relay paths, along with the channels to hand them their result.
+type Batch = Vec<(Contribution, Sender<BatchResult>)>;
+
+/// Aggregates the messages submitted within a short window by the relay paths
+/// bound for the same chain, eg. the paths of several channels between the same
+/// pair of chains, and submits them together, reducing the number of transactions
+/// and thus the pressure on the account sequence of the relayer.
+///
+/// The first relay path submitting messages opens a batch, which is submitted by
+/// a thread of its own once the window has elapsed, so that no relay path holds
+/// its link while the window elapses. Each relay path waits for the result of its
+/// own contribution, for at most a fixed timeout past the window.
+///
+/// If the submission of the whole batch fails, eg. because one of its messages
+/// fails the simulation, the contributions are submitted one by one, so that the
+/// failure of a channel does not fail the others, and each relay path gets the
+/// error of its own messages.
+///
+/// The messages of the batch are submitted in the order the paths contributed them,
+/// unless `commit_order` is set, in which case the contributions are ordered by the
+/// height at which the events they relay were committed on their source chain, so that
+/// the oldest packets are relayed first regardless of their channel. The messages of
+/// each contribution are kept contiguous and in order in both cases, which preserves
+/// the order of the packets of ordered channels.
+#[derive(Clone)]
+pub struct TxAggregator {
+    window: Duration,
+    commit_order: bool,
+    batches: Arc<Mutex<HashMap<BatchKey, Batch>>>,
+}
+
+impl TxAggregator {
+    pub fn new(window: Duration, commit_order: bool) -> Self {
+        Self {
+            window,
+            commit_order,
+            batches: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Returns the aggregator shared by all the relay paths aggregating
+    /// their messages within the given `window`, in the given order.
+    pub fn shared(window: Duration, commit_order: bool) -> Self {
+        TX_AGGREGATORS
+            .lock()
+            .unwrap()
+            .entry((window, commit_order))
+            .or_insert_with(|| Self::new(window, commit_order))
+            .clone()
+    }
+
+    /// Submits the given messages to the `target` chain, along with the messages
+    /// submitted by other relay paths to the same chain, with the same key,
+    /// within the aggregation window.
+    ///
+    /// The `commit_height` is the lowest height at which the events relayed by
+    /// the messages were committed on their source chain.
+    ///
+    /// Blocks until the batch the messages were added to has been submitted,
+    /// and returns the responses for the batch the messages were submitted in.
+    pub fn submit<Chain: ChainHandle>(
+        &self,
+        target: &Chain,
+        msgs: TrackedMsgs,
+        commit_height: Height,
+    ) -> Result<AsyncReply, LinkError> {
+        let key = (target.id(), msgs.key_name.clone());
+        let (sender, receiver) = crossbeam_channel::bounded(1);
+
+        let contribution = Contribution {
+            commit_height,
+            msgs: msgs.msgs,
+            source_heights: msgs.source_heights,
+        };
+
+        {
+            let mut batches = self.batches.lock().unwrap();
+
+            match batches.get_mut(&key) {
+                // Another relay path opened a batch for this chain and key, contribute to it.
+                Some(batch) => batch.push((contribution, sender)),
+                None => {
+                    batches.insert(key.clone(), vec![(contribution, sender)]);
+                    self.spawn_batch_submission(target.clone(), key, msgs.tracking_id);
+                }
+            }
+        }
+
+        match receiver.recv_timeout(self.window + BATCH_SUBMIT_TIMEOUT) {
+            Ok(result) => result.map(AsyncReply::new).map_err(LinkError::relayer),
+            Err(RecvTimeoutError::Timeout) => Err(LinkError::aggregated_submit(format!(
+                "the batch was not submitted within {:?}",
+                self.window + BATCH_SUBMIT_TIMEOUT
+            ))),
+            Err(RecvTimeoutError::Disconnected) => Err(LinkError::aggregated_submit(
+                "the batch was dropped".to_string(),
+            )),
+        }
+    }
+
+    /// Spawns the thread submitting the batch opened for the given chain and key
+    /// once the aggregation window has elapsed.
+    fn spawn_batch_submission<Chain: ChainHandle>(
+        &self,
+        target: Chain,
+        key: BatchKey,
+        tracking_id: TrackingId,
+    ) {
+        let aggregator = self.clone();
+
+        thread::spawn(move || {
+            thread::sleep(aggregator.window);
+
+            // The relay paths submitting from now on open a new batch.
+            let batch = aggregator.batches.lock().unwrap().remove(&key);
+
+            match batch {
+                Some(batch) => aggregator.submit_batch(&target, key.1, tracking_id, batch),
+                None => warn!("the aggregated batch for {} was already submitted", key.0),
+            }
+        });
+    }
+
+    /// Submits the given batch to the `target` chain, and hands each contributor
+    /// the result of the submission of its messages.
+    fn submit_batch(
+        &self,
+        target: &impl ChainHandle,
+        key_name: Option<String>,
+        tracking_id: TrackingId,
+        mut batch: Batch,
+    ) {
+        order_contributions(&mut batch, self.commit_order);
+
+        debug!(
+            "submitting to {} a batch aggregating the messages of {} relay path(s)",
+            target.id(),
+            batch.len()
+        );
+
+        let contributions: Vec<&Contribution> =
+            batch.iter().map(|(contribution, _)| contribution).collect();
+
+        let result = send_contributions(target, &key_name, tracking_id, &contributions);
+
+        // The contributors only stop waiting if they timed out or their worker was shut down.
+        match result {
+            Ok(responses) => {
+                for (_, contributor) in batch {
+                    let _ = contributor.send(Ok(responses.clone()));
+                }
+            }
+            Err(e) if batch.len() == 1 => {
+                if let Some((_, contributor)) = batch.pop() {
+                    let _ = contributor.send(Err(e));
+                }
+            }
+            Err(e) => {
+                warn!(
+                    "failed to submit to {} the aggregated batch of {} relay paths, \
+                    submitting their messages one by one: {}",
+                    target.id(),
+                    batch.len(),
+                    e
+                );
+
+                for (contribution, contributor) in batch {
+                    let result =
+                        send_contributions(target, &key_name, tracking_id, &[&contribution]);
+
+                    let _ = contributor.send(result);
+                }
+            }
+        }
+    }
+}
+
+/// Submits the messages of the given contributions in a single transaction.
+fn send_contributions(
+    target: &impl ChainHandle,
+    key_name: &Option<String>,
+    tracking_id: TrackingId,
+    contributions: &[&Contribution],
+) -> BatchResult {
+    let msgs = contributions
+        .iter()
+        .flat_map(|contribution| contribution.msgs.iter().cloned())
+        .collect();
+
+    let source_heights = contributions
+        .iter()
+        .flat_map(|contribution| contribution.source_heights.iter().copied())
+        .collect();
+
+    target.send_messages_and_wait_check_tx(
+        TrackedMsgs::new(msgs, tracking_id)
+            .with_key_name(key_name.clone())
+            .with_source_heights(source_heights),
+    )
+}
+
+/// Orders the given contributions in the order they were contributed or, with
+/// `commit_order`, by their commit height. The sort is stable, so that the
+/// contributions committed at the same height keep their order.
+fn order_contributions<T>(contributions: &mut [(Contribution, T)], commit_order: bool) {
+    if commit_order {
+        contributions.sort_by_key(|(contribution, _)| contribution.commit_height);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::thread;
+
+    use crossbeam_channel::Receiver;
+    use ibc::core::ics24_host::identifier::ChainId;
+    use ibc::Height;
+    use ibc_proto::google::protobuf::Any;
+
+    use crate::chain::handle::{BaseChainHandle, ChainRequest};
+    use crate::chain::tracking::TrackedMsgs;
+    use crate::error::Error;
+    use crate::link::error::{LinkError, LinkErrorDetail};
+
+    use super::{order_contributions, Contribution, TxAggregator};
+
+    fn msg(channel: &str, sequence: u64) -> Any {
+        Any {
+            type_url: format!("{}/{}", channel, sequence),
+            value: Vec::new(),
+        }
+    }
+
+    fn contribution(height: u64, msgs: Vec<Any>) -> (Contribution, ()) {
+        let contribution = Contribution {
+            commit_height: Height::new(0, height),
+            msgs,
+            source_heights: vec![],
+        };
+
+        (contribution, ())
+    }
+
+    fn order(msgs: Vec<Any>) -> Vec<String> {
+        msgs.into_iter().map(|msg| msg.type_url).collect()
+    }
+
+    fn concat(mut contributions: Vec<(Contribution, ())>, commit_order: bool) -> Vec<Any> {
+        order_contributions(&mut contributions, commit_order);
+
+        contributions
+            .into_iter()
+            .flat_map(|(contribution, _)| contribution.msgs)
+            .collect()
+    }
+
+    fn two_channels() -> Vec<(Contribution, ())> {
+        vec![
+            // The contributions of channel-0, then those of channel-1.
+            contribution(5, vec![msg("channel-0", 1), msg("channel-0", 2)]),
+            contribution(9, vec![msg("channel-0", 3)]),
+            contribution(3, vec![msg("channel-1", 1)]),
+            contribution(7, vec![msg("channel-1", 2), msg("channel-1", 3)]),
+        ]
+    }
+
+    /// Spawns a chain runtime answering the submissions of messages, which fail
+    /// if they include the `failing` message, and reporting the messages of each
+    /// transaction submitted to it.
+    fn spawn_chain(failing: Option<&'static str>) -> (BaseChainHandle, Receiver<Vec<String>>) {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let (submitted_sender, submitted) = crossbeam_channel::unbounded();
+
+        thread::spawn(move || {
+            for request in receiver {
+                if let ChainRequest::SendMessagesAndWaitCheckTx {
+                    tracked_msgs,
+                    reply_to,
+                } = request
+                {
+                    let msgs = order(tracked_msgs.msgs);
+                    let fails = msgs.iter().any(|msg| Some(msg.as_str()) == failing);
+
+                    submitted_sender.send(msgs).unwrap();
+
+                    if fails {
+                        reply_to.send(Err(Error::grpc())).unwrap();
+                    } else {
+                        reply_to.send(Ok(vec![])).unwrap();
+                    }
+                }
+            }
+        });
+
+        let chain = BaseChainHandle::new(ChainId::new("ibc".to_string(), 0), sender);
+
+        (chain, submitted)
+    }
+
+    /// Submits the messages of two channels through the given aggregator, the
+    /// second within the aggregation window opened by the first, and returns
+    /// the result of each submission.
+    fn submit_two_channels(
+        aggregator: &TxAggregator,
+        chain: &BaseChainHandle,
+    ) -> Vec<Result<(), LinkError>> {
+        let submissions: Vec<_> = ["channel-0", "channel-1"]
+            .iter()
+            .map(|channel| {
+                let aggregator = aggregator.clone();
+                let chain = chain.clone();
+                let msgs = TrackedMsgs::new_static(vec![msg(channel, 1)], "test");
+
+                let submission = thread::spawn(move || {
+                    aggregator
+                        .submit(&chain, msgs, Height::new(0, 1))
+                        .map(|_| ())
+                });
+
+                // Let the first relay path open the batch.
+                thread::sleep(Duration::from_millis(50));
+
+                submission
+            })
+            .collect();
+
+        submissions
+            .into_iter()
+            .map(|submission| submission.join().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn commit_order_relays_oldest_packets_first_across_channels() {
+        let msgs = concat(two_channels(), true);
+
+        assert_eq!(
+            order(msgs),
+            vec![
+                "channel-1/1",
+                "channel-0/1",
+                "channel-0/2",
+                "channel-1/2",
+                "channel-1/3",
+                "channel-0/3",
+            ]
+        );
+    }
+
+    #[test]
+    fn contribution_order_is_kept_by_default() {
+        let msgs = concat(two_channels(), false);
+
+        assert_eq!(
+            order(msgs),
+            vec![
+                "channel-0/1",
+                "channel-0/2",
+                "channel-0/3",
+                "channel-1/1",
+                "channel-1/2",
+                "channel-1/3",
+            ]
+        );
+    }
+
+    #[test]
+    fn contributions_within_the_window_are_submitted_together() {
+        let (chain, submitted) = spawn_chain(None);
+        let aggregator = TxAggregator::new(Duration::from_millis(500), false);
+
+        let results = submit_two_channels(&aggregator, &chain);
+
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(
+            submitted.try_iter().collect::<Vec<_>>(),
+            vec![vec!["channel-0/1", "channel-1/1"]]
+        );
+    }
+
+    #[test]
+    fn failing_contribution_only_fails_its_own_relay_path() {
+        let (chain, submitted) = spawn_chain(Some("channel-1/1"));
+        let aggregator = TxAggregator::new(Duration::from_millis(500), false);
+
+        let results = submit_two_channels(&aggregator, &chain);
+
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1].as_ref().map_err(|e| e.detail()),
+            Err(LinkErrorDetail::Relayer(_))
+        ));
+
+        // The failed batch is submitted again, one contribution at a time.
+        assert_eq!(
+            submitted.try_iter().collect::<Vec<_>>(),
+            vec![
+                vec!["channel-0/1", "channel-1/1"],
+                vec!["channel-0/1"],
+                vec!["channel-1/1"],
+            ]
+        );
+    }
+}
@@ -46,6 +46,11 @@ impl<Chain: ChainHandle> Registry<Chain> {
         self.handles.len()
     }
 
+    /// Return the handle of the runtime spawned for the given chain, if any.
+    pub fn get(&self, chain_id: &ChainId) -> Option<&Chain> {
+        self.handles.get(chain_id)
+    }
+
     /// Return an iterator overall the chain handles managed by the registry.
     pub fn chains(&self) -> impl Iterator<Item = &Chain> {
         self.handles.values()
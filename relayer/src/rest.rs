@@ -1,11 +1,14 @@
 use crossbeam_channel::TryRecvError;
 use tracing::{error, trace};
 
+use ibc::core::ics24_host::identifier::ChainId;
+
 use crate::{
+    account::AccountInfo,
     config::Config,
     rest::request::ReplySender,
-    rest::request::{Request, VersionInfo},
-    supervisor::dump_state::SupervisorState,
+    rest::request::{AccountSequenceParams, Request, VersionInfo},
+    supervisor::{dump_state::SupervisorState, reload::ReloadReport},
 };
 
 pub mod request;
@@ -31,6 +34,8 @@ pub type Receiver = crossbeam_channel::Receiver<Request>;
 //  e.g., adjusting chain config, removing chains, etc.
 pub enum Command {
     DumpState(ReplySender<SupervisorState>),
+    Reload(Box<Config>, ReplySender<ReloadReport>),
+    AccountSequence(ChainId, AccountSequenceParams, ReplySender<AccountInfo>),
 }
 
 /// Process incoming REST requests.
@@ -82,6 +87,22 @@ pub fn process_incoming_requests(config: &Config, channel: &Receiver) -> Option<
 
                 return Some(Command::DumpState(reply_to));
             }
+
+            Request::Reload { config, reply_to } => {
+                trace!("Reload");
+
+                return Some(Command::Reload(config, reply_to));
+            }
+
+            Request::AccountSequence {
+                chain_id,
+                params,
+                reply_to,
+            } => {
+                trace!("AccountSequence {}", chain_id);
+
+                return Some(Command::AccountSequence(chain_id, params, reply_to));
+            }
         },
         Err(e) => {
             if !matches!(e, TryRecvError::Empty) {
@@ -23,6 +23,15 @@ pub enum RestApiError {
     #[error("failed while parsing the request body into a chain configuration: {0}")]
     InvalidChainConfig(String),
 
+    #[error("failed while parsing the request body: {0}")]
+    InvalidRequestBody(String),
+
+    #[error("the chain runtime of chain {0} failed: {1}")]
+    ChainRuntime(ChainId, String),
+
+    #[error("failed while parsing the request body into a configuration: {0}")]
+    InvalidConfig(String),
+
     #[error("not implemented")]
     Unimplemented,
 }
@@ -36,6 +45,9 @@ impl RestApiError {
             RestApiError::ChainConfigNotFound(_) => "ChainConfigNotFound",
             RestApiError::InvalidChainId(_, _) => "InvalidChainId",
             RestApiError::InvalidChainConfig(_) => "InvalidChainConfig",
+            RestApiError::InvalidRequestBody(_) => "InvalidRequestBody",
+            RestApiError::ChainRuntime(_, _) => "ChainRuntime",
+            RestApiError::InvalidConfig(_) => "InvalidConfig",
             RestApiError::Unimplemented => "Unimplemented",
         }
     }
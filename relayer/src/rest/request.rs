@@ -1,8 +1,13 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use ibc::core::ics24_host::identifier::ChainId;
 
-use crate::{config::ChainConfig, rest::RestApiError, supervisor::dump_state::SupervisorState};
+use crate::{
+    account::AccountInfo,
+    config::{ChainConfig, Config},
+    rest::RestApiError,
+    supervisor::{dump_state::SupervisorState, reload::ReloadReport},
+};
 
 pub type ReplySender<T> = crossbeam_channel::Sender<Result<T, RestApiError>>;
 pub type ReplyReceiver<T> = crossbeam_channel::Receiver<Result<T, RestApiError>>;
@@ -17,6 +22,17 @@ pub struct VersionInfo {
     pub version: String,
 }
 
+/// The parameters of a request for the account sequence of a chain.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountSequenceParams {
+    /// The key of the account, defaults to the `key_name` of the chain configuration
+    #[serde(default)]
+    pub key_name: Option<String>,
+    /// Whether to clear the account sequence cached by the relayer
+    #[serde(default)]
+    pub reset: bool,
+}
+
 /// REST API request variants
 #[derive(Clone, Debug)]
 pub enum Request {
@@ -36,4 +52,15 @@ pub enum Request {
         chain_id: ChainId,
         reply_to: ReplySender<ChainConfig>,
     },
+
+    Reload {
+        config: Box<Config>,
+        reply_to: ReplySender<ReloadReport>,
+    },
+
+    AccountSequence {
+        chain_id: ChainId,
+        params: AccountSequenceParams,
+        reply_to: ReplySender<AccountInfo>,
+    },
 }
@@ -2,6 +2,8 @@ use flex_error::define_error;
 use tendermint::abci::Code;
 use tendermint_rpc::endpoint::broadcast::tx_commit::TxResult;
 
+use crate::chain::endpoint::SimulationError;
+
 // Provides mapping for errors returned from ibc-go and cosmos-sdk
 define_error! {
     SdkError {
@@ -189,3 +191,96 @@ pub fn sdk_error_from_tx_sync_error_code(code: u32) -> SdkError {
         _ => SdkError::unknown_sdk(code),
     }
 }
+
+/// The description of the common errors of the `sdk` codespace, as found in
+/// error messages, along with a human-readable reason for the failure, by code.
+/// Cf: <https://github.com/cosmos/cosmos-sdk/blob/v0.44.5/types/errors/errors.go>
+const SDK_ERRORS: &[(u32, &str, &str)] = &[
+    (2, "tx parse error", "the transaction could not be decoded by the chain"),
+    (3, "invalid sequence", "the signature sequence of the transaction is invalid"),
+    (4, "unauthorized", "the signer is not authorized to submit the messages"),
+    (5, "insufficient funds", "the relayer account cannot pay for the transaction, check its balance"),
+    (6, "unknown request", "the chain does not support the messages of the transaction"),
+    (7, "invalid address", "an address in the transaction is invalid, check the `account_prefix` in the Hermes config.toml"),
+    (8, "invalid pubkey", "the public key of the relayer account is invalid, check the `address_type` in the Hermes config.toml"),
+    (9, "unknown address", "the relayer account does not exist on chain, it must be funded first"),
+    (10, "invalid coins", "the coins in the transaction are invalid"),
+    (11, "out of gas", "the gas requirement is higher than the configured maximum gas, check the `max_gas` in the Hermes config.toml"),
+    (12, "memo too large", "the memo of the transaction is too large, check the `memo_prefix` in the Hermes config.toml"),
+    (13, "insufficient fee", "the price configuration for this chain may be too low, check the `gas_price.price` in the Hermes config.toml"),
+    (18, "invalid request", "the chain rejected the messages of the transaction as invalid"),
+    (19, "tx already in mempool", "the transaction is already in the mempool of the node"),
+    (20, "mempool is full", "the mempool of the node is full, try again later"),
+    (21, "tx too large", "the transaction is too large, check the `max_tx_size` in the Hermes config.toml"),
+    (28, "invalid chain-id", "the chain identifier of the transaction does not match the chain, check the `id` in the Hermes config.toml"),
+    (30, "tx timeout height", "the transaction timed out before it could be included in a block"),
+    (32, "incorrect account sequence", "the account sequence cached by the relayer is stale, the relayer wallet may be used elsewhere concurrently"),
+];
+
+/// Decodes the error a transaction simulation failed with, from the message of
+/// the gRPC status returned by the node.
+///
+/// The message carries no error code, only the descriptions of the registered
+/// errors wrapped into each other, eg. `account sequence mismatch, expected 5,
+/// got 4: incorrect account sequence: invalid request`. The innermost of them,
+/// ie. the first registered error found in the message, is the most specific,
+/// and thus the one decoded. The message itself is the reason of the failure
+/// if no registered error is found.
+pub fn decode_simulation_error(message: &str) -> SimulationError {
+    let decoded = message.split(": ").map(str::trim).find_map(|segment| {
+        let sdk_error = SDK_ERRORS
+            .iter()
+            .find(|(_, description, _)| *description == segment)
+            .map(|(code, _, reason)| ("sdk", *code, reason.to_string()));
+
+        sdk_error.or_else(|| {
+            (2..=29)
+                .find(|code| client_error_from_code(*code).detail().to_string() == segment)
+                .map(|code| ("client", code, format!("ICS02 client error: {}", segment)))
+        })
+    });
+
+    match decoded {
+        Some((codespace, code, reason)) => SimulationError {
+            codespace: Some(codespace.to_string()),
+            code: Some(code),
+            reason,
+        },
+        None => SimulationError {
+            codespace: None,
+            code: None,
+            reason: message.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode_simulation_error;
+
+    #[test]
+    fn innermost_registered_error_is_decoded() {
+        let error = decode_simulation_error(
+            "account sequence mismatch, expected 166791, got 166793: incorrect account sequence: invalid request",
+        );
+        assert_eq!(error.codespace.as_deref(), Some("sdk"));
+        assert_eq!(error.code, Some(32));
+
+        let error = decode_simulation_error(
+            "failed to execute message; message index: 0: acknowledge packet verification failed: \
+             packet acknowledgement verification failed: failed packet acknowledgement verification \
+             for client (07-tendermint-0): client state height < proof height ({0 243} < {0 554}): \
+             invalid height: invalid request",
+        );
+        assert_eq!(error.codespace.as_deref(), Some("client"));
+        assert_eq!(error.code, Some(19));
+    }
+
+    #[test]
+    fn unknown_errors_are_reported_as_is() {
+        let error = decode_simulation_error("something went wrong");
+
+        assert_eq!(error.code, None);
+        assert_eq!(error.reason, "something went wrong");
+    }
+}
@@ -1,4 +1,5 @@
 use alloc::sync::Arc;
+use std::thread;
 
 use flex_error::define_error;
 use tokio::runtime::Runtime as TokioRuntime;
@@ -67,3 +68,46 @@ pub fn spawn_chain_runtime<Handle: ChainHandle>(
 
     Ok(handle)
 }
+
+/// Spawns the runtimes of all the chains in the configuration, concurrently,
+/// except for the chains listed in its `spawn_priority`, which are spawned
+/// first, one after the other, so that their handles are available before
+/// the runtimes of the other chains are spawned.
+///
+/// Returns the outcome of the spawn of each chain, in spawn order.
+pub fn spawn_chain_runtimes<Handle: ChainHandle>(
+    config: &Config,
+    rt: Arc<TokioRuntime>,
+) -> Vec<(ChainId, Result<Handle, SpawnError>)> {
+    let (prioritized, others) = config.chains_in_spawn_order();
+
+    let mut spawned: Vec<_> = prioritized
+        .into_iter()
+        .map(|chain_id| {
+            let result = spawn_chain_runtime(config, chain_id, rt.clone());
+            (chain_id.clone(), result)
+        })
+        .collect();
+
+    let shared_config = Arc::new(config.clone());
+
+    let threads: Vec<_> = others
+        .into_iter()
+        .map(|chain_id| {
+            let (config, rt, chain_id) = (shared_config.clone(), rt.clone(), chain_id.clone());
+
+            thread::spawn(move || {
+                let result = spawn_chain_runtime(&config, &chain_id, rt);
+                (chain_id, result)
+            })
+        })
+        .collect();
+
+    spawned.extend(
+        threads
+            .into_iter()
+            .map(|thread| thread.join().expect("chain runtime spawn thread panicked")),
+    );
+
+    spawned
+}
@@ -16,19 +16,20 @@ use ibc::{
 };
 
 use crate::{
+    account::AccountInfo,
     chain::{endpoint::HealthCheck, handle::ChainHandle, tracking::TrackingId},
     config::Config,
     event::monitor::{self, Error as EventError, ErrorDetail as EventErrorDetail, EventBatch},
-    object::Object,
+    object::{Object, Packet},
     registry::{Registry, SharedRegistry},
-    rest,
+    rest::{self, request::AccountSequenceParams, RestApiError},
     supervisor::scan::ScanMode,
     telemetry,
     util::{
         lock::LockExt,
         task::{spawn_background_task, Next, TaskError, TaskHandle},
     },
-    worker::WorkerMap,
+    worker::{packet::RelayedPacketsCallback, WorkerMap},
 };
 
 pub mod client_state_filter;
@@ -40,6 +41,9 @@ pub use error::{Error, ErrorDetail};
 pub mod dump_state;
 use dump_state::SupervisorState;
 
+pub mod reload;
+use reload::reload_config;
+
 pub mod scan;
 pub mod spawn;
 
@@ -72,6 +76,10 @@ pub struct SupervisorOptions {
     /// even when an allow list is configured for a chain and the full scan could
     /// be omitted.
     pub force_full_scan: bool,
+
+    /// Invoked by the packet workers with the summary of
+    /// every non-empty batch of packets they relayed.
+    pub on_relayed: Option<RelayedPacketsCallback>,
 }
 
 /**
@@ -128,7 +136,10 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
         health_check(&config, &mut registry.write());
     }
 
-    let workers = Arc::new(RwLock::new(WorkerMap::new()));
+    let mut worker_map = WorkerMap::new();
+    worker_map.set_relayed_packets_callback(options.on_relayed);
+
+    let workers = Arc::new(RwLock::new(worker_map));
     let client_state_filter = Arc::new(RwLock::new(FilterPolicy::default()));
 
     let scan = chain_scanner(
@@ -150,21 +161,30 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
 
     let subscriptions = init_subscriptions(&config, &mut registry.write())?;
 
+    // Shared by the tasks below, so that they all see the configuration once reloaded.
+    let config = Arc::new(RwLock::new(config));
+
     let batch_tasks = spawn_batch_workers(
-        &config,
+        config.clone(),
         registry.clone(),
-        client_state_filter,
+        client_state_filter.clone(),
         workers.clone(),
         subscriptions,
     );
 
-    let cmd_task = spawn_cmd_worker(registry.clone(), workers.clone(), cmd_rx);
+    let cmd_task = spawn_cmd_worker(
+        config.clone(),
+        registry.clone(),
+        client_state_filter.clone(),
+        workers.clone(),
+        cmd_rx,
+    );
 
     let mut tasks = vec![cmd_task];
     tasks.extend(batch_tasks);
 
     if let Some(rest_rx) = rest_rx {
-        let rest_task = spawn_rest_worker(config, registry, workers, rest_rx);
+        let rest_task = spawn_rest_worker(config, registry, client_state_filter, workers, rest_rx);
         tasks.push(rest_task);
     }
 
@@ -172,7 +192,7 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
 }
 
 fn spawn_batch_workers<Chain: ChainHandle>(
-    config: &Config,
+    config: Arc<RwLock<Config>>,
     registry: SharedRegistry<Chain>,
     client_state_filter: Arc<RwLock<FilterPolicy>>,
     workers: Arc<RwLock<WorkerMap>>,
@@ -189,10 +209,11 @@ fn spawn_batch_workers<Chain: ChainHandle>(
         let handle = spawn_background_task(
             tracing::Span::none(),
             Some(Duration::from_millis(5)),
+            None,
             move || -> Result<Next, TaskError<Infallible>> {
                 if let Ok(batch) = subscription.try_recv() {
                     handle_batch(
-                        &config,
+                        &config.acquire_read(),
                         &mut registry.write(),
                         &mut client_state_filter.acquire_write(),
                         &mut workers.acquire_write(),
@@ -212,19 +233,38 @@ fn spawn_batch_workers<Chain: ChainHandle>(
 }
 
 pub fn spawn_cmd_worker<Chain: ChainHandle>(
+    config: Arc<RwLock<Config>>,
     registry: SharedRegistry<Chain>,
+    client_state_filter: Arc<RwLock<FilterPolicy>>,
     workers: Arc<RwLock<WorkerMap>>,
     cmd_rx: Receiver<SupervisorCmd>,
 ) -> TaskHandle {
     spawn_background_task(
         error_span!("cmd"),
         Some(Duration::from_millis(500)),
+        None,
         move || -> Result<Next, TaskError<Infallible>> {
             if let Ok(cmd) = cmd_rx.try_recv() {
                 match cmd {
                     SupervisorCmd::DumpState(reply_to) => {
                         dump_state(&registry.read(), &workers.acquire_read(), reply_to);
                     }
+                    SupervisorCmd::Reload(new_config, reply_to) => {
+                        let report = reload_config(
+                            &mut config.acquire_write(),
+                            *new_config,
+                            &mut registry.write(),
+                            &mut client_state_filter.acquire_write(),
+                            &mut workers.acquire_write(),
+                        );
+                        let _ = reply_to.try_send(report);
+                    }
+                    SupervisorCmd::PausePath(path, reply_to) => {
+                        set_path_paused(&workers.acquire_read(), path, true, reply_to);
+                    }
+                    SupervisorCmd::ResumePath(path, reply_to) => {
+                        set_path_paused(&workers.acquire_read(), path, false, reply_to);
+                    }
                 }
             }
 
@@ -234,16 +274,18 @@ pub fn spawn_cmd_worker<Chain: ChainHandle>(
 }
 
 pub fn spawn_rest_worker<Chain: ChainHandle>(
-    config: Config,
+    config: Arc<RwLock<Config>>,
     registry: SharedRegistry<Chain>,
+    client_state_filter: Arc<RwLock<FilterPolicy>>,
     workers: Arc<RwLock<WorkerMap>>,
     rest_rx: rest::Receiver,
 ) -> TaskHandle {
     spawn_background_task(
         error_span!("rest"),
         Some(Duration::from_millis(500)),
+        None,
         move || -> Result<Next, TaskError<Infallible>> {
-            handle_rest_requests(&config, &registry.read(), &workers.acquire_read(), &rest_rx);
+            handle_rest_requests(&config, &registry, &client_state_filter, &workers, &rest_rx);
 
             Ok(Next::Continue)
         },
@@ -290,6 +332,13 @@ fn relay_on_object<Chain: ChainHandle>(
     chain_id: &ChainId,
     object: &Object,
 ) -> bool {
+    // Packets are only relayed in the configured directions.
+    if let Object::Packet(p) = object {
+        if !config.relays_direction(&p.src_chain_id, &p.dst_chain_id) {
+            return false;
+        }
+    }
+
     // No filter is enabled, bail fast.
     if !channel_filter_enabled(config) && !client_filter_enabled(config) {
         return true;
@@ -435,22 +484,28 @@ pub fn collect_events(
                 });
             }
             IbcEvent::SendPacket(ref packet) => {
-                collect_event(&mut collected, event, mode.packets.enabled, || {
+                let enabled = mode.packets.enabled && mode.packets.forward_events.send_packet;
+                collect_event(&mut collected, event, enabled, || {
                     Object::for_send_packet(packet, src_chain).ok()
                 });
             }
             IbcEvent::TimeoutPacket(ref packet) => {
-                collect_event(&mut collected, event, mode.packets.enabled, || {
+                let enabled = mode.packets.enabled && mode.packets.forward_events.timeout_packet;
+                collect_event(&mut collected, event, enabled, || {
                     Object::for_timeout_packet(packet, src_chain).ok()
                 });
             }
             IbcEvent::WriteAcknowledgement(ref packet) => {
-                collect_event(&mut collected, event, mode.packets.enabled, || {
+                let enabled =
+                    mode.packets.enabled && mode.packets.forward_events.write_acknowledgement;
+                collect_event(&mut collected, event, enabled, || {
                     Object::for_write_ack(packet, src_chain).ok()
                 });
             }
             IbcEvent::CloseInitChannel(ref packet) => {
-                collect_event(&mut collected, event, mode.packets.enabled, || {
+                let enabled =
+                    mode.packets.enabled && mode.packets.forward_events.close_init_channel;
+                collect_event(&mut collected, event, enabled, || {
                     Object::for_close_init_channel(packet, src_chain).ok()
                 });
             }
@@ -557,6 +612,21 @@ fn dump_state<Chain: ChainHandle>(
     let _ = reply_to.try_send(state);
 }
 
+/// Pauses or resumes relaying on the given path, replying whether it has a packet worker.
+fn set_path_paused(workers: &WorkerMap, path: Packet, pause: bool, reply_to: Sender<bool>) {
+    let worker = workers.get(&Object::Packet(path));
+
+    if let Some(worker) = worker {
+        if pause {
+            worker.pause();
+        } else {
+            worker.resume();
+        }
+    }
+
+    let _ = reply_to.try_send(worker.is_some());
+}
+
 /// Returns a representation of the supervisor's internal state
 /// as a [`SupervisorState`].
 fn state<Chain: ChainHandle>(registry: &Registry<Chain>, workers: &WorkerMap) -> SupervisorState {
@@ -565,31 +635,86 @@ fn state<Chain: ChainHandle>(registry: &Registry<Chain>, workers: &WorkerMap) ->
 }
 
 fn handle_rest_requests<Chain: ChainHandle>(
-    config: &Config,
-    registry: &Registry<Chain>,
-    workers: &WorkerMap,
+    config: &RwLock<Config>,
+    registry: &SharedRegistry<Chain>,
+    client_state_filter: &RwLock<FilterPolicy>,
+    workers: &RwLock<WorkerMap>,
     rest_rx: &rest::Receiver,
 ) {
-    if let Some(cmd) = rest::process_incoming_requests(config, rest_rx) {
-        handle_rest_cmd(registry, workers, cmd);
+    // The lock on the configuration is released before handling the command,
+    // which may need to replace the configuration.
+    let cmd = rest::process_incoming_requests(&config.acquire_read(), rest_rx);
+
+    if let Some(cmd) = cmd {
+        handle_rest_cmd(config, registry, client_state_filter, workers, cmd);
     }
 }
 
 fn handle_rest_cmd<Chain: ChainHandle>(
-    registry: &Registry<Chain>,
-    workers: &WorkerMap,
+    config: &RwLock<Config>,
+    registry: &SharedRegistry<Chain>,
+    client_state_filter: &RwLock<FilterPolicy>,
+    workers: &RwLock<WorkerMap>,
     m: rest::Command,
 ) {
     match m {
         rest::Command::DumpState(reply) => {
-            let state = state(registry, workers);
+            let state = state(&registry.read(), &workers.acquire_read());
             reply
                 .send(Ok(state))
                 .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
         }
+        rest::Command::AccountSequence(chain_id, params, reply) => {
+            let result = account_sequence(&registry.read(), &chain_id, params);
+            reply
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+        rest::Command::Reload(new_config, reply) => {
+            let report = reload_config(
+                &mut config.acquire_write(),
+                *new_config,
+                &mut registry.write(),
+                &mut client_state_filter.acquire_write(),
+                &mut workers.acquire_write(),
+            );
+            reply
+                .send(Ok(report))
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
     }
 }
 
+/// Returns the on-chain and cached account sequence of the given chain,
+/// clearing the cached sequence afterwards if requested.
+fn account_sequence<Chain: ChainHandle>(
+    registry: &Registry<Chain>,
+    chain_id: &ChainId,
+    params: AccountSequenceParams,
+) -> Result<AccountInfo, RestApiError> {
+    let runtime_error =
+        |e: crate::error::Error| RestApiError::ChainRuntime(chain_id.clone(), e.to_string());
+
+    let chain = registry.get(chain_id).ok_or_else(|| {
+        RestApiError::ChainRuntime(
+            chain_id.clone(),
+            "no runtime was spawned for this chain".to_string(),
+        )
+    })?;
+
+    let account = chain
+        .query_account_info(params.key_name.clone())
+        .map_err(runtime_error)?;
+
+    if params.reset {
+        chain
+            .reset_account_sequence(params.key_name)
+            .map_err(runtime_error)?;
+    }
+
+    Ok(account)
+}
+
 fn clear_pending_packets(workers: &mut WorkerMap, chain_id: &ChainId) -> Result<(), Error> {
     for worker in workers.workers_for_chain(chain_id) {
         worker.clear_pending_packets();
@@ -1,8 +1,18 @@
 use crossbeam_channel::Sender;
 
+use crate::config::Config;
+use crate::object::Packet;
+
 use super::dump_state::SupervisorState;
+use super::reload::ReloadReport;
 
 #[derive(Clone, Debug)]
 pub enum SupervisorCmd {
     DumpState(Sender<SupervisorState>),
+    Reload(Box<Config>, Sender<ReloadReport>),
+    /// Pause relaying on the given path, replying whether it has a packet worker.
+    PausePath(Packet, Sender<bool>),
+    /// Resume relaying on a path paused by [`SupervisorCmd::PausePath`],
+    /// replying whether it has a packet worker.
+    ResumePath(Packet, Sender<bool>),
 }
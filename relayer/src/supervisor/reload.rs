@@ -0,0 +1,260 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::{
+    chain::handle::ChainHandle, config::Config, object::Object, registry::Registry,
+    worker::WorkerMap,
+};
+
+use super::{
+    chain_scanner, client_state_filter::FilterPolicy, relay_on_object, scan::ScanMode,
+    spawn_context,
+};
+
+/// The outcome of reloading the configuration of a running supervisor.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReloadReport {
+    /// The packet workers which were sent the updated packet relaying parameters.
+    pub reconfigured: Vec<String>,
+    /// The packet workers which were shut down, as their channel is no longer allowed.
+    pub shut_down: Vec<String>,
+    /// The workers spawned for the channels newly allowed by the configuration.
+    pub spawned: Vec<String>,
+    /// The changes which only take effect after restarting the relayer.
+    pub requires_restart: Vec<String>,
+}
+
+/// Applies the `new_config` to the running supervisor, whose configuration
+/// is replaced by the new one, except for the chains which were added.
+///
+/// The packet workers of the channels which are still allowed are reconfigured
+/// with the new packet relaying parameters, those of the channels which are not
+/// allowed anymore are shut down, and workers are spawned for the newly allowed
+/// channels. The changes which cannot be applied at runtime are only reported.
+pub fn reload_config<Chain: ChainHandle>(
+    config: &mut Config,
+    mut new_config: Config,
+    registry: &mut Registry<Chain>,
+    client_state_filter: &mut FilterPolicy,
+    workers: &mut WorkerMap,
+) -> ReloadReport {
+    let requires_restart = changes_requiring_restart(config, &new_config);
+
+    // The chain runtimes and event sources are only spawned at startup.
+    new_config
+        .chains
+        .retain(|chain| config.has_chain(&chain.id));
+    *config = new_config;
+
+    let mut report = ReloadReport {
+        requires_restart,
+        ..ReloadReport::default()
+    };
+
+    let packets = config.mode.packets;
+
+    let packet_workers: Vec<Object> = workers
+        .objects()
+        .filter(|object| matches!(object, Object::Packet(_)))
+        .cloned()
+        .collect();
+
+    for object in packet_workers {
+        let allowed = packets.enabled
+            && relay_on_object(
+                config,
+                registry,
+                client_state_filter,
+                object.src_chain_id(),
+                &object,
+            );
+
+        if allowed {
+            if let Some(worker) = workers.get(&object) {
+                worker.reconfigure(
+                    packets.clear_interval,
+                    packets.relay_acks,
+                    packets.relay_timeouts,
+                );
+
+                report.reconfigured.push(object.short_name());
+            }
+        } else {
+            info!("shutting down worker for '{}'", object.short_name());
+
+            workers.shutdown_worker(&object);
+            report.shut_down.push(object.short_name());
+        }
+    }
+
+    let existing: HashSet<Object> = workers.objects().cloned().collect();
+
+    let scan = chain_scanner(config, registry, client_state_filter, ScanMode::Auto).scan_chains();
+    spawn_context(config, registry, workers).spawn_workers(scan);
+
+    report.spawned = workers
+        .objects()
+        .filter(|object| !existing.contains(*object))
+        .map(|object| object.short_name())
+        .collect();
+
+    for change in &report.requires_restart {
+        warn!("configuration change requires a restart: {}", change);
+    }
+
+    report
+}
+
+/// The fields of the `[mode.packets]` section applied to the running packet
+/// workers, or deciding which of them keep running.
+const APPLIED_PACKETS_FIELDS: &[&str] =
+    &["enabled", "clear_interval", "relay_acks", "relay_timeouts"];
+
+/// The fields of a chain configuration deciding which of the running
+/// workers keep running, all the others are only read when it is spawned.
+const APPLIED_CHAIN_FIELDS: &[&str] = &["id", "packet_filter"];
+
+/// Describes the differences between the `current` and `new` configurations
+/// which can only take effect after restarting the relayer, ie. all of them
+/// but the packet relaying parameters of the packet workers, the packet
+/// filters, and the relay directions.
+fn changes_requiring_restart(current: &Config, new: &Config) -> Vec<String> {
+    let mut changes = Vec::new();
+
+    for chain in &new.chains {
+        match current.find_chain(&chain.id) {
+            Some(current_chain) => changes.extend(
+                changed_fields(current_chain, chain)
+                    .into_iter()
+                    .filter(|field| !APPLIED_CHAIN_FIELDS.contains(&field.as_str()))
+                    .map(|field| format!("'{}' of chain {} changed", field, chain.id)),
+            ),
+            None => changes.push(format!("chain {} was added", chain.id)),
+        }
+    }
+
+    for chain in &current.chains {
+        if !new.has_chain(&chain.id) {
+            changes.push(format!(
+                "chain {} was removed, its event source keeps running",
+                chain.id
+            ));
+        }
+    }
+
+    let mut changed_section = |section: &str, fields: Vec<String>, applied: &[&str]| {
+        changes.extend(
+            fields
+                .into_iter()
+                .filter(|field| !applied.contains(&field.as_str()))
+                .map(|field| format!("'{}.{}' changed", section, field)),
+        )
+    };
+
+    changed_section("global", changed_fields(&current.global, &new.global), &[]);
+    changed_section(
+        "mode.clients",
+        changed_fields(&current.mode.clients, &new.mode.clients),
+        &[],
+    );
+    changed_section(
+        "mode.connections",
+        changed_fields(&current.mode.connections, &new.mode.connections),
+        &[],
+    );
+    changed_section(
+        "mode.channels",
+        changed_fields(&current.mode.channels, &new.mode.channels),
+        &[],
+    );
+    changed_section(
+        "mode.packets",
+        changed_fields(&current.mode.packets, &new.mode.packets),
+        APPLIED_PACKETS_FIELDS,
+    );
+    changed_section("rest", changed_fields(&current.rest, &new.rest), &[]);
+    changed_section(
+        "telemetry",
+        changed_fields(&current.telemetry, &new.telemetry),
+        &[],
+    );
+
+    changes
+}
+
+/// The names of the fields whose value differs between the `current` and `new`
+/// configuration sections, compared as they are written in the configuration file.
+fn changed_fields<T: Serialize>(current: &T, new: &T) -> Vec<String> {
+    let (current, new) = match (toml::Value::try_from(current), toml::Value::try_from(new)) {
+        (Ok(toml::Value::Table(current)), Ok(toml::Value::Table(new))) => (current, new),
+        // A section which cannot be compared field by field is reported as a whole.
+        _ => return vec!["*".to_string()],
+    };
+
+    let mut fields: Vec<String> = current
+        .keys()
+        .chain(new.keys())
+        .filter(|field| current.get(*field) != new.get(*field))
+        .cloned()
+        .collect();
+
+    fields.sort();
+    fields.dedup();
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use crate::config::{load, ChainPairDirection, Config, RelayDirection};
+
+    use super::changes_requiring_restart;
+
+    fn config() -> Config {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        load(path).expect("could not parse config")
+    }
+
+    #[test]
+    fn applied_changes_do_not_require_restart() {
+        let current = config();
+        let mut new = config();
+
+        new.mode.packets.clear_interval += 1;
+        new.mode.packets.relay_acks = !new.mode.packets.relay_acks;
+        new.relay_directions.push(ChainPairDirection {
+            chain_a: new.chains[0].id.clone(),
+            chain_b: new.chains[1].id.clone(),
+            relay_direction: RelayDirection::AToB,
+        });
+
+        assert!(changes_requiring_restart(&current, &new).is_empty());
+    }
+
+    #[test]
+    fn non_applied_changes_require_restart() {
+        let current = config();
+        let mut new = config();
+
+        new.mode.packets.max_event_batch_size += 1;
+        new.mode.clients.refresh = !new.mode.clients.refresh;
+        new.chains[0].rpc_timeout += Duration::from_secs(1);
+
+        assert_eq!(
+            changes_requiring_restart(&current, &new),
+            vec![
+                format!("'rpc_timeout' of chain {} changed", new.chains[0].id),
+                "'mode.clients.refresh' changed".to_string(),
+                "'mode.packets.max_event_batch_size' changed".to_string(),
+            ]
+        );
+    }
+}
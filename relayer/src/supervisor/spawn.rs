@@ -257,7 +257,11 @@ impl<'a, Chain: ChainHandle> SpawnContext<'a, Chain> {
                     .then(|| info!("spawned client worker: {}", client_object.short_name()));
             }
 
-            if mode.packets.enabled {
+            if mode.packets.enabled
+                && self
+                    .config
+                    .relays_direction(&chain.id(), &counterparty_chain.id())
+            {
                 let has_packets = || {
                     !channel_scan
                         .unreceived_packets_on_counterparty(&chain, &counterparty_chain)
@@ -10,6 +10,12 @@ pub struct TelemetryDisabled;
 #[cfg(not(feature = "telemetry"))]
 pub type Telemetry = TelemetryDisabled;
 
+/// Whether the relayer was built with the `telemetry` feature, for the
+/// callers which cannot check the feature flag themselves.
+pub const fn is_enabled() -> bool {
+    cfg!(feature = "telemetry")
+}
+
 /// A macro to send metric updates via a telemetry handle,
 /// only if the `telemetry` feature is enabled.
 /// Otherwise, it compiles to a no-op.
@@ -1,6 +1,8 @@
 use core::time::Duration;
 
+use bech32::{ToBase32, Variant};
 use flex_error::{define_error, DetailOnly};
+use ibc::applications::transfer::context::cosmos_adr028_escrow_address;
 use ibc::applications::transfer::error::Error as Ics20Error;
 use ibc::applications::transfer::msgs::transfer::MsgTransfer;
 use ibc::applications::transfer::Amount;
@@ -117,6 +119,19 @@ pub struct TransferOptions {
     pub number_msgs: usize,
 }
 
+/// Returns the bech32 address, with the given account prefix, of the account in which
+/// the transfer module of a Cosmos SDK chain escrows the tokens sent over the given channel.
+pub fn escrow_address(
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    account_prefix: &str,
+) -> Result<String, Error> {
+    let address = cosmos_adr028_escrow_address(port_id, *channel_id);
+
+    bech32::encode(account_prefix, address.to_base32(), Variant::Bech32)
+        .map_err(Error::bech32_encoding)
+}
+
 pub fn build_transfer_message(
     packet_src_port_id: PortId,
     packet_src_channel_id: ChannelId,
@@ -201,3 +216,19 @@ pub fn build_and_send_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHa
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ibc::core::ics24_host::identifier::{ChannelId, PortId};
+
+    use super::escrow_address;
+
+    #[test]
+    fn escrow_address_is_derived_from_the_channel() {
+        // Obtained with `gaiad query ibc-transfer escrow-address transfer channel-141`
+        assert_eq!(
+            escrow_address(&PortId::transfer(), &ChannelId::new(141), "cosmos").unwrap(),
+            "cosmos1x54ltnyg88k0ejmk8ytwrhd3ltm84xehrnlslf"
+        );
+    }
+}
@@ -5,6 +5,7 @@ pub mod diff;
 pub mod iter;
 pub mod lock;
 pub mod queue;
+pub mod rate_limit;
 pub mod retry;
 pub mod stream;
 pub mod task;
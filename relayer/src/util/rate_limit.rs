@@ -0,0 +1,172 @@
+//! Token-bucket rate limiting of the transactions submitted to each chain.
+
+use core::time::Duration;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use once_cell::sync::Lazy;
+
+use ibc::core::ics24_host::identifier::ChainId;
+
+use crate::config::TxRateLimit;
+
+/// The rate limiters created so far, by chain, so that all the
+/// workers submitting transactions to a chain share its limiter.
+static TX_RATE_LIMITERS: Lazy<Mutex<HashMap<ChainId, TxRateLimiter>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A bucket holding up to `capacity` tokens, refilled continuously
+/// at the rate of `refill_per_second` tokens per second.
+#[derive(Debug)]
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_second: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a full bucket.
+    pub fn new(capacity: u64, refill_per_second: f64, now: Instant) -> Self {
+        Self {
+            capacity: capacity as f64,
+            refill_per_second,
+            tokens: capacity as f64,
+            last_refill: now,
+        }
+    }
+
+    /// Takes a token from the bucket if it holds one, or returns
+    /// how long to wait until the bucket holds a token again.
+    pub fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        if now > self.last_refill {
+            let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+            self.tokens = (self.tokens + elapsed * self.refill_per_second).min(self.capacity);
+            self.last_refill = now;
+        }
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / self.refill_per_second,
+            ))
+        }
+    }
+}
+
+/// Limits the rate of the transactions submitted to a chain
+/// by all the workers sharing the limiter.
+#[derive(Clone, Debug)]
+pub struct TxRateLimiter {
+    bucket: Arc<Mutex<TokenBucket>>,
+}
+
+impl TxRateLimiter {
+    pub fn new(limit: TxRateLimit) -> Self {
+        Self {
+            bucket: Arc::new(Mutex::new(TokenBucket::new(
+                limit.capacity,
+                limit.refill_per_second,
+                Instant::now(),
+            ))),
+        }
+    }
+
+    /// Returns the limiter shared by all the workers submitting transactions
+    /// to the given chain, creating it with the given `limit` on first use.
+    pub fn shared(chain_id: &ChainId, limit: TxRateLimit) -> Self {
+        TX_RATE_LIMITERS
+            .lock()
+            .unwrap()
+            .entry(chain_id.clone())
+            .or_insert_with(|| Self::new(limit))
+            .clone()
+    }
+
+    /// Acquires the permission to submit a transaction without waiting, or
+    /// returns how long to wait until a transaction may be submitted.
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        self.bucket.lock().unwrap().try_acquire(Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Instant;
+
+    use super::{TokenBucket, TxRateLimiter};
+    use crate::config::TxRateLimit;
+
+    #[test]
+    fn token_bucket_refills_over_time() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(2, 10.0, start);
+
+        assert_eq!(bucket.try_acquire(start), Ok(()));
+        assert_eq!(bucket.try_acquire(start), Ok(()));
+
+        let wait = bucket.try_acquire(start).unwrap_err();
+        assert_eq!(wait, Duration::from_millis(100));
+
+        assert_eq!(bucket.try_acquire(start + wait), Ok(()));
+        assert!(bucket.try_acquire(start + wait).is_err());
+
+        // The bucket never holds more than its capacity.
+        let later = start + Duration::from_secs(60);
+        assert_eq!(bucket.try_acquire(later), Ok(()));
+        assert_eq!(bucket.try_acquire(later), Ok(()));
+        assert!(bucket.try_acquire(later).is_err());
+    }
+
+    #[test]
+    fn rate_ceiling_holds_under_concurrent_workers() {
+        let limit = TxRateLimit {
+            capacity: 5,
+            refill_per_second: 50.0,
+        };
+
+        let limiter = TxRateLimiter::new(limit);
+        let acquired = Arc::new(AtomicU64::new(0));
+        let start = Instant::now();
+
+        let workers: Vec<_> = (0..8)
+            .map(|_| {
+                let limiter = limiter.clone();
+                let acquired = acquired.clone();
+
+                thread::spawn(move || {
+                    while start.elapsed() < Duration::from_millis(300) {
+                        match limiter.try_acquire() {
+                            Ok(()) => {
+                                acquired.fetch_add(1, Ordering::SeqCst);
+                            }
+                            Err(wait) => thread::sleep(wait.min(Duration::from_millis(5))),
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        let elapsed = start.elapsed().as_secs_f64();
+        let ceiling = limit.capacity as f64 + elapsed * limit.refill_per_second;
+        let acquired = acquired.load(Ordering::SeqCst) as f64;
+
+        assert!(
+            acquired <= ceiling,
+            "acquired {} tokens, above the ceiling of {}",
+            acquired,
+            ceiling
+        );
+    }
+}
@@ -2,6 +2,8 @@ use core::fmt::Display;
 use core::mem;
 use core::time::Duration;
 use crossbeam_channel::{bounded, Sender};
+use rand::Rng;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 use std::thread;
 use tracing::{debug, error, warn};
@@ -54,6 +56,8 @@ pub enum TaskError<E> {
 
 pub enum Next {
     Continue,
+    /// Continue after waiting for the given duration, on top of the interval pause.
+    ContinueAfter(Duration),
     Abort,
 }
 
@@ -73,6 +77,12 @@ pub enum Next {
    argument is also given for the task runner to sleep for the given
    duration before calling the step runner again.
 
+   An optional maximum number of iterations can also be given, after which
+   the task runner stops calling the step runner and terminates the task,
+   as if the step runner had returned [`Next::Abort`]. This is meant for
+   tests which need the background task to terminate deterministically.
+   Long-running tasks should use `None`.
+
    The function returns a [`TaskHandle`] that can be used to shutdown the
    background task. If the [`TaskHandle`] is dropped or if explicit shutdown
    instruction is sent, the task runner will stop calling the step runner
@@ -88,6 +98,7 @@ pub enum Next {
 pub fn spawn_background_task<E: Display>(
     span: tracing::Span,
     interval_pause: Option<Duration>,
+    max_iterations: Option<u64>,
     mut step_runner: impl FnMut() -> Result<Next, TaskError<E>> + Send + Sync + 'static,
 ) -> TaskHandle {
     debug!(parent: &span, "spawning task");
@@ -99,13 +110,26 @@ pub fn spawn_background_task<E: Display>(
 
     let join_handle = thread::spawn(move || {
         let _entered = span.enter();
+        let mut iterations: u64 = 0;
         loop {
+            if max_iterations.map_or(false, |max| iterations >= max) {
+                debug!("aborting task after {} iterations", iterations);
+                break;
+            }
+
+            iterations += 1;
+
             match receiver.try_recv() {
                 Ok(()) => {
                     break;
                 }
                 _ => match step_runner() {
                     Ok(Next::Continue) => {}
+                    Ok(Next::ContinueAfter(delay)) => {
+                        if receiver.recv_timeout(delay).is_ok() {
+                            break;
+                        }
+                    }
                     Ok(Next::Abort) => {
                         debug!("aborting task");
                         break;
@@ -136,6 +160,52 @@ pub fn spawn_background_task<E: Display>(
     }
 }
 
+/**
+   A token through which the holder of a clone of the token can ask the
+   step runners holding the other clones to stop cooperatively.
+
+   Unlike [`TaskHandle::shutdown`], which is given to the task runner,
+   the token is checked by the step runner itself at the top of each of
+   its iterations, so that it can decide how to terminate cleanly, eg.
+   after the step which is broadcasting a transaction has completed.
+*/
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+       Signal the step runners holding a clone of this token to stop.
+    */
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /**
+       Whether [`cancel`](CancellationToken::cancel) was called on a clone of this token.
+    */
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/**
+   Returns a random delay between zero and the given `interval`, for a
+   step runner to return as [`Next::ContinueAfter`] on its first call, so
+   that the background tasks spawned together do not all run their first
+   step at the same time.
+*/
+pub fn startup_jitter(interval: Duration) -> Duration {
+    if interval.is_zero() {
+        return Duration::ZERO;
+    }
+
+    rand::thread_rng().gen_range(Duration::ZERO..interval)
+}
+
 impl TaskHandle {
     /**
        Wait for the background task to terminate.
@@ -194,3 +264,102 @@ impl Drop for TaskHandle {
         let _ = self.shutdown_sender.send(());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{spawn_background_task, CancellationToken, Next, TaskError};
+
+    use core::convert::Infallible;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    fn counting_task(max_iterations: Option<u64>, abort_at: Option<u64>) -> u64 {
+        let count = Arc::new(AtomicU64::new(0));
+        let task_count = count.clone();
+
+        let handle = spawn_background_task(
+            tracing::Span::none(),
+            None,
+            max_iterations,
+            move || -> Result<Next, TaskError<Infallible>> {
+                let count = task_count.fetch_add(1, Ordering::SeqCst) + 1;
+
+                if abort_at == Some(count) {
+                    Ok(Next::Abort)
+                } else {
+                    Ok(Next::Continue)
+                }
+            },
+        );
+
+        handle.join();
+
+        count.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn task_stops_after_max_iterations() {
+        assert_eq!(counting_task(Some(5), None), 5);
+    }
+
+    #[test]
+    fn task_with_zero_max_iterations_never_runs() {
+        assert_eq!(counting_task(Some(0), None), 0);
+    }
+
+    #[test]
+    fn task_aborts_before_max_iterations() {
+        assert_eq!(counting_task(Some(5), Some(3)), 3);
+    }
+
+    #[test]
+    fn task_counts_ignored_errors_as_iterations() {
+        let count = Arc::new(AtomicU64::new(0));
+        let task_count = count.clone();
+
+        let handle = spawn_background_task(
+            tracing::Span::none(),
+            None,
+            Some(4),
+            move || -> Result<Next, TaskError<&'static str>> {
+                task_count.fetch_add(1, Ordering::SeqCst);
+                Err(TaskError::Ignore("ignored"))
+            },
+        );
+
+        handle.join();
+
+        assert_eq!(count.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn cancelled_task_stops_at_the_next_iteration() {
+        let count = Arc::new(AtomicU64::new(0));
+        let task_count = count.clone();
+
+        let token = CancellationToken::new();
+        let task_token = token.clone();
+
+        let handle = spawn_background_task(
+            tracing::Span::none(),
+            None,
+            Some(10),
+            move || -> Result<Next, TaskError<Infallible>> {
+                if task_token.is_cancelled() {
+                    return Ok(Next::Abort);
+                }
+
+                // The current iteration completes even though the token is cancelled.
+                if task_count.fetch_add(1, Ordering::SeqCst) + 1 == 3 {
+                    token.cancel();
+                }
+
+                Ok(Next::Continue)
+            },
+        );
+
+        handle.join();
+
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+    }
+}
@@ -1,16 +1,23 @@
 use alloc::sync::Arc;
 use core::fmt;
+use core::time::Duration;
 use ibc::core::ics04_channel::channel::Order;
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64};
 use std::sync::Mutex;
-use tracing::error;
+use tracing::{error, info, warn};
 
 use crate::foreign_client::ForeignClient;
-use crate::link::{Link, LinkParameters, Resubmit};
+use crate::link::ack_coalescer::AckFlushPolicy;
+use crate::link::direction_priority::DirectionPriority;
+use crate::link::schedule_snapshot::SCHEDULE_SNAPSHOT_FOLDER;
+use crate::link::{Link, LinkParameters, RelaySummary, TxAggregator};
+use crate::util::rate_limit::TxRateLimiter;
+use crate::util::task::CancellationToken;
 use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
     config::Config,
-    object::Object,
+    object::{Object, Packet},
 };
 
 pub mod retry_strategy;
@@ -27,6 +34,15 @@ pub use cmd::WorkerCmd;
 mod map;
 pub use map::WorkerMap;
 
+mod circuit_breaker;
+pub use circuit_breaker::CircuitBreaker;
+
+mod idle_backoff;
+pub use idle_backoff::IdleBackoff;
+
+mod relay_log;
+pub use relay_log::RelayLog;
+
 pub mod channel;
 pub mod client;
 pub mod connection;
@@ -58,9 +74,13 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
     id: WorkerId,
     object: Object,
     config: &Config,
+    on_relayed: Option<packet::RelayedPacketsCallback>,
 ) -> WorkerHandle {
     let mut task_handles = Vec::new();
 
+    // Only set for packet workers, whose tasks stop cooperatively once cancelled.
+    let mut cancellation = None;
+
     let (cmd_tx, data) = match &object {
         Object::Client(client) => {
             let client = ForeignClient::restore(client.dst_client_id.clone(), chains.b, chains.a);
@@ -121,28 +141,201 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
             );
 
             match link_res {
-                Ok(link) => {
-                    let channel_ordering = link.a_to_b.channel().ordering;
-                    let should_clear_on_start =
-                        packets_config.clear_on_start || channel_ordering == Order::Ordered;
-
-                    let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
-                    let link = Arc::new(Mutex::new(link));
-                    let resubmit = Resubmit::from_clear_interval(packets_config.clear_interval);
-
-                    let packet_task = packet::spawn_packet_cmd_worker(
-                        cmd_rx,
-                        link.clone(),
-                        should_clear_on_start,
-                        packets_config.clear_interval,
-                        path.clone(),
+                Ok(mut link) => {
+                    link.a_to_b.set_relay_acks(packets_config.relay_acks);
+                    link.a_to_b
+                        .set_relay_timeouts(packets_config.relay_timeouts);
+
+                    let extra_key_names = |chain_id| {
+                        config
+                            .find_chain(chain_id)
+                            .map(|chain_config| chain_config.extra_key_names.clone())
+                            .unwrap_or_default()
+                    };
+
+                    link.a_to_b.set_extra_key_names(
+                        extra_key_names(&path.src_chain_id),
+                        extra_key_names(&path.dst_chain_id),
+                    );
+
+                    link.a_to_b
+                        .set_confirmation_blocks(packets_config.confirmation_blocks);
+                    link.a_to_b
+                        .set_reconcile_schedule(packets_config.reconcile_schedule);
+
+                    if packets_config.client_refresh_margin != Duration::ZERO {
+                        link.a_to_b
+                            .set_client_refresh_margin(Some(packets_config.client_refresh_margin));
+                    }
+
+                    if packets_config.cost_aware_direction {
+                        let gas_price = |chain_id| {
+                            config
+                                .find_chain(chain_id)
+                                .map_or(0.0, |chain_config| chain_config.gas_price.price)
+                        };
+
+                        link.a_to_b
+                            .set_direction_priority(Some(DirectionPriority::new(
+                                path.src_chain_id.clone(),
+                                path.dst_chain_id.clone(),
+                                path.src_port_id.clone(),
+                                path.src_channel_id,
+                                gas_price(&path.src_chain_id),
+                                gas_price(&path.dst_chain_id),
+                            )));
+                    }
+
+                    let tx_rate_limiter = |chain_id| {
+                        config
+                            .find_chain(chain_id)
+                            .and_then(|chain_config| chain_config.tx_rate_limit)
+                            .map(|limit| TxRateLimiter::shared(chain_id, limit))
+                    };
+
+                    link.a_to_b.set_tx_rate_limiters(
+                        tx_rate_limiter(&path.src_chain_id),
+                        tx_rate_limiter(&path.dst_chain_id),
                     );
-                    task_handles.push(packet_task);
 
-                    let link_task = packet::spawn_packet_worker(path.clone(), link, resubmit);
-                    task_handles.push(link_task);
+                    if packets_config.relay_grace_period != Duration::ZERO {
+                        link.a_to_b
+                            .set_relay_grace_period(Some(packets_config.relay_grace_period));
+                    }
+
+                    link.a_to_b.set_ack_verification(packets_config.verify_acks);
+
+                    link.a_to_b.set_ack_flush_policy(AckFlushPolicy {
+                        max_acks: packets_config.ack_flush_count,
+                        max_delay: packets_config.ack_flush_interval,
+                    });
+
+                    if packets_config.max_in_flight_txs != 0 {
+                        link.a_to_b
+                            .set_max_in_flight_txs(Some(packets_config.max_in_flight_txs));
+                    }
+
+                    if packets_config.clear_max_duration != Duration::ZERO {
+                        link.a_to_b
+                            .set_clear_max_duration(Some(packets_config.clear_max_duration));
+                    }
+
+                    link.a_to_b
+                        .set_separate_client_updates(packets_config.separate_client_updates);
+                    link.a_to_b
+                        .set_memo_source_heights(packets_config.memo_source_heights);
+                    link.a_to_b
+                        .set_collect_timings(packets_config.collect_timings);
+                    link.a_to_b
+                        .set_proof_query_retries(packets_config.proof_query_retries);
+
+                    if packets_config.max_packet_data_bytes != 0 {
+                        link.a_to_b
+                            .set_max_packet_data_bytes(Some(packets_config.max_packet_data_bytes));
+                    }
+
+                    if packets_config.tx_aggregation_window != Duration::ZERO {
+                        link.a_to_b.set_tx_aggregator(Some(TxAggregator::shared(
+                            packets_config.tx_aggregation_window,
+                            packets_config.aggregate_in_commit_order,
+                        )));
+                    }
+
+                    if packets_config.execute_schedule_timeout != Duration::ZERO {
+                        link.a_to_b.set_execute_schedule_timeout(Some(
+                            packets_config.execute_schedule_timeout,
+                        ));
+                    }
+
+                    if packets_config.observe_only {
+                        let observer_task = packet::spawn_observer_worker(path.clone(), link);
+                        task_handles.push(observer_task);
+
+                        (None, None)
+                    } else {
+                        let channel_ordering = link.a_to_b.channel().ordering;
+
+                        let restored_msgs = if packets_config.persist_schedule {
+                            restore_schedule(config, &mut link)
+                        } else {
+                            0
+                        };
+
+                        // The restored schedule of an unordered channel makes clearing on start
+                        // redundant, the packets of ordered channels must be cleared in order.
+                        let should_clear_on_start = (packets_config.clear_on_start
+                            && restored_msgs == 0)
+                            || channel_ordering == Order::Ordered;
+
+                        let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
+                        let link = Arc::new(Mutex::new(link));
+
+                        // Shared by both tasks, so that a reconfigured clear interval
+                        // also changes the resubmission policy.
+                        let clear_interval =
+                            Arc::new(AtomicU64::new(packets_config.clear_interval));
+
+                        // Shared by both tasks, so that they pause and resume together.
+                        let circuit_breaker = Arc::new(Mutex::new(CircuitBreaker::new(
+                            packets_config.circuit_breaker_threshold,
+                            packets_config.circuit_breaker_window,
+                            packets_config.circuit_breaker_cooldown,
+                        )));
+
+                        // Shared by both tasks, so that a paused path stops being relayed
+                        // by either of them.
+                        let paused = Arc::new(AtomicBool::new(false));
+
+                        // Shared by both tasks, so that an incoming command ends the backoff.
+                        let idle_backoff = Arc::new(Mutex::new(IdleBackoff::new(
+                            packet::PACKET_WORKER_INTERVAL,
+                            packets_config.idle_backoff_max_interval,
+                        )));
+
+                        // Shared by both tasks, so that they stop together.
+                        let token = CancellationToken::new();
 
-                    (Some(cmd_tx), None)
+                        let on_relayed = relayed_packets_callback(config, on_relayed);
+
+                        let packet_task = packet::spawn_packet_cmd_worker(
+                            cmd_rx,
+                            link.clone(),
+                            should_clear_on_start,
+                            packets_config.clear_on_start_max_packets,
+                            clear_interval.clone(),
+                            packets_config.attempt_client_recovery_on_frozen,
+                            packets_config.max_event_batch_size,
+                            packets_config.max_command_retries,
+                            circuit_breaker.clone(),
+                            paused.clone(),
+                            idle_backoff.clone(),
+                            path.clone(),
+                            packets_config.startup_jitter,
+                            on_relayed.clone(),
+                            None,
+                            token.clone(),
+                        );
+                        task_handles.push(packet_task);
+
+                        let link_task = packet::spawn_packet_worker(
+                            path.clone(),
+                            link,
+                            clear_interval,
+                            packets_config.attempt_client_recovery_on_frozen,
+                            circuit_breaker,
+                            paused,
+                            idle_backoff,
+                            packets_config.startup_jitter,
+                            on_relayed,
+                            None,
+                            token.clone(),
+                        );
+                        task_handles.push(link_task);
+
+                        cancellation = Some(token);
+
+                        (Some(cmd_tx), None)
+                    }
                 }
                 Err(e) => {
                     error!("error initializing link object for packet worker: {}", e);
@@ -161,5 +354,84 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
         }
     };
 
-    WorkerHandle::new(id, object, data, cmd_tx, task_handles)
+    WorkerHandle::new(id, object, data, cmd_tx, task_handles, cancellation)
+}
+
+/// Sets the packet worker of the given link to store its schedule in the state
+/// directory, and restores the schedule it stored before a restart, if any.
+///
+/// Returns the number of messages restored.
+fn restore_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
+    config: &Config,
+    link: &mut Link<ChainA, ChainB>,
+) -> usize {
+    let dir = match config.global.state_dir() {
+        Some(dir) => dir.join(SCHEDULE_SNAPSHOT_FOLDER),
+        None => {
+            error!("failed to locate the state directory, the schedule will not be stored");
+            return 0;
+        }
+    };
+
+    link.a_to_b.set_schedule_snapshot_dir(Some(dir));
+
+    match link.a_to_b.restore_schedule() {
+        Ok(restored_msgs) => {
+            if restored_msgs > 0 {
+                info!("restored {} messages of the stored schedule", restored_msgs);
+            }
+
+            restored_msgs
+        }
+        Err(e) => {
+            warn!("failed to restore the stored schedule, ignoring it: {}", e);
+            0
+        }
+    }
+}
+
+/// The callback invoked by the packet workers with the packets they relayed: the
+/// given `on_relayed` callback, if any, and the recording of the packets in the
+/// relay log, if configured.
+fn relayed_packets_callback(
+    config: &Config,
+    on_relayed: Option<packet::RelayedPacketsCallback>,
+) -> Option<packet::RelayedPacketsCallback> {
+    match (relay_log_callback(config), on_relayed) {
+        (Some(relay_log), Some(on_relayed)) => Some(packet::RelayedPacketsCallback::new(
+            move |path: &Packet, summary: &RelaySummary| {
+                relay_log.call(path, summary)?;
+                on_relayed.call(path, summary)
+            },
+        )),
+        (relay_log, on_relayed) => relay_log.or(on_relayed),
+    }
+}
+
+/// A callback recording the packets relayed by a packet worker in the
+/// relay log, if the `relay_log_path` is set in the configuration.
+fn relay_log_callback(config: &Config) -> Option<packet::RelayedPacketsCallback> {
+    let path = config.global.relay_log_path.as_ref()?;
+
+    match RelayLog::shared(path) {
+        Ok(relay_log) => {
+            let callback = packet::RelayedPacketsCallback::new(
+                move |path: &Packet, summary: &RelaySummary| {
+                    relay_log.record(path, summary);
+                    Ok(())
+                },
+            );
+
+            Some(callback)
+        }
+        Err(e) => {
+            error!(
+                "failed to open the relay log at '{}', relayed packets will not be logged: {}",
+                path.display(),
+                e
+            );
+
+            None
+        }
+    }
 }
@@ -0,0 +1,156 @@
+use core::time::Duration;
+use std::time::Instant;
+
+/// A circuit breaker which trips after a number of consecutive failures,
+/// and stays open for a cooldown period before closing again.
+///
+/// The packet workers use it to pause relaying on a path whose schedule
+/// executions keep failing, instead of retrying endlessly.
+#[derive(Clone, Debug)]
+pub struct CircuitBreaker {
+    /// The number of consecutive failures after which the circuit opens,
+    /// or `0` if the circuit breaker is disabled.
+    threshold: u64,
+    /// The window in which the consecutive failures must occur,
+    /// or zero for no time limit.
+    window: Duration,
+    /// For how long the circuit stays open once it has been tripped.
+    cooldown: Duration,
+    failures: u64,
+    first_failure: Option<Instant>,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u64, window: Duration, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            window,
+            cooldown,
+            failures: 0,
+            first_failure: None,
+            opened_at: None,
+        }
+    }
+
+    /// A circuit breaker which never opens.
+    pub fn disabled() -> Self {
+        Self::new(0, Duration::ZERO, Duration::ZERO)
+    }
+
+    pub fn threshold(&self) -> u64 {
+        self.threshold
+    }
+
+    pub fn cooldown(&self) -> Duration {
+        self.cooldown
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.opened_at.is_some()
+    }
+
+    /// Closes the circuit if it is open and its cooldown has elapsed at `now`.
+    ///
+    /// Returns whether the circuit was closed by this call.
+    pub fn try_close(&mut self, now: Instant) -> bool {
+        match self.opened_at {
+            Some(opened_at) if now.saturating_duration_since(opened_at) >= self.cooldown => {
+                self.reset();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Resets the count of consecutive failures.
+    pub fn record_success(&mut self) {
+        if !self.is_open() {
+            self.reset();
+        }
+    }
+
+    /// Records a failure happening at `now`.
+    ///
+    /// Returns whether the failure tripped the circuit.
+    pub fn record_failure(&mut self, now: Instant) -> bool {
+        if self.threshold == 0 || self.is_open() {
+            return false;
+        }
+
+        let window_elapsed = self.first_failure.map_or(true, |first| {
+            !self.window.is_zero() && now.saturating_duration_since(first) > self.window
+        });
+
+        if window_elapsed {
+            self.first_failure = Some(now);
+            self.failures = 0;
+        }
+
+        self.failures += 1;
+
+        if self.failures >= self.threshold {
+            self.opened_at = Some(now);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn reset(&mut self) {
+        self.failures = 0;
+        self.first_failure = None;
+        self.opened_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use super::CircuitBreaker;
+
+    #[test]
+    fn opens_after_consecutive_failures_and_closes_after_cooldown() {
+        let start = Instant::now();
+        let mut breaker = CircuitBreaker::new(3, Duration::from_secs(60), Duration::from_secs(10));
+
+        assert!(!breaker.record_failure(start));
+        assert!(!breaker.record_failure(start + Duration::from_secs(1)));
+        assert!(breaker.record_failure(start + Duration::from_secs(2)));
+        assert!(breaker.is_open());
+
+        assert!(!breaker.try_close(start + Duration::from_secs(5)));
+        assert!(breaker.is_open());
+
+        assert!(breaker.try_close(start + Duration::from_secs(12)));
+        assert!(!breaker.is_open());
+    }
+
+    #[test]
+    fn success_and_elapsed_window_reset_failures() {
+        let start = Instant::now();
+        let mut breaker = CircuitBreaker::new(2, Duration::from_secs(60), Duration::from_secs(10));
+
+        assert!(!breaker.record_failure(start));
+        breaker.record_success();
+        assert!(!breaker.record_failure(start + Duration::from_secs(1)));
+
+        // The previous failure falls outside of the window.
+        assert!(!breaker.record_failure(start + Duration::from_secs(90)));
+        assert!(breaker.record_failure(start + Duration::from_secs(91)));
+    }
+
+    #[test]
+    fn disabled_breaker_never_opens() {
+        let start = Instant::now();
+        let mut breaker = CircuitBreaker::disabled();
+
+        for i in 0..10 {
+            assert!(!breaker.record_failure(start + Duration::from_secs(i)));
+        }
+
+        assert!(!breaker.is_open());
+    }
+}
@@ -33,6 +33,7 @@ pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
                 dst_chain = %client.dst_chain.id(),
             ),
             Some(Duration::from_secs(1)),
+            None,
             move || {
                 let res = client.refresh().map_err(|e| {
                     if e.is_expired_or_frozen_error() {
@@ -75,6 +76,7 @@ pub fn detect_misbehavior_task<ChainA: ChainHandle, ChainB: ChainHandle>(
             dst_chain = %client.dst_chain.id(),
         ),
         Some(Duration::from_millis(600)),
+        None,
         move || -> Result<Next, TaskError<Infallible>> {
             if !first_check_done {
                 first_check_done = true;
@@ -124,6 +126,9 @@ pub fn detect_misbehavior_task<ChainA: ChainHandle, ChainB: ChainHandle>(
 
                     WorkerCmd::NewBlock { .. } => {}
                     WorkerCmd::ClearPendingPackets => {}
+                    WorkerCmd::RefreshClient => {}
+                    WorkerCmd::Pause | WorkerCmd::Resume => {}
+                    WorkerCmd::Reconfigure { .. } => {}
                 }
             }
 
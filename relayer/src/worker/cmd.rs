@@ -15,6 +15,24 @@ pub enum WorkerCmd {
 
     /// Trigger a pending packets clear
     ClearPendingPackets,
+
+    /// Re-fetch the latest client and consensus states of the
+    /// clients of the relay path, eg. after an out-of-band update
+    RefreshClient,
+
+    /// Stop relaying on the path until resumed, while still
+    /// consuming commands and updating the schedule
+    Pause,
+
+    /// Resume relaying on a path paused by [`WorkerCmd::Pause`]
+    Resume,
+
+    /// Apply the packet relaying parameters of a reloaded configuration
+    Reconfigure {
+        clear_interval: u64,
+        relay_acks: bool,
+        relay_timeouts: bool,
+    },
 }
 
 impl fmt::Display for WorkerCmd {
@@ -31,6 +49,18 @@ impl fmt::Display for WorkerCmd {
                 write!(f, "NewBlock({}, {:?})", height, new_block)
             }
             WorkerCmd::ClearPendingPackets => write!(f, "CleaPendingPackets"),
+            WorkerCmd::RefreshClient => write!(f, "RefreshClient"),
+            WorkerCmd::Pause => write!(f, "Pause"),
+            WorkerCmd::Resume => write!(f, "Resume"),
+            WorkerCmd::Reconfigure {
+                clear_interval,
+                relay_acks,
+                relay_timeouts,
+            } => write!(
+                f,
+                "Reconfigure(clear_interval: {}, relay_acks: {}, relay_timeouts: {})",
+                clear_interval, relay_acks, relay_timeouts
+            ),
         }
     }
 }
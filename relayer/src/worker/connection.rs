@@ -22,6 +22,7 @@ pub fn spawn_connection_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     spawn_background_task(
         error_span!("connection", connection = %connection.short_name()),
         Some(Duration::from_millis(200)),
+        None,
         move || {
             if let Ok(cmd) = cmd_rx.try_recv() {
                 match cmd {
@@ -76,6 +77,9 @@ pub fn spawn_connection_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
 
                     // nothing to do
                     WorkerCmd::ClearPendingPackets => Ok(Next::Continue),
+                    WorkerCmd::RefreshClient => Ok(Next::Continue),
+                    WorkerCmd::Pause | WorkerCmd::Resume => Ok(Next::Continue),
+                    WorkerCmd::Reconfigure { .. } => Ok(Next::Continue),
                 }
             } else {
                 Ok(Next::Continue)
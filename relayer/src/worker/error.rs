@@ -1,3 +1,5 @@
+use core::time::Duration;
+
 use crossbeam_channel::RecvError;
 use flex_error::{define_error, DisplayOnly};
 use ibc::core::ics02_client::error::Error as Ics02Error;
@@ -28,6 +30,10 @@ define_error! {
             { retries: retry::Error<u64> }
             | e | { format_args!("worker failed after {} retries", e.retries) },
 
+        ExecuteTimeout
+            { timeout: Duration }
+            | e | { format_args!("schedule execution did not complete within {:?}", e.timeout) },
+
         Recv
             [ DisplayOnly<RecvError> ]
             | _ | { "error receiving from channel: sender end has been closed" },
@@ -14,7 +14,7 @@ use ibc::{
 
 use crate::chain::tracking::TrackingId;
 use crate::util::lock::{LockExt, RwArc};
-use crate::util::task::TaskHandle;
+use crate::util::task::{CancellationToken, TaskHandle};
 use crate::{event::monitor::EventBatch, object::Object};
 
 use super::{WorkerCmd, WorkerId};
@@ -31,6 +31,7 @@ pub struct WorkerHandle {
     data: Option<WorkerData>,
     tx: RwArc<Option<Sender<WorkerCmd>>>,
     task_handles: Vec<TaskHandle>,
+    cancellation: Option<CancellationToken>,
 }
 
 impl WorkerHandle {
@@ -40,6 +41,7 @@ impl WorkerHandle {
         data: Option<WorkerData>,
         tx: Option<Sender<WorkerCmd>>,
         task_handles: Vec<TaskHandle>,
+        cancellation: Option<CancellationToken>,
     ) -> Self {
         Self {
             id,
@@ -47,6 +49,7 @@ impl WorkerHandle {
             data,
             tx: <RwArc<_>>::new_lock(tx),
             task_handles,
+            cancellation,
         }
     }
 
@@ -91,6 +94,45 @@ impl WorkerHandle {
         self.try_send_command(WorkerCmd::ClearPendingPackets);
     }
 
+    /// Instruct the worker to re-fetch the latest state of the clients it relays through.
+    pub fn refresh_client(&self) {
+        self.try_send_command(WorkerCmd::RefreshClient);
+    }
+
+    /// Instruct the worker to stop relaying until resumed.
+    pub fn pause(&self) {
+        self.try_send_command(WorkerCmd::Pause);
+    }
+
+    /// Instruct a paused worker to resume relaying.
+    pub fn resume(&self) {
+        self.try_send_command(WorkerCmd::Resume);
+    }
+
+    /// Instruct the worker to apply the given packet relaying parameters.
+    pub fn reconfigure(&self, clear_interval: u64, relay_acks: bool, relay_timeouts: bool) {
+        self.try_send_command(WorkerCmd::Reconfigure {
+            clear_interval,
+            relay_acks,
+            relay_timeouts,
+        });
+    }
+
+    /// Ask the worker tasks which support cooperative cancellation, ie. the ones
+    /// of packet workers, to stop once their current iteration completes,
+    /// without waiting for them to terminate.
+    ///
+    /// Returns whether the worker supports cooperative cancellation.
+    pub fn cancel(&self) -> bool {
+        match &self.cancellation {
+            Some(cancellation) => {
+                cancellation.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Shutdown all worker tasks without waiting for them to terminate.
     pub fn shutdown(&self) {
         for task in self.task_handles.iter() {
@@ -0,0 +1,146 @@
+use core::time::Duration;
+use std::time::Instant;
+
+/// The number of consecutive empty schedules after which the interval
+/// between two schedule executions starts being lengthened.
+const IDLE_THRESHOLD: u64 = 3;
+
+/// An adaptive interval between two schedule executions of a packet worker,
+/// which is doubled after each run of empty schedules, up to a maximum, so that
+/// idle paths are polled less often.
+///
+/// The interval snaps back to its base as soon as a schedule is not empty,
+/// or a worker command arrives, so that the latency is unaffected once the
+/// traffic resumes.
+#[derive(Clone, Debug)]
+pub struct IdleBackoff {
+    base: Duration,
+    /// The maximum interval, no longer than `base` if the backoff is disabled.
+    max: Duration,
+    interval: Duration,
+    empty_schedules: u64,
+    last_execution: Option<Instant>,
+}
+
+impl IdleBackoff {
+    pub fn new(base: Duration, max: Duration) -> Self {
+        Self {
+            base,
+            max,
+            interval: base,
+            empty_schedules: 0,
+            last_execution: None,
+        }
+    }
+
+    /// The current interval between two schedule executions.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Whether the schedule should be executed at `now`, ie. whether the
+    /// current interval has elapsed since the last execution.
+    pub fn is_due(&self, now: Instant) -> bool {
+        match self.last_execution {
+            Some(last) if self.interval > self.base => {
+                now.saturating_duration_since(last) >= self.interval
+            }
+            _ => true,
+        }
+    }
+
+    /// Records a schedule execution happening at `now`, which found the
+    /// schedule `empty` or not.
+    ///
+    /// Returns whether the interval changed.
+    pub fn record_execution(&mut self, empty: bool, now: Instant) -> bool {
+        self.last_execution = Some(now);
+
+        if !empty {
+            return self.reset();
+        }
+
+        self.empty_schedules += 1;
+
+        if self.empty_schedules < IDLE_THRESHOLD || self.interval >= self.max {
+            return false;
+        }
+
+        self.interval = (self.interval * 2).min(self.max);
+        true
+    }
+
+    /// Snaps the interval back to its base.
+    ///
+    /// Returns whether the interval changed.
+    pub fn reset(&mut self) -> bool {
+        self.empty_schedules = 0;
+
+        let changed = self.interval != self.base;
+        self.interval = self.base;
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::time::Instant;
+
+    use super::{IdleBackoff, IDLE_THRESHOLD};
+
+    const BASE: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn interval_doubles_after_a_run_of_empty_schedules() {
+        let mut backoff = IdleBackoff::new(BASE, Duration::from_secs(5));
+        let now = Instant::now();
+
+        for _ in 1..IDLE_THRESHOLD {
+            assert!(!backoff.record_execution(true, now));
+        }
+        assert_eq!(backoff.interval(), BASE);
+
+        assert!(backoff.record_execution(true, now));
+        assert_eq!(backoff.interval(), Duration::from_secs(2));
+
+        backoff.record_execution(true, now);
+        backoff.record_execution(true, now);
+        assert_eq!(backoff.interval(), Duration::from_secs(5));
+
+        assert!(!backoff.is_due(now + Duration::from_secs(4)));
+        assert!(backoff.is_due(now + Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn interval_snaps_back_on_activity() {
+        let mut backoff = IdleBackoff::new(BASE, Duration::from_secs(8));
+        let now = Instant::now();
+
+        for _ in 0..IDLE_THRESHOLD {
+            backoff.record_execution(true, now);
+        }
+        assert!(backoff.record_execution(false, now));
+        assert_eq!(backoff.interval(), BASE);
+        assert!(backoff.is_due(now));
+
+        for _ in 0..IDLE_THRESHOLD {
+            backoff.record_execution(true, now);
+        }
+        assert!(backoff.reset());
+        assert!(!backoff.reset());
+    }
+
+    #[test]
+    fn backoff_is_disabled_without_a_longer_maximum() {
+        let mut backoff = IdleBackoff::new(BASE, Duration::ZERO);
+        let now = Instant::now();
+
+        for _ in 0..10 {
+            assert!(!backoff.record_execution(true, now));
+        }
+
+        assert_eq!(backoff.interval(), BASE);
+        assert!(backoff.is_due(now));
+    }
+}
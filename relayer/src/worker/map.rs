@@ -13,6 +13,7 @@ use crate::{
     telemetry,
 };
 
+use super::packet::RelayedPacketsCallback;
 use super::{spawn_worker_tasks, WorkerHandle, WorkerId};
 
 /// Manage the lifecycle of [`WorkerHandle`]s associated with [`Object`]s.
@@ -20,6 +21,7 @@ use super::{spawn_worker_tasks, WorkerHandle, WorkerId};
 pub struct WorkerMap {
     workers: HashMap<Object, WorkerHandle>,
     latest_worker_id: WorkerId,
+    on_relayed: Option<RelayedPacketsCallback>,
 }
 
 impl Default for WorkerMap {
@@ -27,6 +29,7 @@ impl Default for WorkerMap {
         Self {
             workers: HashMap::new(),
             latest_worker_id: WorkerId::new(0),
+            on_relayed: None,
         }
     }
 }
@@ -39,17 +42,29 @@ impl WorkerMap {
         Self::default()
     }
 
+    /// Sets the callback invoked by the packet workers spawned from now on
+    /// with the summary of the packets they relay.
+    pub fn set_relayed_packets_callback(&mut self, on_relayed: Option<RelayedPacketsCallback>) {
+        self.on_relayed = on_relayed;
+    }
+
     /// Returns `true` if there is a spawned [`WorkerHandle`] associated with the given [`Object`].
     pub fn contains(&self, object: &Object) -> bool {
         self.workers.contains_key(object)
     }
 
+    /// Returns the [`WorkerHandle`] associated with the given [`Object`], if any.
+    pub fn get(&self, object: &Object) -> Option<&WorkerHandle> {
+        self.workers.get(object)
+    }
+
     /// Remove the [`WorkerHandle`] associated with the given [`Object`] from
     /// the map and wait for its thread to terminate.
     pub fn remove_stopped(&mut self, id: WorkerId, object: Object) -> bool {
         match self.workers.remove(&object) {
             Some(handle) if handle.id() == id => {
                 telemetry!(worker, metric_type(&object), -1);
+                telemetry!(packet_worker_status(&object, false));
 
                 let id = handle.id();
 
@@ -160,12 +175,14 @@ impl WorkerMap {
         config: &Config,
     ) -> WorkerHandle {
         telemetry!(worker, metric_type(object), 1);
+        telemetry!(packet_worker_status(object, true));
 
         spawn_worker_tasks(
             ChainHandlePair { a: src, b: dst },
             self.next_worker_id(),
             object.clone(),
             config,
+            self.on_relayed.clone(),
         )
     }
 
@@ -194,6 +211,11 @@ impl WorkerMap {
             .collect()
     }
 
+    /// List the [`Object`]s for which there is an associated worker.
+    pub fn objects(&self) -> impl Iterator<Item = &Object> {
+        self.workers.keys()
+    }
+
     /// Return all the handles to the workers tracked in this map.
     pub fn handles(&self) -> impl Iterator<Item = &WorkerHandle> {
         self.workers.values()
@@ -203,6 +225,7 @@ impl WorkerMap {
     pub fn shutdown_worker(&mut self, object: &Object) {
         if let Some(handle) = self.workers.remove(object) {
             telemetry!(worker, metric_type(object), -1);
+            telemetry!(packet_worker_status(object, false));
 
             handle.shutdown_and_wait();
         }
@@ -239,3 +262,27 @@ fn metric_type(o: &Object) -> ibc_telemetry::state::WorkerType {
         Object::Wallet(_) => WorkerType::Wallet,
     }
 }
+
+/// Starts or stops reporting the state of the worker of the given object,
+/// if it is a packet worker.
+#[cfg(feature = "telemetry")]
+fn packet_worker_status(o: &Object, active: bool) {
+    if let Object::Packet(path) = o {
+        let telemetry = ibc_telemetry::global();
+
+        if active {
+            telemetry.packet_worker_started(
+                &path.src_chain_id,
+                &path.src_channel_id,
+                &path.src_port_id,
+                &path.dst_chain_id,
+            );
+        } else {
+            telemetry.packet_worker_stopped(
+                &path.src_chain_id,
+                &path.src_channel_id,
+                &path.src_port_id,
+            );
+        }
+    }
+}
@@ -1,22 +1,31 @@
+use core::fmt;
 use core::time::Duration;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use crossbeam_channel::Receiver;
-use tracing::{error, error_span, trace};
+use tracing::{error, error_span, info, trace, warn};
 
 use ibc::Height;
 
 use crate::chain::handle::ChainHandle;
 use crate::event::monitor::EventBatch;
 use crate::foreign_client::HasExpiredOrFrozenError;
-use crate::link::Resubmit;
-use crate::link::{error::LinkError, Link};
+use crate::link::{
+    error::{LinkError, LinkErrorDetail},
+    Link,
+};
+use crate::link::{RelaySummary, Resubmit};
 use crate::object::Packet;
 use crate::telemetry;
-use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
+use crate::util::task::{
+    spawn_background_task, startup_jitter, CancellationToken, Next, TaskError, TaskHandle,
+};
 
 use super::error::RunError;
-use super::WorkerCmd;
+use super::{CircuitBreaker, IdleBackoff, WorkerCmd};
 
 fn handle_link_error_in_task(e: LinkError) -> TaskError<RunError> {
     if e.is_expired_or_frozen_error() {
@@ -28,13 +37,77 @@ fn handle_link_error_in_task(e: LinkError) -> TaskError<RunError> {
     }
 }
 
+/// A callback invoked by the packet workers with the [`RelaySummary`]
+/// of every non-empty batch of relayed packets, shared by all of them.
+///
+/// An error returned by the callback is logged, and never aborts the worker.
+#[derive(Clone)]
+pub struct RelayedPacketsCallback(
+    Arc<dyn Fn(&Packet, &RelaySummary) -> Result<(), String> + Send + Sync>,
+);
+
+impl RelayedPacketsCallback {
+    pub fn new(
+        callback: impl Fn(&Packet, &RelaySummary) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    pub fn call(&self, path: &Packet, summary: &RelaySummary) -> Result<(), String> {
+        (self.0)(path, summary)
+    }
+}
+
+impl fmt::Debug for RelayedPacketsCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RelayedPacketsCallback")
+    }
+}
+
+/// Pause between two iterations of the packet worker.
+pub(super) const PACKET_WORKER_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// Pause between two iterations of the packet command worker.
+const PACKET_CMD_WORKER_INTERVAL: Duration = Duration::from_millis(200);
+
 /// Spawns a packet worker task in the background that handles the work of
 /// processing pending txs between `ChainA` and `ChainB`.
+///
+/// If given, the `on_relayed` callback is invoked after pending txs have been
+/// processed, without holding the lock on the `link`.
+///
+/// The worker does nothing while the `circuit_breaker` of the path is open,
+/// or while the path is `paused` by a [`WorkerCmd::Pause`] command. Only the
+/// outcomes of the schedule executions of this worker are recorded in the
+/// circuit breaker, so that the commands handled successfully by the command
+/// worker do not reset the failures it is meant to catch.
+///
+/// The resubmission policy is derived from the `clear_interval` shared with the
+/// command worker, so that it follows a [`WorkerCmd::Reconfigure`] command.
+///
+/// While the path is idle, the schedule is executed less often, as told by the
+/// `idle_backoff` shared with the command worker, which resets it whenever a
+/// command other than [`WorkerCmd::NewBlock`] arrives.
+///
+/// With `jitter_startup`, the first iteration is delayed by a random fraction
+/// of the pause between two iterations.
+///
+/// Once `cancellation` is cancelled, the worker stops at the top of its next
+/// iteration, so that the schedule being executed, if any, completes first.
+/// It also stops after `max_iterations`, if any.
 pub fn spawn_packet_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     path: Packet,
     // Mutex is used to prevent race condition between the packet workers
     link: Arc<Mutex<Link<ChainA, ChainB>>>,
-    resubmit: Resubmit,
+    clear_interval: Arc<AtomicU64>,
+    attempt_client_recovery: bool,
+    circuit_breaker: Arc<Mutex<CircuitBreaker>>,
+    paused: Arc<AtomicBool>,
+    idle_backoff: Arc<Mutex<IdleBackoff>>,
+    jitter_startup: bool,
+    on_relayed: Option<RelayedPacketsCallback>,
+    max_iterations: Option<u64>,
+    cancellation: CancellationToken,
 ) -> TaskHandle {
     let span = {
         let relay_path = &link.lock().unwrap().a_to_b;
@@ -47,19 +120,201 @@ pub fn spawn_packet_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
         )
     };
 
-    spawn_background_task(span, Some(Duration::from_millis(1000)), move || {
-        handle_execute_schedule(&mut link.lock().unwrap(), &path, resubmit)?;
+    let mut startup_delay = jitter_startup.then(|| startup_jitter(PACKET_WORKER_INTERVAL));
+
+    telemetry!(
+        ibc_packet_worker_interval,
+        &path.src_chain_id,
+        &path.src_channel_id,
+        &path.src_port_id,
+        idle_backoff.lock().unwrap().interval()
+    );
+
+    spawn_background_task(
+        span,
+        Some(PACKET_WORKER_INTERVAL),
+        max_iterations,
+        move || {
+            if cancellation.is_cancelled() {
+                info!("packet worker cancelled, stopping");
+                return Ok(Next::Abort);
+            }
+
+            if let Some(delay) = startup_delay.take() {
+                return Ok(Next::ContinueAfter(delay));
+            }
+
+            if paused.load(Ordering::SeqCst) || is_path_paused(&circuit_breaker, &path) {
+                return Ok(Next::Continue);
+            }
+
+            if !idle_backoff.lock().unwrap().is_due(Instant::now()) {
+                return Ok(Next::Continue);
+            }
+
+            let resubmit = Resubmit::from_clear_interval(clear_interval.load(Ordering::SeqCst));
+
+            // The lock on the link is released at the end of this statement.
+            let result = handle_execute_schedule(&mut link.lock().unwrap(), &path, resubmit);
+
+            let result =
+                recover_from_frozen_client(&link.lock().unwrap(), attempt_client_recovery, result);
+
+            record_circuit_result(&circuit_breaker, &path, &result);
+
+            let summary = result?;
+
+            let idle = summary.is_empty() && link.lock().unwrap().a_to_b.is_idle();
+            update_idle_backoff(&idle_backoff, &path, |idle_backoff| {
+                idle_backoff.record_execution(idle, Instant::now())
+            });
+
+            if let Some(callback) = &on_relayed {
+                if !summary.is_empty() {
+                    run_relayed_packets_callback(callback, &path, &summary);
+                }
+            }
+
+            // Wait for the transaction rate limits without holding the lock on the link.
+            match link.lock().unwrap().a_to_b.take_tx_rate_limit_delay() {
+                Some(delay) => Ok(Next::ContinueAfter(delay)),
+                None => Ok(Next::Continue),
+            }
+        },
+    )
+}
+
+/// Applies the given `update` to the `idle_backoff`, reporting the interval
+/// between two schedule executions whenever the update changes it.
+fn update_idle_backoff(
+    idle_backoff: &Mutex<IdleBackoff>,
+    path: &Packet,
+    update: impl FnOnce(&mut IdleBackoff) -> bool,
+) {
+    let mut idle_backoff = idle_backoff.lock().unwrap();
+
+    if update(&mut idle_backoff) {
+        trace!(
+            interval = ?idle_backoff.interval(),
+            "changed the interval between schedule executions"
+        );
+
+        telemetry!(
+            ibc_packet_worker_interval,
+            &path.src_chain_id,
+            &path.src_channel_id,
+            &path.src_port_id,
+            idle_backoff.interval()
+        );
+    }
+}
+
+/// Interval at which the observer workers look for pending packets.
+const OBSERVER_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Spawns an observer worker task in the background which periodically queries
+/// the sequences of the packets and acknowledgements pending on the path from
+/// `ChainA` to `ChainB`, and reports their number through telemetry, without
+/// ever building nor submitting any message.
+///
+/// Because it never submits any transaction, an observer worker is safe to run
+/// alongside a relayer which relays on the same channel.
+pub fn spawn_observer_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
+    path: Packet,
+    link: Link<ChainA, ChainB>,
+) -> TaskHandle {
+    let span = error_span!(
+        "packet_observer",
+        src_chain = %path.src_chain_id,
+        src_port = %path.src_port_id,
+        src_channel = %path.src_channel_id,
+        dst_chain = %path.dst_chain_id,
+    );
+
+    let link = Mutex::new(link);
+
+    spawn_background_task(span, Some(OBSERVER_INTERVAL), None, move || {
+        let (pending_packets, pending_acks) = link
+            .lock()
+            .unwrap()
+            .a_to_b
+            .count_pending_packets()
+            .map_err(handle_link_error_in_task)?;
+
+        trace!(
+            "observed {} pending packet(s) and {} pending acknowledgement(s)",
+            pending_packets,
+            pending_acks
+        );
+
+        telemetry!(
+            ibc_pending_packets,
+            &path.src_chain_id,
+            &path.src_channel_id,
+            &path.src_port_id,
+            pending_packets,
+        );
+
+        telemetry!(
+            ibc_pending_acks,
+            &path.src_chain_id,
+            &path.src_channel_id,
+            &path.src_port_id,
+            pending_acks,
+        );
+
         Ok(Next::Continue)
     })
 }
 
+fn run_relayed_packets_callback(
+    callback: &RelayedPacketsCallback,
+    path: &Packet,
+    summary: &RelaySummary,
+) {
+    match panic::catch_unwind(AssertUnwindSafe(|| callback.call(path, summary))) {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => error!("relayed packets callback failed: {}", e),
+        Err(_) => error!("relayed packets callback panicked"),
+    }
+}
+
+/// Spawns a packet worker task in the background that handles the worker
+/// commands, ie. the IBC events and new blocks of the source chain.
+///
+/// If given, the `on_relayed` callback is invoked with the summary of every
+/// command which relayed packets, without holding the lock on the `link`.
+///
+/// While the path is `paused`, the worker keeps consuming the commands and
+/// updating the schedule, but does not execute it. The commands are left queued
+/// while the `circuit_breaker` is open, their outcomes not being recorded in it.
+///
+/// With `jitter_startup`, the first iteration is delayed by a random fraction
+/// of the pause between two iterations.
+///
+/// The packets cleared on start are capped to `clear_on_start_max_packets`, unless it is zero.
+///
+/// Once `cancellation` is cancelled, the worker stops at the top of its next
+/// iteration, leaving the remaining commands unprocessed. It also stops after
+/// `max_iterations`, if any, each iteration handling at most one command.
 pub fn spawn_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     cmd_rx: Receiver<WorkerCmd>,
     // Mutex is used to prevent race condition between the packet workers
     link: Arc<Mutex<Link<ChainA, ChainB>>>,
     mut should_clear_on_start: bool,
-    clear_interval: u64,
+    clear_on_start_max_packets: usize,
+    clear_interval: Arc<AtomicU64>,
+    attempt_client_recovery: bool,
+    max_event_batch_size: usize,
+    max_command_retries: u64,
+    circuit_breaker: Arc<Mutex<CircuitBreaker>>,
+    paused: Arc<AtomicBool>,
+    idle_backoff: Arc<Mutex<IdleBackoff>>,
     path: Packet,
+    jitter_startup: bool,
+    on_relayed: Option<RelayedPacketsCallback>,
+    max_iterations: Option<u64>,
+    cancellation: CancellationToken,
 ) -> TaskHandle {
     let span = {
         let relay_path = &link.lock().unwrap().a_to_b;
@@ -74,34 +329,207 @@ pub fn spawn_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
 
     let mut current_command = None;
 
-    spawn_background_task(span, Some(Duration::from_millis(200)), move || {
-        if current_command.is_none() {
-            // Only try to receive the next command if the
-            // previous command was processed successfully.
-            current_command = cmd_rx.try_recv().ok();
-        }
+    // The height at which packets were last cleared by this worker.
+    let mut last_cleared_height = None;
+
+    // The number of times the current command has failed so far.
+    let mut command_failures: u64 = 0;
+
+    let mut startup_delay = jitter_startup.then(|| startup_jitter(PACKET_CMD_WORKER_INTERVAL));
+
+    spawn_background_task(
+        span,
+        Some(PACKET_CMD_WORKER_INTERVAL),
+        max_iterations,
+        move || {
+            if cancellation.is_cancelled() {
+                info!("packet command worker cancelled, stopping");
+                return Ok(Next::Abort);
+            }
+
+            if let Some(delay) = startup_delay.take() {
+                return Ok(Next::ContinueAfter(delay));
+            }
+
+            // Commands are left queued while the path is paused.
+            if is_path_paused(&circuit_breaker, &path) {
+                return Ok(Next::Continue);
+            }
+
+            if current_command.is_none() {
+                // Only try to receive the next command if the
+                // previous command was processed successfully.
+                current_command = cmd_rx.try_recv().ok();
+
+                // A new block alone does not mean there is anything to relay.
+                if let Some(cmd) = &current_command {
+                    if !matches!(cmd, WorkerCmd::NewBlock { .. }) {
+                        update_idle_backoff(&idle_backoff, &path, IdleBackoff::reset);
+                    }
+                }
+            }
+
+            if let Some(cmd) = &current_command {
+                let result = handle_packet_cmd(
+                    &mut link.lock().unwrap(),
+                    &mut should_clear_on_start,
+                    clear_on_start_max_packets,
+                    &mut last_cleared_height,
+                    &paused,
+                    &clear_interval,
+                    max_event_batch_size,
+                    &path,
+                    cmd.clone(),
+                );
+
+                let result = recover_from_frozen_client(
+                    &link.lock().unwrap(),
+                    attempt_client_recovery,
+                    result,
+                );
+
+                if let Err(TaskError::Ignore(e)) = &result {
+                    command_failures += 1;
+
+                    if max_command_retries != 0 && command_failures > max_command_retries {
+                        error!(
+                            "dropping worker command after {} failed attempts, last error: {}",
+                            command_failures, e
+                        );
+
+                        telemetry!(
+                            ibc_dropped_worker_commands,
+                            &path.src_chain_id,
+                            &path.src_channel_id,
+                            &path.src_port_id,
+                            1
+                        );
+
+                        current_command = None;
+                        command_failures = 0;
+
+                        return Ok(Next::Continue);
+                    }
+                }
+
+                let summary = result?;
+
+                if let Some(callback) = &on_relayed {
+                    if !summary.is_empty() {
+                        run_relayed_packets_callback(callback, &path, &summary);
+                    }
+                }
+
+                // Only reset current_command if handle_packet_cmd succeeds.
+                // Otherwise the same command will be retried in the next step,
+                // unless it has exhausted its retries.
+                current_command = None;
+                command_failures = 0;
+            }
+
+            // Wait for the transaction rate limits without holding the lock on the link.
+            match link.lock().unwrap().a_to_b.take_tx_rate_limit_delay() {
+                Some(delay) => Ok(Next::ContinueAfter(delay)),
+                None => Ok(Next::Continue),
+            }
+        },
+    )
+}
+
+/// Whether relaying on the path is paused by its circuit breaker.
+/// Closes the circuit, resuming relaying, once its cooldown has elapsed.
+fn is_path_paused(circuit_breaker: &Mutex<CircuitBreaker>, path: &Packet) -> bool {
+    let mut circuit_breaker = circuit_breaker.lock().unwrap();
+
+    if !circuit_breaker.is_open() {
+        return false;
+    }
+
+    if circuit_breaker.try_close(Instant::now()) {
+        info!(
+            path = %path.short_name(),
+            "circuit breaker cooldown elapsed, resuming relaying on the path"
+        );
+        telemetry!(circuit_metrics(path, false));
+
+        false
+    } else {
+        true
+    }
+}
+
+/// Records the given `result` of processing the path in its circuit breaker,
+/// pausing the path if the failure trips the circuit. Fatal errors are not
+/// recorded, as they stop the worker anyway.
+fn record_circuit_result<T>(
+    circuit_breaker: &Mutex<CircuitBreaker>,
+    path: &Packet,
+    result: &Result<T, TaskError<RunError>>,
+) {
+    let mut circuit_breaker = circuit_breaker.lock().unwrap();
 
-        if let Some(cmd) = &current_command {
-            handle_packet_cmd(
-                &mut link.lock().unwrap(),
-                &mut should_clear_on_start,
-                clear_interval,
-                &path,
-                cmd.clone(),
-            )?;
-
-            // Only reset current_command if handle_packet_cmd succeeds.
-            // Otherwise the same command will be retried in the next step.
-            current_command = None;
+    match result {
+        Ok(_) => circuit_breaker.record_success(),
+        Err(TaskError::Ignore(e)) => {
+            if circuit_breaker.record_failure(Instant::now()) {
+                error!(
+                    path = %path.short_name(),
+                    "pausing relaying on the path for {} after {} consecutive failures, last error: {}",
+                    humantime::format_duration(circuit_breaker.cooldown()),
+                    circuit_breaker.threshold(),
+                    e
+                );
+
+                telemetry!(circuit_metrics(path, true));
+            }
         }
+        Err(TaskError::Fatal(_)) => {}
+    }
+}
 
-        Ok(Next::Continue)
-    })
+#[cfg(feature = "telemetry")]
+fn circuit_metrics(path: &Packet, open: bool) {
+    telemetry!(
+        ibc_path_circuit_open,
+        &path.src_chain_id,
+        &path.src_channel_id,
+        &path.src_port_id,
+        open,
+    );
+}
+
+/// If `attempt_client_recovery` is set and the given `result` is a fatal error,
+/// which happens when a client is expired or frozen, attempts to recover the
+/// clients of the link. If the recovery succeeds, the error is downgraded so that
+/// the worker retries at its next step, otherwise the error remains fatal.
+fn recover_from_frozen_client<ChainA: ChainHandle, ChainB: ChainHandle, T>(
+    link: &Link<ChainA, ChainB>,
+    attempt_client_recovery: bool,
+    result: Result<T, TaskError<RunError>>,
+) -> Result<T, TaskError<RunError>> {
+    match result {
+        Err(TaskError::Fatal(e)) if attempt_client_recovery => {
+            warn!("attempting client recovery after fatal error: {}", e);
+
+            match link.a_to_b.attempt_client_recovery() {
+                Ok(()) => {
+                    info!("client recovery succeeded, will retry");
+                    Err(TaskError::Ignore(e))
+                }
+                Err(recovery_error) => {
+                    error!("client recovery failed: {}", recovery_error);
+                    Err(TaskError::Fatal(e))
+                }
+            }
+        }
+        result => result,
+    }
 }
 
 /// Receives worker commands, which may be:
 ///     - IbcEvent => then it updates schedule
 ///     - NewBlock => schedules packet clearing
+///     - Reconfigure => applies the parameters of a reloaded configuration
 ///     - Shutdown => exits
 ///
 /// Regardless of the incoming command, this method
@@ -110,12 +538,30 @@ pub fn spawn_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
 fn handle_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
     link: &mut Link<ChainA, ChainB>,
     should_clear_on_start: &mut bool,
-    clear_interval: u64,
+    clear_on_start_max_packets: usize,
+    last_cleared_height: &mut Option<Height>,
+    paused: &AtomicBool,
+    shared_clear_interval: &AtomicU64,
+    max_event_batch_size: usize,
     path: &Packet,
     cmd: WorkerCmd,
-) -> Result<(), TaskError<RunError>> {
+) -> Result<RelaySummary, TaskError<RunError>> {
+    let is_paused = paused.load(Ordering::SeqCst);
+    let clear_interval = shared_clear_interval.load(Ordering::SeqCst);
+
     match cmd {
-        WorkerCmd::IbcEvents { batch } => handle_update_schedule(link, clear_interval, path, batch),
+        WorkerCmd::IbcEvents { batch } => handle_update_schedule(
+            link,
+            clear_interval,
+            max_event_batch_size,
+            path,
+            batch,
+            is_paused,
+        ),
+
+        // Packet clearing is deferred until the path is resumed,
+        // as its operational data would only go stale in the meantime.
+        WorkerCmd::NewBlock { .. } if is_paused => Ok(RelaySummary::empty()),
 
         // Handle the arrival of an event signaling that the
         // source chain has advanced to a new block.
@@ -124,49 +570,240 @@ fn handle_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
             new_block: _,
         } => {
             if *should_clear_on_start {
-                handle_clear_packet(link, clear_interval, path, Some(height))?;
+                let summary = handle_clear_packet(
+                    link,
+                    clear_interval,
+                    clear_on_start_max_packets,
+                    path,
+                    Some(height),
+                )?;
 
                 // Clear the flag only if handle_clear_packet succeeds
                 *should_clear_on_start = false;
-                Ok(())
-            } else if should_clear_packets(clear_interval, height) {
-                handle_clear_packet(link, clear_interval, path, Some(height))
+                *last_cleared_height = Some(height);
+                Ok(summary)
+            } else if should_clear_packets(clear_interval, *last_cleared_height, height) {
+                let summary = handle_clear_packet(link, clear_interval, 0, path, Some(height))?;
+
+                *last_cleared_height = Some(height);
+                Ok(summary)
             } else {
-                Ok(())
+                Ok(RelaySummary::empty())
             }
         }
 
-        WorkerCmd::ClearPendingPackets => handle_clear_packet(link, clear_interval, path, None),
+        WorkerCmd::ClearPendingPackets if is_paused => {
+            warn!(path = %path.short_name(), "ignoring request to clear packets on paused path");
+            Ok(RelaySummary::empty())
+        }
+
+        WorkerCmd::ClearPendingPackets => handle_clear_packet(link, clear_interval, 0, path, None),
+
+        WorkerCmd::RefreshClient => {
+            handle_refresh_client(link, path)?;
+            Ok(RelaySummary::empty())
+        }
+
+        WorkerCmd::Pause => {
+            set_path_paused(paused, path, true);
+            Ok(RelaySummary::empty())
+        }
+
+        WorkerCmd::Resume => {
+            set_path_paused(paused, path, false);
+            Ok(RelaySummary::empty())
+        }
+
+        WorkerCmd::Reconfigure {
+            clear_interval,
+            relay_acks,
+            relay_timeouts,
+        } => {
+            info!(
+                path = %path.short_name(),
+                clear_interval, relay_acks, relay_timeouts, "reconfiguring the path"
+            );
+
+            shared_clear_interval.store(clear_interval, Ordering::SeqCst);
+            link.a_to_b.set_relay_acks(relay_acks);
+            link.a_to_b.set_relay_timeouts(relay_timeouts);
+
+            Ok(RelaySummary::empty())
+        }
+    }
+}
+
+/// Pauses or resumes relaying on the path.
+fn set_path_paused(paused: &AtomicBool, path: &Packet, pause: bool) {
+    if paused.swap(pause, Ordering::SeqCst) == pause {
+        return;
+    }
+
+    if pause {
+        info!(path = %path.short_name(), "pausing relaying on the path");
+    } else {
+        info!(path = %path.short_name(), "resuming relaying on the path");
     }
+
+    telemetry!(
+        ibc_path_paused,
+        &path.src_chain_id,
+        &path.src_channel_id,
+        &path.src_port_id,
+        pause
+    );
 }
 
 /// Whether or not to clear pending packets at this `step` for the given height.
 /// Packets are cleared if `clear_interval` is not `0` and if we have reached the interval.
-fn should_clear_packets(clear_interval: u64, height: Height) -> bool {
-    clear_interval != 0 && height.revision_height % clear_interval == 0
+///
+/// If packets were previously cleared at `last_cleared_height`, packets are also cleared
+/// when at least one interval boundary was crossed since then, even if the boundary height
+/// itself was never seen, eg. because the relayer was down or missed some blocks.
+fn should_clear_packets(
+    clear_interval: u64,
+    last_cleared_height: Option<Height>,
+    height: Height,
+) -> bool {
+    if clear_interval == 0 {
+        return false;
+    }
+
+    match last_cleared_height {
+        None => height.revision_height % clear_interval == 0,
+        Some(last) if last.revision_number != height.revision_number => {
+            last.revision_number < height.revision_number
+        }
+        Some(last) => {
+            height.revision_height / clear_interval > last.revision_height / clear_interval
+        }
+    }
+}
+
+fn handle_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
+    link: &mut Link<ChainA, ChainB>,
+    path: &Packet,
+) -> Result<(), TaskError<RunError>> {
+    info!(path = %path.short_name(), "refreshing the clients of the relay path");
+
+    link.a_to_b
+        .refresh_clients()
+        .map_err(handle_link_error_in_task)
 }
 
 fn handle_update_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
     link: &mut Link<ChainA, ChainB>,
     clear_interval: u64,
+    max_event_batch_size: usize,
     path: &Packet,
     batch: EventBatch,
-) -> Result<(), TaskError<RunError>> {
-    link.a_to_b
-        .update_schedule(batch)
-        .map_err(handle_link_error_in_task)?;
+    is_paused: bool,
+) -> Result<RelaySummary, TaskError<RunError>> {
+    telemetry!(
+        ibc_events_received,
+        &path.src_chain_id,
+        batch.events.len() as u64
+    );
+
+    for sub_batch in split_event_batch(batch, max_event_batch_size) {
+        let packet_events = sub_batch
+            .events
+            .iter()
+            .filter(|event| event.packet().is_some())
+            .count() as u64;
+
+        let deferred = link
+            .a_to_b
+            .defer_within_grace_period(sub_batch)
+            .map_err(handle_link_error_in_task)?;
+
+        match deferred {
+            Some(sub_batch) => link
+                .a_to_b
+                .update_schedule(sub_batch)
+                .map_err(handle_link_error_in_task)?,
+            None => {
+                trace!(
+                    "leaving {} packet event(s) to other relayers for the relay grace period",
+                    packet_events
+                );
+
+                telemetry!(
+                    ibc_grace_skipped_packets,
+                    &path.src_chain_id,
+                    &path.src_channel_id,
+                    &path.src_port_id,
+                    packet_events
+                );
+            }
+        }
+    }
+
+    // The schedule is kept up to date while paused, to be executed once resumed.
+    if is_paused {
+        return Ok(RelaySummary::empty());
+    }
 
     handle_execute_schedule(link, path, Resubmit::from_clear_interval(clear_interval))
 }
 
+/// Feeds a recorded event batch to the given link, as the packet worker of the
+/// `path` would upon receiving it from the event monitor, and executes the
+/// resulting schedule, eg. to reproduce the relaying of a batch stored with
+/// [`BatchDump`](crate::event::dump::BatchDump).
+pub fn replay_event_batch<ChainA: ChainHandle, ChainB: ChainHandle>(
+    link: &mut Link<ChainA, ChainB>,
+    clear_interval: u64,
+    max_event_batch_size: usize,
+    path: &Packet,
+    batch: EventBatch,
+) -> Result<RelaySummary, RunError> {
+    handle_update_schedule(
+        link,
+        clear_interval,
+        max_event_batch_size,
+        path,
+        batch,
+        false,
+    )
+    .map_err(|e| match e {
+        TaskError::Ignore(e) | TaskError::Fatal(e) => e,
+    })
+}
+
+/// Splits the given batch into sub-batches of at most `max_events` events each,
+/// preserving the order of the events. A `max_events` of `0` disables splitting.
+fn split_event_batch(mut batch: EventBatch, max_events: usize) -> Vec<EventBatch> {
+    if max_events == 0 || batch.events.len() <= max_events {
+        return vec![batch];
+    }
+
+    let mut sub_batches = Vec::with_capacity((batch.events.len() + max_events - 1) / max_events);
+
+    while !batch.events.is_empty() {
+        let len = max_events.min(batch.events.len());
+
+        sub_batches.push(EventBatch {
+            chain_id: batch.chain_id.clone(),
+            tracking_id: batch.tracking_id,
+            height: batch.height,
+            events: batch.events.drain(..len).collect(),
+        });
+    }
+
+    sub_batches
+}
+
+/// Clears the pending packets, at most `max_packets` of them unless it is zero.
 fn handle_clear_packet<ChainA: ChainHandle, ChainB: ChainHandle>(
     link: &mut Link<ChainA, ChainB>,
     clear_interval: u64,
+    max_packets: usize,
     path: &Packet,
     height: Option<Height>,
-) -> Result<(), TaskError<RunError>> {
+) -> Result<RelaySummary, TaskError<RunError>> {
     link.a_to_b
-        .schedule_packet_clearing(height)
+        .schedule_packet_clearing_capped(height, max_packets)
         .map_err(handle_link_error_in_task)?;
 
     handle_execute_schedule(link, path, Resubmit::from_clear_interval(clear_interval))
@@ -174,41 +811,140 @@ fn handle_clear_packet<ChainA: ChainHandle, ChainB: ChainHandle>(
 
 fn handle_execute_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
     link: &mut Link<ChainA, ChainB>,
-    _path: &Packet,
+    path: &Packet,
     resubmit: Resubmit,
-) -> Result<(), TaskError<RunError>> {
+) -> Result<RelaySummary, TaskError<RunError>> {
     link.a_to_b
-        .refresh_schedule()
+        .refresh_clients_within_margin()
         .map_err(handle_link_error_in_task)?;
 
-    link.a_to_b.execute_schedule().map_err(|e| {
-        if e.is_expired_or_frozen_error() {
-            TaskError::Fatal(RunError::link(e))
-        } else {
-            error!("will retry: schedule execution encountered error: {}", e,);
-            TaskError::Ignore(RunError::link(e))
-        }
-    })?;
+    // The packets whose relay grace period elapsed are only scheduled if still pending.
+    for batch in link.a_to_b.take_batches_past_grace_period() {
+        link.a_to_b
+            .update_schedule(batch)
+            .map_err(handle_link_error_in_task)?;
+    }
+
+    // Time the refresh on its own, as its queries may dominate the schedule execution.
+    let refresh_start = Instant::now();
+    let refreshed = link.a_to_b.refresh_schedule();
+    let refresh_elapsed = refresh_start.elapsed();
+
+    trace!(elapsed = ?refresh_elapsed, "refreshed schedule");
+
+    telemetry!(
+        ibc_schedule_refresh_seconds,
+        &path.src_chain_id,
+        &path.src_channel_id,
+        &path.src_port_id,
+        refresh_elapsed
+    );
+
+    refreshed.map_err(handle_link_error_in_task)?;
+
+    // The acknowledgements have their own flush policy, the other messages are relayed promptly.
+    link.a_to_b
+        .coalesce_acks()
+        .map_err(handle_link_error_in_task)?;
+
+    if link.a_to_b.should_defer_execution() {
+        trace!("deferring the schedule execution to the cheaper, reverse direction");
+    } else {
+        link.a_to_b.execute_schedule().map_err(|e| {
+            if e.is_expired_or_frozen_error() {
+                TaskError::Fatal(RunError::link(e))
+            } else if let LinkErrorDetail::ExecuteScheduleTimeout(detail) = e.detail() {
+                warn!("will retry: {}", e);
+                TaskError::Ignore(RunError::execute_timeout(detail.timeout))
+            } else {
+                error!("will retry: schedule execution encountered error: {}", e,);
+                TaskError::Ignore(RunError::link(e))
+            }
+        })?;
+    }
 
     let summary = link.a_to_b.process_pending_txs(resubmit);
 
+    link.a_to_b.persist_schedule_if_due();
+
+    telemetry!(
+        ibc_in_flight_txs,
+        &path.src_chain_id,
+        &path.src_channel_id,
+        &path.src_port_id,
+        link.a_to_b.in_flight_txs() as u64
+    );
+
+    let mismatches = link.a_to_b.verify_acks(&summary);
+
+    for mismatch in &mismatches {
+        error!(
+            relayed = ?mismatch.relayed,
+            acknowledged = ?mismatch.acknowledged,
+            "acknowledgement written for packet {} differs from the packet relayed, \
+            the counterparty may be misbehaving",
+            mismatch.relayed.sequence,
+        );
+    }
+
+    if !mismatches.is_empty() {
+        telemetry!(
+            ibc_ack_mismatch,
+            &path.src_chain_id,
+            &path.src_channel_id,
+            &path.src_port_id,
+            mismatches.len() as u64,
+        );
+    }
+
     if !summary.is_empty() {
+        info!(
+            "{}",
+            summary.describe(&path.src_chain_id, &path.src_channel_id)
+        );
         trace!("produced relay summary: {:?}", summary);
     }
 
-    telemetry!(packet_metrics(_path, &summary));
+    telemetry!(packet_metrics(path, &summary));
+    telemetry!(scheduled_packets_metrics(path, link));
 
-    Ok(())
+    Ok(summary)
 }
 
+/// Reports the packets and acknowledgements still scheduled after the
+/// schedule execution as pending on the `/workers` route.
 #[cfg(feature = "telemetry")]
-use crate::link::RelaySummary;
+fn scheduled_packets_metrics<ChainA: ChainHandle, ChainB: ChainHandle>(
+    path: &Packet,
+    link: &Link<ChainA, ChainB>,
+) {
+    let (packets, acks) = link.a_to_b.count_scheduled_packets();
+
+    telemetry!(
+        packet_worker_pending,
+        &path.src_chain_id,
+        &path.src_channel_id,
+        &path.src_port_id,
+        packets,
+        acks,
+    );
+}
 
 #[cfg(feature = "telemetry")]
 fn packet_metrics(path: &Packet, summary: &RelaySummary) {
     receive_packet_metrics(path, summary);
     acknowledgment_metrics(path, summary);
     timeout_metrics(path, summary);
+    last_relayed_height_metrics(path, summary);
+
+    if !summary.is_empty() {
+        telemetry!(
+            packet_worker_progress,
+            &path.src_chain_id,
+            &path.src_channel_id,
+            &path.src_port_id,
+        );
+    }
 }
 
 #[cfg(feature = "telemetry")]
@@ -266,3 +1002,347 @@ fn timeout_metrics(path: &Packet, summary: &RelaySummary) {
         count as u64,
     );
 }
+
+#[cfg(feature = "telemetry")]
+fn last_relayed_height_metrics(path: &Packet, summary: &RelaySummary) {
+    if let Some(height) = summary.latest_packet_height() {
+        telemetry!(
+            ibc_last_relayed_height,
+            &path.src_chain_id,
+            &path.dst_chain_id,
+            &path.src_channel_id,
+            height.revision_height,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        should_clear_packets, spawn_packet_cmd_worker, spawn_packet_worker, split_event_batch,
+    };
+
+    use core::time::Duration;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use crossbeam_channel::Receiver;
+    use ibc::core::ics02_client::events::NewBlock;
+    use ibc::core::ics04_channel::channel::Order;
+    use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+    use ibc::events::IbcEvent;
+    use ibc::Height;
+
+    use crate::chain::handle::{BaseChainHandle, ChainRequest};
+    use crate::chain::tracking::TrackingId;
+    use crate::channel::{Channel, ChannelSide};
+    use crate::event::monitor::EventBatch;
+    use crate::link::operational_data::{OperationalData, OperationalDataTarget};
+    use crate::link::Link;
+    use crate::object::Packet;
+    use crate::util::task::{CancellationToken, TaskHandle};
+    use crate::worker::{CircuitBreaker, IdleBackoff, WorkerCmd};
+
+    fn event_batch(events_count: u64) -> EventBatch {
+        EventBatch {
+            chain_id: ChainId::new("ibc".to_string(), 0),
+            tracking_id: TrackingId::new_static("test"),
+            height: Height::new(0, 1),
+            events: (1..=events_count)
+                .map(|h| IbcEvent::NewBlock(NewBlock::new(Height::new(0, h))))
+                .collect(),
+        }
+    }
+
+    fn event_heights(batch: &EventBatch) -> Vec<u64> {
+        batch
+            .events
+            .iter()
+            .map(|event| event.height().revision_height)
+            .collect()
+    }
+
+    fn packet() -> Packet {
+        Packet {
+            dst_chain_id: ChainId::new("ibc".to_string(), 1),
+            src_chain_id: ChainId::new("ibc".to_string(), 0),
+            src_channel_id: ChannelId::default(),
+            src_port_id: PortId::transfer(),
+        }
+    }
+
+    /// A link on the channel of the `packet()` path, between chains whose
+    /// runtimes never answer, along with the receiver of the requests sent
+    /// to these runtimes.
+    fn link() -> (
+        Link<BaseChainHandle, BaseChainHandle>,
+        Receiver<ChainRequest>,
+    ) {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let path = packet();
+
+        let side = |chain_id: &ChainId| {
+            ChannelSide::new(
+                BaseChainHandle::new(chain_id.clone(), sender.clone()),
+                ClientId::default(),
+                ConnectionId::default(),
+                path.src_port_id.clone(),
+                Some(path.src_channel_id),
+                None,
+            )
+        };
+
+        let channel = Channel {
+            ordering: Order::Unordered,
+            a_side: side(&path.src_chain_id),
+            b_side: side(&path.dst_chain_id),
+            connection_delay: Duration::ZERO,
+        };
+
+        (Link::new(channel, false).unwrap(), receiver)
+    }
+
+    fn idle_backoff() -> Arc<Mutex<IdleBackoff>> {
+        let interval = Duration::from_millis(10);
+        Arc::new(Mutex::new(IdleBackoff::new(interval, interval)))
+    }
+
+    fn circuit_breaker() -> Arc<Mutex<CircuitBreaker>> {
+        Arc::new(Mutex::new(CircuitBreaker::new(
+            0,
+            Duration::ZERO,
+            Duration::ZERO,
+        )))
+    }
+
+    /// Spawns a packet command worker handling the given commands, for at most
+    /// `max_iterations`, along with the sender of the commands left unhandled.
+    fn spawn_cmd_worker(
+        link: Arc<Mutex<Link<BaseChainHandle, BaseChainHandle>>>,
+        paused: Arc<AtomicBool>,
+        cmds: Vec<WorkerCmd>,
+        max_iterations: u64,
+    ) -> (crossbeam_channel::Sender<WorkerCmd>, TaskHandle) {
+        let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
+
+        for cmd in cmds {
+            cmd_tx.send(cmd).unwrap();
+        }
+
+        let task = spawn_packet_cmd_worker(
+            cmd_rx,
+            link,
+            false,
+            0,
+            Arc::new(AtomicU64::new(0)),
+            false,
+            0,
+            0,
+            circuit_breaker(),
+            paused,
+            idle_backoff(),
+            packet(),
+            false,
+            None,
+            Some(max_iterations),
+            CancellationToken::new(),
+        );
+
+        (cmd_tx, task)
+    }
+
+    /// Spawns a packet worker executing the schedule of the given link,
+    /// after adding an operational data to it, for at most `max_iterations`.
+    fn spawn_schedule_worker(
+        link: Arc<Mutex<Link<BaseChainHandle, BaseChainHandle>>>,
+        paused: Arc<AtomicBool>,
+        max_iterations: u64,
+    ) -> TaskHandle {
+        link.lock()
+            .unwrap()
+            .a_to_b
+            .dst_operational_data
+            .push_back(OperationalData::new(
+                Height::new(0, 1),
+                OperationalDataTarget::Destination,
+                TrackingId::new_static("test"),
+                Duration::ZERO,
+            ));
+
+        spawn_packet_worker(
+            packet(),
+            link,
+            Arc::new(AtomicU64::new(0)),
+            false,
+            circuit_breaker(),
+            paused,
+            idle_backoff(),
+            false,
+            None,
+            Some(max_iterations),
+            CancellationToken::new(),
+        )
+    }
+
+    #[test]
+    fn packet_cmd_worker_stops_after_max_iterations() {
+        let (link, _requests) = link();
+        let cmds = vec![WorkerCmd::Resume; 5];
+
+        let (cmd_tx, task) = spawn_cmd_worker(
+            Arc::new(Mutex::new(link)),
+            Arc::new(AtomicBool::new(false)),
+            cmds,
+            3,
+        );
+
+        task.join();
+
+        // Each of the 3 iterations handled one of the 5 commands.
+        assert_eq!(cmd_tx.len(), 2);
+    }
+
+    #[test]
+    fn paused_packet_worker_skips_schedule_execution() {
+        let (link, requests) = link();
+        let link = Arc::new(Mutex::new(link));
+        let paused = Arc::new(AtomicBool::new(false));
+
+        let (_cmd_tx, cmd_task) =
+            spawn_cmd_worker(link.clone(), paused.clone(), vec![WorkerCmd::Pause], 1);
+        cmd_task.join();
+
+        assert!(paused.load(Ordering::SeqCst));
+
+        spawn_schedule_worker(link, paused, 3).join();
+
+        // Executing the schedule would have queried the destination chain.
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[test]
+    fn resumed_packet_worker_executes_schedule() {
+        let (link, requests) = link();
+        let task = spawn_schedule_worker(
+            Arc::new(Mutex::new(link)),
+            Arc::new(AtomicBool::new(false)),
+            1,
+        );
+
+        let request = requests.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert!(matches!(
+            request,
+            ChainRequest::QueryApplicationStatus { .. }
+        ));
+
+        // Dropping the request fails the schedule execution waiting for its reply.
+        drop(request);
+        task.join();
+    }
+
+    #[test]
+    fn split_large_event_batch_in_chunks() {
+        let batch = event_batch(1050);
+        let sub_batches = split_event_batch(batch.clone(), 500);
+
+        let sizes: Vec<usize> = sub_batches.iter().map(|b| b.events.len()).collect();
+        assert_eq!(sizes, vec![500, 500, 50]);
+
+        for sub_batch in &sub_batches {
+            assert_eq!(sub_batch.chain_id, batch.chain_id);
+            assert_eq!(sub_batch.height, batch.height);
+        }
+
+        let heights: Vec<u64> = sub_batches.iter().flat_map(event_heights).collect();
+        assert_eq!(heights, event_heights(&batch));
+    }
+
+    #[test]
+    fn do_not_split_small_event_batch() {
+        let sub_batches = split_event_batch(event_batch(500), 500);
+        assert_eq!(sub_batches.len(), 1);
+        assert_eq!(sub_batches[0].events.len(), 500);
+    }
+
+    #[test]
+    fn do_not_split_event_batch_with_zero_max() {
+        let sub_batches = split_event_batch(event_batch(1050), 0);
+        assert_eq!(sub_batches.len(), 1);
+        assert_eq!(sub_batches[0].events.len(), 1050);
+    }
+
+    #[test]
+    fn should_not_clear_with_zero_interval() {
+        assert!(!should_clear_packets(0, None, Height::new(0, 100)));
+        assert!(!should_clear_packets(
+            0,
+            Some(Height::new(0, 1)),
+            Height::new(0, 100)
+        ));
+    }
+
+    #[test]
+    fn should_clear_on_interval_without_previous_clearing() {
+        assert!(should_clear_packets(10, None, Height::new(0, 20)));
+        assert!(!should_clear_packets(10, None, Height::new(0, 21)));
+    }
+
+    #[test]
+    fn should_clear_on_interval_after_previous_clearing() {
+        assert!(should_clear_packets(
+            10,
+            Some(Height::new(0, 10)),
+            Height::new(0, 20)
+        ));
+        assert!(!should_clear_packets(
+            10,
+            Some(Height::new(0, 10)),
+            Height::new(0, 19)
+        ));
+    }
+
+    #[test]
+    fn should_clear_when_straddling_interval() {
+        // Height 20 was never seen, but the interval boundary was crossed.
+        assert!(should_clear_packets(
+            10,
+            Some(Height::new(0, 15)),
+            Height::new(0, 23)
+        ));
+
+        // Several interval boundaries were crossed.
+        assert!(should_clear_packets(
+            10,
+            Some(Height::new(0, 15)),
+            Height::new(0, 57)
+        ));
+    }
+
+    #[test]
+    fn should_not_clear_within_same_interval() {
+        assert!(!should_clear_packets(
+            10,
+            Some(Height::new(0, 20)),
+            Height::new(0, 20)
+        ));
+        assert!(!should_clear_packets(
+            10,
+            Some(Height::new(0, 21)),
+            Height::new(0, 29)
+        ));
+    }
+
+    #[test]
+    fn should_clear_after_revision_upgrade() {
+        assert!(should_clear_packets(
+            10,
+            Some(Height::new(0, 25)),
+            Height::new(1, 3)
+        ));
+        assert!(!should_clear_packets(
+            10,
+            Some(Height::new(1, 3)),
+            Height::new(0, 25)
+        ));
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::SystemTime;
+
+use crossbeam_channel::{Receiver, Sender};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tendermint::abci::transaction::Hash as TxHash;
+use tracing::error;
+
+use ibc::core::ics04_channel::packet::Sequence;
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc::Height;
+
+use crate::link::RelaySummary;
+use crate::object::Packet;
+
+/// The relay logs opened so far, by path, so that all the packet
+/// workers logging to the same file share a single writer.
+static RELAY_LOGS: Lazy<Mutex<HashMap<PathBuf, RelayLog>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// An append-only log of the packets relayed by the packet workers,
+/// with one JSON object per line, for auditing and reconciliation.
+///
+/// The lines are written to the file by a dedicated thread, so that
+/// recording the relayed packets never blocks the packet workers.
+#[derive(Clone, Debug)]
+pub struct RelayLog {
+    sender: Sender<String>,
+}
+
+/// A packet relayed by a packet worker, as recorded in the relay log.
+#[derive(Debug, Serialize)]
+struct RelayLogEntry<'a> {
+    /// When the packet was recorded, in RFC 3339 format.
+    timestamp: String,
+    src_chain: &'a ChainId,
+    dst_chain: &'a ChainId,
+    src_port: &'a PortId,
+    src_channel: &'a ChannelId,
+    dst_port: &'a PortId,
+    dst_channel: &'a ChannelId,
+    sequence: Sequence,
+    /// The type of the event emitted when relaying the packet,
+    /// eg. `write_acknowledgement` for a received packet.
+    #[serde(rename = "type")]
+    event_type: &'static str,
+    /// The hash of the transaction which relayed the packet, if known.
+    tx_hash: Option<TxHash>,
+    /// The height of the event which triggered the relaying of the packet,
+    /// if `memo_source_heights` is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_height: Option<Height>,
+}
+
+impl RelayLog {
+    /// Returns the relay log appending to the file at `path`,
+    /// opening the file on first use.
+    pub fn shared(path: &Path) -> io::Result<Self> {
+        let mut logs = RELAY_LOGS.lock().unwrap();
+
+        if let Some(log) = logs.get(path) {
+            return Ok(log.clone());
+        }
+
+        let log = Self::open(path)?;
+        logs.insert(path.to_path_buf(), log.clone());
+
+        Ok(log)
+    }
+
+    fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let (sender, receiver) = crossbeam_channel::unbounded();
+
+        let log_path = path.to_path_buf();
+        thread::Builder::new()
+            .name("relay-log".to_string())
+            .spawn(move || write_lines(&log_path, file, receiver))?;
+
+        Ok(Self { sender })
+    }
+
+    /// Records one line for each packet event in the `summary`
+    /// of the packets relayed on the given `path`.
+    pub fn record(&self, path: &Packet, summary: &RelaySummary) {
+        let timestamp = humantime::format_rfc3339_millis(SystemTime::now()).to_string();
+
+        let events = summary
+            .events_with_tx_hashes()
+            .zip(summary.source_heights());
+
+        for ((event, tx_hash), source_height) in events {
+            let packet = match event.packet() {
+                Some(packet) => packet,
+                None => continue,
+            };
+
+            // The path relays packets sent from both of its ends.
+            let (src_chain, dst_chain) = if packet.source_channel == path.src_channel_id
+                && packet.source_port == path.src_port_id
+            {
+                (&path.src_chain_id, &path.dst_chain_id)
+            } else {
+                (&path.dst_chain_id, &path.src_chain_id)
+            };
+
+            let entry = RelayLogEntry {
+                timestamp: timestamp.clone(),
+                src_chain,
+                dst_chain,
+                src_port: &packet.source_port,
+                src_channel: &packet.source_channel,
+                dst_port: &packet.destination_port,
+                dst_channel: &packet.destination_channel,
+                sequence: packet.sequence,
+                event_type: event.event_type().as_str(),
+                tx_hash,
+                source_height,
+            };
+
+            match serde_json::to_string(&entry) {
+                Ok(line) => {
+                    // The writer thread only stops if writing to the file failed,
+                    // which it already reported.
+                    let _ = self.sender.send(line);
+                }
+                Err(e) => error!("failed to serialize relay log entry: {}", e),
+            }
+        }
+    }
+}
+
+/// Appends the received lines to the file, flushing them
+/// whenever no other line is immediately available.
+fn write_lines(path: &Path, file: File, receiver: Receiver<String>) {
+    let mut writer = BufWriter::new(file);
+
+    while let Ok(line) = receiver.recv() {
+        let result = writeln!(writer, "{}", line)
+            .and_then(|()| {
+                receiver
+                    .try_iter()
+                    .try_for_each(|line| writeln!(writer, "{}", line))
+            })
+            .and_then(|()| writer.flush());
+
+        if let Err(e) = result {
+            error!(
+                "failed to write to the relay log at '{}', no more packets will be logged: {}",
+                path.display(),
+                e
+            );
+
+            return;
+        }
+    }
+}
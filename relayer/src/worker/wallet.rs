@@ -11,7 +11,7 @@ use crate::{
 pub fn spawn_wallet_worker<Chain: ChainHandle>(chain: Chain) -> TaskHandle {
     let span = error_span!("wallet", chain = %chain.id());
 
-    spawn_background_task(span, Some(Duration::from_secs(5)), move || {
+    spawn_background_task(span, Some(Duration::from_secs(5)), None, move || {
         let key = chain.get_key().map_err(|e| {
             TaskError::Fatal(format!("failed to get key in use by the relayer: {e}"))
         })?;
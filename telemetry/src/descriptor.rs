@@ -0,0 +1,390 @@
+//! Catalog of the metrics exported by the relayer.
+//!
+//! The metrics registered in [`TelemetryState`](crate::state::TelemetryState) take
+//! their name and description from the descriptors below, so that the catalog
+//! returned by [`metric_descriptors`] always reflects what is actually exported.
+
+use core::fmt;
+
+/// The kind of instrument backing a metric.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MetricType {
+    /// A monotonically increasing counter.
+    Counter,
+    /// A counter which can both increase and decrease.
+    UpDownCounter,
+    /// A value recorder only reporting the last recorded value.
+    Gauge,
+    /// A value recorder reporting the distribution of the recorded values.
+    Histogram,
+}
+
+impl MetricType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::UpDownCounter => "up_down_counter",
+            Self::Gauge => "gauge",
+            Self::Histogram => "histogram",
+        }
+    }
+}
+
+impl fmt::Display for MetricType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Describes a metric exported by the relayer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MetricDescriptor {
+    /// The name of the metric, as registered with the exporter.
+    pub name: &'static str,
+    pub metric_type: MetricType,
+    /// The labels attached to each measurement of the metric.
+    pub labels: &'static [&'static str],
+    /// The help text of the metric.
+    pub help: &'static str,
+}
+
+const CLIENT_LABELS: &[&str] = &["chain", "client"];
+const PACKET_LABELS: &[&str] = &["src_chain", "src_channel", "src_port", "relayer_id"];
+const CHANNEL_LABELS: &[&str] = &["src_chain", "src_channel", "src_port"];
+const PATH_LABELS: &[&str] = &["chain", "counterparty", "channel", "port"];
+const QUERY_LABELS: &[&str] = &["chain", "query_type"];
+const CHAIN_LABELS: &[&str] = &["chain"];
+
+pub const WORKERS: MetricDescriptor = MetricDescriptor {
+    name: "workers",
+    metric_type: MetricType::UpDownCounter,
+    labels: &["type"],
+    help: "Number of workers per object",
+};
+
+pub const IBC_CLIENT_UPDATES: MetricDescriptor = MetricDescriptor {
+    name: "ibc_client_updates",
+    metric_type: MetricType::Counter,
+    labels: CLIENT_LABELS,
+    help: "Number of client updates performed per client",
+};
+
+pub const IBC_CLIENT_MISBEHAVIOURS: MetricDescriptor = MetricDescriptor {
+    name: "ibc_client_misbehaviours",
+    metric_type: MetricType::Counter,
+    labels: CLIENT_LABELS,
+    help: "Number of misbehaviours detected per client",
+};
+
+pub const IBC_RECEIVE_PACKETS: MetricDescriptor = MetricDescriptor {
+    name: "ibc_receive_packets",
+    metric_type: MetricType::Counter,
+    labels: PACKET_LABELS,
+    help: "Number of receive packets relayed per channel",
+};
+
+pub const IBC_ACKNOWLEDGMENT_PACKETS: MetricDescriptor = MetricDescriptor {
+    name: "ibc_acknowledgment_packets",
+    metric_type: MetricType::Counter,
+    labels: PACKET_LABELS,
+    help: "Number of acknowledgment packets relayed per channel",
+};
+
+pub const IBC_TIMEOUT_PACKETS: MetricDescriptor = MetricDescriptor {
+    name: "ibc_timeout_packets",
+    metric_type: MetricType::Counter,
+    labels: PACKET_LABELS,
+    help: "Number of timeout packets relayed per channel",
+};
+
+pub const IBC_EVENTS_RECEIVED: MetricDescriptor = MetricDescriptor {
+    name: "ibc_events_received",
+    metric_type: MetricType::Counter,
+    labels: &["src_chain"],
+    help: "Number of IBC events received by the packet workers, before filtering, per source chain",
+};
+
+pub const IBC_LAST_RELAYED_HEIGHT: MetricDescriptor = MetricDescriptor {
+    name: "ibc_last_relayed_height",
+    metric_type: MetricType::Gauge,
+    labels: &["src_chain", "dst_chain", "src_channel"],
+    help: "The height of the most recently relayed packet event, per path",
+};
+
+pub const IBC_PENDING_PACKETS: MetricDescriptor = MetricDescriptor {
+    name: "ibc_pending_packets",
+    metric_type: MetricType::Gauge,
+    labels: CHANNEL_LABELS,
+    help: "Number of packets pending to be received or timed out, per channel",
+};
+
+pub const IBC_PENDING_ACKS: MetricDescriptor = MetricDescriptor {
+    name: "ibc_pending_acks",
+    metric_type: MetricType::Gauge,
+    labels: CHANNEL_LABELS,
+    help: "Number of acknowledgements pending to be relayed, per channel",
+};
+
+pub const IBC_DROPPED_WORKER_COMMANDS: MetricDescriptor = MetricDescriptor {
+    name: "ibc_dropped_worker_commands",
+    metric_type: MetricType::Counter,
+    labels: CHANNEL_LABELS,
+    help: "Number of commands dropped by the packet workers after exhausting their retries, per channel",
+};
+
+pub const IBC_GRACE_SKIPPED_PACKETS: MetricDescriptor = MetricDescriptor {
+    name: "ibc_grace_skipped_packets",
+    metric_type: MetricType::Counter,
+    labels: CHANNEL_LABELS,
+    help: "Number of packet events left to other relayers for the relay grace period, per channel",
+};
+
+pub const IBC_OVERSIZED_PACKETS: MetricDescriptor = MetricDescriptor {
+    name: "ibc_oversized_packets",
+    metric_type: MetricType::Counter,
+    labels: CHANNEL_LABELS,
+    help: "Number of packets skipped as their data exceeds max_packet_data_bytes, per channel",
+};
+
+pub const IBC_ACK_MISMATCH: MetricDescriptor = MetricDescriptor {
+    name: "ibc_ack_mismatch",
+    metric_type: MetricType::Counter,
+    labels: CHANNEL_LABELS,
+    help: "Number of acknowledgements written for a packet differing from the one relayed, per channel",
+};
+
+pub const IBC_PACKET_RESUBMISSIONS: MetricDescriptor = MetricDescriptor {
+    name: "ibc_packet_resubmissions",
+    metric_type: MetricType::Counter,
+    labels: PATH_LABELS,
+    help: "Number of pending transactions resubmitted after they could not be confirmed in time, per path",
+};
+
+pub const IBC_PROOF_QUERY_RETRIES: MetricDescriptor = MetricDescriptor {
+    name: "ibc_proof_query_retries",
+    metric_type: MetricType::Counter,
+    labels: PATH_LABELS,
+    help: "Number of failed queries of the proofs of the relayed messages which were retried, per path",
+};
+
+pub const IBC_CLIENT_UPDATE_FAILURES: MetricDescriptor = MetricDescriptor {
+    name: "ibc_client_update_failures",
+    metric_type: MetricType::Counter,
+    labels: PATH_LABELS,
+    help:
+        "Number of client updates submitted apart from the relayed messages which failed, per path",
+};
+
+pub const IBC_PACKET_RELAY_FAILURES: MetricDescriptor = MetricDescriptor {
+    name: "ibc_packet_relay_failures",
+    metric_type: MetricType::Counter,
+    labels: PATH_LABELS,
+    help: "Number of transactions relaying packet messages which failed to be submitted, per path",
+};
+
+pub const IBC_PATH_CIRCUIT_OPEN: MetricDescriptor = MetricDescriptor {
+    name: "ibc_path_circuit_open",
+    metric_type: MetricType::Gauge,
+    labels: CHANNEL_LABELS,
+    help: "Whether relaying on a path is paused by its circuit breaker (1) or not (0), per channel",
+};
+
+pub const IBC_PATH_PAUSED: MetricDescriptor = MetricDescriptor {
+    name: "ibc_path_paused",
+    metric_type: MetricType::Gauge,
+    labels: CHANNEL_LABELS,
+    help: "Whether relaying on a path is paused by an operator (1) or not (0), per channel",
+};
+
+pub const IBC_IN_FLIGHT_TXS: MetricDescriptor = MetricDescriptor {
+    name: "ibc_in_flight_txs",
+    metric_type: MetricType::Gauge,
+    labels: CHANNEL_LABELS,
+    help: "Number of relay transactions submitted by the packet workers which are awaiting confirmation, per channel",
+};
+
+pub const IBC_PACKET_WORKER_INTERVAL: MetricDescriptor = MetricDescriptor {
+    name: "ibc_packet_worker_interval",
+    metric_type: MetricType::Gauge,
+    labels: CHANNEL_LABELS,
+    help: "The current interval between two schedule executions of the packet workers, lengthened while their path is idle, per channel. Seconds",
+};
+
+pub const IBC_SCHEDULE_REFRESH_SECONDS: MetricDescriptor = MetricDescriptor {
+    name: "ibc_schedule_refresh_seconds",
+    metric_type: MetricType::Histogram,
+    labels: CHANNEL_LABELS,
+    help: "The time taken by the packet workers to refresh their schedule before executing it, per channel. Seconds.",
+};
+
+pub const IBC_RELAY_PHASE_SECONDS: MetricDescriptor = MetricDescriptor {
+    name: "ibc_relay_phase_seconds",
+    metric_type: MetricType::Histogram,
+    labels: &["chain", "counterparty", "channel", "port", "phase"],
+    help: "The time spent in each phase of the relaying of the confirmed transactions, per path and phase. Seconds.",
+};
+
+pub const IBC_PACKET_ATTEMPTS: MetricDescriptor = MetricDescriptor {
+    name: "ibc_packet_attempts",
+    metric_type: MetricType::Histogram,
+    labels: PATH_LABELS,
+    help: "The number of times the transactions relaying each confirmed packet message were submitted, per path",
+};
+
+pub const QUERIES: MetricDescriptor = MetricDescriptor {
+    name: "queries",
+    metric_type: MetricType::Counter,
+    labels: QUERY_LABELS,
+    help: "Number of queries emitted by the relayer, per chain and query type",
+};
+
+pub const QUERY_CACHE_HITS: MetricDescriptor = MetricDescriptor {
+    name: "cache_hits",
+    metric_type: MetricType::Counter,
+    labels: QUERY_LABELS,
+    help: "Number of cache hits for queries emitted by the relayer, per chain and query type",
+};
+
+pub const WS_RECONNECT: MetricDescriptor = MetricDescriptor {
+    name: "ws_reconnect",
+    metric_type: MetricType::Counter,
+    labels: CHAIN_LABELS,
+    help: "Number of time the relayer had to reconnect to the WebSocket endpoint, per chain",
+};
+
+pub const WS_EVENTS: MetricDescriptor = MetricDescriptor {
+    name: "ws_events",
+    metric_type: MetricType::Counter,
+    labels: CHAIN_LABELS,
+    help: "How many IBC events did Hermes receive via the WebSocket subscription, per chain",
+};
+
+pub const MSG_NUM: MetricDescriptor = MetricDescriptor {
+    name: "msg_num",
+    metric_type: MetricType::Counter,
+    labels: CHAIN_LABELS,
+    help: "How many messages Hermes submitted to the chain, per chain",
+};
+
+pub const ACCOUNT_RESYNCS: MetricDescriptor = MetricDescriptor {
+    name: "account_resyncs",
+    metric_type: MetricType::Counter,
+    labels: &["chain", "throttled"],
+    help: "How many times Hermes resynced the cached account sequence after a mismatch, per chain, the throttled resyncs reusing the last resynced sequence",
+};
+
+pub const WALLET_BALANCE: MetricDescriptor = MetricDescriptor {
+    name: "wallet_balance",
+    metric_type: MetricType::Gauge,
+    labels: &["chain", "account", "denom"],
+    help: "The balance in each wallet that Hermes is using, per wallet, denom and chain",
+};
+
+pub const TX_LATENCY_SUBMITTED: MetricDescriptor = MetricDescriptor {
+    name: "tx_latency_submitted",
+    metric_type: MetricType::Histogram,
+    labels: PATH_LABELS,
+    help: "The latency for all transactions submitted to a specific chain, \
+        i.e. the difference between the moment when Hermes received a batch of events \
+        and when it submitted the corresponding transaction(s). Milliseconds.",
+};
+
+pub const TX_LATENCY_CONFIRMED: MetricDescriptor = MetricDescriptor {
+    name: "tx_latency_confirmed",
+    metric_type: MetricType::Histogram,
+    labels: PATH_LABELS,
+    help: "The latency for all transactions submitted to a specific chain, \
+        i.e. the difference between the moment when Hermes received a batch of events \
+        until the corresponding transaction(s) were confirmed. Milliseconds.",
+};
+
+pub const IBC_TX_GAS_USED: MetricDescriptor = MetricDescriptor {
+    name: "ibc_tx_gas_used",
+    metric_type: MetricType::Histogram,
+    labels: CHAIN_LABELS,
+    help: "The gas used by each confirmed relay transaction, per chain",
+};
+
+pub const IBC_TX_FEE_SPENT: MetricDescriptor = MetricDescriptor {
+    name: "ibc_tx_fee_spent",
+    metric_type: MetricType::Counter,
+    labels: &["chain", "denom"],
+    help: "The fees paid by the confirmed relay transactions, per chain and fee denom",
+};
+
+const METRICS: &[MetricDescriptor] = &[
+    WORKERS,
+    IBC_CLIENT_UPDATES,
+    IBC_CLIENT_MISBEHAVIOURS,
+    IBC_RECEIVE_PACKETS,
+    IBC_ACKNOWLEDGMENT_PACKETS,
+    IBC_TIMEOUT_PACKETS,
+    IBC_EVENTS_RECEIVED,
+    IBC_LAST_RELAYED_HEIGHT,
+    IBC_PENDING_PACKETS,
+    IBC_PENDING_ACKS,
+    IBC_DROPPED_WORKER_COMMANDS,
+    IBC_GRACE_SKIPPED_PACKETS,
+    IBC_OVERSIZED_PACKETS,
+    IBC_ACK_MISMATCH,
+    IBC_PACKET_RESUBMISSIONS,
+    IBC_PROOF_QUERY_RETRIES,
+    IBC_CLIENT_UPDATE_FAILURES,
+    IBC_PACKET_RELAY_FAILURES,
+    IBC_PATH_CIRCUIT_OPEN,
+    IBC_PATH_PAUSED,
+    IBC_IN_FLIGHT_TXS,
+    IBC_PACKET_WORKER_INTERVAL,
+    IBC_SCHEDULE_REFRESH_SECONDS,
+    IBC_RELAY_PHASE_SECONDS,
+    IBC_PACKET_ATTEMPTS,
+    QUERIES,
+    QUERY_CACHE_HITS,
+    WS_RECONNECT,
+    WS_EVENTS,
+    MSG_NUM,
+    ACCOUNT_RESYNCS,
+    WALLET_BALANCE,
+    TX_LATENCY_SUBMITTED,
+    TX_LATENCY_CONFIRMED,
+    IBC_TX_GAS_USED,
+    IBC_TX_FEE_SPENT,
+];
+
+/// Returns the descriptors of all the metrics exported by the relayer.
+pub fn metric_descriptors() -> Vec<MetricDescriptor> {
+    METRICS.to_vec()
+}
+
+/// Returns the type of the metric with the given name, if it is part of the catalog.
+pub fn metric_type(name: &str) -> Option<MetricType> {
+    METRICS
+        .iter()
+        .find(|descriptor| descriptor.name == name)
+        .map(|descriptor| descriptor.metric_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{metric_descriptors, metric_type, MetricType};
+
+    #[test]
+    fn metric_names_are_unique() {
+        let descriptors = metric_descriptors();
+        let names: HashSet<_> = descriptors.iter().map(|d| d.name).collect();
+
+        assert_eq!(names.len(), descriptors.len());
+    }
+
+    #[test]
+    fn metric_type_is_looked_up_by_name() {
+        assert_eq!(metric_type("wallet_balance"), Some(MetricType::Gauge));
+        assert_eq!(
+            metric_type("tx_latency_confirmed"),
+            Some(MetricType::Histogram)
+        );
+        assert_eq!(metric_type("unknown"), None);
+    }
+}
@@ -1,7 +1,9 @@
 extern crate alloc;
 
+pub mod descriptor;
 pub mod server;
 pub mod state;
+pub mod worker_status;
 
 use alloc::sync::Arc;
 use once_cell::sync::Lazy;
@@ -11,6 +13,7 @@ use std::{
     thread::JoinHandle,
 };
 
+pub use crate::descriptor::{metric_descriptors, MetricDescriptor, MetricType};
 pub use crate::state::TelemetryState;
 
 pub fn new_state() -> Arc<TelemetryState> {
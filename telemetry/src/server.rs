@@ -8,15 +8,16 @@ use crate::state::TelemetryState;
 
 enum Route {
     Metrics,
+    Workers,
     Other,
 }
 
 impl Route {
     fn from_request(request: &Request) -> Route {
-        if request.url() == "/metrics" {
-            Route::Metrics
-        } else {
-            Route::Other
+        match request.url().as_str() {
+            "/metrics" => Route::Metrics,
+            "/workers" => Route::Workers,
+            _ => Route::Other,
         }
     }
 }
@@ -37,6 +38,9 @@ pub fn listen(
                 rouille::Response::from_data(encoder.format_type().to_string(), buffer)
             }
 
+            // The state of the active packet workers, as JSON
+            Route::Workers => rouille::Response::json(&telemetry_state.worker_statuses()),
+
             // Any other route
             // Return an empty response with a 404 status code.
             Route::Other => rouille::Response::empty_404(),
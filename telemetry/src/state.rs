@@ -1,6 +1,7 @@
 use core::fmt;
 use std::time::{Duration, Instant};
 
+use once_cell::sync::OnceCell;
 use opentelemetry::{
     global,
     metrics::{Counter, UpDownCounter, ValueRecorder},
@@ -11,6 +12,9 @@ use prometheus::proto::MetricFamily;
 
 use ibc::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
 
+use crate::descriptor::{self, metric_type, MetricType};
+use crate::worker_status::{WorkerStatus, WorkerStatuses};
+
 #[derive(Copy, Clone, Debug)]
 pub enum WorkerType {
     Client,
@@ -53,6 +57,78 @@ pub struct TelemetryState {
     /// Number of timeout packets relayed, per channel
     timeout_packets: Counter<u64>,
 
+    /// Number of IBC events received by the packet workers, before filtering, per source chain
+    events_received: Counter<u64>,
+
+    /// The height of the most recently relayed packet event, per path
+    last_relayed_height: ValueRecorder<u64>,
+
+    /// Number of packets pending to be received or timed out, per channel,
+    /// as observed by the observer workers
+    pending_packets: ValueRecorder<u64>,
+
+    /// Number of acknowledgements pending to be relayed, per channel,
+    /// as observed by the observer workers
+    pending_acks: ValueRecorder<u64>,
+
+    /// Number of commands dropped by the packet workers after exhausting
+    /// their retries, per channel
+    dropped_worker_commands: Counter<u64>,
+
+    /// Number of packet events left to other relayers for the relay
+    /// grace period, per channel
+    grace_skipped_packets: Counter<u64>,
+
+    /// Number of packets skipped as their data exceeds
+    /// the maximum size, per channel
+    oversized_packets: Counter<u64>,
+
+    /// Number of acknowledgements written for a packet differing
+    /// from the one relayed, per channel
+    ack_mismatch: Counter<u64>,
+
+    /// Number of pending transactions resubmitted after they could not be
+    /// confirmed in time, per path
+    packet_resubmissions: Counter<u64>,
+
+    /// Number of failed queries of the proofs of the relayed messages
+    /// which were retried, per path
+    proof_query_retries: Counter<u64>,
+
+    /// Number of client updates submitted apart from the relayed messages
+    /// which failed, per path
+    client_update_failures: Counter<u64>,
+
+    /// Number of transactions relaying packet messages which failed to be
+    /// submitted, per path
+    packet_relay_failures: Counter<u64>,
+
+    /// Whether the circuit breaker of a path is open, ie. whether relaying
+    /// on the path is paused, per channel
+    path_circuit_open: ValueRecorder<u64>,
+
+    /// Whether relaying on a path is paused by an operator, per channel
+    path_paused: ValueRecorder<u64>,
+
+    /// Number of relay transactions awaiting confirmation, per channel
+    in_flight_txs: ValueRecorder<u64>,
+
+    /// The current interval between two schedule executions of the packet
+    /// workers, per channel. Seconds.
+    packet_worker_interval: ValueRecorder<f64>,
+
+    /// The time taken by the packet workers to refresh their schedule, per channel.
+    /// Seconds.
+    schedule_refresh_seconds: ValueRecorder<f64>,
+
+    /// The time spent in each phase of the relaying of the confirmed
+    /// transactions, per path and phase. Seconds.
+    relay_phase_seconds: ValueRecorder<f64>,
+
+    /// The number of times the transactions relaying each confirmed packet
+    /// message were submitted, per path
+    packet_attempts: ValueRecorder<u64>,
+
     /// Number of queries emitted by the relayer, per chain and query type
     queries: Counter<u64>,
 
@@ -68,6 +144,10 @@ pub struct TelemetryState {
     /// How many messages Hermes submitted to the chain, per chain
     msg_num: Counter<u64>,
 
+    /// How many times Hermes resynced the cached account sequence after a mismatch,
+    /// per chain, the throttled resyncs reusing the last resynced sequence
+    account_resyncs: Counter<u64>,
+
     /// The balance in each wallet that Hermes is using, per wallet, denom and chain
     wallet_balance: ValueRecorder<u64>,
 
@@ -81,9 +161,26 @@ pub struct TelemetryState {
     /// until the corresponding transaction(s) were confirmed. Milliseconds.
     tx_latency_confirmed: ValueRecorder<u64>,
 
+    /// The gas used by each confirmed relay transaction, per chain
+    tx_gas_used: ValueRecorder<u64>,
+
+    /// The fees paid by the confirmed relay transactions, per chain and fee denom
+    tx_fee_spent: Counter<u64>,
+
+    /// The hashes of the transactions whose cost was recorded, as a transaction
+    /// aggregating the messages of several channels is confirmed by each of them.
+    recorded_tx_costs: moka::sync::Cache<String, ()>,
+
     /// Records the time at which we started processing an event batch.
     /// Used for computing the `tx_latency` metric.
     in_flight_events: moka::sync::Cache<String, Instant>,
+
+    /// Identifies this relayer instance in the packet metrics.
+    /// Defaults to the hostname if not set before its first use.
+    relayer_id: OnceCell<String>,
+
+    /// The state of the active packet workers, served by the `/workers` route.
+    worker_statuses: WorkerStatuses,
 }
 
 impl TelemetryState {
@@ -92,6 +189,16 @@ impl TelemetryState {
         self.exporter.registry().gather()
     }
 
+    /// Set the identity of this relayer instance, used to label the packet metrics.
+    /// Has no effect if the identity was already set, or already used by a metric.
+    pub fn set_relayer_id(&self, relayer_id: String) {
+        let _ = self.relayer_id.set(relayer_id);
+    }
+
+    fn relayer_id(&self) -> &str {
+        self.relayer_id.get_or_init(hostname)
+    }
+
     /// Update the number of workers per object
     pub fn worker(&self, worker_type: WorkerType, count: i64) {
         let labels = &[KeyValue::new("type", worker_type.to_string())];
@@ -130,6 +237,7 @@ impl TelemetryState {
             KeyValue::new("src_chain", src_chain.to_string()),
             KeyValue::new("src_channel", src_channel.to_string()),
             KeyValue::new("src_port", src_port.to_string()),
+            KeyValue::new("relayer_id", self.relayer_id().to_string()),
         ];
 
         self.receive_packets.add(count, labels);
@@ -147,6 +255,7 @@ impl TelemetryState {
             KeyValue::new("src_chain", src_chain.to_string()),
             KeyValue::new("src_channel", src_channel.to_string()),
             KeyValue::new("src_port", src_port.to_string()),
+            KeyValue::new("relayer_id", self.relayer_id().to_string()),
         ];
 
         self.acknowledgment_packets.add(count, labels);
@@ -164,11 +273,432 @@ impl TelemetryState {
             KeyValue::new("src_chain", src_chain.to_string()),
             KeyValue::new("src_channel", src_channel.to_string()),
             KeyValue::new("src_port", src_port.to_string()),
+            KeyValue::new("relayer_id", self.relayer_id().to_string()),
         ];
 
         self.timeout_packets.add(count, labels);
     }
 
+    /// Number of IBC events received by the packet workers, before filtering, per source chain
+    pub fn ibc_events_received(&self, src_chain: &ChainId, count: u64) {
+        let labels = &[KeyValue::new("src_chain", src_chain.to_string())];
+
+        self.events_received.add(count, labels);
+    }
+
+    /// The height of the most recently relayed packet event, per path.
+    /// Stops advancing when nothing gets relayed on the path.
+    pub fn ibc_last_relayed_height(
+        &self,
+        src_chain: &ChainId,
+        dst_chain: &ChainId,
+        src_channel: &ChannelId,
+        height: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("dst_chain", dst_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+        ];
+
+        self.last_relayed_height.record(height, labels);
+    }
+
+    /// Number of packets pending to be received or timed out, per channel
+    pub fn ibc_pending_packets(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        count: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        self.pending_packets.record(count, labels);
+
+        self.worker_statuses
+            .update(src_chain, src_channel, src_port, |status| {
+                status.pending_packets = count
+            });
+    }
+
+    /// Number of acknowledgements pending to be relayed, per channel
+    pub fn ibc_pending_acks(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        count: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        self.pending_acks.record(count, labels);
+
+        self.worker_statuses
+            .update(src_chain, src_channel, src_port, |status| {
+                status.pending_acks = count
+            });
+    }
+
+    /// Number of commands dropped by the packet workers after exhausting their retries,
+    /// per channel
+    pub fn ibc_dropped_worker_commands(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        count: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        self.dropped_worker_commands.add(count, labels);
+    }
+
+    /// Number of packet events left to other relayers for the relay grace period,
+    /// per channel
+    pub fn ibc_grace_skipped_packets(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        count: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        self.grace_skipped_packets.add(count, labels);
+    }
+
+    /// Number of packets skipped as their data exceeds the maximum size,
+    /// per channel
+    pub fn ibc_oversized_packets(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        count: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        self.oversized_packets.add(count, labels);
+    }
+
+    /// Number of acknowledgements written for a packet differing
+    /// from the one relayed, per channel
+    pub fn ibc_ack_mismatch(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        count: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        self.ack_mismatch.add(count, labels);
+    }
+
+    /// Number of pending transactions resubmitted after they could not be
+    /// confirmed in time, per path
+    pub fn ibc_packet_resubmissions(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        counterparty_chain_id: &ChainId,
+        count: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("counterparty", counterparty_chain_id.to_string()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+        ];
+
+        self.packet_resubmissions.add(count, labels);
+    }
+
+    /// Number of failed queries of the proofs of the relayed messages
+    /// which were retried, per path
+    pub fn ibc_proof_query_retries(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        counterparty_chain_id: &ChainId,
+        count: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("counterparty", counterparty_chain_id.to_string()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+        ];
+
+        self.proof_query_retries.add(count, labels);
+    }
+
+    /// Number of client updates submitted apart from the relayed messages
+    /// which failed, per path
+    pub fn ibc_client_update_failures(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        counterparty_chain_id: &ChainId,
+        count: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("counterparty", counterparty_chain_id.to_string()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+        ];
+
+        self.client_update_failures.add(count, labels);
+    }
+
+    /// Number of transactions relaying packet messages which failed to be
+    /// submitted, per path
+    pub fn ibc_packet_relay_failures(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        counterparty_chain_id: &ChainId,
+        count: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("counterparty", counterparty_chain_id.to_string()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+        ];
+
+        self.packet_relay_failures.add(count, labels);
+    }
+
+    /// Whether the circuit breaker of a path is open, per channel
+    pub fn ibc_path_circuit_open(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        open: bool,
+    ) {
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        self.path_circuit_open.record(u64::from(open), labels);
+
+        self.worker_statuses
+            .update(src_chain, src_channel, src_port, |status| {
+                status.circuit_open = open
+            });
+    }
+
+    /// Whether relaying on a path is paused by an operator, per channel
+    pub fn ibc_path_paused(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        paused: bool,
+    ) {
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        self.path_paused.record(u64::from(paused), labels);
+
+        self.worker_statuses
+            .update(src_chain, src_channel, src_port, |status| {
+                status.paused = paused
+            });
+    }
+
+    /// Starts reporting the state of the packet worker of the given path
+    /// on the `/workers` route.
+    pub fn packet_worker_started(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        dst_chain: &ChainId,
+    ) {
+        self.worker_statuses
+            .register(src_chain, src_channel, src_port, dst_chain);
+    }
+
+    /// Stops reporting the state of the packet worker of the given path.
+    pub fn packet_worker_stopped(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+    ) {
+        self.worker_statuses
+            .unregister(src_chain, src_channel, src_port);
+    }
+
+    /// Records that the packet worker of the given path relayed events.
+    pub fn packet_worker_progress(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+    ) {
+        self.worker_statuses
+            .record_progress(src_chain, src_channel, src_port);
+    }
+
+    /// Records the number of packets and acknowledgements scheduled
+    /// by the packet worker of the given path.
+    pub fn packet_worker_pending(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        pending_packets: u64,
+        pending_acks: u64,
+    ) {
+        self.worker_statuses
+            .update(src_chain, src_channel, src_port, |status| {
+                status.pending_packets = pending_packets;
+                status.pending_acks = pending_acks;
+            });
+    }
+
+    /// The state of the active packet workers.
+    pub fn worker_statuses(&self) -> Vec<WorkerStatus> {
+        self.worker_statuses.snapshot()
+    }
+
+    /// Number of relay transactions submitted by a packet worker which are
+    /// awaiting confirmation, per channel
+    pub fn ibc_in_flight_txs(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        count: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        self.in_flight_txs.record(count, labels);
+    }
+
+    /// The current interval between two schedule executions of a packet worker,
+    /// lengthened while its path is idle, per channel
+    pub fn ibc_packet_worker_interval(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        interval: Duration,
+    ) {
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        self.packet_worker_interval
+            .record(interval.as_secs_f64(), labels);
+    }
+
+    /// The time taken by a packet worker to refresh its schedule, per channel
+    pub fn ibc_schedule_refresh_seconds(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        elapsed: Duration,
+    ) {
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("src_channel", src_channel.to_string()),
+            KeyValue::new("src_port", src_port.to_string()),
+        ];
+
+        self.schedule_refresh_seconds
+            .record(elapsed.as_secs_f64(), labels);
+    }
+
+    /// The time spent in the given phase of the relaying of a confirmed
+    /// transaction, per path and phase
+    pub fn ibc_relay_phase_seconds(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        counterparty_chain_id: &ChainId,
+        phase: &'static str,
+        elapsed: Duration,
+    ) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("counterparty", counterparty_chain_id.to_string()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+            KeyValue::new("phase", phase),
+        ];
+
+        self.relay_phase_seconds
+            .record(elapsed.as_secs_f64(), labels);
+    }
+
+    /// The number of times the transactions relaying a confirmed packet
+    /// message were submitted, per path
+    pub fn ibc_packet_attempts(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        counterparty_chain_id: &ChainId,
+        attempts: u64,
+    ) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("counterparty", counterparty_chain_id.to_string()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+        ];
+
+        self.packet_attempts.record(attempts, labels);
+    }
+
     /// Number of queries emitted by the relayer, per chain and query type
     pub fn query(&self, chain_id: &ChainId, query_type: &'static str) {
         let labels = &[
@@ -210,6 +740,17 @@ impl TelemetryState {
         self.msg_num.add(count, labels);
     }
 
+    /// A resync of the cached account sequence after a mismatch, which was
+    /// `throttled` if it reused the last resynced sequence instead of querying it
+    pub fn account_resyncs(&self, chain_id: &ChainId, throttled: bool) {
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("throttled", throttled.to_string()),
+        ];
+
+        self.account_resyncs.add(1, labels);
+    }
+
     /// The balance in each wallet that Hermes is using, per account, denom and chain
     pub fn wallet_balance(&self, chain_id: &ChainId, account: &str, amount: u64, denom: &str) {
         let labels = &[
@@ -221,6 +762,38 @@ impl TelemetryState {
         self.wallet_balance.record(amount, labels);
     }
 
+    /// Records the gas used and the fee paid by the confirmed transaction with the
+    /// given hash, unless it was already recorded. The `fee` holds the amount paid
+    /// in each fee denom.
+    pub fn tx_cost(
+        &self,
+        chain_id: &ChainId,
+        tx_hash: impl ToString,
+        gas_used: u64,
+        fee: &[(u64, &str)],
+    ) {
+        let tx_hash = tx_hash.to_string();
+
+        if self.recorded_tx_costs.contains_key(&tx_hash) {
+            return;
+        }
+
+        self.recorded_tx_costs.insert(tx_hash, ());
+
+        let labels = &[KeyValue::new("chain", chain_id.to_string())];
+
+        self.tx_gas_used.record(gas_used, labels);
+
+        for (amount, denom) in fee {
+            let labels = &[
+                KeyValue::new("chain", chain_id.to_string()),
+                KeyValue::new("denom", denom.to_string()),
+            ];
+
+            self.tx_fee_spent.add(*amount, labels);
+        }
+    }
+
     pub fn received_event_batch(&self, tracking_id: impl ToString) {
         self.in_flight_events
             .insert(tracking_id.to_string(), Instant::now());
@@ -288,10 +861,9 @@ use opentelemetry::sdk::metrics::aggregators::{histogram, last_value, sum};
 struct CustomAggregatorSelector;
 impl AggregatorSelector for CustomAggregatorSelector {
     fn aggregator_for(&self, descriptor: &Descriptor) -> Option<Arc<dyn Aggregator + Send + Sync>> {
-        match descriptor.name() {
-            "wallet_balance" => Some(Arc::new(last_value())),
-            "tx_latency_submitted" => Some(Arc::new(histogram(descriptor, &[0.5, 0.9, 0.99]))),
-            "tx_latency_confirmed" => Some(Arc::new(histogram(descriptor, &[0.5, 0.9, 0.99]))),
+        match metric_type(descriptor.name()) {
+            Some(MetricType::Gauge) => Some(Arc::new(last_value())),
+            Some(MetricType::Histogram) => Some(Arc::new(histogram(descriptor, &[0.5, 0.9, 0.99]))),
             _ => Some(Arc::new(sum())),
         }
     }
@@ -309,85 +881,207 @@ impl Default for TelemetryState {
             exporter,
 
             workers: meter
-                .i64_up_down_counter("workers")
-                .with_description("Number of workers per object")
+                .i64_up_down_counter(descriptor::WORKERS.name)
+                .with_description(descriptor::WORKERS.help)
                 .init(),
 
             ibc_client_updates: meter
-                .u64_counter("ibc_client_updates")
-                .with_description("Number of client updates performed per client")
+                .u64_counter(descriptor::IBC_CLIENT_UPDATES.name)
+                .with_description(descriptor::IBC_CLIENT_UPDATES.help)
                 .init(),
 
             ibc_client_misbehaviours: meter
-                .u64_counter("ibc_client_misbehaviours")
-                .with_description("Number of misbehaviours detected per client")
+                .u64_counter(descriptor::IBC_CLIENT_MISBEHAVIOURS.name)
+                .with_description(descriptor::IBC_CLIENT_MISBEHAVIOURS.help)
                 .init(),
 
             receive_packets: meter
-                .u64_counter("ibc_receive_packets")
-                .with_description("Number of receive packets relayed per channel")
+                .u64_counter(descriptor::IBC_RECEIVE_PACKETS.name)
+                .with_description(descriptor::IBC_RECEIVE_PACKETS.help)
                 .init(),
 
             acknowledgment_packets: meter
-                .u64_counter("ibc_acknowledgment_packets")
-                .with_description("Number of acknowledgment packets relayed per channel")
+                .u64_counter(descriptor::IBC_ACKNOWLEDGMENT_PACKETS.name)
+                .with_description(descriptor::IBC_ACKNOWLEDGMENT_PACKETS.help)
                 .init(),
 
             timeout_packets: meter
-                .u64_counter("ibc_timeout_packets")
-                .with_description("Number of timeout packets relayed per channel")
+                .u64_counter(descriptor::IBC_TIMEOUT_PACKETS.name)
+                .with_description(descriptor::IBC_TIMEOUT_PACKETS.help)
+                .init(),
+
+            events_received: meter
+                .u64_counter(descriptor::IBC_EVENTS_RECEIVED.name)
+                .with_description(descriptor::IBC_EVENTS_RECEIVED.help)
+                .init(),
+
+            last_relayed_height: meter
+                .u64_value_recorder(descriptor::IBC_LAST_RELAYED_HEIGHT.name)
+                .with_description(descriptor::IBC_LAST_RELAYED_HEIGHT.help)
+                .init(),
+
+            pending_packets: meter
+                .u64_value_recorder(descriptor::IBC_PENDING_PACKETS.name)
+                .with_description(descriptor::IBC_PENDING_PACKETS.help)
+                .init(),
+
+            pending_acks: meter
+                .u64_value_recorder(descriptor::IBC_PENDING_ACKS.name)
+                .with_description(descriptor::IBC_PENDING_ACKS.help)
+                .init(),
+
+            dropped_worker_commands: meter
+                .u64_counter(descriptor::IBC_DROPPED_WORKER_COMMANDS.name)
+                .with_description(descriptor::IBC_DROPPED_WORKER_COMMANDS.help)
+                .init(),
+
+            grace_skipped_packets: meter
+                .u64_counter(descriptor::IBC_GRACE_SKIPPED_PACKETS.name)
+                .with_description(descriptor::IBC_GRACE_SKIPPED_PACKETS.help)
+                .init(),
+
+            oversized_packets: meter
+                .u64_counter(descriptor::IBC_OVERSIZED_PACKETS.name)
+                .with_description(descriptor::IBC_OVERSIZED_PACKETS.help)
+                .init(),
+
+            ack_mismatch: meter
+                .u64_counter(descriptor::IBC_ACK_MISMATCH.name)
+                .with_description(descriptor::IBC_ACK_MISMATCH.help)
+                .init(),
+
+            packet_resubmissions: meter
+                .u64_counter(descriptor::IBC_PACKET_RESUBMISSIONS.name)
+                .with_description(descriptor::IBC_PACKET_RESUBMISSIONS.help)
+                .init(),
+
+            proof_query_retries: meter
+                .u64_counter(descriptor::IBC_PROOF_QUERY_RETRIES.name)
+                .with_description(descriptor::IBC_PROOF_QUERY_RETRIES.help)
+                .init(),
+
+            client_update_failures: meter
+                .u64_counter(descriptor::IBC_CLIENT_UPDATE_FAILURES.name)
+                .with_description(descriptor::IBC_CLIENT_UPDATE_FAILURES.help)
+                .init(),
+
+            packet_relay_failures: meter
+                .u64_counter(descriptor::IBC_PACKET_RELAY_FAILURES.name)
+                .with_description(descriptor::IBC_PACKET_RELAY_FAILURES.help)
+                .init(),
+
+            path_circuit_open: meter
+                .u64_value_recorder(descriptor::IBC_PATH_CIRCUIT_OPEN.name)
+                .with_description(descriptor::IBC_PATH_CIRCUIT_OPEN.help)
+                .init(),
+
+            path_paused: meter
+                .u64_value_recorder(descriptor::IBC_PATH_PAUSED.name)
+                .with_description(descriptor::IBC_PATH_PAUSED.help)
+                .init(),
+
+            in_flight_txs: meter
+                .u64_value_recorder(descriptor::IBC_IN_FLIGHT_TXS.name)
+                .with_description(descriptor::IBC_IN_FLIGHT_TXS.help)
+                .init(),
+
+            packet_worker_interval: meter
+                .f64_value_recorder(descriptor::IBC_PACKET_WORKER_INTERVAL.name)
+                .with_description(descriptor::IBC_PACKET_WORKER_INTERVAL.help)
+                .init(),
+
+            schedule_refresh_seconds: meter
+                .f64_value_recorder(descriptor::IBC_SCHEDULE_REFRESH_SECONDS.name)
+                .with_description(descriptor::IBC_SCHEDULE_REFRESH_SECONDS.help)
+                .init(),
+
+            relay_phase_seconds: meter
+                .f64_value_recorder(descriptor::IBC_RELAY_PHASE_SECONDS.name)
+                .with_description(descriptor::IBC_RELAY_PHASE_SECONDS.help)
+                .init(),
+
+            packet_attempts: meter
+                .u64_value_recorder(descriptor::IBC_PACKET_ATTEMPTS.name)
+                .with_description(descriptor::IBC_PACKET_ATTEMPTS.help)
                 .init(),
 
             queries: meter
-                .u64_counter("queries")
-                .with_description(
-                    "Number of queries emitted by the relayer, per chain and query type",
-                )
+                .u64_counter(descriptor::QUERIES.name)
+                .with_description(descriptor::QUERIES.help)
                 .init(),
 
             query_cache_hits: meter
-                .u64_counter("cache_hits")
-                .with_description("Number of cache hits for queries emitted by the relayer, per chain and query type")
+                .u64_counter(descriptor::QUERY_CACHE_HITS.name)
+                .with_description(descriptor::QUERY_CACHE_HITS.help)
                 .init(),
 
             ws_reconnect: meter
-                .u64_counter("ws_reconnect")
-                .with_description("Number of time the relayer had to reconnect to the WebSocket endpoint, per chain")
+                .u64_counter(descriptor::WS_RECONNECT.name)
+                .with_description(descriptor::WS_RECONNECT.help)
                 .init(),
 
             ws_events: meter
-                .u64_counter("ws_events")
-                .with_description("How many IBC events did Hermes receive via the WebSocket subscription, per chain")
+                .u64_counter(descriptor::WS_EVENTS.name)
+                .with_description(descriptor::WS_EVENTS.help)
                 .init(),
 
             msg_num: meter
-                .u64_counter("msg_num")
-                .with_description("How many messages Hermes submitted to the chain, per chain")
+                .u64_counter(descriptor::MSG_NUM.name)
+                .with_description(descriptor::MSG_NUM.help)
+                .init(),
+
+            account_resyncs: meter
+                .u64_counter(descriptor::ACCOUNT_RESYNCS.name)
+                .with_description(descriptor::ACCOUNT_RESYNCS.help)
                 .init(),
 
             wallet_balance: meter
-                .u64_value_recorder("wallet_balance")
-                .with_description("The balance in each wallet that Hermes is using, per wallet, denom and chain")
+                .u64_value_recorder(descriptor::WALLET_BALANCE.name)
+                .with_description(descriptor::WALLET_BALANCE.help)
                 .init(),
 
             tx_latency_submitted: meter
-                .u64_value_recorder("tx_latency_submitted")
-                .with_description("The latency for all transactions submitted to a specific chain, \
-                    i.e. the difference between the moment when Hermes received a batch of events \
-                    and when it submitted the corresponding transaction(s). Milliseconds.")
+                .u64_value_recorder(descriptor::TX_LATENCY_SUBMITTED.name)
+                .with_description(descriptor::TX_LATENCY_SUBMITTED.help)
                 .init(),
 
             tx_latency_confirmed: meter
-                .u64_value_recorder("tx_latency_confirmed")
-                .with_description("The latency for all transactions submitted to a specific chain, \
-                    i.e. the difference between the moment when Hermes received a batch of events \
-                    until the corresponding transaction(s) were confirmed. Milliseconds.")
+                .u64_value_recorder(descriptor::TX_LATENCY_CONFIRMED.name)
+                .with_description(descriptor::TX_LATENCY_CONFIRMED.help)
+                .init(),
+
+            tx_gas_used: meter
+                .u64_value_recorder(descriptor::IBC_TX_GAS_USED.name)
+                .with_description(descriptor::IBC_TX_GAS_USED.help)
+                .init(),
+
+            tx_fee_spent: meter
+                .u64_counter(descriptor::IBC_TX_FEE_SPENT.name)
+                .with_description(descriptor::IBC_TX_FEE_SPENT.help)
                 .init(),
 
+            recorded_tx_costs: moka::sync::Cache::builder()
+                .time_to_live(Duration::from_secs(60 * 60)) // Remove entries after 1 hour
+                .build(),
+
             in_flight_events: moka::sync::Cache::builder()
-                    .time_to_live(Duration::from_secs(60 * 60)) // Remove entries after 1 hour
-                    .time_to_idle(Duration::from_secs(30 * 60)) // Remove entries if they have been idle for 30 minutes
-                    .build(),
+                .time_to_live(Duration::from_secs(60 * 60)) // Remove entries after 1 hour
+                .time_to_idle(Duration::from_secs(30 * 60)) // Remove entries if they have been idle for 30 minutes
+                .build(),
+
+            relayer_id: OnceCell::new(),
+
+            worker_statuses: WorkerStatuses::default(),
         }
     }
 }
+
+/// Best-effort lookup of the hostname of the machine the relayer runs on.
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|hostname| hostname.trim().to_string())
+        .filter(|hostname| !hostname.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
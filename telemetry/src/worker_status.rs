@@ -0,0 +1,157 @@
+//! Live snapshot of the state of the packet workers, served as JSON by the
+//! telemetry server, so that operators can check the health of the relaying
+//! without aggregating the Prometheus metrics reported by each worker.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+/// The state of an active packet worker.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct WorkerStatus {
+    pub src_chain: String,
+    pub dst_chain: String,
+    pub src_channel: String,
+    pub src_port: String,
+    /// When the worker last relayed events, in seconds since the UNIX epoch,
+    /// if it did since it was spawned.
+    pub last_progress: Option<u64>,
+    /// The number of packets pending to be received or timed out, as last
+    /// observed by the worker.
+    pub pending_packets: u64,
+    /// The number of acknowledgements pending to be relayed, as last observed
+    /// by the worker.
+    pub pending_acks: u64,
+    /// Whether relaying is paused by the circuit breaker of the path.
+    pub circuit_open: bool,
+    /// Whether relaying is paused by an operator.
+    pub paused: bool,
+}
+
+type WorkerKey = (String, String, String);
+
+fn worker_key(src_chain: &ChainId, src_channel: &ChannelId, src_port: &PortId) -> WorkerKey {
+    (
+        src_chain.to_string(),
+        src_channel.to_string(),
+        src_port.to_string(),
+    )
+}
+
+/// The states of the active packet workers, identified by their source chain,
+/// channel and port.
+#[derive(Debug, Default)]
+pub struct WorkerStatuses {
+    statuses: Mutex<BTreeMap<WorkerKey, WorkerStatus>>,
+}
+
+impl WorkerStatuses {
+    /// Starts tracking the state of the packet worker of the given path.
+    pub fn register(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        dst_chain: &ChainId,
+    ) {
+        let status = WorkerStatus {
+            src_chain: src_chain.to_string(),
+            dst_chain: dst_chain.to_string(),
+            src_channel: src_channel.to_string(),
+            src_port: src_port.to_string(),
+            last_progress: None,
+            pending_packets: 0,
+            pending_acks: 0,
+            circuit_open: false,
+            paused: false,
+        };
+
+        self.statuses
+            .lock()
+            .unwrap()
+            .insert(worker_key(src_chain, src_channel, src_port), status);
+    }
+
+    /// Stops tracking the state of the packet worker of the given path.
+    pub fn unregister(&self, src_chain: &ChainId, src_channel: &ChannelId, src_port: &PortId) {
+        self.statuses
+            .lock()
+            .unwrap()
+            .remove(&worker_key(src_chain, src_channel, src_port));
+    }
+
+    /// Updates the state of the packet worker of the given path, if it is tracked.
+    pub fn update(
+        &self,
+        src_chain: &ChainId,
+        src_channel: &ChannelId,
+        src_port: &PortId,
+        f: impl FnOnce(&mut WorkerStatus),
+    ) {
+        if let Some(status) =
+            self.statuses
+                .lock()
+                .unwrap()
+                .get_mut(&worker_key(src_chain, src_channel, src_port))
+        {
+            f(status);
+        }
+    }
+
+    /// Records that the packet worker of the given path relayed events just now.
+    pub fn record_progress(&self, src_chain: &ChainId, src_channel: &ChannelId, src_port: &PortId) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| since_epoch.as_secs());
+
+        self.update(src_chain, src_channel, src_port, |status| {
+            status.last_progress = Some(now)
+        });
+    }
+
+    /// The states of the tracked packet workers, ordered by path.
+    pub fn snapshot(&self) -> Vec<WorkerStatus> {
+        self.statuses.lock().unwrap().values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+    use super::WorkerStatuses;
+
+    fn chain(name: &str) -> ChainId {
+        ChainId::new(name.to_string(), 0)
+    }
+
+    #[test]
+    fn only_registered_workers_are_updated() {
+        let statuses = WorkerStatuses::default();
+        let (channel, port) = (ChannelId::new(0), PortId::transfer());
+
+        statuses.register(&chain("ibc"), &channel, &port, &chain("other"));
+
+        statuses.update(&chain("ibc"), &channel, &port, |status| {
+            status.pending_packets = 3
+        });
+        statuses.update(&chain("unknown"), &channel, &port, |status| {
+            status.paused = true
+        });
+        statuses.record_progress(&chain("ibc"), &channel, &port);
+
+        let snapshot = statuses.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].dst_chain, "other-0");
+        assert_eq!(snapshot[0].pending_packets, 3);
+        assert!(snapshot[0].last_progress.is_some());
+        assert!(!snapshot[0].paused);
+
+        statuses.unregister(&chain("ibc"), &channel, &port);
+        assert!(statuses.snapshot().is_empty());
+    }
+}
@@ -157,6 +157,7 @@ impl TestOverrides for IbcTransferMBT {
                 clear_interval: 10,
                 clear_on_start: true,
                 tx_confirmation: true,
+                ..Default::default()
             },
         };
 
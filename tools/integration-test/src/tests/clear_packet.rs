@@ -1,5 +1,6 @@
 use ibc_test_framework::ibc::denom::derive_ibc_denom;
 use ibc_test_framework::prelude::*;
+use ibc_test_framework::util::packet::assert_no_relayed_packets;
 use ibc_test_framework::util::random::random_u64_range;
 
 #[test]
@@ -122,6 +123,9 @@ impl BinaryChannelTest for ClearPacketTest {
                 &denom_b.as_ref(),
             )?;
 
+            // The first IBC transfer should still not be relayed
+            assert_no_relayed_packets(&chains, &channel, Duration::from_secs(5))?;
+
             Ok(())
         })
     }
@@ -15,6 +15,7 @@ pub mod python;
 mod query_packet;
 pub mod supervisor;
 pub mod ternary_transfer;
+pub mod timeout_packet;
 pub mod transfer;
 
 #[cfg(any(doc, feature = "ordered"))]
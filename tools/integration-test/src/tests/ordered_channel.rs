@@ -1,5 +1,10 @@
+use ibc::core::ics04_channel::packet::Sequence;
+use ibc_relayer::chain::counterparty::pending_packet_summary;
+
 use ibc_test_framework::ibc::denom::derive_ibc_denom;
 use ibc_test_framework::prelude::*;
+use ibc_test_framework::relayer::channel::query_identified_channel_end;
+use ibc_test_framework::relayer::fault::{fail_recv_packet, restore_recv_packet};
 use ibc_test_framework::util::random::random_u64_range;
 
 #[test]
@@ -7,7 +12,23 @@ fn test_ordered_channel() -> Result<(), Error> {
     run_binary_channel_test(&OrderedChannelTest)
 }
 
+#[test]
+fn test_ordered_clear_packet() -> Result<(), Error> {
+    run_binary_channel_test(&OrderedClearPacketTest)
+}
+
+#[test]
+fn test_ordered_channel_stall() -> Result<(), Error> {
+    run_binary_channel_test(&OrderedChannelStallTest)
+}
+
 pub struct OrderedChannelTest;
+pub struct OrderedClearPacketTest;
+pub struct OrderedChannelStallTest;
+
+/// Number of packets sent before the relayer is started
+/// in [`OrderedClearPacketTest`].
+const ORDERED_CLEAR_PACKET_COUNT: u64 = 5;
 
 impl TestOverrides for OrderedChannelTest {
     fn modify_relayer_config(&self, config: &mut Config) {
@@ -26,6 +47,40 @@ impl TestOverrides for OrderedChannelTest {
     }
 }
 
+impl TestOverrides for OrderedClearPacketTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        // Only the clearing on start should pick up the packets
+        // that were sent before the relayer is started.
+        config.mode.packets.clear_on_start = true;
+        config.mode.packets.clear_interval = 0;
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+
+    fn channel_order(&self) -> Order {
+        Order::Ordered
+    }
+}
+
+impl TestOverrides for OrderedChannelStallTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        // Only the packet worker reacting to new events should relay
+        // the packets, so that nothing else can work around the failure.
+        config.mode.packets.clear_on_start = false;
+        config.mode.packets.clear_interval = 0;
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+
+    fn channel_order(&self) -> Order {
+        Order::Ordered
+    }
+}
+
 impl BinaryChannelTest for OrderedChannelTest {
     fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
         &self,
@@ -107,3 +162,196 @@ impl BinaryChannelTest for OrderedChannelTest {
         })
     }
 }
+
+impl BinaryChannelTest for OrderedClearPacketTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let denom_a = chains.node_a.denom();
+
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        let balance_a = chains
+            .node_a
+            .chain_driver()
+            .query_balance(&wallet_a.address(), &denom_a)?;
+
+        let mut total_amount = 0;
+
+        for _ in 0..ORDERED_CLEAR_PACKET_COUNT {
+            let amount = random_u64_range(1000, 5000);
+            total_amount += amount;
+
+            info!(
+                "Performing IBC transfer with amount {}, which should be relayed on start",
+                amount
+            );
+
+            chains.node_a.chain_driver().ibc_transfer_token(
+                &channel.port_a.as_ref(),
+                &channel.channel_id_a.as_ref(),
+                &wallet_a.as_ref(),
+                &wallet_b.address(),
+                &denom_a,
+                amount,
+            )?;
+        }
+
+        sleep(Duration::from_secs(2));
+
+        let channel_end = query_identified_channel_end(
+            chains.handle_a(),
+            channel.channel_id_a.as_ref(),
+            channel.port_a.as_ref(),
+        )?;
+
+        let summary =
+            pending_packet_summary(chains.handle_a(), chains.handle_b(), channel_end.value())?;
+
+        let expected_sequences: Vec<Sequence> = (1..=ORDERED_CLEAR_PACKET_COUNT)
+            .map(Sequence::from)
+            .collect();
+
+        assert_eq!(summary.unreceived_packets, expected_sequences);
+
+        let denom_b = derive_ibc_denom(
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &denom_a,
+        )?;
+
+        relayer.with_supervisor(|| {
+            chains.node_a.chain_driver().assert_eventual_wallet_amount(
+                &wallet_a.address(),
+                balance_a - total_amount,
+                &denom_a,
+            )?;
+
+            // On an ordered channel, the destination chain only accepts packets
+            // in sequence, so receiving the full amount means that every packet
+            // was relayed in order and without gaps.
+            chains.node_b.chain_driver().assert_eventual_wallet_amount(
+                &wallet_b.address(),
+                total_amount,
+                &denom_b.as_ref(),
+            )?;
+
+            let summary =
+                pending_packet_summary(chains.handle_a(), chains.handle_b(), channel_end.value())?;
+
+            assert!(summary.unreceived_packets.is_empty());
+
+            Ok(())
+        })
+    }
+}
+
+impl BinaryChannelTest for OrderedChannelStallTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        let denom_a = chains.node_a.denom();
+
+        let wallet_a = chains.node_a.wallets().user1().cloned();
+        let wallet_b = chains.node_b.wallets().user1().cloned();
+
+        let denom_b = derive_ibc_denom(
+            &channel.port_b.as_ref(),
+            &channel.channel_id_b.as_ref(),
+            &denom_a,
+        )?;
+
+        let channel_end = query_identified_channel_end(
+            chains.handle_a(),
+            channel.channel_id_a.as_ref(),
+            channel.port_a.as_ref(),
+        )?;
+
+        let chain_id_a = chains.handle_a().id();
+        let failing_sequence = Sequence::from(2);
+
+        let transfer = |amount: u64| {
+            chains.node_a.chain_driver().ibc_transfer_token(
+                &channel.port_a.as_ref(),
+                &channel.channel_id_a.as_ref(),
+                &wallet_a.as_ref(),
+                &wallet_b.address(),
+                &denom_a,
+                amount,
+            )
+        };
+
+        relayer.with_supervisor(|| {
+            let amount1 = random_u64_range(1000, 5000);
+
+            info!(
+                "Performing IBC transfer with amount {}, which should be relayed",
+                amount1
+            );
+
+            transfer(amount1)?;
+
+            chains.node_b.chain_driver().assert_eventual_wallet_amount(
+                &wallet_b.address(),
+                amount1,
+                &denom_b.as_ref(),
+            )?;
+
+            info!("Making the relaying of packet {} fail", failing_sequence);
+
+            fail_recv_packet(&chain_id_a, channel.channel_id_a.value(), failing_sequence);
+
+            let amount2 = random_u64_range(1000, 5000);
+            let amount3 = random_u64_range(1000, 5000);
+
+            info!(
+                "Performing IBC transfers with amounts {} and {}, which should not be relayed",
+                amount2, amount3
+            );
+
+            transfer(amount2)?;
+            sleep(Duration::from_secs(1));
+            transfer(amount3)?;
+
+            sleep(Duration::from_secs(10));
+
+            // The worker must stall on the failing packet, instead of skipping it
+            // and relaying the packet after it out of order.
+            let summary =
+                pending_packet_summary(chains.handle_a(), chains.handle_b(), channel_end.value())?;
+
+            assert_eq!(
+                summary.unreceived_packets,
+                vec![Sequence::from(2), Sequence::from(3)]
+            );
+
+            let balance_b = chains
+                .node_b
+                .chain_driver()
+                .query_balance(&wallet_b.address(), &denom_b.as_ref())?;
+
+            assert_eq!(balance_b, amount1);
+
+            info!("Restoring the relaying of packet {}", failing_sequence);
+
+            restore_recv_packet(&chain_id_a, channel.channel_id_a.value(), failing_sequence);
+
+            chains.node_b.chain_driver().assert_eventual_wallet_amount(
+                &wallet_b.address(),
+                amount1 + amount2 + amount3,
+                &denom_b.as_ref(),
+            )?;
+
+            Ok(())
+        })
+    }
+}
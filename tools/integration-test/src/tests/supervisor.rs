@@ -29,6 +29,7 @@ impl TestOverrides for SupervisorTest {
                 clear_interval: 10,
                 clear_on_start: true,
                 tx_confirmation: true,
+                ..Default::default()
             },
         };
     }
@@ -0,0 +1,191 @@
+use ibc::core::ics04_channel::channel::State;
+use ibc_relayer::chain::counterparty::pending_packet_summary;
+
+use ibc_test_framework::ibc::denom::derive_ibc_denom;
+use ibc_test_framework::prelude::*;
+use ibc_test_framework::relayer::channel::query_identified_channel_end;
+use ibc_test_framework::relayer::transfer::build_transfer_message;
+use ibc_test_framework::util::random::random_u64_range;
+
+#[test]
+fn test_timeout_packet() -> Result<(), Error> {
+    run_binary_channel_test(&TimeoutPacketTest)
+}
+
+#[cfg(feature = "ordered")]
+#[test]
+fn test_ordered_timeout_packet() -> Result<(), Error> {
+    run_binary_channel_test(&OrderedTimeoutPacketTest)
+}
+
+pub struct TimeoutPacketTest;
+pub struct OrderedTimeoutPacketTest;
+
+/// The timeout of the packets sent by the tests, measured from the local time.
+const PACKET_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The delay before the relayer observes the events of the chains, long
+/// enough for the packets to time out before their receive is relayed.
+const RELAY_LATENCY: Duration = Duration::from_secs(10);
+
+fn modify_relayer_config(config: &mut Config) {
+    // Only the packet worker reacting to the `SendPacket` event should
+    // relay the packet, so that no clearing relays it in time.
+    config.mode.packets.clear_on_start = false;
+    config.mode.packets.clear_interval = 0;
+}
+
+impl TestOverrides for TimeoutPacketTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        modify_relayer_config(config);
+    }
+
+    fn relay_latency(&self) -> Duration {
+        RELAY_LATENCY
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+}
+
+impl TestOverrides for OrderedTimeoutPacketTest {
+    fn modify_relayer_config(&self, config: &mut Config) {
+        modify_relayer_config(config);
+    }
+
+    fn relay_latency(&self) -> Duration {
+        RELAY_LATENCY
+    }
+
+    fn should_spawn_supervisor(&self) -> bool {
+        false
+    }
+
+    fn channel_order(&self) -> Order {
+        Order::Ordered
+    }
+}
+
+impl BinaryChannelTest for TimeoutPacketTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        relayer.with_supervisor(|| {
+            transfer_and_assert_refund(&chains, &channel)?;
+
+            // The commitment of the packet is removed from chain A once
+            // its timeout is relayed, leaving no packet pending.
+            let channel_end = query_identified_channel_end(
+                chains.handle_a(),
+                channel.channel_id_a.as_ref(),
+                channel.port_a.as_ref(),
+            )?;
+
+            let summary =
+                pending_packet_summary(chains.handle_a(), chains.handle_b(), channel_end.value())?;
+
+            assert!(summary.unreceived_packets.is_empty());
+            assert_eq!(channel_end.value().channel_end.state(), &State::Open);
+
+            Ok(())
+        })
+    }
+}
+
+impl BinaryChannelTest for OrderedTimeoutPacketTest {
+    fn run<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        _config: &TestConfig,
+        relayer: RelayerDriver,
+        chains: ConnectedChains<ChainA, ChainB>,
+        channel: ConnectedChannel<ChainA, ChainB>,
+    ) -> Result<(), Error> {
+        relayer.with_supervisor(|| {
+            transfer_and_assert_refund(&chains, &channel)?;
+
+            // Timing out a packet of an ordered channel closes the channel.
+            let channel_end = query_identified_channel_end(
+                chains.handle_a(),
+                channel.channel_id_a.as_ref(),
+                channel.port_a.as_ref(),
+            )?;
+
+            assert_eq!(channel_end.value().channel_end.state(), &State::Closed);
+
+            Ok(())
+        })
+    }
+}
+
+/// Performs an IBC transfer from chain A timing out before the relayer can
+/// relay its receive, and asserts that the relayer times the packet out on
+/// chain A instead, refunding the sender.
+fn transfer_and_assert_refund<ChainA: ChainHandle, ChainB: ChainHandle>(
+    chains: &ConnectedChains<ChainA, ChainB>,
+    channel: &ConnectedChannel<ChainA, ChainB>,
+) -> Result<(), Error> {
+    let denom_a = chains.node_a.denom();
+
+    let wallet_a = chains.node_a.wallets().user1().cloned();
+    let wallet_b = chains.node_b.wallets().user1().cloned();
+
+    let denom_b = derive_ibc_denom(
+        &channel.port_b.as_ref(),
+        &channel.channel_id_b.as_ref(),
+        &denom_a,
+    )?;
+
+    let balance_a = chains
+        .node_a
+        .chain_driver()
+        .query_balance(&wallet_a.address(), &denom_a)?;
+
+    let amount = random_u64_range(1000, 5000);
+
+    info!(
+        "Performing IBC transfer with amount {} timing out after {:?}, which should be timed out",
+        amount, PACKET_TIMEOUT
+    );
+
+    let message = build_transfer_message(
+        &channel.port_a.as_ref(),
+        &channel.channel_id_a.as_ref(),
+        &wallet_a.as_ref(),
+        &wallet_b.address(),
+        &denom_a,
+        amount,
+        PACKET_TIMEOUT,
+    )?;
+
+    chains
+        .node_a
+        .chain_driver()
+        .send_tx(&wallet_a.as_ref(), vec![message])?;
+
+    chains.node_a.chain_driver().assert_eventual_wallet_amount(
+        &wallet_a.address(),
+        balance_a - amount,
+        &denom_a,
+    )?;
+
+    // The amount is refunded once the relayer submits the `MsgTimeout` on chain A.
+    chains.node_a.chain_driver().assert_eventual_wallet_amount(
+        &wallet_a.address(),
+        balance_a,
+        &denom_a,
+    )?;
+
+    let balance_b = chains
+        .node_b
+        .chain_driver()
+        .query_balance(&wallet_b.address(), &denom_b.as_ref())?;
+
+    assert_eq!(balance_b, 0);
+
+    Ok(())
+}
@@ -18,6 +18,7 @@ use std::path::Path;
 use tracing::{debug, info};
 
 use crate::relayer::driver::RelayerDriver;
+use crate::relayer::fault::FaultyChainHandle;
 use crate::types::binary::chains::ConnectedChains;
 use crate::types::binary::foreign_client::ForeignClientPair;
 use crate::types::config::TestConfig;
@@ -239,10 +240,12 @@ pub fn add_keys_to_chain_handle<Chain: ChainHandle>(
 
 /**
    Create a new [`SharedRegistry`] that uses [`CountingAndCachingChainHandle`]
-   as the [`ChainHandle`] implementation.
+   wrapped in a [`FaultyChainHandle`] as the [`ChainHandle`] implementation.
 */
-pub fn new_registry(config: Config) -> SharedRegistry<CountingAndCachingChainHandle> {
-    <SharedRegistry<CountingAndCachingChainHandle>>::new(config)
+pub fn new_registry(
+    config: Config,
+) -> SharedRegistry<FaultyChainHandle<CountingAndCachingChainHandle>> {
+    <SharedRegistry<FaultyChainHandle<CountingAndCachingChainHandle>>>::new(config)
 }
 
 /**
@@ -21,6 +21,7 @@ use crate::framework::binary::connection::ConnectionDelayOverride;
 use crate::framework::binary::node::{NodeConfigOverride, NodeGenesisOverride};
 use crate::framework::nary::channel::PortsOverride as NaryPortsOverride;
 use crate::framework::supervisor::SupervisorOverride;
+use crate::relayer::fault::set_relay_latency;
 use crate::types::config::TestConfig;
 
 /**
@@ -78,6 +79,20 @@ pub trait TestOverrides {
         // No modification by default
     }
 
+    /**
+       Return the artificial delay before the relayer observes the events of
+       the chains, simulating a slow relayer, eg. so that packets
+       deterministically time out before being relayed. Defaults to zero,
+       ie. no delay.
+
+       Set on the chains of the relayer config, through
+       [`set_relay_latency`], after [`modify_relayer_config`](TestOverrides::modify_relayer_config),
+       as part of the implementation of [`RelayerConfigOverride`].
+    */
+    fn relay_latency(&self) -> Duration {
+        Duration::ZERO
+    }
+
     /// Returns the settings for the foreign client on the first chain for the
     /// second chain. The defaults are for a client connecting two Cosmos chains
     /// with no custom settings.
@@ -175,7 +190,13 @@ impl<Test: TestOverrides> NodeGenesisOverride for Test {
 
 impl<Test: TestOverrides> RelayerConfigOverride for Test {
     fn modify_relayer_config(&self, config: &mut Config) {
-        TestOverrides::modify_relayer_config(self, config)
+        TestOverrides::modify_relayer_config(self, config);
+
+        let latency = TestOverrides::relay_latency(self);
+
+        for chain in config.chains.iter() {
+            set_relay_latency(&chain.id, latency);
+        }
     }
 }
 
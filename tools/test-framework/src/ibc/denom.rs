@@ -24,6 +24,40 @@ pub enum Denom {
     },
 }
 
+/**
+   The scheme used by a chain to derive the denomination of the vouchers of
+   the tokens transferred to it, from the trace of the tokens.
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DenomScheme {
+    /**
+       The scheme of ICS-20, `ibc/<HASH>`, with the upper-case hex-encoded
+       SHA-256 hash of the trace of the token.
+    */
+    Classic,
+
+    /**
+       The hex-encoded SHA-256 hash of the trace of the token, with the given
+       prefix instead of `ibc/`, as used by some non-standard chains.
+    */
+    Prefixed(String),
+}
+
+impl Default for DenomScheme {
+    fn default() -> Self {
+        Self::Classic
+    }
+}
+
+impl DenomScheme {
+    pub fn prefix(&self) -> &str {
+        match self {
+            Self::Classic => "ibc/",
+            Self::Prefixed(prefix) => prefix,
+        }
+    }
+}
+
 /**
    Type alias for [`Denom`] tagged with the chain it belongs to.
 */
@@ -58,50 +92,92 @@ pub fn derive_ibc_denom<ChainA, ChainB>(
     channel_id: &TaggedChannelIdRef<ChainB, ChainA>,
     denom: &TaggedDenomRef<ChainA>,
 ) -> Result<TaggedDenom<ChainB>, Error> {
-    fn derive_denom(
-        port_id: &PortId,
-        channel_id: &ChannelId,
-        denom: &str,
-    ) -> Result<String, Error> {
-        let transfer_path = format!("{}/{}/{}", port_id, channel_id, denom);
-        derive_denom_with_path(&transfer_path)
-    }
+    derive_ibc_denom_with_scheme(port_id, channel_id, denom, &DenomScheme::Classic)
+}
 
-    /// Derive the transferred token denomination using
-    /// <https://github.com/cosmos/ibc-go/blob/main/docs/architecture/adr-001-coin-source-tracing.md>
-    fn derive_denom_with_path(transfer_path: &str) -> Result<String, Error> {
-        let mut hasher = Sha256::new();
-        hasher.update(transfer_path.as_bytes());
+/**
+   Same as [`derive_ibc_denom`], for a `ChainB` deriving the denominations of
+   the vouchers with the given [`DenomScheme`].
+*/
+pub fn derive_ibc_denom_with_scheme<ChainA, ChainB>(
+    port_id: &TaggedPortIdRef<ChainB, ChainA>,
+    channel_id: &TaggedChannelIdRef<ChainB, ChainA>,
+    denom: &TaggedDenomRef<ChainA>,
+    scheme: &DenomScheme,
+) -> Result<TaggedDenom<ChainB>, Error> {
+    let derived = derive_hop_denom(port_id.value(), channel_id.value(), denom.value(), scheme)?;
 
-        let denom_bytes = hasher.finalize();
-        let denom_hex = String::from_utf8(hex::encode_upper(denom_bytes))?;
+    Ok(MonoTagged::new(derived))
+}
 
-        Ok(format!("ibc/{}", denom_hex))
-    }
+/**
+   Derives the denom obtained after transferring the given base denom
+   through several hops, eg. with packet forwarding.
+
+   Accepts the following arguments:
+
+   - The hops taken by the token, in the order in which they are taken.
+     Each hop is given by the `PortId` and `ChannelId` on its receiving
+     chain.
+
+   - The original denomination on the first sending chain.
+
+   Returns the derived denomination on the last receiving chain, which
+   is the base denomination itself if there are no hops.
+*/
+pub fn derive_ibc_denom_multi_hop(
+    hops: &[(PortId, ChannelId)],
+    base_denom: &str,
+) -> Result<Denom, Error> {
+    hops.iter()
+        .try_fold(Denom::base(base_denom), |denom, (port_id, channel_id)| {
+            derive_hop_denom(port_id, channel_id, &denom, &DenomScheme::Classic)
+        })
+}
 
-    match denom.value() {
+fn derive_hop_denom(
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    denom: &Denom,
+    scheme: &DenomScheme,
+) -> Result<Denom, Error> {
+    match denom {
         Denom::Base(denom) => {
-            let hashed = derive_denom(port_id.value(), channel_id.value(), denom)?;
+            let hashed =
+                derive_denom_with_path(&format!("{}/{}/{}", port_id, channel_id, denom), scheme)?;
 
-            Ok(MonoTagged::new(Denom::Ibc {
+            Ok(Denom::Ibc {
                 path: format!("{}/{}", port_id, channel_id),
                 denom: denom.clone(),
                 hashed,
-            }))
+            })
         }
         Denom::Ibc { path, denom, .. } => {
             let new_path = format!("{}/{}/{}", port_id, channel_id, path);
-            let hashed = derive_denom_with_path(&format!("{}/{}", new_path, denom))?;
+            let hashed = derive_denom_with_path(&format!("{}/{}", new_path, denom), scheme)?;
 
-            Ok(MonoTagged::new(Denom::Ibc {
+            Ok(Denom::Ibc {
                 path: new_path,
                 denom: denom.clone(),
                 hashed,
-            }))
+            })
         }
     }
 }
 
+/// Derive the transferred token denomination using
+/// <https://github.com/cosmos/ibc-go/blob/main/docs/architecture/adr-001-coin-source-tracing.md>,
+/// with the prefix of the given scheme.
+fn derive_denom_with_path(transfer_path: &str, scheme: &DenomScheme) -> Result<String, Error> {
+    let mut hasher = Sha256::new();
+    hasher.update(transfer_path.as_bytes());
+
+    let denom_bytes = hasher.finalize();
+    let denom_hex = String::from_utf8(hex::encode_upper(denom_bytes))?;
+
+    Ok(format!("{}{}", scheme.prefix(), denom_hex))
+}
+
 impl Denom {
     pub fn base(denom: &str) -> Self {
         Denom::Base(denom.to_string())
@@ -127,3 +203,103 @@ impl Display for Denom {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{derive_ibc_denom_multi_hop, derive_ibc_denom_with_scheme, Denom, DenomScheme};
+
+    use ibc::core::ics24_host::identifier::{ChannelId, PortId};
+
+    use crate::types::tagged::{DualTagged, MonoTagged};
+
+    fn hops(channels: &[u64]) -> Vec<(PortId, ChannelId)> {
+        channels
+            .iter()
+            .map(|c| (PortId::transfer(), ChannelId::new(*c)))
+            .collect()
+    }
+
+    #[test]
+    fn multi_hop_without_hops_is_base_denom() {
+        let denom = derive_ibc_denom_multi_hop(&[], "uatom").unwrap();
+
+        assert!(matches!(denom, Denom::Base(ref d) if d == "uatom"));
+    }
+
+    #[test]
+    fn multi_hop_with_single_hop() {
+        let denom = derive_ibc_denom_multi_hop(&hops(&[0]), "uatom").unwrap();
+
+        assert_eq!(
+            denom.as_str(),
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        );
+    }
+
+    #[test]
+    fn multi_hop_with_two_hops() {
+        let denom = derive_ibc_denom_multi_hop(&hops(&[0, 1]), "uatom").unwrap();
+
+        match &denom {
+            Denom::Ibc { path, denom, .. } => {
+                assert_eq!(path, "transfer/channel-1/transfer/channel-0");
+                assert_eq!(denom, "uatom");
+            }
+            Denom::Base(_) => panic!("expected an IBC denom"),
+        }
+
+        assert_eq!(
+            denom.as_str(),
+            "ibc/FA0006F056DB6719B8C16C551FC392B62F5729978FC0B125AC9A432DBB2AA1A5"
+        );
+    }
+
+    #[test]
+    fn multi_hop_with_three_hops() {
+        let denom = derive_ibc_denom_multi_hop(&hops(&[0, 1, 2]), "uatom").unwrap();
+
+        assert_eq!(
+            denom.as_str(),
+            "ibc/68BF93EFD7B575148289F1CBD19D0585A80E0BE596484D3D7A99499B7A2EC0D3"
+        );
+    }
+
+    fn derive_with_scheme(scheme: &DenomScheme) -> String {
+        let port_id = PortId::transfer();
+        let channel_id = ChannelId::new(0);
+        let denom = Denom::base("uatom");
+
+        let derived = derive_ibc_denom_with_scheme::<(), ()>(
+            &DualTagged::new(&port_id),
+            &DualTagged::new(&channel_id),
+            &MonoTagged::new(&denom),
+            scheme,
+        )
+        .unwrap();
+
+        derived.value().as_str().to_string()
+    }
+
+    #[test]
+    fn classic_scheme_is_the_default() {
+        assert_eq!(DenomScheme::default(), DenomScheme::Classic);
+
+        assert_eq!(
+            derive_with_scheme(&DenomScheme::Classic),
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        );
+    }
+
+    #[test]
+    fn prefixed_scheme_replaces_the_ibc_prefix() {
+        assert_eq!(
+            derive_with_scheme(&DenomScheme::Prefixed("ibc-".to_string())),
+            "ibc-27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        );
+
+        assert_eq!(
+            derive_with_scheme(&DenomScheme::Prefixed("voucher:".to_string())),
+            "voucher:27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        );
+    }
+}
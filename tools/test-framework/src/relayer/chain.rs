@@ -26,10 +26,10 @@ use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientS
 use ibc::core::ics02_client::events::UpdateClient;
 use ibc::core::ics02_client::misbehaviour::MisbehaviourEvidence;
 use ibc::core::ics03_connection::connection::IdentifiedConnectionEnd;
-use ibc::core::ics04_channel::channel::IdentifiedChannelEnd;
+use ibc::core::ics04_channel::channel::{IdentifiedChannelEnd, QueryPacketEventDataRequest};
 use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
 use ibc::core::ics23_commitment::merkle::MerkleProof;
-use ibc::query::QueryTxRequest;
+use ibc::query::{QueryTxHash, QueryTxRequest};
 use ibc::{
     core::ics02_client::header::AnyHeader,
     core::ics03_connection::connection::ConnectionEnd,
@@ -39,15 +39,17 @@ use ibc::{
     core::ics24_host::identifier::ChainId,
     core::ics24_host::identifier::ChannelId,
     core::ics24_host::identifier::{ClientId, ConnectionId, PortId},
-    events::IbcEvent,
+    events::{IbcEvent, IbcEventType},
     proofs::Proofs,
     query::QueryBlockRequest,
     signer::Signer,
     Height,
 };
-use ibc_relayer::account::Balance;
+use ibc_relayer::account::{AccountInfo, Balance, DenomTrace};
 use ibc_relayer::chain::client::ClientSettings;
-use ibc_relayer::chain::endpoint::{ChainStatus, HealthCheck};
+use ibc_relayer::chain::endpoint::{
+    ChainStatus, ClientStatus, HealthCheck, PacketEventTx, TxEvent, TxResult, TxSimulation,
+};
 use ibc_relayer::chain::handle::{ChainHandle, ChainRequest, Subscription};
 use ibc_relayer::chain::requests::{
     IncludeProof, QueryChannelClientStateRequest, QueryChannelRequest, QueryChannelsRequest,
@@ -61,7 +63,7 @@ use ibc_relayer::chain::requests::{
     QueryUpgradedConsensusStateRequest,
 };
 use ibc_relayer::chain::tracking::TrackedMsgs;
-use ibc_relayer::config::ChainConfig;
+use ibc_relayer::config::{ChainConfig, GasPrice};
 use ibc_relayer::connection::ConnectionMsgType;
 use ibc_relayer::error::Error;
 use ibc_relayer::keyring::KeyEntry;
@@ -112,6 +114,14 @@ where
         self.value().send_messages_and_wait_check_tx(tracked_msgs)
     }
 
+    fn estimate_gas(&self, tracked_msgs: TrackedMsgs) -> Result<u64, Error> {
+        self.value().estimate_gas(tracked_msgs)
+    }
+
+    fn simulate_tx(&self, tracked_msgs: TrackedMsgs) -> Result<TxSimulation, Error> {
+        self.value().simulate_tx(tracked_msgs)
+    }
+
     fn get_signer(&self) -> Result<Signer, Error> {
         self.value().get_signer()
     }
@@ -155,6 +165,10 @@ where
         self.value().query_client_state(request, include_proof)
     }
 
+    fn query_client_status(&self, client_id: ClientId) -> Result<ClientStatus, Error> {
+        self.value().query_client_status(client_id)
+    }
+
     fn query_client_connections(
         &self,
         request: QueryClientConnectionsRequest,
@@ -245,6 +259,10 @@ where
         self.value().query_channel(request, include_proof)
     }
 
+    fn query_channel_uncached(&self, request: QueryChannelRequest) -> Result<ChannelEnd, Error> {
+        self.value().query_channel_uncached(request)
+    }
+
     fn query_channel_client_state(
         &self,
         request: QueryChannelClientStateRequest,
@@ -384,6 +402,29 @@ where
         self.value().query_txs(request)
     }
 
+    fn query_tx_by_hash(&self, hash: QueryTxHash) -> Result<Option<Vec<IbcEvent>>, Error> {
+        self.value().query_tx_by_hash(hash)
+    }
+
+    fn query_tx_result(&self, hash: QueryTxHash) -> Result<Option<TxResult>, Error> {
+        self.value().query_tx_result(hash)
+    }
+
+    fn query_tx_decoded_events(
+        &self,
+        hash: QueryTxHash,
+        decode_only: Vec<IbcEventType>,
+    ) -> Result<Option<Vec<TxEvent>>, Error> {
+        self.value().query_tx_decoded_events(hash, decode_only)
+    }
+
+    fn query_packet_event_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<PacketEventTx>, Error> {
+        self.value().query_packet_event_txs(request)
+    }
+
     fn query_blocks(
         &self,
         request: QueryBlockRequest,
@@ -401,4 +442,32 @@ where
     fn query_balance(&self, key_name: Option<String>) -> Result<Balance, Error> {
         self.value().query_balance(key_name)
     }
+
+    fn query_balances(&self, key_names: Vec<String>) -> Result<Vec<Result<Balance, Error>>, Error> {
+        self.value().query_balances(key_names)
+    }
+
+    fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
+        self.value().query_denom_trace(hash)
+    }
+
+    fn query_supply_of(&self, denom: String) -> Result<Balance, Error> {
+        self.value().query_supply_of(denom)
+    }
+
+    fn query_balance_of_address(&self, address: String, denom: String) -> Result<Balance, Error> {
+        self.value().query_balance_of_address(address, denom)
+    }
+
+    fn query_min_gas_price(&self) -> Result<Vec<GasPrice>, Error> {
+        self.value().query_min_gas_price()
+    }
+
+    fn query_account_info(&self, key_name: Option<String>) -> Result<AccountInfo, Error> {
+        self.value().query_account_info(key_name)
+    }
+
+    fn reset_account_sequence(&self, key_name: Option<String>) -> Result<(), Error> {
+        self.value().reset_account_sequence(key_name)
+    }
 }
@@ -9,6 +9,7 @@ use ibc_relayer::supervisor::{spawn_supervisor, SupervisorHandle, SupervisorOpti
 use std::path::PathBuf;
 
 use crate::error::Error;
+use crate::relayer::fault::FaultyChainHandle;
 use crate::types::env::{EnvWriter, ExportEnv};
 use crate::util::suspend::hang_on_error;
 
@@ -41,7 +42,7 @@ pub struct RelayerDriver {
        Use this shared registry when spawning new supervisor using
        [`spawn_supervisor`](ibc_relayer::supervisor::spawn_supervisor).
     */
-    pub registry: SharedRegistry<CountingAndCachingChainHandle>,
+    pub registry: SharedRegistry<FaultyChainHandle<CountingAndCachingChainHandle>>,
 
     /**
        Whether the driver should hang the test when the continuation
@@ -62,6 +63,7 @@ impl RelayerDriver {
             SupervisorOptions {
                 health_check: false,
                 force_full_scan: false,
+                on_relayed: None,
             },
         )
         .map_err(Error::supervisor)
@@ -0,0 +1,592 @@
+/*!
+   A proxy [`ChainHandle`] implementation injecting faults into the chain
+   handles spawned by the relayer supervisor of the tests, so that the tests
+   can check how the relayer copes with them.
+
+   The faults are set per chain, through process-wide functions such as
+   [`set_relay_latency`] and [`fail_recv_packet`], as the chain handles are spawned by the chain
+   registry from only the identifier of their chain. Since the identifiers
+   of the chains are unique to each test, tests running concurrently do not
+   interfere with each other.
+
+   The faults only exist in the test framework, keeping them out of the
+   relayer itself.
+*/
+
+use core::time::Duration;
+use crossbeam_channel as channel;
+use once_cell::sync::Lazy;
+use serde::{Serialize, Serializer};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Instant;
+
+use ibc::core::ics02_client::client_consensus::{AnyConsensusState, AnyConsensusStateWithHeight};
+use ibc::core::ics02_client::client_state::{AnyClientState, IdentifiedAnyClientState};
+use ibc::core::ics02_client::events::UpdateClient;
+use ibc::core::ics02_client::misbehaviour::MisbehaviourEvidence;
+use ibc::core::ics03_connection::connection::IdentifiedConnectionEnd;
+use ibc::core::ics04_channel::channel::{IdentifiedChannelEnd, QueryPacketEventDataRequest};
+use ibc::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc::core::ics23_commitment::merkle::MerkleProof;
+use ibc::query::{QueryTxHash, QueryTxRequest};
+use ibc::{
+    core::ics02_client::header::AnyHeader,
+    core::ics03_connection::connection::ConnectionEnd,
+    core::ics03_connection::version::Version,
+    core::ics04_channel::channel::ChannelEnd,
+    core::ics23_commitment::commitment::CommitmentPrefix,
+    core::ics24_host::identifier::ChainId,
+    core::ics24_host::identifier::ChannelId,
+    core::ics24_host::identifier::{ClientId, ConnectionId, PortId},
+    events::{IbcEvent, IbcEventType},
+    proofs::Proofs,
+    query::QueryBlockRequest,
+    signer::Signer,
+    Height,
+};
+use ibc_relayer::account::{AccountInfo, Balance, DenomTrace};
+use ibc_relayer::chain::client::ClientSettings;
+use ibc_relayer::chain::endpoint::{
+    ChainStatus, ClientStatus, HealthCheck, PacketEventTx, TxEvent, TxResult, TxSimulation,
+};
+use ibc_relayer::chain::handle::{ChainHandle, ChainRequest, Subscription};
+use ibc_relayer::chain::requests::{
+    IncludeProof, QueryChannelClientStateRequest, QueryChannelRequest, QueryChannelsRequest,
+    QueryClientConnectionsRequest, QueryClientStateRequest, QueryClientStatesRequest,
+    QueryConnectionChannelsRequest, QueryConnectionRequest, QueryConnectionsRequest,
+    QueryConsensusStateRequest, QueryConsensusStatesRequest, QueryHostConsensusStateRequest,
+    QueryNextSequenceReceiveRequest, QueryPacketAcknowledgementRequest,
+    QueryPacketAcknowledgementsRequest, QueryPacketCommitmentRequest,
+    QueryPacketCommitmentsRequest, QueryPacketReceiptRequest, QueryUnreceivedAcksRequest,
+    QueryUnreceivedPacketsRequest, QueryUpgradedClientStateRequest,
+    QueryUpgradedConsensusStateRequest,
+};
+use ibc_relayer::chain::tracking::TrackedMsgs;
+use ibc_relayer::config::{ChainConfig, GasPrice};
+use ibc_relayer::connection::ConnectionMsgType;
+use ibc_relayer::error::Error;
+use ibc_relayer::keyring::KeyEntry;
+
+/**
+   The faults injected into the chain handles of a chain.
+*/
+#[derive(Clone, Debug, Default)]
+struct ChainFaults {
+    relay_latency: Option<Duration>,
+    failing_recv_packets: HashSet<(ChannelId, Sequence)>,
+}
+
+static CHAIN_FAULTS: Lazy<Mutex<HashMap<ChainId, ChainFaults>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn chain_faults(chain_id: &ChainId) -> ChainFaults {
+    CHAIN_FAULTS
+        .lock()
+        .unwrap()
+        .get(chain_id)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/**
+   Delays the delivery of the events of the given chain to the relayer by
+   `latency`, so that the relayer reacts to them only once the latency has
+   elapsed, eg. so that the packets deterministically time out before being
+   relayed. Only applies to the subscriptions made after the call.
+*/
+pub fn set_relay_latency(chain_id: &ChainId, latency: Duration) {
+    let mut faults = CHAIN_FAULTS.lock().unwrap();
+
+    faults.entry(chain_id.clone()).or_default().relay_latency =
+        Some(latency).filter(|latency| !latency.is_zero());
+}
+
+/**
+   Makes the building of the proofs of the `MsgRecvPacket` for the packet with
+   the given `sequence`, sent on `src_channel_id` of `src_chain_id`, fail until
+   [`restore_recv_packet`] is called for that packet, so that the packet cannot
+   be relayed.
+*/
+pub fn fail_recv_packet(src_chain_id: &ChainId, src_channel_id: &ChannelId, sequence: Sequence) {
+    let mut faults = CHAIN_FAULTS.lock().unwrap();
+
+    faults
+        .entry(src_chain_id.clone())
+        .or_default()
+        .failing_recv_packets
+        .insert((*src_channel_id, sequence));
+}
+
+/**
+   Undoes a previous call to [`fail_recv_packet`] for the given packet.
+*/
+pub fn restore_recv_packet(src_chain_id: &ChainId, src_channel_id: &ChannelId, sequence: Sequence) {
+    let mut faults = CHAIN_FAULTS.lock().unwrap();
+
+    if let Some(faults) = faults.get_mut(src_chain_id) {
+        faults
+            .failing_recv_packets
+            .remove(&(*src_channel_id, sequence));
+    }
+}
+
+/**
+   Forwards the event batches of the given subscription, each once the given
+   latency has elapsed since it was received.
+*/
+fn delay_subscription(subscription: Subscription, latency: Duration) -> Subscription {
+    let (received_sender, received) = channel::unbounded();
+    let (sender, receiver) = channel::unbounded();
+
+    // Timestamp the batches as soon as they are received, so that the delay
+    // of a batch does not add up to the delays of the batches before it.
+    thread::spawn(move || {
+        for batch in subscription.iter() {
+            if received_sender.send((Instant::now(), batch)).is_err() {
+                break;
+            }
+        }
+    });
+
+    thread::spawn(move || {
+        for (received_at, batch) in received.iter() {
+            thread::sleep((received_at + latency).saturating_duration_since(Instant::now()));
+
+            if sender.send(batch).is_err() {
+                break;
+            }
+        }
+    });
+
+    receiver
+}
+
+/**
+   A [`ChainHandle`] injecting the faults set for its chain into the
+   underlying `Handle`.
+*/
+#[derive(Clone, Debug)]
+pub struct FaultyChainHandle<Handle> {
+    inner: Handle,
+}
+
+impl<Handle: Serialize> Serialize for FaultyChainHandle<Handle> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.inner.serialize(serializer)
+    }
+}
+
+impl<Handle: ChainHandle> ChainHandle for FaultyChainHandle<Handle> {
+    fn new(chain_id: ChainId, sender: channel::Sender<ChainRequest>) -> Self {
+        Self {
+            inner: Handle::new(chain_id, sender),
+        }
+    }
+
+    fn id(&self) -> ChainId {
+        self.inner.id()
+    }
+
+    fn shutdown(&self) -> Result<(), Error> {
+        self.inner.shutdown()
+    }
+
+    fn health_check(&self) -> Result<HealthCheck, Error> {
+        self.inner.health_check()
+    }
+
+    fn subscribe(&self) -> Result<Subscription, Error> {
+        let subscription = self.inner.subscribe()?;
+
+        match chain_faults(&self.id()).relay_latency {
+            Some(latency) => Ok(delay_subscription(subscription, latency)),
+            None => Ok(subscription),
+        }
+    }
+
+    fn send_messages_and_wait_commit(
+        &self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEvent>, Error> {
+        self.inner.send_messages_and_wait_commit(tracked_msgs)
+    }
+
+    fn send_messages_and_wait_check_tx(
+        &self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>, Error> {
+        self.inner.send_messages_and_wait_check_tx(tracked_msgs)
+    }
+
+    fn estimate_gas(&self, tracked_msgs: TrackedMsgs) -> Result<u64, Error> {
+        self.inner.estimate_gas(tracked_msgs)
+    }
+
+    fn simulate_tx(&self, tracked_msgs: TrackedMsgs) -> Result<TxSimulation, Error> {
+        self.inner.simulate_tx(tracked_msgs)
+    }
+
+    fn get_signer(&self) -> Result<Signer, Error> {
+        self.inner.get_signer()
+    }
+
+    fn config(&self) -> Result<ChainConfig, Error> {
+        self.inner.config()
+    }
+
+    fn get_key(&self) -> Result<KeyEntry, Error> {
+        self.inner.get_key()
+    }
+
+    fn add_key(&self, key_name: String, key: KeyEntry) -> Result<(), Error> {
+        self.inner.add_key(key_name, key)
+    }
+
+    fn ibc_version(&self) -> Result<Option<semver::Version>, Error> {
+        self.inner.ibc_version()
+    }
+
+    fn query_application_status(&self) -> Result<ChainStatus, Error> {
+        self.inner.query_application_status()
+    }
+
+    fn query_latest_height(&self) -> Result<Height, Error> {
+        self.inner.query_latest_height()
+    }
+
+    fn query_clients(
+        &self,
+        request: QueryClientStatesRequest,
+    ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
+        self.inner.query_clients(request)
+    }
+
+    fn query_client_state(
+        &self,
+        request: QueryClientStateRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(AnyClientState, Option<MerkleProof>), Error> {
+        self.inner.query_client_state(request, include_proof)
+    }
+
+    fn query_client_status(&self, client_id: ClientId) -> Result<ClientStatus, Error> {
+        self.inner.query_client_status(client_id)
+    }
+
+    fn query_client_connections(
+        &self,
+        request: QueryClientConnectionsRequest,
+    ) -> Result<Vec<ConnectionId>, Error> {
+        self.inner.query_client_connections(request)
+    }
+
+    fn query_consensus_states(
+        &self,
+        request: QueryConsensusStatesRequest,
+    ) -> Result<Vec<AnyConsensusStateWithHeight>, Error> {
+        self.inner.query_consensus_states(request)
+    }
+
+    fn query_consensus_state(
+        &self,
+        request: QueryConsensusStateRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(AnyConsensusState, Option<MerkleProof>), Error> {
+        self.inner.query_consensus_state(request, include_proof)
+    }
+
+    fn query_upgraded_client_state(
+        &self,
+        request: QueryUpgradedClientStateRequest,
+    ) -> Result<(AnyClientState, MerkleProof), Error> {
+        self.inner.query_upgraded_client_state(request)
+    }
+
+    fn query_upgraded_consensus_state(
+        &self,
+        request: QueryUpgradedConsensusStateRequest,
+    ) -> Result<(AnyConsensusState, MerkleProof), Error> {
+        self.inner.query_upgraded_consensus_state(request)
+    }
+
+    fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
+        self.inner.query_commitment_prefix()
+    }
+
+    fn query_compatible_versions(&self) -> Result<Vec<Version>, Error> {
+        self.inner.query_compatible_versions()
+    }
+
+    fn query_connection(
+        &self,
+        request: QueryConnectionRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ConnectionEnd, Option<MerkleProof>), Error> {
+        self.inner.query_connection(request, include_proof)
+    }
+
+    fn query_connections(
+        &self,
+        request: QueryConnectionsRequest,
+    ) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
+        self.inner.query_connections(request)
+    }
+
+    fn query_connection_channels(
+        &self,
+        request: QueryConnectionChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        self.inner.query_connection_channels(request)
+    }
+
+    fn query_next_sequence_receive(
+        &self,
+        request: QueryNextSequenceReceiveRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Sequence, Option<MerkleProof>), Error> {
+        self.inner
+            .query_next_sequence_receive(request, include_proof)
+    }
+
+    fn query_channels(
+        &self,
+        request: QueryChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        self.inner.query_channels(request)
+    }
+
+    fn query_channel(
+        &self,
+        request: QueryChannelRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ChannelEnd, Option<MerkleProof>), Error> {
+        self.inner.query_channel(request, include_proof)
+    }
+
+    fn query_channel_uncached(&self, request: QueryChannelRequest) -> Result<ChannelEnd, Error> {
+        self.inner.query_channel_uncached(request)
+    }
+
+    fn query_channel_client_state(
+        &self,
+        request: QueryChannelClientStateRequest,
+    ) -> Result<Option<IdentifiedAnyClientState>, Error> {
+        self.inner.query_channel_client_state(request)
+    }
+
+    fn build_header(
+        &self,
+        trusted_height: Height,
+        target_height: Height,
+        client_state: AnyClientState,
+    ) -> Result<(AnyHeader, Vec<AnyHeader>), Error> {
+        self.inner
+            .build_header(trusted_height, target_height, client_state)
+    }
+
+    /// Constructs a client state at the given height
+    fn build_client_state(
+        &self,
+        height: Height,
+        settings: ClientSettings,
+    ) -> Result<AnyClientState, Error> {
+        self.inner.build_client_state(height, settings)
+    }
+
+    /// Constructs a consensus state at the given height
+    fn build_consensus_state(
+        &self,
+        trusted: Height,
+        target: Height,
+        client_state: AnyClientState,
+    ) -> Result<AnyConsensusState, Error> {
+        self.inner
+            .build_consensus_state(trusted, target, client_state)
+    }
+
+    fn check_misbehaviour(
+        &self,
+        update: UpdateClient,
+        client_state: AnyClientState,
+    ) -> Result<Option<MisbehaviourEvidence>, Error> {
+        self.inner.check_misbehaviour(update, client_state)
+    }
+
+    fn build_connection_proofs_and_client_state(
+        &self,
+        message_type: ConnectionMsgType,
+        connection_id: &ConnectionId,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<(Option<AnyClientState>, Proofs), Error> {
+        self.inner.build_connection_proofs_and_client_state(
+            message_type,
+            connection_id,
+            client_id,
+            height,
+        )
+    }
+
+    fn build_channel_proofs(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: Height,
+    ) -> Result<Proofs, Error> {
+        self.inner.build_channel_proofs(port_id, channel_id, height)
+    }
+
+    fn build_packet_proofs(
+        &self,
+        packet_type: PacketMsgType,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        height: Height,
+    ) -> Result<Proofs, Error> {
+        if packet_type == PacketMsgType::Recv
+            && chain_faults(&self.id())
+                .failing_recv_packets
+                .contains(&(*channel_id, sequence))
+        {
+            return Err(Error::query(format!(
+                "the proofs of packet {} (injected failure)",
+                sequence
+            )));
+        }
+
+        self.inner
+            .build_packet_proofs(packet_type, port_id, channel_id, sequence, height)
+    }
+
+    fn query_packet_commitment(
+        &self,
+        request: QueryPacketCommitmentRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.inner.query_packet_commitment(request, include_proof)
+    }
+
+    fn query_packet_commitments(
+        &self,
+        request: QueryPacketCommitmentsRequest,
+    ) -> Result<(Vec<Sequence>, Height), Error> {
+        self.inner.query_packet_commitments(request)
+    }
+
+    fn query_packet_receipt(
+        &self,
+        request: QueryPacketReceiptRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.inner.query_packet_receipt(request, include_proof)
+    }
+
+    fn query_unreceived_packets(
+        &self,
+        request: QueryUnreceivedPacketsRequest,
+    ) -> Result<Vec<Sequence>, Error> {
+        self.inner.query_unreceived_packets(request)
+    }
+
+    fn query_packet_acknowledgement(
+        &self,
+        request: QueryPacketAcknowledgementRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.inner
+            .query_packet_acknowledgement(request, include_proof)
+    }
+
+    fn query_packet_acknowledgements(
+        &self,
+        request: QueryPacketAcknowledgementsRequest,
+    ) -> Result<(Vec<Sequence>, Height), Error> {
+        self.inner.query_packet_acknowledgements(request)
+    }
+
+    fn query_unreceived_acknowledgements(
+        &self,
+        request: QueryUnreceivedAcksRequest,
+    ) -> Result<Vec<Sequence>, Error> {
+        self.inner.query_unreceived_acknowledgements(request)
+    }
+
+    fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEvent>, Error> {
+        self.inner.query_txs(request)
+    }
+
+    fn query_tx_by_hash(&self, hash: QueryTxHash) -> Result<Option<Vec<IbcEvent>>, Error> {
+        self.inner.query_tx_by_hash(hash)
+    }
+
+    fn query_tx_result(&self, hash: QueryTxHash) -> Result<Option<TxResult>, Error> {
+        self.inner.query_tx_result(hash)
+    }
+
+    fn query_tx_decoded_events(
+        &self,
+        hash: QueryTxHash,
+        decode_only: Vec<IbcEventType>,
+    ) -> Result<Option<Vec<TxEvent>>, Error> {
+        self.inner.query_tx_decoded_events(hash, decode_only)
+    }
+
+    fn query_packet_event_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<PacketEventTx>, Error> {
+        self.inner.query_packet_event_txs(request)
+    }
+
+    fn query_blocks(
+        &self,
+        request: QueryBlockRequest,
+    ) -> Result<(Vec<IbcEvent>, Vec<IbcEvent>), Error> {
+        self.inner.query_blocks(request)
+    }
+
+    fn query_host_consensus_state(
+        &self,
+        request: QueryHostConsensusStateRequest,
+    ) -> Result<AnyConsensusState, Error> {
+        self.inner.query_host_consensus_state(request)
+    }
+
+    fn query_balance(&self, key_name: Option<String>) -> Result<Balance, Error> {
+        self.inner.query_balance(key_name)
+    }
+
+    fn query_balances(&self, key_names: Vec<String>) -> Result<Vec<Result<Balance, Error>>, Error> {
+        self.inner.query_balances(key_names)
+    }
+
+    fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
+        self.inner.query_denom_trace(hash)
+    }
+
+    fn query_supply_of(&self, denom: String) -> Result<Balance, Error> {
+        self.inner.query_supply_of(denom)
+    }
+
+    fn query_balance_of_address(&self, address: String, denom: String) -> Result<Balance, Error> {
+        self.inner.query_balance_of_address(address, denom)
+    }
+
+    fn query_min_gas_price(&self) -> Result<Vec<GasPrice>, Error> {
+        self.inner.query_min_gas_price()
+    }
+
+    fn query_account_info(&self, key_name: Option<String>) -> Result<AccountInfo, Error> {
+        self.inner.query_account_info(key_name)
+    }
+
+    fn reset_account_sequence(&self, key_name: Option<String>) -> Result<(), Error> {
+        self.inner.reset_account_sequence(key_name)
+    }
+}
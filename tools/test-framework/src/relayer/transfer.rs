@@ -21,6 +21,10 @@ use crate::types::id::{TaggedChannelIdRef, TaggedPortIdRef};
 use crate::types::tagged::*;
 use crate::types::wallet::{Wallet, WalletAddress};
 
+/**
+   Build an IBC token transfer message timing out after the given duration,
+   measured from the local time.
+*/
 pub fn build_transfer_message<SrcChain, DstChain>(
     port_id: &TaggedPortIdRef<'_, SrcChain, DstChain>,
     channel_id: &TaggedChannelIdRef<'_, SrcChain, DstChain>,
@@ -28,9 +32,10 @@ pub fn build_transfer_message<SrcChain, DstChain>(
     recipient: &MonoTagged<DstChain, &WalletAddress>,
     denom: &MonoTagged<SrcChain, &Denom>,
     amount: u64,
+    timeout: Duration,
 ) -> Result<Any, Error> {
     let timeout_timestamp = Timestamp::now()
-        .add(Duration::from_secs(60))
+        .add(timeout)
         .map_err(handle_generic_error)?;
 
     let sender = sender
@@ -73,7 +78,7 @@ pub fn build_transfer_message<SrcChain, DstChain>(
    in most cases.
 
    If tests require explicit timeout, they should explicitly construct the
-   transfer message and pass it to send_tx.
+   transfer message with [`build_transfer_message`] and pass it to send_tx.
 */
 pub async fn ibc_token_transfer<SrcChain, DstChain>(
     tx_config: &MonoTagged<SrcChain, &TxConfig>,
@@ -84,7 +89,15 @@ pub async fn ibc_token_transfer<SrcChain, DstChain>(
     denom: &MonoTagged<SrcChain, &Denom>,
     amount: u64,
 ) -> Result<(), Error> {
-    let message = build_transfer_message(port_id, channel_id, sender, recipient, denom, amount)?;
+    let message = build_transfer_message(
+        port_id,
+        channel_id,
+        sender,
+        recipient,
+        denom,
+        amount,
+        Duration::from_secs(60),
+    )?;
 
     simple_send_tx(tx_config.value(), &sender.value().key, vec![message]).await?;
 
@@ -69,6 +69,8 @@ pub fn new_tx_config_for_test(
         grpc_address,
         rpc_timeout,
         address_type,
+        broadcast_mode: Default::default(),
+        account_resync_interval: Duration::ZERO,
     })
 }
 
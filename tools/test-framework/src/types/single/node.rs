@@ -128,6 +128,7 @@ impl FullNode {
             rpc_timeout: Duration::from_secs(10),
             account_prefix: self.chain_driver.account_prefix.clone(),
             key_name: self.wallets.relayer.id.0.clone(),
+            extra_key_names: Vec::new(),
 
             // By default we use in-memory key store to avoid polluting
             // ~/.hermes/keys. See
@@ -150,6 +151,11 @@ impl FullNode {
             address_type: Default::default(),
             memo_prefix: Default::default(),
             proof_specs: Default::default(),
+            tx_rate_limit: None,
+            display_denom_map: Default::default(),
+            event_source: Default::default(),
+            broadcast_mode: Default::default(),
+            account_resync_interval: Duration::from_secs(1),
         })
     }
 
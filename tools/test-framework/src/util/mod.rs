@@ -5,6 +5,7 @@
 pub mod array;
 pub mod assert;
 pub mod file;
+pub mod packet;
 pub mod random;
 pub mod retry;
 pub mod suspend;
@@ -0,0 +1,82 @@
+/*!
+   Utilities for asserting on the relaying of packets.
+*/
+
+use core::time::Duration;
+use std::thread::sleep;
+
+use ibc_relayer::chain::counterparty::unreceived_packets;
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::path::PathIdentifiers;
+use tracing::info;
+
+use crate::error::Error;
+use crate::types::binary::chains::ConnectedChains;
+use crate::types::binary::channel::ConnectedChannel;
+
+/**
+   Assert that none of the packets sent from chain A over the given
+   channel, and not yet received on chain B, get received on chain B
+   within the given duration.
+
+   The unreceived packets are queried once per second, and the assertion
+   fails as soon as any of the packets which were initially unreceived
+   is no longer. This gives a direct assertion for test cases where the
+   relayer is expected to *not* relay some packets, eg. when
+   `clear_on_start` is disabled.
+
+   The assertion also fails if no packet is unreceived to begin with,
+   as happens when the packets were already relayed.
+*/
+pub fn assert_no_relayed_packets<ChainA: ChainHandle, ChainB: ChainHandle>(
+    chains: &ConnectedChains<ChainA, ChainB>,
+    channel: &ConnectedChannel<ChainA, ChainB>,
+    within: Duration,
+) -> Result<(), Error> {
+    let path = PathIdentifiers {
+        port_id: channel.port_b.cloned_value(),
+        channel_id: *channel.channel_id_b.value(),
+        counterparty_port_id: channel.port_a.cloned_value(),
+        counterparty_channel_id: *channel.channel_id_a.value(),
+    };
+
+    let query = || unreceived_packets(&chains.handle_b, &chains.handle_a, &path);
+
+    let (initial, _) = query()?;
+
+    if initial.is_empty() {
+        return Err(Error::assertion(
+            "expected some packets to not be relayed, but none is unreceived".to_string(),
+        ));
+    }
+
+    info!(
+        "asserting that none of the unreceived packets {:?} get relayed within {:?}",
+        initial, within
+    );
+
+    let interval = Duration::from_secs(1);
+    let mut waited = Duration::ZERO;
+
+    while waited < within {
+        let step = interval.min(within - waited);
+        sleep(step);
+        waited += step;
+
+        let (unreceived, _) = query()?;
+
+        let relayed = initial
+            .iter()
+            .filter(|sequence| !unreceived.contains(sequence))
+            .collect::<Vec<_>>();
+
+        if !relayed.is_empty() {
+            return Err(Error::assertion(format!(
+                "expected packets {:?} to not be relayed, but they were received after {:?}",
+                relayed, waited
+            )));
+        }
+    }
+
+    Ok(())
+}